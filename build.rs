@@ -0,0 +1,39 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "ffi")]
+    write_header();
+}
+
+/// Generates the C header for the `ffi` feature in `OUT_DIR`
+///
+/// The committed `include/cellularity.h` is only rewritten when
+/// `CELLULARITY_WRITE_HEADER` is set; a test checks that it matches the
+/// generated one.
+#[cfg(feature = "ffi")]
+fn write_header() {
+    use std::path::PathBuf;
+
+    let crate_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").expect("set by cargo"));
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").expect("set by cargo"));
+    println!("cargo:rerun-if-changed=src/ffi");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-env-changed=CELLULARITY_WRITE_HEADER");
+
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))
+        .expect("cbindgen.toml is valid");
+    // Only the ffi module is part of the C API; the rest of the crate is not
+    let ffi_dir = crate_dir.join("src").join("ffi");
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(ffi_dir.join("mod.rs"))
+        .with_src(ffi_dir.join("status.rs"))
+        .with_src(ffi_dir.join("automaton.rs"))
+        .generate()
+        .expect("the C API can be described in C")
+        .write_to_file(out_dir.join("cellularity.h"));
+
+    if std::env::var_os("CELLULARITY_WRITE_HEADER").is_some() {
+        std::fs::copy(out_dir.join("cellularity.h"), crate_dir.join("include/cellularity.h"))
+            .expect("include/cellularity.h is writable");
+    }
+}
@@ -0,0 +1,378 @@
+#ifndef CELLULARITY_H
+#define CELLULARITY_H
+
+/* Generated by cbindgen from src/ffi with --features ffi; do not edit. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#define CELLULARITY_CENTER SIZE_MAX
+
+/**
+ * Result code returned by every fallible function of the C API
+ *
+ * The first codes mirror the variants of [`Error`]; the rest report misuse
+ * of the API itself. [`cellularity_last_error`](super::cellularity_last_error)
+ * describes the latest failure in words.
+ */
+typedef enum CellularityStatus {
+  /**
+   * Success
+   */
+  CELLULARITY_STATUS_OK = 0,
+  /**
+   * [`Error::InvalidDimensions`]
+   */
+  CELLULARITY_STATUS_INVALID_DIMENSIONS = 1,
+  /**
+   * [`Error::OutOfBounds`]
+   */
+  CELLULARITY_STATUS_OUT_OF_BOUNDS = 2,
+  /**
+   * [`Error::InvalidRuleFormat`]
+   */
+  CELLULARITY_STATUS_INVALID_RULE = 3,
+  /**
+   * [`Error::PatternParseError`]
+   */
+  CELLULARITY_STATUS_PATTERN_PARSE = 4,
+  /**
+   * [`Error::IoError`]
+   */
+  CELLULARITY_STATUS_IO = 5,
+  /**
+   * [`Error::Other`] or [`Error::ScriptError`]
+   */
+  CELLULARITY_STATUS_OTHER = 6,
+  /**
+   * A required pointer argument was null
+   */
+  CELLULARITY_STATUS_NULL_POINTER = 7,
+  /**
+   * A string argument was not valid UTF-8
+   */
+  CELLULARITY_STATUS_INVALID_UTF8 = 8,
+  /**
+   * A buffer did not hold one byte per cell
+   */
+  CELLULARITY_STATUS_BUFFER_SIZE = 9,
+  /**
+   * The engine panicked; the automaton should be freed
+   */
+  CELLULARITY_STATUS_PANIC = 10,
+} CellularityStatus;
+
+/**
+ * An automaton owned by C code, created by [`cellularity_automaton_new`]
+ * and released by [`cellularity_automaton_free`]
+ */
+typedef struct CellularityAutomaton CellularityAutomaton;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Returns the message of the latest failed call on this thread, or an
+ * empty string if the latest call succeeded
+ *
+ * The string stays valid until the next API call on the same thread.
+ */
+const char *cellularity_last_error(void);
+
+/**
+ * Creates an automaton
+ *
+ * `rule` takes any notation the engine parses, such as `B3/S23`,
+ * `Wireworld` or `B3/S23:T80,60`; a topology after the rule sets the size
+ * and edges. `boundary` is `torus`, `plane`, `walled`, `mirror`, `alive`,
+ * `klein`, `cross` or `sphere`, and null means `torus`.
+ *
+ * # Safety
+ * `rule` and `boundary` must be null or NUL-terminated strings, and `out`
+ * must be null or point to writable memory for a handle
+ */
+enum CellularityStatus cellularity_automaton_new(const char *rule,
+                                                 size_t width,
+                                                 size_t height,
+                                                 const char *boundary,
+                                                 struct CellularityAutomaton **out);
+
+/**
+ * Releases an automaton; null is ignored
+ *
+ * # Safety
+ * `handle` must be null or come from [`cellularity_automaton_new`] and not
+ * have been freed already
+ */
+void cellularity_automaton_free(struct CellularityAutomaton *handle);
+
+/**
+ * Returns the width of the grid, or 0 for a null handle
+ *
+ * # Safety
+ * `handle` must be null or a live handle
+ */
+size_t cellularity_automaton_width(const struct CellularityAutomaton *handle);
+
+/**
+ * Returns the height of the grid, or 0 for a null handle
+ *
+ * # Safety
+ * `handle` must be null or a live handle
+ */
+size_t cellularity_automaton_height(const struct CellularityAutomaton *handle);
+
+/**
+ * Returns the current generation, or 0 for a null handle
+ *
+ * # Safety
+ * `handle` must be null or a live handle
+ */
+uint64_t cellularity_automaton_generation(const struct CellularityAutomaton *handle);
+
+/**
+ * Returns the number of live cells, or 0 for a null handle
+ *
+ * # Safety
+ * `handle` must be null or a live handle
+ */
+size_t cellularity_automaton_population(const struct CellularityAutomaton *handle);
+
+/**
+ * Reads the state number of one cell (0 dead, 1 alive, higher for
+ * multi-state rules)
+ *
+ * # Safety
+ * `handle` must be null or a live handle, and `state` null or writable
+ */
+enum CellularityStatus cellularity_automaton_get_cell(const struct CellularityAutomaton *handle,
+                                                      size_t x,
+                                                      size_t y,
+                                                      uint8_t *state);
+
+/**
+ * Sets the state number of one cell
+ *
+ * # Safety
+ * `handle` must be null or a live handle
+ */
+enum CellularityStatus cellularity_automaton_set_cell(struct CellularityAutomaton *handle,
+                                                      size_t x,
+                                                      size_t y,
+                                                      uint8_t state);
+
+/**
+ * Advances `steps` generations
+ *
+ * # Safety
+ * `handle` must be null or a live handle
+ */
+enum CellularityStatus cellularity_automaton_step(struct CellularityAutomaton *handle,
+                                                  uint64_t steps);
+
+/**
+ * Kills every cell and goes back to generation 0
+ *
+ * # Safety
+ * `handle` must be null or a live handle
+ */
+enum CellularityStatus cellularity_automaton_reset(struct CellularityAutomaton *handle);
+
+/**
+ * Copies the state number of every cell, row by row, into a buffer of
+ * exactly width × height bytes
+ *
+ * # Safety
+ * `handle` must be null or a live handle, and `buffer` null or valid for
+ * `length` writable bytes
+ */
+enum CellularityStatus cellularity_automaton_read_grid(const struct CellularityAutomaton *handle,
+                                                       uint8_t *buffer,
+                                                       size_t length);
+
+/**
+ * Sets every cell from a buffer of width × height state numbers, row by
+ * row
+ *
+ * # Safety
+ * `handle` must be null or a live handle, and `buffer` null or valid for
+ * `length` readable bytes
+ */
+enum CellularityStatus cellularity_automaton_write_grid(struct CellularityAutomaton *handle,
+                                                        const uint8_t *buffer,
+                                                        size_t length);
+
+/**
+ * Places an RLE pattern with its top-left cell at (`x`, `y`), centring it
+ * along any axis given as [`CELLULARITY_CENTER`]
+ *
+ * Cells past the edges are dropped. With `clear` the rest of the grid is
+ * killed first; otherwise the pattern's live cells are drawn over it.
+ *
+ * # Safety
+ * `handle` must be null or a live handle, and `rle` null or a
+ * NUL-terminated string
+ */
+enum CellularityStatus cellularity_automaton_load_rle(struct CellularityAutomaton *handle,
+                                                      const char *rle,
+                                                      size_t x,
+                                                      size_t y,
+                                                      bool clear);
+
+/**
+ * Returns a short, static description of a status code
+ */
+const char *cellularity_status_name(enum CellularityStatus status);
+
+/**
+ * Returns a short, static description of a status code
+ */
+const char *cellularity_status_name(enum CellularityStatus status);
+
+/**
+ * Creates an automaton
+ *
+ * `rule` takes any notation the engine parses, such as `B3/S23`,
+ * `Wireworld` or `B3/S23:T80,60`; a topology after the rule sets the size
+ * and edges. `boundary` is `torus`, `plane`, `walled`, `mirror`, `alive`,
+ * `klein`, `cross` or `sphere`, and null means `torus`.
+ *
+ * # Safety
+ * `rule` and `boundary` must be null or NUL-terminated strings, and `out`
+ * must be null or point to writable memory for a handle
+ */
+enum CellularityStatus cellularity_automaton_new(const char *rule,
+                                                 size_t width,
+                                                 size_t height,
+                                                 const char *boundary,
+                                                 struct CellularityAutomaton **out);
+
+/**
+ * Releases an automaton; null is ignored
+ *
+ * # Safety
+ * `handle` must be null or come from [`cellularity_automaton_new`] and not
+ * have been freed already
+ */
+void cellularity_automaton_free(struct CellularityAutomaton *handle);
+
+/**
+ * Returns the width of the grid, or 0 for a null handle
+ *
+ * # Safety
+ * `handle` must be null or a live handle
+ */
+size_t cellularity_automaton_width(const struct CellularityAutomaton *handle);
+
+/**
+ * Returns the height of the grid, or 0 for a null handle
+ *
+ * # Safety
+ * `handle` must be null or a live handle
+ */
+size_t cellularity_automaton_height(const struct CellularityAutomaton *handle);
+
+/**
+ * Returns the current generation, or 0 for a null handle
+ *
+ * # Safety
+ * `handle` must be null or a live handle
+ */
+uint64_t cellularity_automaton_generation(const struct CellularityAutomaton *handle);
+
+/**
+ * Returns the number of live cells, or 0 for a null handle
+ *
+ * # Safety
+ * `handle` must be null or a live handle
+ */
+size_t cellularity_automaton_population(const struct CellularityAutomaton *handle);
+
+/**
+ * Reads the state number of one cell (0 dead, 1 alive, higher for
+ * multi-state rules)
+ *
+ * # Safety
+ * `handle` must be null or a live handle, and `state` null or writable
+ */
+enum CellularityStatus cellularity_automaton_get_cell(const struct CellularityAutomaton *handle,
+                                                      size_t x,
+                                                      size_t y,
+                                                      uint8_t *state);
+
+/**
+ * Sets the state number of one cell
+ *
+ * # Safety
+ * `handle` must be null or a live handle
+ */
+enum CellularityStatus cellularity_automaton_set_cell(struct CellularityAutomaton *handle,
+                                                      size_t x,
+                                                      size_t y,
+                                                      uint8_t state);
+
+/**
+ * Advances `steps` generations
+ *
+ * # Safety
+ * `handle` must be null or a live handle
+ */
+enum CellularityStatus cellularity_automaton_step(struct CellularityAutomaton *handle,
+                                                  uint64_t steps);
+
+/**
+ * Kills every cell and goes back to generation 0
+ *
+ * # Safety
+ * `handle` must be null or a live handle
+ */
+enum CellularityStatus cellularity_automaton_reset(struct CellularityAutomaton *handle);
+
+/**
+ * Copies the state number of every cell, row by row, into a buffer of
+ * exactly width × height bytes
+ *
+ * # Safety
+ * `handle` must be null or a live handle, and `buffer` null or valid for
+ * `length` writable bytes
+ */
+enum CellularityStatus cellularity_automaton_read_grid(const struct CellularityAutomaton *handle,
+                                                       uint8_t *buffer,
+                                                       size_t length);
+
+/**
+ * Sets every cell from a buffer of width × height state numbers, row by
+ * row
+ *
+ * # Safety
+ * `handle` must be null or a live handle, and `buffer` null or valid for
+ * `length` readable bytes
+ */
+enum CellularityStatus cellularity_automaton_write_grid(struct CellularityAutomaton *handle,
+                                                        const uint8_t *buffer,
+                                                        size_t length);
+
+/**
+ * Places an RLE pattern with its top-left cell at (`x`, `y`), centring it
+ * along any axis given as [`CELLULARITY_CENTER`]
+ *
+ * Cells past the edges are dropped. With `clear` the rest of the grid is
+ * killed first; otherwise the pattern's live cells are drawn over it.
+ *
+ * # Safety
+ * `handle` must be null or a live handle, and `rle` null or a
+ * NUL-terminated string
+ */
+enum CellularityStatus cellularity_automaton_load_rle(struct CellularityAutomaton *handle,
+                                                      const char *rle,
+                                                      size_t x,
+                                                      size_t y,
+                                                      bool clear);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* CELLULARITY_H */
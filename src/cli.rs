@@ -0,0 +1,426 @@
+use std::path::{Path, PathBuf};
+
+use crate::core::automata::Automata;
+use crate::core::cell::Position;
+use crate::core::dense_grid::DenseGrid;
+use crate::core::grid::Grid;
+use crate::core::rle::RlePattern;
+use crate::core::rules::parse_rule;
+use crate::core::topology::Topology;
+use crate::error::{Error, Result};
+use crate::io::frame_style::FrameStyle;
+use crate::io::image_import::ImageImport;
+use crate::io::recorder::Recorder;
+use crate::io::svg_export::SvgExport;
+
+/// Help printed for `cellularity help` and after usage errors
+pub const USAGE: &str = "\
+Usage:
+  cellularity                                Start the app
+  cellularity record <pattern.rle> <out>     Record generations of a pattern
+  cellularity import <image> <pattern.rle>   Turn a PNG or PBM image into a pattern
+  cellularity export <pattern.rle> <image>   Save a pattern as an image
+  cellularity serve [--port <n>]             Serve the JSON API on 127.0.0.1
+                  [--stream-port <n>]        and stream sessions over WebSocket
+                                             (built with --features server)
+  cellularity script <script.rhai> <out.rle> Run a Rhai script and save the grid
+                                             (built with --features scripting)
+
+Record writes an animated GIF if <out> ends in .gif, otherwise numbered PNG
+images in the directory <out>.
+
+Record options:
+  --rule <rule>        Rule, optionally with a topology such as B3/S23:T80,60
+                       (default: the rule in the RLE header, or B3/S23)
+  --frames <n>         Number of generations to record (default 100)
+  --cell-size <px>     Pixels per cell (default 4)
+  --delay <ms>         Time each GIF frame is shown (default 100)
+  --alive <RRGGBB>     Colour of live cells
+  --dead <RRGGBB>      Colour of dead cells
+  --margin <cells>     Dead cells around the pattern without a topology
+                       (default 16)
+  --crop <x,y,w,h>     Record only this region of the grid
+
+Import reads PNG, PBM, PGM and PPM images. Options:
+  --threshold <0-255>  Brightness below which cells are alive (default 128)
+  --scale <px>         Pixels per cell (default 1)
+  --invert             Make light cells alive instead of dark ones
+  --rule <rule>        Rule written to the pattern; a rule with its own
+                       colours, such as Wireworld, maps pixels to the state
+                       of the nearest colour
+
+Export writes one pixel per cell, black on white or in the colours of the
+pattern's rule, as PBM if <image> ends in .pbm and PNG otherwise. An <image>
+ending in .svg is drawn as vector graphics with these options:
+  --stack <n>          Draw n generations side by side (default 1)
+  --margin <cells>     Dead cells around the pattern, room to evolve (default 0)
+  --cell-size <px>     Pixels per cell (default 10)
+  --alive <RRGGBB>     Colour of live cells
+  --dead <RRGGBB>      Colour of dead cells
+  --crop <x,y,w,h>     Draw only this region, counting the margin
+  --highlight <x,y>    Outline a cell; may be repeated
+  --no-grid            Leave out the grid lines
+  --borders            Outline every live cell
+  --labels             Number the rows, columns and generations
+
+Script installs the rule and neighborhood the script defines, calls its
+run(sim) function if it has one, then runs any further generations. Options:
+  --pattern <file.rle> Pattern to start from, centred (default: empty grid)
+  --rule <rule>        Rule if the script defines none (default: the rule in
+                       the pattern's header, or B3/S23)
+  --size <w,h>         Grid size without a pattern (default 64,64)
+  --margin <cells>     Dead cells around the pattern (default 16)
+  --steps <n>          Generations to run after the script (default 0)";
+
+/// Runs the command line interface
+///
+/// # Arguments
+/// * `args` - Arguments after the program name
+pub fn run(args: &[String]) -> Result<()> {
+    match args.first().map(String::as_str) {
+        Some("record") => record(&args[1..]),
+        Some("import") => import(&args[1..]),
+        Some("export") => export(&args[1..]),
+        Some("serve") => serve(&args[1..]),
+        Some("script") => script(&args[1..]),
+        Some("help" | "--help" | "-h") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(other) => Err(Error::Other(format!("unknown command '{}'\n\n{}", other, USAGE))),
+        None => Ok(()),
+    }
+}
+
+/// Command arguments split into an input, an output and `--name value`
+/// options
+struct Arguments {
+    input: PathBuf,
+    output: PathBuf,
+    options: Vec<(String, String)>,
+}
+
+impl Arguments {
+    /// Splits the arguments of a command
+    ///
+    /// # Arguments
+    /// * `args` - Arguments after the command name
+    /// * `switches` - Options that take no value; they get an empty one
+    fn parse(args: &[String], switches: &[&str]) -> Result<Self> {
+        let mut positional = Vec::new();
+        let mut options = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                positional.push(PathBuf::from(arg));
+            } else if switches.contains(&arg.as_str()) {
+                options.push((arg.clone(), String::new()));
+            } else {
+                let value = args
+                    .next()
+                    .ok_or_else(|| Error::Other(format!("{} needs a value", arg)))?;
+                options.push((arg.clone(), value.clone()));
+            }
+        }
+        match <[PathBuf; 2]>::try_from(positional) {
+            Ok([input, output]) => Ok(Self { input, output, options }),
+            Err(_) => Err(Error::Other(format!("expected an input and an output\n\n{}", USAGE))),
+        }
+    }
+}
+
+/// Reports an option the command does not know
+fn unknown_option(option: &str) -> Error {
+    Error::Other(format!("unknown option '{}'\n\n{}", option, USAGE))
+}
+
+/// Reads an RLE file
+fn read_pattern(path: &Path) -> Result<RlePattern> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| Error::IoError(format!("{}: {}", path.display(), e)))?;
+    RlePattern::parse(&text)
+}
+
+/// Loads a pattern, runs it and writes the recording
+fn record(args: &[String]) -> Result<()> {
+    let Arguments { input, output, options } = Arguments::parse(args, &[])?;
+    let mut rule = None;
+    let mut margin = 16;
+    let mut recorder = Recorder::new();
+    for (option, value) in &options {
+        match option.as_str() {
+            "--rule" => rule = Some(value.clone()),
+            "--frames" => recorder.frames = parse_number(option, value)?,
+            "--cell-size" => recorder.style.cell_size = parse_number(option, value)?,
+            "--delay" => recorder.frame_delay_ms = parse_number(option, value)?,
+            "--alive" => recorder.style.alive_color = parse_color(value)?,
+            "--dead" => recorder.style.dead_color = parse_color(value)?,
+            "--margin" => margin = parse_number(option, value)?,
+            "--crop" => recorder.style.crop = Some(parse_crop(value)?),
+            _ => return Err(unknown_option(option)),
+        }
+    }
+
+    let pattern = read_pattern(&input)?;
+    let rule = rule.or(pattern.rule).unwrap_or_else(|| "B3/S23".to_string());
+    let mut automata = automata_for(&rule, &pattern.grid, margin)?;
+    if recorder.style.palette.is_none() {
+        recorder.style.palette = automata.rule().palette().map(<[_]>::to_vec);
+    }
+    let frames = recorder.capture(&mut automata);
+    let written = recorder.save(&frames, &output)?;
+    println!("Wrote {} frames to {}", frames.len(), output.display());
+    if written.len() > 1 {
+        println!("({} files)", written.len());
+    }
+    Ok(())
+}
+
+/// Converts an image into an RLE pattern
+fn import(args: &[String]) -> Result<()> {
+    let Arguments { input, output, options } = Arguments::parse(args, &["--invert"])?;
+    let mut settings = ImageImport::new();
+    let mut rule = None;
+    for (option, value) in &options {
+        match option.as_str() {
+            "--threshold" => settings.threshold = parse_number(option, value)?,
+            "--scale" => settings.scale = parse_number(option, value)?,
+            "--invert" => settings.invert = true,
+            "--rule" => rule = Some(value.clone()),
+            _ => return Err(unknown_option(option)),
+        }
+    }
+    if let Some(rule) = &rule {
+        let (rule, _) = Topology::split_rule(rule)?;
+        settings.palette = parse_rule(rule)?.rule.palette().map(<[_]>::to_vec);
+    }
+
+    let mut pattern = RlePattern::new(settings.open(&input)?);
+    pattern.rule = rule;
+    std::fs::write(&output, pattern.to_rle())
+        .map_err(|e| Error::IoError(format!("{}: {}", output.display(), e)))?;
+    let grid = &pattern.grid;
+    println!("Wrote a {}x{} pattern to {}", grid.width(), grid.height(), output.display());
+    Ok(())
+}
+
+/// Saves an RLE pattern as an image, or as SVG optionally stacking several
+/// generations
+fn export(args: &[String]) -> Result<()> {
+    let switches = ["--no-grid", "--borders", "--labels"];
+    let Arguments { input, output, options } = Arguments::parse(args, &switches)?;
+    let pattern = read_pattern(&input)?;
+    let rule = pattern.rule.clone().unwrap_or_else(|| "B3/S23".to_string());
+    let mut settings = ImageImport::new();
+    let (rule_only, _) = Topology::split_rule(&rule)?;
+    settings.palette = parse_rule(rule_only)?.rule.palette().map(<[_]>::to_vec);
+
+    let is_svg = output.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+    if !is_svg {
+        if let Some((option, _)) = options.first() {
+            return Err(unknown_option(option));
+        }
+        settings.export_style().save(&pattern.grid, &output)?;
+        println!("Wrote {}", output.display());
+        return Ok(());
+    }
+
+    let mut svg = SvgExport::new();
+    svg.style = FrameStyle { cell_size: svg.style.cell_size, ..settings.export_style() };
+    let (mut generations, mut margin) = (1, 0);
+    for (option, value) in &options {
+        match option.as_str() {
+            "--stack" => generations = parse_number(option, value)?,
+            "--margin" => margin = parse_number(option, value)?,
+            "--cell-size" => svg.style.cell_size = parse_number(option, value)?,
+            "--alive" => svg.style.alive_color = parse_color(value)?,
+            "--dead" => svg.style.dead_color = parse_color(value)?,
+            "--crop" => svg.style.crop = Some(parse_crop(value)?),
+            "--highlight" => {
+                let (x, y) = value
+                    .split_once(',')
+                    .ok_or_else(|| Error::Other(format!("invalid cell '{}'", value)))?;
+                svg.highlights.push(Position::new(
+                    parse_number(option, x.trim())?,
+                    parse_number(option, y.trim())?,
+                ));
+            }
+            "--no-grid" => svg.grid_lines = false,
+            "--borders" => svg.cell_borders = true,
+            "--labels" => svg.labels = true,
+            _ => return Err(unknown_option(option)),
+        }
+    }
+
+    let mut automata = automata_for(&rule, &pattern.grid, margin)?;
+    let recorder = Recorder { frames: generations.max(1), ..Recorder::new() };
+    svg.save_stack(&recorder.capture(&mut automata), &output)?;
+    println!("Wrote {}", output.display());
+    Ok(())
+}
+
+/// Runs the HTTP/JSON server and the WebSocket stream server until the
+/// process is stopped
+#[cfg(feature = "server")]
+fn serve(args: &[String]) -> Result<()> {
+    use crate::server::http_server::Server;
+    use crate::server::stream_server::StreamServer;
+
+    let (mut port, mut stream_port): (u16, Option<u16>) = (8080, None);
+    for pair in args.chunks(2) {
+        match pair {
+            [option, value] if option == "--port" => port = parse_number(option, value)?,
+            [option, value] if option == "--stream-port" => {
+                stream_port = Some(parse_number(option, value)?)
+            }
+            _ => {
+                return Err(Error::Other(format!(
+                    "expected serve [--port <n>] [--stream-port <n>]\n\n{}",
+                    USAGE
+                )))
+            }
+        }
+    }
+    // Port 0 lets both servers pick a free port
+    let stream_port = stream_port.unwrap_or(if port == 0 { 0 } else { port.saturating_add(1) });
+    let server = Server::bind(&format!("127.0.0.1:{}", port))?;
+    let streams = StreamServer::bind(&format!("127.0.0.1:{}", stream_port), server.api())?;
+    if let (Some(addr), Some(stream_addr)) = (server.local_addr(), streams.local_addr()) {
+        println!("Serving the cellularity API on http://{}", addr);
+        println!("Streaming sessions on ws://{}/sessions/{{id}}", stream_addr);
+    }
+    std::thread::spawn(move || streams.run());
+    server.run();
+    Ok(())
+}
+
+/// Explains that the server was left out of this build
+#[cfg(not(feature = "server"))]
+fn serve(_args: &[String]) -> Result<()> {
+    Err(Error::Other(
+        "this build has no server; rebuild with `cargo build --features server`".to_string(),
+    ))
+}
+
+/// Runs a script on a pattern or an empty grid and saves the result
+#[cfg(feature = "scripting")]
+fn script(args: &[String]) -> Result<()> {
+    use crate::scripting::script::Script;
+
+    let Arguments { input, output, options } = Arguments::parse(args, &[])?;
+    let (mut pattern, mut rule, mut size) = (None, None, (64, 64));
+    let (mut margin, mut steps) = (16, 0);
+    for (option, value) in &options {
+        match option.as_str() {
+            "--pattern" => pattern = Some(read_pattern(Path::new(value))?),
+            "--rule" => rule = Some(value.clone()),
+            "--size" => size = parse_size(value)?,
+            "--margin" => margin = parse_number(option, value)?,
+            "--steps" => steps = parse_number(option, value)?,
+            _ => return Err(unknown_option(option)),
+        }
+    }
+
+    let script = Script::load(&input)?;
+    let header_rule = pattern.as_ref().and_then(|pattern| pattern.rule.clone());
+    let rule = rule.or(header_rule).unwrap_or_else(|| "B3/S23".to_string());
+    let mut automata = match &pattern {
+        Some(pattern) => automata_for(&rule, &pattern.grid, margin)?,
+        None => Automata::from_spec(&rule, size.0, size.1, "torus")?,
+    };
+    let (custom_rule, automation) = (script.defines_rule(), script.defines_automation());
+    if custom_rule || script.defines_neighborhood() {
+        script.install(&mut automata)?;
+    } else if !automation {
+        return Err(Error::ScriptError(format!(
+            "{} defines no rule, neighborhood or run(sim) function",
+            input.display()
+        )));
+    }
+    if automation {
+        for line in script.run(&mut automata)? {
+            println!("{}", line);
+        }
+    }
+    automata.step_n(steps);
+
+    // A scripted rule has no notation other programs could read back
+    let mut saved = RlePattern::new(automata.grid().clone());
+    saved.rule = if custom_rule { None } else { Some(rule) };
+    std::fs::write(&output, saved.to_rle())
+        .map_err(|e| Error::IoError(format!("{}: {}", output.display(), e)))?;
+    println!(
+        "Wrote generation {} ({} cells alive) to {}",
+        automata.generation(),
+        automata.grid().count_alive(),
+        output.display()
+    );
+    Ok(())
+}
+
+/// Explains that scripting was left out of this build
+#[cfg(not(feature = "scripting"))]
+fn script(_args: &[String]) -> Result<()> {
+    Err(Error::Other(
+        "this build has no scripting; rebuild with `cargo build --features scripting`".to_string(),
+    ))
+}
+
+/// Builds an automaton running `rule` with the pattern in the middle
+///
+/// A topology after the rule, as in `B3/S23:T80,60`, sets the grid size and
+/// edges; otherwise the grid is the pattern plus `margin` dead cells on each
+/// side, surrounded by dead cells.
+pub fn automata_for(rule: &str, pattern: &DenseGrid, margin: usize) -> Result<Automata> {
+    let (width, height) = (pattern.width() + 2 * margin, pattern.height() + 2 * margin);
+    let mut automata = Automata::from_spec(rule, width, height, "plane")?;
+    let (width, height) = (automata.grid().width(), automata.grid().height());
+    let origin = Position::new(
+        width.saturating_sub(pattern.width()) / 2,
+        height.saturating_sub(pattern.height()) / 2,
+    );
+    automata.grid_mut().stamp(pattern, origin);
+    Ok(automata)
+}
+
+/// Parses a whole number option
+fn parse_number<T: std::str::FromStr>(option: &str, value: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| Error::Other(format!("{} expects a whole number, got '{}'", option, value)))
+}
+
+/// Parses a colour such as `3cdc78` or `#3cdc78`
+pub fn parse_color(text: &str) -> Result<[u8; 3]> {
+    let hex = text.trim().trim_start_matches('#');
+    let invalid = || Error::Other(format!("invalid colour '{}', expected RRGGBB", text));
+    if hex.len() != 6 || !hex.is_ascii() {
+        return Err(invalid());
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid());
+    Ok([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// Parses a grid size such as `80,60`
+#[cfg(feature = "scripting")]
+fn parse_size(text: &str) -> Result<(usize, usize)> {
+    let invalid = || Error::Other(format!("invalid size '{}', expected w,h", text));
+    let (width, height) = text.split_once(',').ok_or_else(invalid)?;
+    match (width.trim().parse(), height.trim().parse()) {
+        (Ok(width), Ok(height)) => Ok((width, height)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Parses a crop region such as `10,5,40,30`
+fn parse_crop(text: &str) -> Result<(Position, usize, usize)> {
+    let values: Vec<usize> = text
+        .split(',')
+        .map(|value| value.trim().parse::<usize>())
+        .collect::<std::result::Result<_, _>>()
+        .map_err(|_| Error::Other(format!("invalid crop '{}', expected x,y,w,h", text)))?;
+    match values[..] {
+        [x, y, width, height] => Ok((Position::new(x, y), width, height)),
+        _ => Err(Error::Other(format!("invalid crop '{}', expected x,y,w,h", text))),
+    }
+}
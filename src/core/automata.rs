@@ -1,10 +1,19 @@
-use crate::core::cell::Position;
+use crate::core::cell::{CellState, Position};
 use crate::core::dense_grid::DenseGrid;
 use crate::core::grid::Grid;
-use crate::core::rules::Rule;
-use crate::core::boundary::Boundary;
-use crate::core::neighborhood::Neighborhood;
-use crate::error::Result;
+use crate::core::rules::{Neighbors, Rule};
+use crate::core::boundary::{parse_boundary, Boundary};
+use crate::core::lattice::Lattice;
+use crate::core::neighborhood::{parse_neighborhood, Neighborhood};
+use crate::core::rules::parse_rule;
+use crate::core::topology::Topology;
+use crate::core::prefix_sum::{RowSpans, SummedAreaTable, PREFIX_SUM_MIN_NEIGHBORS};
+use crate::core::turmite::Ant;
+use crate::core::update_scheme::UpdateScheme;
+use crate::error::{Error, Result};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
 
 /// Main automata simulation engine
 ///
@@ -20,6 +29,18 @@ pub struct Automata {
     boundary: Box<dyn Boundary>,
     /// Neighborhood type for neighbor calculation
     neighborhood: Box<dyn Neighborhood>,
+    /// Row decomposition of large neighborhoods for prefix-sum counting
+    spans: Option<RowSpans>,
+    /// Shape of the cells, used when rendering
+    lattice: Lattice,
+    /// Agents that read and write cells after each rule update
+    ants: Vec<Ant>,
+    /// Order in which cells are updated
+    update_scheme: UpdateScheme,
+    /// Seed the random number generator was last started from
+    seed: u64,
+    /// Random number generator for stochastic rules and update orders
+    rng: StdRng,
     /// Current generation number
     generation: u64,
 }
@@ -42,6 +63,7 @@ impl Automata {
     ) -> Result<Self> {
         let grid = DenseGrid::new(width, height)?;
         let next_grid = DenseGrid::new(width, height)?;
+        let spans = Self::spans_for(neighborhood.as_ref());
 
         Ok(Self {
             grid,
@@ -49,10 +71,158 @@ impl Automata {
             rule,
             boundary,
             neighborhood,
+            spans,
+            lattice: Lattice::Square,
+            ants: Vec::new(),
+            update_scheme: UpdateScheme::Synchronous,
+            seed: 0,
+            rng: StdRng::seed_from_u64(0),
             generation: 0,
         })
     }
 
+    /// Returns the row decomposition used for prefix-sum counting, if the
+    /// neighborhood is large enough to benefit from it
+    fn spans_for(neighborhood: &dyn Neighborhood) -> Option<RowSpans> {
+        let offsets = neighborhood.offsets();
+        if offsets.len() < PREFIX_SUM_MIN_NEIGHBORS
+            || neighborhood.weights().is_some()
+            || !neighborhood.is_uniform()
+        {
+            return None;
+        }
+        RowSpans::from_offsets(offsets)
+    }
+
+    /// Returns the rule applied at each generation
+    pub fn rule(&self) -> &dyn Rule {
+        self.rule.as_ref()
+    }
+
+    /// Replaces the rule applied at each generation
+    pub fn set_rule(&mut self, rule: Box<dyn Rule>) {
+        self.rule = rule;
+    }
+
+    /// Replaces the rule applied at each generation, returning the old one
+    pub fn replace_rule(&mut self, rule: Box<dyn Rule>) -> Box<dyn Rule> {
+        std::mem::replace(&mut self.rule, rule)
+    }
+
+    /// Returns the boundary condition
+    pub fn boundary(&self) -> &dyn Boundary {
+        self.boundary.as_ref()
+    }
+
+    /// Replaces the boundary condition
+    pub fn set_boundary(&mut self, boundary: Box<dyn Boundary>) {
+        self.boundary = boundary;
+    }
+
+    /// Builds an automaton from a rule and a boundary given as text
+    ///
+    /// A rule with a Golly topology, such as `B3/S23:K40*,30`, sets the grid
+    /// size and boundary itself, and `width`, `height` and `boundary` are
+    /// ignored.
+    ///
+    /// # Arguments
+    /// * `rule` - Rule in any notation [`parse_rule`] accepts
+    /// * `width` - Width of the grid
+    /// * `height` - Height of the grid
+    /// * `boundary` - Edge behavior, see [`parse_boundary`]
+    pub fn from_spec(rule: &str, width: usize, height: usize, boundary: &str) -> Result<Self> {
+        let (rule, topology) = Topology::split_rule(rule)?;
+        let parsed = parse_rule(rule)?;
+        let (width, height, boundary) = match topology {
+            Some(topology) => (topology.width(), topology.height(), topology.boundary()),
+            None => (width, height, parse_boundary(boundary)?),
+        };
+        Self::new(width, height, parsed.rule, boundary, parsed.neighborhood)
+    }
+
+    /// Returns the neighborhood used for neighbor counting
+    pub fn neighborhood(&self) -> &dyn Neighborhood {
+        self.neighborhood.as_ref()
+    }
+
+    /// Replaces the neighborhood used for neighbor counting
+    ///
+    /// The neighborhood is not checked against the rule; set the rule
+    /// first when changing both.
+    pub fn set_neighborhood(&mut self, neighborhood: Box<dyn Neighborhood>) {
+        self.spans = Self::spans_for(neighborhood.as_ref());
+        self.neighborhood = neighborhood;
+    }
+
+    /// Replaces the neighborhood with one given by name, ignoring case
+    ///
+    /// `hexagonal` and `triangular` switch the lattice as
+    /// [`set_lattice`](Self::set_lattice) does; other names are read by
+    /// [`parse_neighborhood`].
+    ///
+    /// # Errors
+    /// Returns an error if the name is unknown or the rule does not accept
+    /// the neighborhood, see [`Rule::check_neighborhood`]
+    pub fn set_neighborhood_by_name(&mut self, name: &str) -> Result<()> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "hexagonal" => self.set_lattice(Lattice::Hexagonal),
+            "triangular" => self.set_lattice(Lattice::Triangular),
+            _ => {
+                let neighborhood = parse_neighborhood(name)?;
+                self.rule.check_neighborhood(neighborhood.as_ref())?;
+                self.set_neighborhood(neighborhood);
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the order in which cells are updated
+    pub fn update_scheme(&self) -> UpdateScheme {
+        self.update_scheme
+    }
+
+    /// Changes the order in which cells are updated
+    pub fn set_update_scheme(&mut self, scheme: UpdateScheme) {
+        self.update_scheme = scheme;
+    }
+
+    /// Returns the seed the random number generator was last started from
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Restarts the random number generator from a seed
+    ///
+    /// The generator drives stochastic rules and random update orders. A new
+    /// automaton is seeded with 0, and [`Automata::reset`] restarts the
+    /// generator from the current seed, so a run can be replayed exactly.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Returns the lattice the grid is laid out on
+    pub fn lattice(&self) -> Lattice {
+        self.lattice
+    }
+
+    /// Switches to another lattice and its usual neighborhood
+    ///
+    /// The grid contents are kept; only adjacency and rendering change.
+    ///
+    /// # Errors
+    /// Returns an error if the grid dimensions do not suit the lattice, see
+    /// [`Lattice::check_dimensions`], or the rule does not accept its
+    /// neighborhood, see [`Rule::check_neighborhood`]
+    pub fn set_lattice(&mut self, lattice: Lattice) -> Result<()> {
+        lattice.check_dimensions(self.grid.width(), self.grid.height())?;
+        let neighborhood = lattice.neighborhood();
+        self.rule.check_neighborhood(neighborhood.as_ref())?;
+        self.lattice = lattice;
+        self.set_neighborhood(neighborhood);
+        Ok(())
+    }
+
     /// Returns a reference to the current grid
     pub fn grid(&self) -> &DenseGrid {
         &self.grid
@@ -63,66 +233,209 @@ impl Automata {
         &mut self.grid
     }
 
+    /// Resizes the grid, keeping the cells that still fit and removing any
+    /// ants that no longer do
+    ///
+    /// # Arguments
+    /// * `width` - New width of the grid
+    /// * `height` - New height of the grid
+    ///
+    /// # Errors
+    /// Returns an error if either dimension is zero or the new size does not
+    /// suit the lattice
+    pub fn resize(&mut self, width: usize, height: usize) -> Result<()> {
+        self.lattice.check_dimensions(width, height)?;
+        self.grid.resize(width, height)?;
+        self.next_grid.resize(width, height)?;
+        self.ants.retain(|ant| ant.position.is_within_bounds(width, height));
+        Ok(())
+    }
+
+    /// Returns the ants walking over the grid
+    pub fn ants(&self) -> &[Ant] {
+        &self.ants
+    }
+
+    /// Returns the ants walking over the grid, for editing
+    pub fn ants_mut(&mut self) -> &mut [Ant] {
+        &mut self.ants
+    }
+
+    /// Adds an ant to the agent layer
+    ///
+    /// Ants move in the order they were added, each one after the rule has
+    /// updated the grid. Use [`IdentityRule`](crate::core::rules::IdentityRule)
+    /// for a grid changed by ants alone.
+    ///
+    /// # Errors
+    /// Returns an error if the ant is outside the grid
+    pub fn add_ant(&mut self, ant: Ant) -> Result<()> {
+        if !ant.position.is_within_bounds(self.grid.width(), self.grid.height()) {
+            return Err(Error::OutOfBounds {
+                x: ant.position.x,
+                y: ant.position.y,
+                width: self.grid.width(),
+                height: self.grid.height(),
+            });
+        }
+        self.ants.push(ant);
+        Ok(())
+    }
+
+    /// Removes all ants
+    pub fn clear_ants(&mut self) {
+        self.ants.clear();
+    }
+
     /// Returns the current generation number
     pub fn generation(&self) -> u64 {
         self.generation
     }
 
-    /// Resets the automata to generation 0 and clears the grid
+    /// Resets the automata to generation 0, clears the grid, removes all ants
+    /// and restarts the random number generator from the current seed
     pub fn reset(&mut self) {
+        self.set_seed(self.seed);
         self.grid.clear();
+        self.ants.clear();
         self.next_grid.clear();
         self.generation = 0;
     }
 
-    /// Counts alive neighbors for a given position
-    fn count_alive_neighbors(&self, pos: Position) -> usize {
-        let mut count = 0;
+    /// Counts alive neighbors and their weighted sum for a given position
+    ///
+    /// `states` is given for rules that report
+    /// [`Rule::needs_configuration`]: it is filled with the state of every
+    /// neighbor and returned as [`Neighbors::states`], and
+    /// [`Neighbors::mask`] is built too. Otherwise both are left empty.
+    fn count_neighbors<'a>(
+        &self,
+        pos: Position,
+        states: Option<&'a mut Vec<CellState>>,
+    ) -> Neighbors<'a> {
+        let mut alive = 0;
+        let mut weighted_sum = 0;
+        let mut mask = 0u64;
         let x = pos.x as isize;
         let y = pos.y as isize;
+        let weights = self.neighborhood.weights();
+        let outside = self.boundary.outside_state();
+        let mut states = states;
+        let configuration = states.is_some();
+        if let Some(states) = states.as_deref_mut() {
+            states.clear();
+        }
 
-        for &(dx, dy) in self.neighborhood.offsets() {
+        for (i, &(dx, dy)) in self.neighborhood.offsets_at(pos).iter().enumerate() {
             let neighbor_x = x + dx;
             let neighbor_y = y + dy;
 
-            if let Some(neighbor_pos) = self.boundary.wrap(
-                neighbor_x,
-                neighbor_y,
-                self.grid.width(),
-                self.grid.height(),
-            ) {
-                if let Ok(state) = self.grid.get(neighbor_pos) {
-                    if state.is_alive() {
-                        count += 1;
-                    }
+            let state = self
+                .boundary
+                .wrap(neighbor_x, neighbor_y, self.grid.width(), self.grid.height())
+                .and_then(|neighbor_pos| self.grid.get(neighbor_pos).ok())
+                .unwrap_or(outside);
+            if state.is_alive() {
+                alive += 1;
+                weighted_sum += weights.map_or(1, |w| w[i] as i64);
+                if configuration && i < 64 {
+                    mask |= 1 << i;
                 }
             }
+            if let Some(states) = states.as_deref_mut() {
+                states.push(state);
+            }
         }
 
-        count
+        Neighbors {
+            alive,
+            weighted_sum,
+            mask,
+            states: states.map_or(&[], |states| states.as_slice()),
+        }
     }
 
     /// Advances the simulation by one generation
     ///
-    /// Applies the rule to each cell based on its current state and neighbor count,
-    /// then swaps the current and next grids.
+    /// Applies the rule to cells in the order given by the
+    /// [`UpdateScheme`]. Synchronous updates write into a second grid that is
+    /// then swapped in; unweighted neighborhoods with at least
+    /// [`PREFIX_SUM_MIN_NEIGHBORS`] cells are counted with a summed-area
+    /// table instead of one lookup per offset.
     pub fn step(&mut self) {
+        let cells = self.grid.width() * self.grid.height();
+        match self.update_scheme {
+            UpdateScheme::Synchronous => self.step_synchronous(1.0),
+            UpdateScheme::Asynchronous { alpha } => self.step_synchronous(alpha),
+            UpdateScheme::RandomSequential => {
+                let mut order: Vec<usize> = (0..cells).collect();
+                order.shuffle(&mut self.rng);
+                self.step_in_place(order);
+            }
+            UpdateScheme::LineByLine => self.step_in_place(0..cells),
+        }
+
+        // Agents act on the updated grid, one after another
+        for ant in &mut self.ants {
+            ant.step(&mut self.grid, self.boundary.as_ref());
+        }
+
+        // Increment generation counter
+        self.generation += 1;
+    }
+
+    /// Updates every cell from the previous generation, each with probability
+    /// `alpha`
+    fn step_synchronous(&mut self, alpha: f64) {
+        // Large neighborhoods are counted with a summed-area table, unless the
+        // rule needs to see which neighbors are alive
+        let spans = self.spans.as_ref().filter(|_| !self.rule.needs_configuration());
+        let table = spans.map(|spans| {
+            let table = SummedAreaTable::build(&self.grid, self.boundary.as_ref(), spans.radius());
+            (spans, table)
+        });
+
+        // Rules that read the configuration also get every neighbor's state
+        let mut states = Vec::with_capacity(self.neighborhood.offsets().len());
+        let configuration = self.rule.needs_configuration();
+
         // Calculate next state for all cells
         for y in 0..self.grid.height() {
             for x in 0..self.grid.width() {
                 let pos = Position::new(x, y);
                 let current_state = self.grid.get(pos).unwrap();
-                let alive_neighbors = self.count_alive_neighbors(pos);
-                let next_state = self.rule.apply(current_state, alive_neighbors);
+                if alpha < 1.0 && self.rng.random::<f64>() >= alpha {
+                    self.next_grid.set(pos, current_state).unwrap();
+                    continue;
+                }
+                let neighbors = match &table {
+                    Some((spans, table)) => Neighbors::new(spans.count(table, &self.grid, pos)),
+                    None => self.count_neighbors(pos, configuration.then_some(&mut states)),
+                };
+                let next_state = self.rule.apply_random(current_state, &neighbors, &mut self.rng);
                 self.next_grid.set(pos, next_state).unwrap();
             }
         }
 
-        // Swap grids (double-buffering)
-        std::mem::swap(&mut self.grid, &mut self.next_grid);
+        // Copy the new generation back rather than swapping buffers, so the
+        // grid's storage stays put for views of it such as NumPy arrays
+        self.grid.states_mut().copy_from_slice(self.next_grid.states());
+    }
 
-        // Increment generation counter
-        self.generation += 1;
+    /// Updates cells one at a time in the given order of row-major indices,
+    /// writing each result straight into the grid
+    fn step_in_place(&mut self, order: impl IntoIterator<Item = usize>) {
+        let width = self.grid.width();
+        let mut states = Vec::with_capacity(self.neighborhood.offsets().len());
+        let configuration = self.rule.needs_configuration();
+
+        for index in order {
+            let pos = Position::new(index % width, index / width);
+            let current_state = self.grid.get(pos).unwrap();
+            let neighbors = self.count_neighbors(pos, configuration.then_some(&mut states));
+            let next_state = self.rule.apply_random(current_state, &neighbors, &mut self.rng);
+            self.grid.set(pos, next_state).unwrap();
+        }
     }
 
     /// Advances the simulation by multiple generations
@@ -0,0 +1,143 @@
+use crate::core::boundary_3d::Boundary3D;
+use crate::core::cell::Position3D;
+use crate::core::dense_grid_3d::DenseGrid3D;
+use crate::core::neighborhood_3d::Neighborhood3D;
+use crate::core::rules::{Neighbors, Rule};
+use crate::error::Result;
+
+/// Simulation engine for three-dimensional automata
+///
+/// Works like [`Automata`](crate::core::automata::Automata) on a
+/// [`DenseGrid3D`], applying any totalistic [`Rule`] to the number of live
+/// neighbors of each cell.
+pub struct Automata3D {
+    /// Current grid state
+    grid: DenseGrid3D,
+    /// Next grid state (for double-buffering)
+    next_grid: DenseGrid3D,
+    /// Rule to apply for evolution
+    rule: Box<dyn Rule>,
+    /// Boundary condition for edge handling
+    boundary: Box<dyn Boundary3D>,
+    /// Neighborhood type for neighbor calculation
+    neighborhood: Box<dyn Neighborhood3D>,
+    /// Current generation number
+    generation: u64,
+}
+
+impl Automata3D {
+    /// Creates a new 3D automata instance
+    ///
+    /// # Arguments
+    /// * `width` - Extent of the grid along x
+    /// * `height` - Extent of the grid along y
+    /// * `depth` - Extent of the grid along z
+    /// * `rule` - Rule to apply for evolution
+    /// * `boundary` - Boundary condition for edge handling
+    /// * `neighborhood` - Neighborhood type for neighbor calculation
+    pub fn new(
+        width: usize,
+        height: usize,
+        depth: usize,
+        rule: Box<dyn Rule>,
+        boundary: Box<dyn Boundary3D>,
+        neighborhood: Box<dyn Neighborhood3D>,
+    ) -> Result<Self> {
+        Ok(Self {
+            grid: DenseGrid3D::new(width, height, depth)?,
+            next_grid: DenseGrid3D::new(width, height, depth)?,
+            rule,
+            boundary,
+            neighborhood,
+            generation: 0,
+        })
+    }
+
+    /// Returns a reference to the current rule
+    pub fn rule(&self) -> &dyn Rule {
+        self.rule.as_ref()
+    }
+
+    /// Changes the rule
+    pub fn set_rule(&mut self, rule: Box<dyn Rule>) {
+        self.rule = rule;
+    }
+
+    /// Returns the neighborhood used for neighbor counting
+    pub fn neighborhood(&self) -> &dyn Neighborhood3D {
+        self.neighborhood.as_ref()
+    }
+
+    /// Replaces the neighborhood used for neighbor counting
+    pub fn set_neighborhood(&mut self, neighborhood: Box<dyn Neighborhood3D>) {
+        self.neighborhood = neighborhood;
+    }
+
+    /// Changes the boundary condition
+    pub fn set_boundary(&mut self, boundary: Box<dyn Boundary3D>) {
+        self.boundary = boundary;
+    }
+
+    /// Returns a reference to the current grid
+    pub fn grid(&self) -> &DenseGrid3D {
+        &self.grid
+    }
+
+    /// Returns a mutable reference to the current grid
+    pub fn grid_mut(&mut self) -> &mut DenseGrid3D {
+        &mut self.grid
+    }
+
+    /// Returns the current generation number
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Resets the automata to generation 0 and clears the grid
+    pub fn reset(&mut self) {
+        self.grid.clear();
+        self.next_grid.clear();
+        self.generation = 0;
+    }
+
+    /// Counts alive neighbors for a given position
+    fn count_neighbors(&self, pos: Position3D) -> usize {
+        let (width, height, depth) = (self.grid.width(), self.grid.height(), self.grid.depth());
+        self.neighborhood
+            .offsets()
+            .iter()
+            .filter(|&&(dx, dy, dz)| {
+                let x = pos.x as isize + dx;
+                let y = pos.y as isize + dy;
+                let z = pos.z as isize + dz;
+                self.boundary
+                    .wrap(x, y, z, width, height, depth)
+                    .and_then(|neighbor| self.grid.get(neighbor).ok())
+                    .is_some_and(|state| state.is_alive())
+            })
+            .count()
+    }
+
+    /// Advances the simulation by one generation
+    pub fn step(&mut self) {
+        for (pos, current_state) in self.grid.iter() {
+            let neighbors = Neighbors::new(self.count_neighbors(pos));
+            let next_state = self.rule.apply_neighbors(current_state, &neighbors);
+            self.next_grid.set(pos, next_state).unwrap();
+        }
+
+        // Swap grids (double-buffering)
+        std::mem::swap(&mut self.grid, &mut self.next_grid);
+        self.generation += 1;
+    }
+
+    /// Advances the simulation by multiple generations
+    ///
+    /// # Arguments
+    /// * `steps` - Number of generations to advance
+    pub fn step_n(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+}
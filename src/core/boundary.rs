@@ -1,4 +1,13 @@
-use crate::core::cell::Position;
+use crate::core::cell::{CellState, Position};
+use crate::core::constant_boundary::ConstantBoundary;
+use crate::core::cross_surface_boundary::CrossSurfaceBoundary;
+use crate::core::dead_boundary::DeadBoundary;
+use crate::core::klein_bottle_boundary::KleinBottleBoundary;
+use crate::core::mirror_boundary::MirrorBoundary;
+use crate::core::sphere_boundary::SphereBoundary;
+use crate::core::toroidal_boundary::ToroidalBoundary;
+use crate::core::walled_boundary::WalledBoundary;
+use crate::error::{Error, Result};
 
 /// Trait defining boundary behavior for grid coordinates
 pub trait Boundary: Send + Sync {
@@ -13,4 +22,48 @@ pub trait Boundary: Send + Sync {
     /// # Returns
     /// A valid position within the grid bounds, or None if out of bounds
     fn wrap(&self, x: isize, y: isize, width: usize, height: usize) -> Option<Position>;
-}
\ No newline at end of file
+
+    /// Returns the state read at positions that [`Boundary::wrap`] maps to
+    /// no cell
+    ///
+    /// Dead by default, so cells beyond the edge never count as neighbors.
+    fn outside_state(&self) -> CellState {
+        CellState::Dead
+    }
+}
+
+/// One of the two pairs of opposite grid edges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgePair {
+    /// The top and bottom edges, crossed by moving vertically
+    TopBottom,
+    /// The left and right edges, crossed by moving horizontally
+    LeftRight,
+}
+
+/// Splits a coordinate into the number of times it crosses the grid and its
+/// position within the grid
+pub(crate) fn crossings(coordinate: isize, size: usize) -> (isize, usize) {
+    let size = size as isize;
+    (coordinate.div_euclid(size), coordinate.rem_euclid(size) as usize)
+}
+
+/// Parses an edge behavior by name, ignoring case
+///
+/// Accepted names are `torus`, `plane` (dead cells beyond the edges),
+/// `walled`, `mirror`, `alive` (live cells beyond the edges), `klein`,
+/// `cross` and `sphere`.
+pub fn parse_boundary(name: &str) -> Result<Box<dyn Boundary>> {
+    let boundary: Box<dyn Boundary> = match name.trim().to_ascii_lowercase().as_str() {
+        "torus" | "toroidal" => Box::new(ToroidalBoundary::new()),
+        "plane" | "dead" => Box::new(DeadBoundary::new()),
+        "walled" => Box::new(WalledBoundary::new()),
+        "mirror" => Box::new(MirrorBoundary::new()),
+        "alive" => Box::new(ConstantBoundary::new(CellState::Alive)),
+        "klein" => Box::new(KleinBottleBoundary::new(EdgePair::TopBottom)),
+        "cross" => Box::new(CrossSurfaceBoundary::new()),
+        "sphere" => Box::new(SphereBoundary::new()),
+        _ => return Err(Error::Other(format!("unknown boundary '{}'", name))),
+    };
+    Ok(boundary)
+}
@@ -0,0 +1,29 @@
+use crate::core::cell::Position3D;
+
+/// Trait defining boundary behavior for three-dimensional grid coordinates
+///
+/// The 2D boundaries implement this as well, applying the same rule along
+/// the third axis.
+pub trait Boundary3D: Send + Sync {
+    /// Wraps a position according to the boundary rules
+    ///
+    /// # Arguments
+    /// * `x` - X coordinate (can be negative)
+    /// * `y` - Y coordinate (can be negative)
+    /// * `z` - Z coordinate (can be negative)
+    /// * `width` - Grid extent along x
+    /// * `height` - Grid extent along y
+    /// * `depth` - Grid extent along z
+    ///
+    /// # Returns
+    /// A valid position within the grid bounds, or None if out of bounds
+    fn wrap(
+        &self,
+        x: isize,
+        y: isize,
+        z: isize,
+        width: usize,
+        height: usize,
+        depth: usize,
+    ) -> Option<Position3D>;
+}
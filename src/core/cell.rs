@@ -7,6 +7,12 @@ pub enum CellState {
     Alive,
     /// Cell is dead
     Dead,
+    /// Additional state of a multi-state rule, numbered from 2 upwards
+    ///
+    /// States 0 and 1 are always written as [`CellState::Dead`] and
+    /// [`CellState::Alive`]; use [`CellState::from_index`] to build a state
+    /// from its number.
+    State(u8),
 }
 
 impl CellState {
@@ -21,14 +27,37 @@ impl CellState {
     }
 
     /// Toggles the cell state
+    ///
+    /// Additional states toggle to dead.
     pub fn toggle(&self) -> Self {
         match self {
-            CellState::Alive => CellState::Dead,
+            CellState::Alive | CellState::State(_) => CellState::Dead,
             CellState::Dead => CellState::Alive,
         }
     }
+
+    /// Creates a cell state from its number
+    ///
+    /// 0 is dead, 1 is alive and higher numbers are additional states.
+    pub fn from_index(index: u8) -> Self {
+        match index {
+            0 => CellState::Dead,
+            1 => CellState::Alive,
+            n => CellState::State(n),
+        }
+    }
+
+    /// Returns the number of the state (0 for dead, 1 for alive)
+    pub fn index(&self) -> u8 {
+        match self {
+            CellState::Dead => 0,
+            CellState::Alive => 1,
+            CellState::State(n) => *n,
+        }
+    }
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for CellState {
     fn default() -> Self {
         CellState::Dead
@@ -54,4 +83,26 @@ impl Position {
     pub fn is_within_bounds(&self, width: usize, height: usize) -> bool {
         self.x < width && self.y < height
     }
-}
\ No newline at end of file
+}
+/// Represents a position in a three-dimensional grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Position3D {
+    /// X coordinate (column)
+    pub x: usize,
+    /// Y coordinate (row)
+    pub y: usize,
+    /// Z coordinate (layer)
+    pub z: usize,
+}
+
+impl Position3D {
+    /// Creates a new position
+    pub fn new(x: usize, y: usize, z: usize) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Checks if this position is within the given bounds
+    pub fn is_within_bounds(&self, width: usize, height: usize, depth: usize) -> bool {
+        self.x < width && self.y < height && self.z < depth
+    }
+}
@@ -0,0 +1,46 @@
+use crate::core::neighborhood::{offsets_where, Neighborhood};
+
+/// Circular neighborhood - cells whose centres lie within a disc
+///
+/// A cell at offset (dx, dy) is a neighbor when its centre is within
+/// `r + 0.5` of the centre cell, i.e. dx² + dy² <= r² + r. This matches
+/// Golly's `NC` Larger than Life neighborhood.
+///
+/// Pattern for r = 2:
+/// ```text
+///   X X X
+/// X X X X X
+/// X X O X X
+/// X X X X X
+///   X X X
+/// ```
+#[derive(Debug, Clone)]
+pub struct CircularNeighborhood {
+    offsets: Vec<(isize, isize)>,
+    radius: usize,
+    name: String,
+}
+
+impl CircularNeighborhood {
+    /// Creates a circular neighborhood of the given range
+    pub fn new(radius: usize) -> Self {
+        let r = radius as isize;
+        let offsets = offsets_where(radius, |dx, dy| dx * dx + dy * dy <= r * r + r);
+        let name = format!("Circular (r={}, {} neighbors)", radius, offsets.len());
+        Self { offsets, radius, name }
+    }
+}
+
+impl Neighborhood for CircularNeighborhood {
+    fn offsets(&self) -> &[(isize, isize)] {
+        &self.offsets
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn radius(&self) -> usize {
+        self.radius
+    }
+}
@@ -0,0 +1,46 @@
+use crate::core::boundary::Boundary;
+use crate::core::cell::{CellState, Position};
+
+/// Constant boundary - a plane surrounded by cells fixed in one state
+///
+/// Positions beyond the edges have no cell and read as the configured
+/// state, so with [`CellState::Alive`] the grid sits inside a permanently
+/// live frame. The border cells are never updated.
+#[derive(Debug, Clone, Copy)]
+pub struct ConstantBoundary {
+    state: CellState,
+}
+
+impl ConstantBoundary {
+    /// Creates a constant boundary
+    ///
+    /// # Arguments
+    /// * `state` - State of every cell beyond the grid edges
+    pub fn new(state: CellState) -> Self {
+        Self { state }
+    }
+
+    /// Returns the state of the cells beyond the grid edges
+    pub fn state(&self) -> CellState {
+        self.state
+    }
+}
+
+impl Default for ConstantBoundary {
+    fn default() -> Self {
+        Self::new(CellState::Alive)
+    }
+}
+
+impl Boundary for ConstantBoundary {
+    fn wrap(&self, x: isize, y: isize, width: usize, height: usize) -> Option<Position> {
+        if x < 0 || y < 0 || x as usize >= width || y as usize >= height {
+            return None;
+        }
+        Some(Position::new(x as usize, y as usize))
+    }
+
+    fn outside_state(&self) -> CellState {
+        self.state
+    }
+}
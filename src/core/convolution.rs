@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use rustfft::num_complex::Complex;
+use rustfft::{Fft, FftPlanner};
+
+use crate::core::float_grid::FloatGrid;
+use crate::core::kernel_neighborhood::KernelNeighborhood;
+
+/// Kernel size from which [`Convolution::new`] switches from summing each
+/// offset to multiplying spectra with an FFT
+pub const FFT_MIN_NEIGHBORS: usize = 64;
+
+/// Convolution of a [`FloatGrid`] with a kernel on a torus
+///
+/// The grid wraps at its edges in both directions. Small kernels are applied
+/// offset by offset; large ones are applied in the frequency domain, with
+/// the kernel's spectrum computed once for the grid size.
+pub struct Convolution {
+    width: usize,
+    height: usize,
+    method: Method,
+}
+
+enum Method {
+    Direct {
+        offsets: Vec<(isize, isize)>,
+        weights: Vec<f64>,
+    },
+    Fft(Box<FftPlan>),
+}
+
+/// Precomputed transforms for one grid size
+struct FftPlan {
+    row_forward: Arc<dyn Fft<f64>>,
+    row_inverse: Arc<dyn Fft<f64>>,
+    column_forward: Arc<dyn Fft<f64>>,
+    column_inverse: Arc<dyn Fft<f64>>,
+    /// Spectrum of the kernel laid out on the grid
+    kernel: Vec<Complex<f64>>,
+    /// Working buffer, row by row
+    buffer: Vec<Complex<f64>>,
+    /// Working buffer for the transposed grid
+    transposed: Vec<Complex<f64>>,
+}
+
+impl Convolution {
+    /// Prepares a convolution, choosing the FFT for kernels of at least
+    /// [`FFT_MIN_NEIGHBORS`] offsets
+    pub fn new(kernel: &KernelNeighborhood, width: usize, height: usize) -> Self {
+        if kernel.offsets().len() >= FFT_MIN_NEIGHBORS {
+            Self::fft(kernel, width, height)
+        } else {
+            Self::direct(kernel, width, height)
+        }
+    }
+
+    /// Prepares a convolution that sums every offset for every cell
+    pub fn direct(kernel: &KernelNeighborhood, width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            method: Method::Direct {
+                offsets: kernel.offsets().to_vec(),
+                weights: kernel.weights().to_vec(),
+            },
+        }
+    }
+
+    /// Prepares a convolution through the fast Fourier transform
+    pub fn fft(kernel: &KernelNeighborhood, width: usize, height: usize) -> Self {
+        let mut planner = FftPlanner::new();
+        let mut plan = FftPlan {
+            row_forward: planner.plan_fft_forward(width),
+            row_inverse: planner.plan_fft_inverse(width),
+            column_forward: planner.plan_fft_forward(height),
+            column_inverse: planner.plan_fft_inverse(height),
+            kernel: vec![Complex::default(); width * height],
+            buffer: vec![Complex::default(); width * height],
+            transposed: vec![Complex::default(); width * height],
+        };
+
+        // Each cell gathers from cell + offset, so the kernel is mirrored
+        // before it is transformed
+        for (&(dx, dy), &weight) in kernel.offsets().iter().zip(kernel.weights()) {
+            let x = (-dx).rem_euclid(width as isize) as usize;
+            let y = (-dy).rem_euclid(height as isize) as usize;
+            plan.buffer[y * width + x].re += weight;
+        }
+        plan.transform(width, height, true);
+        plan.kernel.copy_from_slice(&plan.buffer);
+
+        Self {
+            width,
+            height,
+            method: Method::Fft(Box::new(plan)),
+        }
+    }
+
+    /// Returns true if the convolution runs through the FFT
+    pub fn is_fft(&self) -> bool {
+        matches!(self.method, Method::Fft(_))
+    }
+
+    /// Computes the weighted neighborhood sum of every cell
+    ///
+    /// # Arguments
+    /// * `grid` - Grid of the size the convolution was prepared for
+    /// * `output` - Receives one value per cell, row by row
+    pub fn apply(&mut self, grid: &FloatGrid, output: &mut Vec<f64>) {
+        let (width, height) = (self.width, self.height);
+        debug_assert_eq!((grid.width(), grid.height()), (width, height));
+        let values = grid.values();
+        output.clear();
+
+        match &mut self.method {
+            Method::Direct { offsets, weights } => {
+                for y in 0..height as isize {
+                    for x in 0..width as isize {
+                        let sum = offsets
+                            .iter()
+                            .zip(weights.iter())
+                            .map(|(&(dx, dy), &weight)| {
+                                let nx = (x + dx).rem_euclid(width as isize) as usize;
+                                let ny = (y + dy).rem_euclid(height as isize) as usize;
+                                weight * values[ny * width + nx]
+                            })
+                            .sum();
+                        output.push(sum);
+                    }
+                }
+            }
+            Method::Fft(plan) => {
+                for (cell, &value) in plan.buffer.iter_mut().zip(values) {
+                    *cell = Complex::new(value, 0.0);
+                }
+                plan.transform(width, height, true);
+                for (cell, &kernel) in plan.buffer.iter_mut().zip(&plan.kernel) {
+                    *cell *= kernel;
+                }
+                plan.transform(width, height, false);
+                let scale = 1.0 / (width * height) as f64;
+                output.extend(plan.buffer.iter().map(|cell| cell.re * scale));
+            }
+        }
+    }
+}
+
+impl FftPlan {
+    /// Transforms `buffer` in place along rows and then columns
+    fn transform(&mut self, width: usize, height: usize, forward: bool) {
+        let (rows, columns) = if forward {
+            (&self.row_forward, &self.column_forward)
+        } else {
+            (&self.row_inverse, &self.column_inverse)
+        };
+        rows.process(&mut self.buffer);
+        transpose(&self.buffer, &mut self.transposed, width, height);
+        columns.process(&mut self.transposed);
+        transpose(&self.transposed, &mut self.buffer, height, width);
+    }
+}
+
+/// Transposes a `width` x `height` row-major matrix
+fn transpose(input: &[Complex<f64>], output: &mut [Complex<f64>], width: usize, height: usize) {
+    for y in 0..height {
+        for x in 0..width {
+            output[x * height + y] = input[y * width + x];
+        }
+    }
+}
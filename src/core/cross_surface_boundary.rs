@@ -0,0 +1,41 @@
+use crate::core::boundary::{crossings, Boundary};
+use crate::core::cell::Position;
+
+/// Cross-surface boundary - the real projective plane
+///
+/// Both pairs of edges are joined with a twist: crossing the top or bottom
+/// edge mirrors the x coordinate, and crossing the left or right edge
+/// mirrors the y coordinate.
+#[derive(Debug, Clone, Copy)]
+pub struct CrossSurfaceBoundary;
+
+impl CrossSurfaceBoundary {
+    /// Creates a new cross-surface boundary
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for CrossSurfaceBoundary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Boundary for CrossSurfaceBoundary {
+    fn wrap(&self, x: isize, y: isize, width: usize, height: usize) -> Option<Position> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        let (x_crossings, mut wrapped_x) = crossings(x, width);
+        let (y_crossings, mut wrapped_y) = crossings(y, height);
+        if y_crossings % 2 != 0 {
+            wrapped_x = width - 1 - wrapped_x;
+        }
+        if x_crossings % 2 != 0 {
+            wrapped_y = height - 1 - wrapped_y;
+        }
+        Some(Position::new(wrapped_x, wrapped_y))
+    }
+}
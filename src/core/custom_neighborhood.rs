@@ -0,0 +1,171 @@
+use std::path::Path;
+
+use crate::core::neighborhood::Neighborhood;
+use crate::error::{Error, Result};
+
+/// User-defined neighborhood with optional integer weights
+///
+/// Built from an explicit offset list or from an ASCII mask centred on the
+/// cell. Unlike the built-in neighborhoods, the centre cell may be part of
+/// the neighborhood, and each neighbor may contribute a weight other than 1
+/// to the weighted neighbor sum seen by rules.
+///
+/// Character masks use one character per cell:
+/// ```text
+/// .X.
+/// XOX
+/// .X.
+/// ```
+/// `.` and `0` are not neighbors, `O` marks an excluded centre, `X`, `*`
+/// and `@` are neighbors of weight 1, and digits `1`-`9` give the weight
+/// directly. Masks with whitespace-separated tokens accept any integer
+/// weight, including negative ones:
+/// ```text
+///  2 -1  2
+/// -1  0 -1
+///  2 -1  2
+/// ```
+/// Lines starting with `#` are comments.
+#[derive(Debug, Clone)]
+pub struct CustomNeighborhood {
+    offsets: Vec<(isize, isize)>,
+    /// Weight of each offset, or `None` if every neighbor counts as 1
+    weights: Option<Vec<i32>>,
+    name: String,
+}
+
+impl CustomNeighborhood {
+    /// Creates a neighborhood from a list of offsets, each of weight 1
+    pub fn from_offsets(offsets: Vec<(isize, isize)>) -> Self {
+        let name = format!("Custom ({} neighbors)", offsets.len());
+        Self {
+            offsets,
+            weights: None,
+            name,
+        }
+    }
+
+    /// Creates a neighborhood from offsets paired with integer weights
+    pub fn from_weighted_offsets(weighted: Vec<((isize, isize), i32)>) -> Self {
+        let (offsets, weights): (Vec<_>, Vec<_>) = weighted.into_iter().unzip();
+        let name = format!("Weighted ({} neighbors)", offsets.len());
+        let weights = if weights.iter().all(|&w| w == 1) {
+            None
+        } else {
+            Some(weights)
+        };
+        Self {
+            offsets,
+            weights,
+            name,
+        }
+    }
+
+    /// Parses a neighborhood from an ASCII mask
+    ///
+    /// The mask must have an odd number of rows and columns so that the
+    /// centre cell is well defined.
+    pub fn from_mask(mask: &str) -> Result<Self> {
+        let rows: Vec<&str> = mask
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+
+        let tokenized = rows.iter().any(|row| row.contains(char::is_whitespace));
+        let weights: Vec<Vec<i32>> = rows
+            .iter()
+            .map(|row| {
+                if tokenized {
+                    row.split_whitespace().map(parse_token).collect()
+                } else {
+                    row.chars().map(parse_char).collect()
+                }
+            })
+            .collect::<Result<_>>()?;
+
+        let height = weights.len();
+        let width = weights.first().map(Vec::len).unwrap_or(0);
+        if height.is_multiple_of(2) || width.is_multiple_of(2) {
+            return Err(Error::PatternParseError(format!(
+                "neighborhood mask must have odd dimensions, got {}x{}",
+                width, height
+            )));
+        }
+        if let Some(row) = weights.iter().find(|row| row.len() != width) {
+            return Err(Error::PatternParseError(format!(
+                "neighborhood mask rows must all have {} cells, found one with {}",
+                width,
+                row.len()
+            )));
+        }
+
+        let cx = (width / 2) as isize;
+        let cy = (height / 2) as isize;
+        let weighted = weights
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter().enumerate().filter(|(_, &w)| w != 0).map(move |(x, &w)| {
+                    ((x as isize - cx, y as isize - cy), w)
+                })
+            })
+            .collect();
+
+        Ok(Self::from_weighted_offsets(weighted))
+    }
+
+    /// Loads a neighborhood mask from a file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let text = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| Error::IoError(format!("{}: {}", path.as_ref().display(), e)))?;
+        Self::from_mask(&text)
+    }
+
+    /// Sets the display name of the neighborhood
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Returns true if the centre cell is part of the neighborhood
+    pub fn includes_center(&self) -> bool {
+        self.offsets.contains(&(0, 0))
+    }
+}
+
+impl Neighborhood for CustomNeighborhood {
+    fn offsets(&self) -> &[(isize, isize)] {
+        &self.offsets
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn weights(&self) -> Option<&[i32]> {
+        self.weights.as_deref()
+    }
+}
+
+fn parse_char(c: char) -> Result<i32> {
+    match c {
+        '.' | '0' | 'O' | 'o' => Ok(0),
+        'X' | 'x' | '*' | '@' => Ok(1),
+        '1'..='9' => Ok(c as i32 - '0' as i32),
+        _ => Err(Error::PatternParseError(format!(
+            "unexpected character '{}' in neighborhood mask",
+            c
+        ))),
+    }
+}
+
+fn parse_token(token: &str) -> Result<i32> {
+    match token {
+        "." | "O" | "o" => Ok(0),
+        "X" | "x" | "*" | "@" => Ok(1),
+        _ => token.parse().map_err(|_| {
+            Error::PatternParseError(format!("unexpected token '{}' in neighborhood mask", token))
+        }),
+    }
+}
@@ -0,0 +1,32 @@
+use crate::core::boundary::Boundary;
+use crate::core::cell::Position;
+
+/// Dead boundary - a plane surrounded by dead cells
+///
+/// Every position beyond any edge has no cell, so it reads as dead and
+/// never counts as a neighbor. All four edges behave the same way. This is
+/// the bounded plane (`P w,h`) of Golly.
+#[derive(Debug, Clone, Copy)]
+pub struct DeadBoundary;
+
+impl DeadBoundary {
+    /// Creates a new dead boundary
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for DeadBoundary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Boundary for DeadBoundary {
+    fn wrap(&self, x: isize, y: isize, width: usize, height: usize) -> Option<Position> {
+        if x < 0 || y < 0 || x as usize >= width || y as usize >= height {
+            return None;
+        }
+        Some(Position::new(x as usize, y as usize))
+    }
+}
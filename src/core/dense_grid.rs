@@ -1,12 +1,18 @@
 use crate::core::cell::{CellState, Position};
 use crate::core::grid::Grid;
+use crate::core::transform::Transform;
 use crate::error::{Error, Result};
 use rand::Rng;
 
 /// Dense grid implementation using a flat vector
-#[derive(Debug, Clone)]
+///
+/// Each cell is stored as its state number, one byte per cell row by row,
+/// so the cells can be handed to other code as a plain byte buffer with
+/// [`states`](Self::states) and [`states_mut`](Self::states_mut).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DenseGrid {
-    cells: Vec<CellState>,
+    /// State number of each cell, see [`CellState::index`]
+    cells: Vec<u8>,
     width: usize,
     height: usize,
 }
@@ -26,7 +32,7 @@ impl DenseGrid {
         }
 
         Ok(Self {
-            cells: vec![CellState::Dead; width * height],
+            cells: vec![0; width * height],
             width,
             height,
         })
@@ -46,11 +52,7 @@ impl DenseGrid {
         let mut rng = rand::rng();
         let cells = (0..width * height)
             .map(|_| {
-                if rng.random::<f64>() < alive_probability {
-                    CellState::Alive
-                } else {
-                    CellState::Dead
-                }
+                u8::from(rng.random::<f64>() < alive_probability)
             })
             .collect();
 
@@ -74,7 +76,7 @@ impl DenseGrid {
             });
         }
 
-        let mut new_cells = vec![CellState::Dead; new_width * new_height];
+        let mut new_cells = vec![0; new_width * new_height];
 
         // Copy existing cells that fit in the new dimensions
         let copy_width = self.width.min(new_width);
@@ -113,20 +115,284 @@ impl DenseGrid {
         self.cells.iter().enumerate().map(|(index, &state)| {
             let x = index % self.width;
             let y = index / self.width;
-            (Position::new(x, y), state)
+            (Position::new(x, y), CellState::from_index(state))
         })
     }
+
+    /// Returns the state number of every cell, row by row
+    ///
+    /// This is the grid's own storage, not a copy.
+    pub fn states(&self) -> &[u8] {
+        &self.cells
+    }
+
+    /// Returns the state numbers of the cells for writing, row by row
+    ///
+    /// Any byte is a valid state, see [`CellState::from_index`]. The buffer
+    /// only moves when the grid is resized.
+    pub fn states_mut(&mut self) -> &mut [u8] {
+        &mut self.cells
+    }
+
+    /// Writes the state number of every cell into a buffer, row by row
+    ///
+    /// # Errors
+    /// Returns an error if the buffer does not hold exactly one byte per cell
+    pub fn copy_states_to(&self, states: &mut [u8]) -> Result<()> {
+        self.check_state_count(states.len())?;
+        states.copy_from_slice(&self.cells);
+        Ok(())
+    }
+
+    /// Sets every cell from state numbers, row by row
+    ///
+    /// # Errors
+    /// Returns an error if there is not exactly one state per cell
+    pub fn copy_states_from(&mut self, states: &[u8]) -> Result<()> {
+        self.check_state_count(states.len())?;
+        self.cells.copy_from_slice(states);
+        Ok(())
+    }
+
+    /// Checks that a buffer of state numbers covers the grid exactly
+    fn check_state_count(&self, count: usize) -> Result<()> {
+        if count != self.cells.len() {
+            return Err(Error::Other(format!(
+                "expected {} states for a {}x{} grid, got {}",
+                self.cells.len(),
+                self.width,
+                self.height,
+                count
+            )));
+        }
+        Ok(())
+    }
+
+    /// Copies a rectangular region into a new grid
+    ///
+    /// # Arguments
+    /// * `origin` - Top-left cell of the region
+    /// * `width` - Width of the region
+    /// * `height` - Height of the region
+    ///
+    /// # Errors
+    /// Returns an error if the region is empty or extends past the grid
+    pub fn extract(&self, origin: Position, width: usize, height: usize) -> Result<DenseGrid> {
+        let mut region = DenseGrid::new(width, height)?;
+        let far = Position::new(origin.x + width - 1, origin.y + height - 1);
+        self.pos_to_index(far)?;
+
+        for y in 0..height {
+            let start = (origin.y + y) * self.width + origin.x;
+            region.cells[y * width..(y + 1) * width]
+                .copy_from_slice(&self.cells[start..start + width]);
+        }
+        Ok(region)
+    }
+
+    /// Copies the non-dead cells of a pattern onto the grid
+    ///
+    /// Dead cells of the pattern leave the grid untouched, and cells that
+    /// fall past the right or bottom edge are dropped.
+    ///
+    /// # Arguments
+    /// * `pattern` - Cells to copy
+    /// * `origin` - Cell the top-left corner of the pattern lands on
+    pub fn stamp(&mut self, pattern: &DenseGrid, origin: Position) {
+        for (pos, state) in pattern.iter() {
+            let target = Position::new(origin.x + pos.x, origin.y + pos.y);
+            if !state.is_dead() && target.is_within_bounds(self.width, self.height) {
+                self.cells[target.y * self.width + target.x] = state.index();
+            }
+        }
+    }
+
+    /// Sets every cell of a rectangular region to one state, clipping the
+    /// region to the grid
+    ///
+    /// # Arguments
+    /// * `origin` - Top-left cell of the region
+    /// * `width` - Width of the region
+    /// * `height` - Height of the region
+    /// * `state` - State to fill with
+    pub fn fill_region(&mut self, origin: Position, width: usize, height: usize, state: CellState) {
+        self.map_region(origin, width, height, |_| state);
+    }
+
+    /// Fills a rectangular region with random live and dead cells, clipping
+    /// the region to the grid
+    ///
+    /// # Arguments
+    /// * `origin` - Top-left cell of the region
+    /// * `width` - Width of the region
+    /// * `height` - Height of the region
+    /// * `alive_probability` - Probability (0.0 to 1.0) that a cell is alive
+    pub fn randomize_region(
+        &mut self,
+        origin: Position,
+        width: usize,
+        height: usize,
+        alive_probability: f64,
+    ) {
+        let mut rng = rand::rng();
+        self.map_region(origin, width, height, |_| {
+            if rng.random::<f64>() < alive_probability {
+                CellState::Alive
+            } else {
+                CellState::Dead
+            }
+        });
+    }
+
+    /// Replaces every cell of a region, clipped to the grid, with `f(cell)`
+    fn map_region(
+        &mut self,
+        origin: Position,
+        width: usize,
+        height: usize,
+        mut f: impl FnMut(CellState) -> CellState,
+    ) {
+        let x_end = (origin.x + width).min(self.width);
+        let y_end = (origin.y + height).min(self.height);
+        for y in origin.y..y_end {
+            for x in origin.x..x_end {
+                let cell = &mut self.cells[y * self.width + x];
+                *cell = f(CellState::from_index(*cell)).index();
+            }
+        }
+    }
+
+    /// Returns a copy of the grid rotated or reflected
+    ///
+    /// Rotations by 90 or 270 degrees and diagonal reflections swap the
+    /// width and height.
+    pub fn transform(&self, transform: Transform) -> DenseGrid {
+        let (width, height) = if transform.swaps_axes() {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        };
+        let mut cells = vec![0; width * height];
+        for (index, &state) in self.cells.iter().enumerate() {
+            let pos = Position::new(index % self.width, index / self.width);
+            let target = transform.apply(pos, self.width, self.height);
+            cells[target.y * width + target.x] = state;
+        }
+        DenseGrid { cells, width, height }
+    }
+
+    /// Returns a copy of the grid rotated a quarter turn clockwise
+    pub fn rotate_clockwise(&self) -> DenseGrid {
+        self.transform(Transform::Rotate90)
+    }
+
+    /// Returns a copy of the grid rotated a quarter turn anticlockwise
+    pub fn rotate_counterclockwise(&self) -> DenseGrid {
+        self.transform(Transform::Rotate270)
+    }
+
+    /// Returns a copy of the grid rotated half a turn
+    pub fn rotate_180(&self) -> DenseGrid {
+        self.transform(Transform::Rotate180)
+    }
+
+    /// Returns a copy of the grid mirrored left to right
+    pub fn flip_horizontal(&self) -> DenseGrid {
+        self.transform(Transform::FlipHorizontal)
+    }
+
+    /// Returns a copy of the grid mirrored top to bottom
+    pub fn flip_vertical(&self) -> DenseGrid {
+        self.transform(Transform::FlipVertical)
+    }
+
+    /// Returns a copy of the grid mirrored across its main diagonal
+    pub fn flip_diagonal(&self) -> DenseGrid {
+        self.transform(Transform::FlipDiagonal)
+    }
+
+    /// Returns a copy of the grid with every cell moved by an offset
+    ///
+    /// The grid keeps its size: cells moved past an edge are dropped and
+    /// the cells uncovered on the other side are dead.
+    ///
+    /// # Arguments
+    /// * `dx` - Columns to move right (negative moves left)
+    /// * `dy` - Rows to move down (negative moves up)
+    pub fn translate(&self, dx: isize, dy: isize) -> DenseGrid {
+        let mut moved = DenseGrid {
+            cells: vec![0; self.cells.len()],
+            width: self.width,
+            height: self.height,
+        };
+        for (pos, state) in self.iter() {
+            let x = pos.x as isize + dx;
+            let y = pos.y as isize + dy;
+            if x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height {
+                moved.cells[y as usize * self.width + x as usize] = state.index();
+            }
+        }
+        moved
+    }
+
+    /// Returns the smallest rectangle holding every non-dead cell, as its
+    /// top-left cell, width and height
+    ///
+    /// # Returns
+    /// None if every cell is dead
+    pub fn bounding_box(&self) -> Option<(Position, usize, usize)> {
+        let mut cells = self.iter().filter(|(_, state)| !state.is_dead()).map(|(pos, _)| pos);
+        let first = cells.next()?;
+        let (min, max) = cells.fold((first, first), |(min, max), pos| {
+            (
+                Position::new(min.x.min(pos.x), min.y.min(pos.y)),
+                Position::new(max.x.max(pos.x), max.y.max(pos.y)),
+            )
+        });
+        Some((min, max.x - min.x + 1, max.y - min.y + 1))
+    }
+
+    /// Returns a copy of the grid cropped to its [bounding box](Self::bounding_box)
+    ///
+    /// # Returns
+    /// None if every cell is dead
+    pub fn crop(&self) -> Option<DenseGrid> {
+        let (origin, width, height) = self.bounding_box()?;
+        self.extract(origin, width, height).ok()
+    }
+
+    /// Returns the pattern in a canonical orientation
+    ///
+    /// The grid is cropped to its bounding box, and of its eight rotations
+    /// and reflections the smallest is returned, comparing height, then
+    /// width, then the cell states row by row. Two patterns are the same up
+    /// to rotation, reflection and position exactly when their canonical
+    /// forms are equal. An empty grid becomes a single dead cell.
+    pub fn canonical(&self) -> DenseGrid {
+        let Some(cropped) = self.crop() else {
+            return DenseGrid {
+                cells: vec![0],
+                width: 1,
+                height: 1,
+            };
+        };
+        Transform::ALL
+            .iter()
+            .map(|&transform| cropped.transform(transform))
+            .min_by_key(|grid| (grid.height, grid.width, grid.cells.clone()))
+            .unwrap_or(cropped)
+    }
 }
 
 impl Grid for DenseGrid {
     fn get(&self, pos: Position) -> Result<CellState> {
         let index = self.pos_to_index(pos)?;
-        Ok(self.cells[index])
+        Ok(CellState::from_index(self.cells[index]))
     }
 
     fn set(&mut self, pos: Position, state: CellState) -> Result<()> {
         let index = self.pos_to_index(pos)?;
-        self.cells[index] = state;
+        self.cells[index] = state.index();
         Ok(())
     }
 
@@ -139,10 +405,10 @@ impl Grid for DenseGrid {
     }
 
     fn clear(&mut self) {
-        self.cells.fill(CellState::Dead);
+        self.cells.fill(0);
     }
 
     fn count_alive(&self) -> usize {
-        self.cells.iter().filter(|c| c.is_alive()).count()
+        self.cells.iter().filter(|&&state| state == 1).count()
     }
 }
\ No newline at end of file
@@ -0,0 +1,189 @@
+use crate::core::cell::{CellState, Position, Position3D};
+use crate::core::dense_grid::DenseGrid;
+use crate::core::grid::Grid;
+use crate::error::{Error, Result};
+use rand::Rng;
+
+/// Axis of a three-dimensional grid, used to pick slices
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Axis {
+    /// Slices are planes of constant x, seen as (y, z) grids
+    X,
+    /// Slices are planes of constant y, seen as (x, z) grids
+    Y,
+    /// Slices are planes of constant z, seen as (x, y) grids
+    #[default]
+    Z,
+}
+
+/// Three-dimensional grid using a flat vector
+///
+/// Cells are stored layer by layer, each layer row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenseGrid3D {
+    cells: Vec<CellState>,
+    width: usize,
+    height: usize,
+    depth: usize,
+}
+
+impl DenseGrid3D {
+    /// Creates a new grid with the specified dimensions
+    ///
+    /// # Arguments
+    /// * `width` - Extent along x
+    /// * `height` - Extent along y
+    /// * `depth` - Extent along z
+    ///
+    /// # Returns
+    /// A new grid with all cells initialized to dead
+    pub fn new(width: usize, height: usize, depth: usize) -> Result<Self> {
+        check_dimensions(width, height, depth)?;
+        Ok(Self {
+            cells: vec![CellState::Dead; width * height * depth],
+            width,
+            height,
+            depth,
+        })
+    }
+
+    /// Creates a new grid with random initial state
+    ///
+    /// # Arguments
+    /// * `width` - Extent along x
+    /// * `height` - Extent along y
+    /// * `depth` - Extent along z
+    /// * `alive_probability` - Probability (0.0 to 1.0) that a cell is alive
+    pub fn new_random(
+        width: usize,
+        height: usize,
+        depth: usize,
+        alive_probability: f64,
+    ) -> Result<Self> {
+        let mut grid = Self::new(width, height, depth)?;
+        let mut rng = rand::rng();
+        for cell in &mut grid.cells {
+            if rng.random::<f64>() < alive_probability {
+                *cell = CellState::Alive;
+            }
+        }
+        Ok(grid)
+    }
+
+    /// Returns the extent along x
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the extent along y
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the extent along z
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the total number of cells in the grid
+    pub fn size(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Gets the cell state at the given position
+    pub fn get(&self, pos: Position3D) -> Result<CellState> {
+        let index = self.pos_to_index(pos)?;
+        Ok(self.cells[index])
+    }
+
+    /// Sets the cell state at the given position
+    pub fn set(&mut self, pos: Position3D, state: CellState) -> Result<()> {
+        let index = self.pos_to_index(pos)?;
+        self.cells[index] = state;
+        Ok(())
+    }
+
+    /// Clears the grid (sets all cells to dead)
+    pub fn clear(&mut self) {
+        self.cells.fill(CellState::Dead);
+    }
+
+    /// Counts the number of alive cells in the grid
+    pub fn count_alive(&self) -> usize {
+        self.cells.iter().filter(|c| c.is_alive()).count()
+    }
+
+    /// Returns an iterator over all positions and their cell states
+    pub fn iter(&self) -> impl Iterator<Item = (Position3D, CellState)> + '_ {
+        self.cells.iter().enumerate().map(|(index, &state)| {
+            let x = index % self.width;
+            let y = index / self.width % self.height;
+            let z = index / (self.width * self.height);
+            (Position3D::new(x, y, z), state)
+        })
+    }
+
+    /// Copies one plane of the grid into a two-dimensional grid
+    ///
+    /// # Arguments
+    /// * `axis` - Axis the plane is perpendicular to
+    /// * `index` - Coordinate of the plane along that axis
+    ///
+    /// # Returns
+    /// The plane as a grid whose columns and rows follow the remaining axes
+    /// in x, y, z order
+    pub fn slice(&self, axis: Axis, index: usize) -> Result<DenseGrid> {
+        let (columns, rows) = self.slice_size(axis);
+        let mut slice = DenseGrid::new(columns, rows)?;
+        for row in 0..rows {
+            for column in 0..columns {
+                let state = self.get(Self::slice_position(axis, index, column, row))?;
+                slice.set(Position::new(column, row), state)?;
+            }
+        }
+        Ok(slice)
+    }
+
+    /// Returns the width and height of the slices perpendicular to an axis
+    pub fn slice_size(&self, axis: Axis) -> (usize, usize) {
+        match axis {
+            Axis::X => (self.height, self.depth),
+            Axis::Y => (self.width, self.depth),
+            Axis::Z => (self.width, self.height),
+        }
+    }
+
+    /// Returns the cell at a column and row of a slice, the inverse of
+    /// [`DenseGrid3D::slice`]
+    pub fn slice_position(axis: Axis, index: usize, column: usize, row: usize) -> Position3D {
+        match axis {
+            Axis::X => Position3D::new(index, column, row),
+            Axis::Y => Position3D::new(column, index, row),
+            Axis::Z => Position3D::new(column, row, index),
+        }
+    }
+
+    /// Converts a position to a vector index
+    fn pos_to_index(&self, pos: Position3D) -> Result<usize> {
+        if !pos.is_within_bounds(self.width, self.height, self.depth) {
+            return Err(Error::Other(format!(
+                "Position ({}, {}, {}) is out of bounds for grid {}x{}x{}",
+                pos.x, pos.y, pos.z, self.width, self.height, self.depth
+            )));
+        }
+        Ok((pos.z * self.height + pos.y) * self.width + pos.x)
+    }
+}
+
+fn check_dimensions(width: usize, height: usize, depth: usize) -> Result<()> {
+    if width == 0 || height == 0 {
+        return Err(Error::InvalidDimensions { width, height });
+    }
+    if depth == 0 {
+        return Err(Error::Other(format!(
+            "Invalid grid dimensions: {}x{}x{}",
+            width, height, depth
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,230 @@
+use crate::core::cell::{CellState, Position};
+use crate::core::dense_grid::DenseGrid;
+use crate::core::grid::Grid;
+use crate::core::rules::Rule1D;
+use crate::error::{Error, Result};
+
+/// Edge handling for one-dimensional automata
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EdgeCondition {
+    /// The row wraps around, so the leftmost and rightmost cells are neighbors
+    #[default]
+    Periodic,
+    /// Cells beyond either edge are held at a fixed state
+    Fixed(u8),
+}
+
+/// One-dimensional cellular automaton
+///
+/// Holds a single row of cells and evolves it with a [`Rule1D`], such as
+/// a [`WolframRule`](crate::core::rules::WolframRule) or a
+/// [`TotalisticCodeRule`](crate::core::rules::TotalisticCodeRule).
+pub struct ElementaryAutomata {
+    /// Current row of cell states
+    cells: Vec<u8>,
+    /// Next row of cell states (for double-buffering)
+    next_cells: Vec<u8>,
+    /// Rule to apply for evolution
+    rule: Box<dyn Rule1D>,
+    /// Edge handling for cells beyond the row
+    edge: EdgeCondition,
+    /// Current generation number
+    generation: u64,
+}
+
+impl ElementaryAutomata {
+    /// Creates a new one-dimensional automaton with all cells in state 0
+    ///
+    /// # Arguments
+    /// * `width` - Number of cells in the row
+    /// * `rule` - Rule to apply for evolution
+    /// * `edge` - Edge handling for cells beyond the row
+    pub fn new(width: usize, rule: Box<dyn Rule1D>, edge: EdgeCondition) -> Result<Self> {
+        if width == 0 {
+            return Err(Error::InvalidDimensions { width, height: 1 });
+        }
+
+        Ok(Self {
+            cells: vec![0; width],
+            next_cells: vec![0; width],
+            rule,
+            edge,
+            generation: 0,
+        })
+    }
+
+    /// Returns the number of cells in the row
+    pub fn width(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns the current row of cell states
+    pub fn cells(&self) -> &[u8] {
+        &self.cells
+    }
+
+    /// Returns the rule driving the automaton
+    pub fn rule(&self) -> &dyn Rule1D {
+        self.rule.as_ref()
+    }
+
+    /// Replaces the rule driving the automaton
+    pub fn set_rule(&mut self, rule: Box<dyn Rule1D>) {
+        self.rule = rule;
+    }
+
+    /// Returns the edge condition
+    pub fn edge(&self) -> EdgeCondition {
+        self.edge
+    }
+
+    /// Returns the current generation number
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Gets the state of the cell at `x`
+    pub fn get(&self, x: usize) -> Result<u8> {
+        self.cells.get(x).copied().ok_or(Error::OutOfBounds {
+            x,
+            y: 0,
+            width: self.cells.len(),
+            height: 1,
+        })
+    }
+
+    /// Sets the state of the cell at `x`
+    pub fn set(&mut self, x: usize, state: u8) -> Result<()> {
+        let width = self.cells.len();
+        let cell = self
+            .cells
+            .get_mut(x)
+            .ok_or(Error::OutOfBounds { x, y: 0, width, height: 1 })?;
+        *cell = state;
+        Ok(())
+    }
+
+    /// Clears the row and sets only the centre cell to state 1
+    ///
+    /// This is the standard initial condition for elementary automata diagrams.
+    pub fn seed_center(&mut self) {
+        self.cells.fill(0);
+        let center = self.cells.len() / 2;
+        self.cells[center] = 1;
+    }
+
+    /// Resets the automaton to generation 0 and clears the row
+    pub fn reset(&mut self) {
+        self.cells.fill(0);
+        self.next_cells.fill(0);
+        self.generation = 0;
+    }
+
+    /// Returns the state of the cell at `x`, applying the edge condition
+    fn cell_at(&self, x: isize) -> u8 {
+        let width = self.cells.len() as isize;
+        if (0..width).contains(&x) {
+            return self.cells[x as usize];
+        }
+
+        match self.edge {
+            EdgeCondition::Periodic => self.cells[x.rem_euclid(width) as usize],
+            EdgeCondition::Fixed(state) => state,
+        }
+    }
+
+    /// Advances the simulation by one generation
+    pub fn step(&mut self) {
+        let radius = self.rule.radius() as isize;
+        let mut window = vec![0u8; 2 * radius as usize + 1];
+
+        for x in 0..self.cells.len() {
+            for (i, dx) in (-radius..=radius).enumerate() {
+                window[i] = self.cell_at(x as isize + dx);
+            }
+            self.next_cells[x] = self.rule.apply(&window);
+        }
+
+        std::mem::swap(&mut self.cells, &mut self.next_cells);
+        self.generation += 1;
+    }
+
+    /// Advances the simulation by multiple generations
+    ///
+    /// # Arguments
+    /// * `steps` - Number of generations to advance
+    pub fn step_n(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+}
+
+/// Spacetime diagram of a one-dimensional automaton
+///
+/// Each generation is recorded as one row of a [`DenseGrid`], from top to
+/// bottom. Once the diagram is full, older rows scroll off the top so the
+/// latest generation is always the bottom row.
+#[derive(Debug, Clone)]
+pub struct SpacetimeDiagram {
+    grid: DenseGrid,
+    /// Number of rows recorded so far, capped at the grid height
+    rows: usize,
+}
+
+impl SpacetimeDiagram {
+    /// Creates an empty diagram
+    ///
+    /// # Arguments
+    /// * `width` - Number of cells per generation
+    /// * `height` - Number of generations kept on screen
+    pub fn new(width: usize, height: usize) -> Result<Self> {
+        Ok(Self {
+            grid: DenseGrid::new(width, height)?,
+            rows: 0,
+        })
+    }
+
+    /// Returns the diagram as a grid, one row per generation
+    pub fn grid(&self) -> &DenseGrid {
+        &self.grid
+    }
+
+    /// Returns the number of generations currently shown
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Appends a generation, scrolling the diagram up once it is full
+    ///
+    /// Each state is stored as [`CellState::from_index`], so rules with more
+    /// than two colours keep their states.
+    pub fn push(&mut self, cells: &[u8]) {
+        let width = self.grid.width();
+        let height = self.grid.height();
+
+        let row = if self.rows < height {
+            self.rows += 1;
+            self.rows - 1
+        } else {
+            for y in 1..height {
+                for x in 0..width {
+                    let state = self.grid.get(Position::new(x, y)).unwrap();
+                    self.grid.set(Position::new(x, y - 1), state).unwrap();
+                }
+            }
+            height - 1
+        };
+
+        for x in 0..width {
+            let state = CellState::from_index(cells.get(x).copied().unwrap_or(0));
+            self.grid.set(Position::new(x, row), state).unwrap();
+        }
+    }
+
+    /// Removes all recorded generations
+    pub fn clear(&mut self) {
+        self.grid.clear();
+        self.rows = 0;
+    }
+}
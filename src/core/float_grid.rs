@@ -0,0 +1,101 @@
+use crate::core::cell::Position;
+use crate::error::{Error, Result};
+use rand::Rng;
+
+/// Grid of continuous cell values, used by Lenia and other continuous rules
+///
+/// Values are normally kept between 0.0 (empty) and 1.0 (full).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloatGrid {
+    cells: Vec<f64>,
+    width: usize,
+    height: usize,
+}
+
+impl FloatGrid {
+    /// Creates a new grid with the specified dimensions
+    ///
+    /// # Returns
+    /// A new grid with all cells set to 0.0
+    pub fn new(width: usize, height: usize) -> Result<Self> {
+        if width == 0 || height == 0 {
+            return Err(Error::InvalidDimensions { width, height });
+        }
+        Ok(Self {
+            cells: vec![0.0; width * height],
+            width,
+            height,
+        })
+    }
+
+    /// Creates a new grid with values drawn uniformly from 0.0 to 1.0
+    pub fn new_random(width: usize, height: usize) -> Result<Self> {
+        let mut grid = Self::new(width, height)?;
+        let mut rng = rand::rng();
+        grid.cells.iter_mut().for_each(|cell| *cell = rng.random());
+        Ok(grid)
+    }
+
+    /// Returns the width of the grid
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the grid
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Gets the value at the given position
+    pub fn get(&self, pos: Position) -> Result<f64> {
+        let index = self.pos_to_index(pos)?;
+        Ok(self.cells[index])
+    }
+
+    /// Sets the value at the given position
+    pub fn set(&mut self, pos: Position, value: f64) -> Result<()> {
+        let index = self.pos_to_index(pos)?;
+        self.cells[index] = value;
+        Ok(())
+    }
+
+    /// Returns all values row by row
+    pub fn values(&self) -> &[f64] {
+        &self.cells
+    }
+
+    /// Returns all values row by row for modification
+    pub fn values_mut(&mut self) -> &mut [f64] {
+        &mut self.cells
+    }
+
+    /// Sets every cell to 0.0
+    pub fn clear(&mut self) {
+        self.cells.fill(0.0);
+    }
+
+    /// Returns the sum of all values, the "mass" of a Lenia pattern
+    pub fn total(&self) -> f64 {
+        self.cells.iter().sum()
+    }
+
+    /// Returns an iterator over all positions and their values
+    pub fn iter(&self) -> impl Iterator<Item = (Position, f64)> + '_ {
+        self.cells.iter().enumerate().map(|(index, &value)| {
+            (Position::new(index % self.width, index / self.width), value)
+        })
+    }
+
+    /// Converts a position to a vector index
+    fn pos_to_index(&self, pos: Position) -> Result<usize> {
+        if !pos.is_within_bounds(self.width, self.height) {
+            return Err(Error::OutOfBounds {
+                x: pos.x,
+                y: pos.y,
+                width: self.width,
+                height: self.height,
+            });
+        }
+        Ok(pos.y * self.width + pos.x)
+    }
+}
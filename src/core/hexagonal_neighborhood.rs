@@ -0,0 +1,69 @@
+use crate::core::neighborhood::{offsets_where, Neighborhood};
+
+/// Hexagonal-distance neighborhood emulated on a square grid
+///
+/// The hexagonal lattice is sheared onto the square grid so that the six
+/// neighbors of a cell are its Moore neighbors minus the top-right and
+/// bottom-left corners. With a larger radius `r` the neighborhood contains
+/// every cell within hexagonal distance `r`, matching Golly's `NH`
+/// Larger than Life neighborhood.
+///
+/// Pattern for r = 1:
+/// ```text
+/// X X .
+/// X O X
+/// . X X
+/// ```
+#[derive(Debug, Clone)]
+pub struct HexagonalNeighborhood {
+    offsets: Vec<(isize, isize)>,
+    radius: usize,
+    name: String,
+}
+
+impl HexagonalNeighborhood {
+    /// Creates a hexagonal neighborhood with the six adjacent cells
+    pub fn new() -> Self {
+        Self::with_radius(1)
+    }
+
+    /// Creates a hexagonal neighborhood of the given range
+    pub fn with_radius(radius: usize) -> Self {
+        let offsets = offsets_where(radius, |dx, dy| hex_distance(dx, dy) <= radius);
+        let name = if radius == 1 {
+            "Hexagonal (6 neighbors)".to_string()
+        } else {
+            format!("Hexagonal (r={}, {} neighbors)", radius, offsets.len())
+        };
+        Self { offsets, radius, name }
+    }
+}
+
+impl Default for HexagonalNeighborhood {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Neighborhood for HexagonalNeighborhood {
+    fn offsets(&self) -> &[(isize, isize)] {
+        &self.offsets
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn radius(&self) -> usize {
+        self.radius
+    }
+}
+
+/// Hexagonal distance of an offset in the sheared square-grid embedding
+pub fn hex_distance(dx: isize, dy: isize) -> usize {
+    if (dx >= 0) == (dy >= 0) {
+        dx.unsigned_abs().max(dy.unsigned_abs())
+    } else {
+        dx.unsigned_abs() + dy.unsigned_abs()
+    }
+}
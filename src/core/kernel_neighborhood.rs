@@ -0,0 +1,111 @@
+/// Weighted neighborhood for continuous automata
+///
+/// Each offset within `radius` carries a real weight, and the weights sum to
+/// one so that convolving a grid with the kernel gives a weighted average of
+/// the neighborhood.
+///
+/// Ring kernels follow Lenia: the distance from the centre is scaled to
+/// `r` in [0, 1), split into one shell per peak, and each shell holds a
+/// smooth bump `exp(4 - 1 / (s (1 - s)))` scaled by its peak height, where
+/// `s` is the position within the shell.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KernelNeighborhood {
+    offsets: Vec<(isize, isize)>,
+    weights: Vec<f64>,
+    radius: usize,
+    name: String,
+}
+
+impl KernelNeighborhood {
+    /// Creates a single-ring kernel
+    ///
+    /// # Arguments
+    /// * `radius` - Kernel radius R in cells
+    pub fn ring(radius: usize) -> Self {
+        Self::rings(radius, &[1.0])
+    }
+
+    /// Creates a kernel of concentric rings
+    ///
+    /// # Arguments
+    /// * `radius` - Kernel radius R in cells
+    /// * `peaks` - Height of each ring from the centre out, Lenia's `b`
+    pub fn rings(radius: usize, peaks: &[f64]) -> Self {
+        let peaks = if peaks.is_empty() { &[1.0][..] } else { peaks };
+        let shells = peaks.len() as f64;
+        let name = if peaks.len() == 1 {
+            format!("Ring kernel (R={})", radius)
+        } else {
+            format!("Ring kernel (R={}, {} rings)", radius, peaks.len())
+        };
+        Self::from_fn(radius, name, |r| {
+            let shell = (r * shells).floor();
+            let s = r * shells - shell;
+            peaks[shell as usize] * bump(s)
+        })
+    }
+
+    /// Creates a kernel of equal weights over a disc, as used for SmoothLife's
+    /// inner filling
+    pub fn disk(radius: usize) -> Self {
+        Self::from_fn(radius, format!("Disk kernel (R={})", radius), |_| 1.0)
+    }
+
+    /// Builds a normalised kernel from a radial profile over r in [0, 1)
+    fn from_fn(radius: usize, name: String, profile: impl Fn(f64) -> f64) -> Self {
+        let radius = radius.max(1);
+        let r = radius as isize;
+        let mut offsets = Vec::new();
+        let mut weights = Vec::new();
+        for dy in -r..=r {
+            for dx in -r..=r {
+                let distance = ((dx * dx + dy * dy) as f64).sqrt() / radius as f64;
+                if distance >= 1.0 {
+                    continue;
+                }
+                let weight = profile(distance);
+                if weight > 0.0 {
+                    offsets.push((dx, dy));
+                    weights.push(weight);
+                }
+            }
+        }
+        let total: f64 = weights.iter().sum();
+        weights.iter_mut().for_each(|weight| *weight /= total);
+        Self {
+            offsets,
+            weights,
+            radius,
+            name,
+        }
+    }
+
+    /// Returns the offsets with a non-zero weight
+    pub fn offsets(&self) -> &[(isize, isize)] {
+        &self.offsets
+    }
+
+    /// Returns the weight of each offset, summing to one
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Returns the kernel radius R
+    pub fn radius(&self) -> usize {
+        self.radius
+    }
+
+    /// Returns the name of the kernel
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Smooth bump over (0, 1) peaking at 1.0 in the middle
+fn bump(s: f64) -> f64 {
+    if s <= 0.0 || s >= 1.0 {
+        0.0
+    } else {
+        (4.0 - 1.0 / (s * (1.0 - s))).exp()
+    }
+}
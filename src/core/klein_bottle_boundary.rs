@@ -0,0 +1,50 @@
+use crate::core::boundary::{crossings, Boundary, EdgePair};
+use crate::core::cell::Position;
+
+/// Klein bottle boundary - a torus with one pair of edges joined with a twist
+///
+/// Crossing the twisted edges mirrors the other coordinate, so a glider
+/// leaving the bottom edge near the left comes back from the top near the
+/// right, mirrored. The other pair of edges wraps as on a torus.
+#[derive(Debug, Clone, Copy)]
+pub struct KleinBottleBoundary {
+    twisted: EdgePair,
+}
+
+impl KleinBottleBoundary {
+    /// Creates a Klein bottle boundary
+    ///
+    /// # Arguments
+    /// * `twisted` - Pair of edges joined with a twist
+    pub fn new(twisted: EdgePair) -> Self {
+        Self { twisted }
+    }
+
+    /// Returns the pair of edges joined with a twist
+    pub fn twisted(&self) -> EdgePair {
+        self.twisted
+    }
+}
+
+impl Default for KleinBottleBoundary {
+    fn default() -> Self {
+        Self::new(EdgePair::TopBottom)
+    }
+}
+
+impl Boundary for KleinBottleBoundary {
+    fn wrap(&self, x: isize, y: isize, width: usize, height: usize) -> Option<Position> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        let (x_crossings, mut wrapped_x) = crossings(x, width);
+        let (y_crossings, mut wrapped_y) = crossings(y, height);
+        match self.twisted {
+            EdgePair::TopBottom if y_crossings % 2 != 0 => wrapped_x = width - 1 - wrapped_x,
+            EdgePair::LeftRight if x_crossings % 2 != 0 => wrapped_y = height - 1 - wrapped_y,
+            _ => {}
+        }
+        Some(Position::new(wrapped_x, wrapped_y))
+    }
+}
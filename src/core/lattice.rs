@@ -0,0 +1,59 @@
+use crate::core::hexagonal_neighborhood::HexagonalNeighborhood;
+use crate::core::moore_neighborhood::MooreNeighborhood;
+use crate::core::neighborhood::Neighborhood;
+use crate::core::triangular_neighborhood::TriangularNeighborhood;
+use crate::error::{Error, Result};
+
+/// Shape of the cells making up a grid
+///
+/// Every lattice is stored on the same rectangular [`DenseGrid`]; the
+/// lattice decides which cells are adjacent and how they are drawn.
+///
+/// [`DenseGrid`]: crate::core::dense_grid::DenseGrid
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Lattice {
+    /// Square cells
+    #[default]
+    Square,
+    /// Hexagonal cells in axial coordinates
+    ///
+    /// Cell (q, r) is stored at x = q, y = r. Each row is shifted half a
+    /// hexagon to the left of the one above, so the grid is drawn as a
+    /// parallelogram and the six neighbors are those of
+    /// [`HexagonalNeighborhood`].
+    Hexagonal,
+    /// Alternating up and down triangles, see [`TriangularNeighborhood`]
+    Triangular,
+}
+
+impl Lattice {
+    /// Returns the display name of the lattice
+    pub fn name(&self) -> &str {
+        match self {
+            Lattice::Square => "Square",
+            Lattice::Hexagonal => "Hexagonal",
+            Lattice::Triangular => "Triangular",
+        }
+    }
+
+    /// Returns the usual neighborhood of the lattice: Moore for squares,
+    /// the six adjacent hexagons, or the three edge-adjacent triangles
+    pub fn neighborhood(&self) -> Box<dyn Neighborhood> {
+        match self {
+            Lattice::Square => Box::new(MooreNeighborhood::new()),
+            Lattice::Hexagonal => Box::new(HexagonalNeighborhood::new()),
+            Lattice::Triangular => Box::new(TriangularNeighborhood::new()),
+        }
+    }
+
+    /// Checks that a grid of the given size tiles consistently when wrapped
+    ///
+    /// Triangular grids need an even width and height so that wrapping
+    /// round an edge lands on a triangle of the right orientation.
+    pub fn check_dimensions(&self, width: usize, height: usize) -> Result<()> {
+        if *self == Lattice::Triangular && !(width.is_multiple_of(2) && height.is_multiple_of(2)) {
+            return Err(Error::InvalidDimensions { width, height });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use crate::core::boundary::Boundary;
+use crate::core::cell::Position;
+
+/// Mirror boundary - the grid edges reflect like mirrors
+///
+/// A position `k` cells beyond an edge reads the cell `k - 1` cells inside
+/// it, so the row above the top edge is a copy of the top row, the row
+/// above that a copy of the second row, and so on. Unlike clamping, a
+/// neighborhood of any radius sees each cell at most once per reflection.
+#[derive(Debug, Clone, Copy)]
+pub struct MirrorBoundary;
+
+impl MirrorBoundary {
+    /// Creates a new mirror boundary
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for MirrorBoundary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Boundary for MirrorBoundary {
+    fn wrap(&self, x: isize, y: isize, width: usize, height: usize) -> Option<Position> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+        Some(Position::new(reflect(x, width), reflect(y, height)))
+    }
+}
+
+/// Reflects a coordinate into `0..size`, repeating with period `2 * size`
+pub(crate) fn reflect(coordinate: isize, size: usize) -> usize {
+    let size = size as isize;
+    let folded = coordinate.rem_euclid(2 * size);
+    (if folded < size { folded } else { 2 * size - 1 - folded }) as usize
+}
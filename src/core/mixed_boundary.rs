@@ -0,0 +1,84 @@
+use crate::core::boundary::Boundary;
+use crate::core::cell::Position;
+use crate::core::mirror_boundary::reflect;
+
+/// How one pair of opposite edges of a [`MixedBoundary`] behaves
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum EdgeBehavior {
+    /// Leaving through one edge re-enters through the opposite edge
+    #[default]
+    Wrap,
+    /// Beyond the edges there are only dead cells
+    Dead,
+    /// The edges reflect like mirrors, see
+    /// [`MirrorBoundary`](crate::core::mirror_boundary::MirrorBoundary)
+    Mirror,
+}
+
+impl EdgeBehavior {
+    /// Every edge behavior, in display order
+    pub const ALL: [EdgeBehavior; 3] =
+        [EdgeBehavior::Wrap, EdgeBehavior::Dead, EdgeBehavior::Mirror];
+
+    /// Returns the display name of the edge behavior
+    pub fn name(&self) -> &str {
+        match self {
+            EdgeBehavior::Wrap => "Wrap",
+            EdgeBehavior::Dead => "Dead",
+            EdgeBehavior::Mirror => "Mirror",
+        }
+    }
+
+    /// Resolves a coordinate along an axis of the given size
+    fn resolve(&self, coordinate: isize, size: usize) -> Option<usize> {
+        match self {
+            EdgeBehavior::Wrap => Some(coordinate.rem_euclid(size as isize) as usize),
+            EdgeBehavior::Dead => {
+                (0..size as isize).contains(&coordinate).then_some(coordinate as usize)
+            }
+            EdgeBehavior::Mirror => Some(reflect(coordinate, size)),
+        }
+    }
+}
+
+/// Mixed boundary - each pair of opposite edges behaves independently
+///
+/// For example wrapping between the left and right edges with dead cells
+/// above and below gives a cylinder.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MixedBoundary {
+    left_right: EdgeBehavior,
+    top_bottom: EdgeBehavior,
+}
+
+impl MixedBoundary {
+    /// Creates a mixed boundary
+    ///
+    /// # Arguments
+    /// * `left_right` - Behavior of the left and right edges
+    /// * `top_bottom` - Behavior of the top and bottom edges
+    pub fn new(left_right: EdgeBehavior, top_bottom: EdgeBehavior) -> Self {
+        Self { left_right, top_bottom }
+    }
+
+    /// Returns the behavior of the left and right edges
+    pub fn left_right(&self) -> EdgeBehavior {
+        self.left_right
+    }
+
+    /// Returns the behavior of the top and bottom edges
+    pub fn top_bottom(&self) -> EdgeBehavior {
+        self.top_bottom
+    }
+}
+
+impl Boundary for MixedBoundary {
+    fn wrap(&self, x: isize, y: isize, width: usize, height: usize) -> Option<Position> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+        let x = self.left_right.resolve(x, width)?;
+        let y = self.top_bottom.resolve(y, height)?;
+        Some(Position::new(x, y))
+    }
+}
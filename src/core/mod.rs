@@ -1,11 +1,43 @@
 pub mod cell;
 pub mod grid;
+pub mod transform;
 pub mod dense_grid;
+pub mod dense_grid_3d;
+pub mod float_grid;
 pub mod rules;
 pub mod boundary;
 pub mod toroidal_boundary;
 pub mod walled_boundary;
+pub mod dead_boundary;
+pub mod constant_boundary;
+pub mod mixed_boundary;
+pub mod klein_bottle_boundary;
+pub mod cross_surface_boundary;
+pub mod sphere_boundary;
+pub mod mirror_boundary;
+pub mod shifted_torus_boundary;
+pub mod topology;
+pub mod boundary_3d;
 pub mod neighborhood;
 pub mod moore_neighborhood;
 pub mod von_neumann_neighborhood;
-pub mod automata;
\ No newline at end of file
+pub mod circular_neighborhood;
+pub mod hexagonal_neighborhood;
+pub mod triangular_neighborhood;
+pub mod custom_neighborhood;
+pub mod neighborhood_3d;
+pub mod moore_neighborhood_3d;
+pub mod von_neumann_neighborhood_3d;
+pub mod kernel_neighborhood;
+pub mod lattice;
+pub mod rle;
+pub mod pattern_library;
+pub mod prefix_sum;
+pub mod convolution;
+pub mod update_scheme;
+pub mod automata;
+pub mod automata_3d;
+pub mod continuous_automata;
+pub mod elementary_automata;
+pub mod turmite;
+pub mod margolus;
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::core::neighborhood::Neighborhood;
+use crate::core::neighborhood::{offsets_where, Neighborhood};
 
 /// Moore neighborhood - includes all 8 surrounding cells
 ///
@@ -9,21 +9,31 @@ use crate::core::neighborhood::Neighborhood;
 /// X X X
 /// ```
 /// Where O is the center cell and X are neighbors
-#[derive(Debug, Clone, Copy)]
+///
+/// With a larger radius `r` the neighborhood is the full
+/// (2r+1) x (2r+1) square around the cell.
+#[derive(Debug, Clone)]
 pub struct MooreNeighborhood {
-    offsets: [(isize, isize); 8],
+    offsets: Vec<(isize, isize)>,
+    radius: usize,
+    name: String,
 }
 
 impl MooreNeighborhood {
     /// Creates a new Moore neighborhood
     pub fn new() -> Self {
-        Self {
-            offsets: [
-                (-1, -1), (0, -1), (1, -1),  // Top row
-                (-1,  0),          (1,  0),  // Middle row (excluding center)
-                (-1,  1), (0,  1), (1,  1),  // Bottom row
-            ],
-        }
+        Self::with_radius(1)
+    }
+
+    /// Creates a Moore neighborhood of the given range
+    pub fn with_radius(radius: usize) -> Self {
+        let offsets = offsets_where(radius, |_, _| true);
+        let name = if radius == 1 {
+            "Moore (8 neighbors)".to_string()
+        } else {
+            format!("Moore (r={}, {} neighbors)", radius, offsets.len())
+        };
+        Self { offsets, radius, name }
     }
 }
 
@@ -39,6 +49,10 @@ impl Neighborhood for MooreNeighborhood {
     }
 
     fn name(&self) -> &str {
-        "Moore (8 neighbors)"
+        &self.name
     }
-}
\ No newline at end of file
+
+    fn radius(&self) -> usize {
+        self.radius
+    }
+}
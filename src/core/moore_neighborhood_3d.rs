@@ -0,0 +1,41 @@
+use crate::core::neighborhood_3d::Neighborhood3D;
+
+/// Three-dimensional Moore neighborhood - the 26 cells of the surrounding
+/// 3x3x3 cube
+#[derive(Debug, Clone)]
+pub struct MooreNeighborhood3D {
+    offsets: Vec<(isize, isize, isize)>,
+}
+
+impl MooreNeighborhood3D {
+    /// Creates a new 3D Moore neighborhood
+    pub fn new() -> Self {
+        let mut offsets = Vec::with_capacity(26);
+        for dz in -1..=1 {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    if (dx, dy, dz) != (0, 0, 0) {
+                        offsets.push((dx, dy, dz));
+                    }
+                }
+            }
+        }
+        Self { offsets }
+    }
+}
+
+impl Default for MooreNeighborhood3D {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Neighborhood3D for MooreNeighborhood3D {
+    fn offsets(&self) -> &[(isize, isize, isize)] {
+        &self.offsets
+    }
+
+    fn name(&self) -> &str {
+        "3D Moore (26 neighbors)"
+    }
+}
@@ -1,4 +1,8 @@
-// no imports needed
+use crate::core::cell::Position;
+use crate::core::circular_neighborhood::CircularNeighborhood;
+use crate::core::moore_neighborhood::MooreNeighborhood;
+use crate::core::von_neumann_neighborhood::VonNeumannNeighborhood;
+use crate::error::{Error, Result};
 
 /// Trait defining neighborhood calculation strategies
 pub trait Neighborhood: Send + Sync {
@@ -8,6 +12,77 @@ pub trait Neighborhood: Send + Sync {
     /// A vector of (dx, dy) offsets relative to a cell
     fn offsets(&self) -> &[(isize, isize)];
 
+    /// Returns the offsets for the neighbors of a particular cell
+    ///
+    /// Defaults to [`Neighborhood::offsets`]. Lattices whose cells come in
+    /// more than one orientation, such as up and down triangles, override
+    /// this and report [`Neighborhood::is_uniform`] as false.
+    fn offsets_at(&self, _pos: Position) -> &[(isize, isize)] {
+        self.offsets()
+    }
+
+    /// Returns true if every cell has the same offsets
+    fn is_uniform(&self) -> bool {
+        true
+    }
+
     /// Returns the name of the neighborhood type
     fn name(&self) -> &str;
-}
\ No newline at end of file
+
+    /// Returns the weight of each offset, in the same order as [`Neighborhood::offsets`]
+    ///
+    /// `None` (the default) means every neighbor has weight 1.
+    fn weights(&self) -> Option<&[i32]> {
+        None
+    }
+
+    /// Returns the range of the neighborhood
+    ///
+    /// Defaults to the largest absolute offset along either axis.
+    fn radius(&self) -> usize {
+        self.offsets()
+            .iter()
+            .map(|&(dx, dy)| dx.unsigned_abs().max(dy.unsigned_abs()))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Collects the offsets within `radius` that satisfy `include`
+///
+/// Offsets are produced in row-major order and the centre cell is always
+/// excluded.
+pub(crate) fn offsets_where(
+    radius: usize,
+    include: impl Fn(isize, isize) -> bool,
+) -> Vec<(isize, isize)> {
+    let r = radius as isize;
+    let mut offsets = Vec::new();
+    for dy in -r..=r {
+        for dx in -r..=r {
+            if (dx, dy) != (0, 0) && include(dx, dy) {
+                offsets.push((dx, dy));
+            }
+        }
+    }
+    offsets
+}
+
+/// Parses a square-lattice neighborhood by name, ignoring case
+///
+/// Accepted names are `moore`, `von_neumann` and `circular:<radius>`.
+pub fn parse_neighborhood(name: &str) -> Result<Box<dyn Neighborhood>> {
+    let lower = name.trim().to_ascii_lowercase();
+    if let Some(radius) = lower.strip_prefix("circular:") {
+        let radius = radius
+            .trim()
+            .parse()
+            .map_err(|_| Error::Other(format!("invalid circular radius '{}'", radius)))?;
+        return Ok(Box::new(CircularNeighborhood::new(radius)));
+    }
+    match lower.as_str() {
+        "moore" => Ok(Box::new(MooreNeighborhood::new())),
+        "von_neumann" | "vonneumann" | "von neumann" => Ok(Box::new(VonNeumannNeighborhood::new())),
+        _ => Err(Error::Other(format!("unknown neighborhood '{}'", name))),
+    }
+}
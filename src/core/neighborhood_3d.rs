@@ -0,0 +1,11 @@
+/// Trait defining neighborhoods on three-dimensional grids
+pub trait Neighborhood3D: Send + Sync {
+    /// Returns the relative offsets for neighbors
+    ///
+    /// # Returns
+    /// A slice of (dx, dy, dz) offsets relative to a cell
+    fn offsets(&self) -> &[(isize, isize, isize)];
+
+    /// Returns the name of the neighborhood type
+    fn name(&self) -> &str;
+}
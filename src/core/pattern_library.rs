@@ -0,0 +1,234 @@
+use crate::core::dense_grid::DenseGrid;
+use crate::core::rle::RlePattern;
+use crate::error::Result;
+
+/// Kind of pattern in the [`PatternLibrary`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PatternCategory {
+    /// Patterns that never change
+    StillLife,
+    /// Patterns that return to their starting state in place
+    Oscillator,
+    /// Patterns that return to their starting state, moved
+    Spaceship,
+    /// Oscillators that emit a stream of spaceships
+    Gun,
+    /// Spaceships that leave debris behind them
+    Puffer,
+    /// Small patterns that take many generations to settle
+    Methuselah,
+}
+
+impl PatternCategory {
+    /// Every category, in display order
+    pub const ALL: [PatternCategory; 6] = [
+        PatternCategory::StillLife,
+        PatternCategory::Oscillator,
+        PatternCategory::Spaceship,
+        PatternCategory::Gun,
+        PatternCategory::Puffer,
+        PatternCategory::Methuselah,
+    ];
+
+    /// Returns the display name of the category
+    pub fn name(&self) -> &str {
+        match self {
+            PatternCategory::StillLife => "Still lifes",
+            PatternCategory::Oscillator => "Oscillators",
+            PatternCategory::Spaceship => "Spaceships",
+            PatternCategory::Gun => "Guns",
+            PatternCategory::Puffer => "Puffers",
+            PatternCategory::Methuselah => "Methuselahs",
+        }
+    }
+}
+
+/// A well-known pattern with its metadata
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LibraryPattern {
+    /// Common name of the pattern
+    pub name: &'static str,
+    /// Discoverer, or "Unknown" for patterns that arise naturally
+    pub author: &'static str,
+    /// Rule the pattern is meant for
+    pub rule: &'static str,
+    /// Number of generations before the pattern repeats, 1 for still lifes
+    ///
+    /// Methuselahs have no period.
+    pub period: Option<u32>,
+    /// Kind of pattern
+    pub category: PatternCategory,
+    /// Cells in RLE, without a header
+    pub rle: &'static str,
+}
+
+impl LibraryPattern {
+    /// Builds a grid just large enough for the pattern
+    pub fn grid(&self) -> Result<DenseGrid> {
+        Ok(RlePattern::parse(self.rle)?.grid)
+    }
+
+    /// Returns the pattern as RLE with its name, author and rule filled in
+    pub fn to_rle_pattern(&self) -> Result<RlePattern> {
+        let mut pattern = RlePattern::parse(self.rle)?;
+        pattern.name = Some(self.name.to_string());
+        pattern.author = Some(self.author.to_string());
+        pattern.rule = Some(self.rule.to_string());
+        Ok(pattern)
+    }
+}
+
+/// Built-in library of named patterns for Conway's Game of Life
+///
+/// Patterns can be listed by category or looked up by name, ignoring case.
+pub struct PatternLibrary;
+
+impl PatternLibrary {
+    /// Returns every pattern in the library, grouped by category
+    pub fn all() -> &'static [LibraryPattern] {
+        PATTERNS
+    }
+
+    /// Looks up a pattern by name, ignoring case
+    pub fn get(name: &str) -> Option<&'static LibraryPattern> {
+        PATTERNS.iter().find(|pattern| pattern.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Returns the patterns of one category
+    pub fn by_category(
+        category: PatternCategory,
+    ) -> impl Iterator<Item = &'static LibraryPattern> {
+        PATTERNS.iter().filter(move |pattern| pattern.category == category)
+    }
+
+    /// Finds the library pattern a grid holds, in any position, rotation or
+    /// reflection
+    ///
+    /// Patterns are compared by their [canonical form](DenseGrid::canonical),
+    /// so oscillators and spaceships are only recognised in the phase the
+    /// library stores.
+    pub fn identify(grid: &DenseGrid) -> Option<&'static LibraryPattern> {
+        let canonical = grid.canonical();
+        PATTERNS
+            .iter()
+            .find(|pattern| pattern.grid().is_ok_and(|cells| cells.canonical() == canonical))
+    }
+}
+
+use PatternCategory::{Gun, Methuselah, Oscillator, Puffer, Spaceship, StillLife};
+
+/// Builds a library entry for a pattern in Conway's Game of Life
+const fn life(
+    name: &'static str,
+    author: &'static str,
+    period: Option<u32>,
+    category: PatternCategory,
+    rle: &'static str,
+) -> LibraryPattern {
+    LibraryPattern {
+        name,
+        author,
+        rule: "B3/S23",
+        period,
+        category,
+        rle,
+    }
+}
+
+const PATTERNS: &[LibraryPattern] = &[
+    life("Block", "Unknown", Some(1), StillLife, "2o$2o!"),
+    life("Beehive", "Unknown", Some(1), StillLife, "b2o$o2bo$b2o!"),
+    life("Loaf", "Unknown", Some(1), StillLife, "b2o$o2bo$bobo$2bo!"),
+    life("Boat", "Unknown", Some(1), StillLife, "2o$obo$bo!"),
+    life("Ship", "Unknown", Some(1), StillLife, "2o$obo$b2o!"),
+    life("Tub", "Unknown", Some(1), StillLife, "bo$obo$bo!"),
+    life("Pond", "Unknown", Some(1), StillLife, "b2o$o2bo$o2bo$b2o!"),
+    life("Barge", "Unknown", Some(1), StillLife, "bo$obo$bobo$2bo!"),
+    life("Long boat", "Unknown", Some(1), StillLife, "2o$obo$bobo$2bo!"),
+    life("Snake", "Unknown", Some(1), StillLife, "2obo$ob2o!"),
+    life("Aircraft carrier", "Unknown", Some(1), StillLife, "2o$o2bo$2b2o!"),
+    life("Eater 1", "Bill Gosper", Some(1), StillLife, "2o$obo$2bo$2b2o!"),
+    life("Blinker", "John Conway", Some(2), Oscillator, "3o!"),
+    life("Toad", "Simon Norton", Some(2), Oscillator, "b3o$3o!"),
+    life("Beacon", "John Conway", Some(2), Oscillator, "2o$2o$2b2o$2b2o!"),
+    life("Clock", "Simon Norton", Some(2), Oscillator, "2bo$obo$bobo$bo!"),
+    life(
+        "Pulsar",
+        "John Conway",
+        Some(3),
+        Oscillator,
+        "2b3o3b3o2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2$2b3o3b3o$o4bobo4bo$\
+         o4bobo4bo$o4bobo4bo2$2b3o3b3o!",
+    ),
+    life("Figure eight", "Simon Norton", Some(8), Oscillator, "3o$3o$3o$3b3o$3b3o$3b3o!"),
+    life(
+        "Kok's galaxy",
+        "Jan Kok",
+        Some(8),
+        Oscillator,
+        "6ob2o$6ob2o$7b2o$2o5b2o$2o5b2o$2o5b2o$2o$2ob6o$2ob6o!",
+    ),
+    life(
+        "Tumbler",
+        "George Collins",
+        Some(14),
+        Oscillator,
+        "bo5bo$obo3bobo$o2bobo2bo$2bo3bo$2b2ob2o!",
+    ),
+    life("Pentadecathlon", "John Conway", Some(15), Oscillator, "2bo4bo$2ob4ob2o$2bo4bo!"),
+    life(
+        "Queen bee shuttle",
+        "Bill Gosper",
+        Some(30),
+        Oscillator,
+        "9bo$7bobo$6bobo$2o3bo2bo11b2o$2o4bobo11b2o$7bobo$9bo!",
+    ),
+    life("Glider", "Richard Guy", Some(4), Spaceship, "bo$2bo$3o!"),
+    life("Lightweight spaceship", "John Conway", Some(4), Spaceship, "bo2bo$o$o3bo$4o!"),
+    life("Middleweight spaceship", "John Conway", Some(4), Spaceship, "3bo$bo3bo$o$o4bo$5o!"),
+    life("Heavyweight spaceship", "John Conway", Some(4), Spaceship, "3b2o$bo4bo$o$o5bo$6o!"),
+    life(
+        "Loafer",
+        "Josh Ball",
+        Some(7),
+        Spaceship,
+        "b2o2bob2o$o2bo2b2o$bobo$2bo$8bo$6b3o$5bo$6bo$7b2o!",
+    ),
+    life(
+        "Copperhead",
+        "zdr",
+        Some(10),
+        Spaceship,
+        "b2o2b2o$3b2o$3b2o$obo2bobo$o6bo2$o6bo$b2o2b2o$2b4o2$3b2o$3b2o!",
+    ),
+    life(
+        "Gosper glider gun",
+        "Bill Gosper",
+        Some(30),
+        Gun,
+        "24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4bobo$\
+         10bo5bo7bo$11bo3bo$12b2o!",
+    ),
+    life(
+        "Simkin glider gun",
+        "Michael Simkin",
+        Some(120),
+        Gun,
+        "2o5b2o$2o5b2o2$4b2o$4b2o5$22b2ob2o$21bo5bo$21bo6bo2b2o$21b3o3bo3b2o$26bo4$\
+         20b2o$20bo$21b3o$23bo!",
+    ),
+    life(
+        "Puffer train",
+        "Bill Gosper",
+        Some(140),
+        Puffer,
+        "3bo$4bo$o3bo$b4o4$o$b2o$2bo$2bo$bo3$3bo$4bo$o3bo$b4o!",
+    ),
+    life("R-pentomino", "John Conway", None, Methuselah, "b2o$2o$bo!"),
+    life("B-heptomino", "John Conway", None, Methuselah, "ob2o$3o$bo!"),
+    life("Pi-heptomino", "John Conway", None, Methuselah, "3o$obo$obo!"),
+    life("Herschel", "John Conway", None, Methuselah, "o$3o$obo$2bo!"),
+    life("Thunderbird", "Unknown", None, Methuselah, "3o2$bo$bo$bo!"),
+    life("Diehard", "Unknown", None, Methuselah, "6bo$2o$bo3b3o!"),
+    life("Acorn", "Charles Corderman", None, Methuselah, "bo$3bo$2o2b3o!"),
+];
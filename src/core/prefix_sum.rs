@@ -0,0 +1,178 @@
+use crate::core::boundary::Boundary;
+use crate::core::cell::Position;
+use crate::core::dense_grid::DenseGrid;
+use crate::core::grid::Grid;
+
+/// Neighborhood size from which [`Automata`](crate::core::automata::Automata)
+/// switches from per-offset counting to summed-area table counting
+pub const PREFIX_SUM_MIN_NEIGHBORS: usize = 24;
+
+/// Summed-area table of alive cells over a padded grid
+///
+/// The grid is padded by `pad` cells on every side, and padding cells are
+/// resolved through the [`Boundary`] exactly as a per-offset neighbor lookup
+/// would resolve them. Any axis-aligned rectangle sum is then available in
+/// constant time.
+#[derive(Debug, Clone)]
+pub struct SummedAreaTable {
+    /// Number of padding cells on each side of the grid
+    pad: usize,
+    /// Padded width plus one (the table has a leading zero row and column)
+    stride: usize,
+    /// Inclusive prefix sums, `sums[(y + 1) * stride + (x + 1)]`
+    sums: Vec<u32>,
+}
+
+impl SummedAreaTable {
+    /// Builds the table for a grid
+    ///
+    /// # Arguments
+    /// * `grid` - Grid to sum over
+    /// * `boundary` - Boundary used to resolve padding cells
+    /// * `pad` - Number of padding cells on each side, normally the neighborhood radius
+    pub fn build(grid: &DenseGrid, boundary: &dyn Boundary, pad: usize) -> Self {
+        let width = grid.width();
+        let height = grid.height();
+        let padded_width = width + 2 * pad;
+        let padded_height = height + 2 * pad;
+        let stride = padded_width + 1;
+        let mut sums = vec![0u32; stride * (padded_height + 1)];
+        let outside = boundary.outside_state();
+
+        for py in 0..padded_height {
+            let mut row_sum = 0;
+            for px in 0..padded_width {
+                let x = px as isize - pad as isize;
+                let y = py as isize - pad as isize;
+                let alive = if x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height {
+                    grid.get(Position::new(x as usize, y as usize))
+                        .map(|s| s.is_alive())
+                        .unwrap_or(false)
+                } else {
+                    boundary
+                        .wrap(x, y, width, height)
+                        .and_then(|pos| grid.get(pos).ok())
+                        .unwrap_or(outside)
+                        .is_alive()
+                };
+                row_sum += alive as u32;
+                sums[(py + 1) * stride + px + 1] = sums[py * stride + px + 1] + row_sum;
+            }
+        }
+
+        Self { pad, stride, sums }
+    }
+
+    /// Returns the number of alive cells in an inclusive rectangle
+    ///
+    /// Coordinates are in grid space and may extend up to `pad` cells
+    /// beyond each edge.
+    pub fn rect_sum(&self, x0: isize, y0: isize, x1: isize, y1: isize) -> usize {
+        let pad = self.pad as isize;
+        let (x0, x1) = ((x0 + pad) as usize, (x1 + pad + 1) as usize);
+        let (y0, y1) = ((y0 + pad) as usize, (y1 + pad + 1) as usize);
+        let s = &self.sums;
+        let stride = self.stride;
+        (s[y1 * stride + x1] + s[y0 * stride + x0] - s[y0 * stride + x1] - s[y1 * stride + x0])
+            as usize
+    }
+}
+
+/// Row-wise decomposition of a neighborhood into rectangles
+///
+/// Most large neighborhoods (square, diamond, disc, hexagon) are convex
+/// along each row, so their neighbor count is the sum of one rectangle
+/// query per row instead of one lookup per offset. Consecutive rows with the
+/// same extent are merged, so a Moore neighborhood needs a single query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowSpans {
+    /// (dx_min, dy_min, dx_max, dy_max) inclusive rectangles
+    rects: Vec<(isize, isize, isize, isize)>,
+    /// Whether the centre cell lies inside a rectangle but is not a neighbor
+    excludes_center: bool,
+}
+
+impl RowSpans {
+    /// Decomposes a list of offsets into row spans
+    ///
+    /// # Returns
+    /// `None` if some row of the neighborhood is not contiguous (apart from
+    /// a missing centre cell) or if an offset appears more than once
+    pub fn from_offsets(offsets: &[(isize, isize)]) -> Option<Self> {
+        let mut sorted: Vec<(isize, isize)> = offsets.iter().map(|&(dx, dy)| (dy, dx)).collect();
+        sorted.sort_unstable();
+        if sorted.windows(2).any(|pair| pair[0] == pair[1]) {
+            return None;
+        }
+
+        let mut rects: Vec<(isize, isize, isize, isize)> = Vec::new();
+        let mut excludes_center = false;
+        let mut i = 0;
+        while i < sorted.len() {
+            let dy = sorted[i].0;
+            let lo = sorted[i].1;
+            let mut hi = lo;
+            i += 1;
+            while i < sorted.len() && sorted[i].0 == dy {
+                let dx = sorted[i].1;
+                if dx == hi + 1 {
+                    hi = dx;
+                } else if dy == 0 && hi == -1 && dx == 1 && !excludes_center {
+                    excludes_center = true;
+                    hi = dx;
+                } else {
+                    return None;
+                }
+                i += 1;
+            }
+
+            match rects.last_mut() {
+                Some(rect) if rect.0 == lo && rect.2 == hi && rect.3 == dy - 1 => rect.3 = dy,
+                _ => rects.push((lo, dy, hi, dy)),
+            }
+        }
+
+        Some(Self { rects, excludes_center })
+    }
+
+    /// Returns the number of rectangle queries needed per cell
+    pub fn len(&self) -> usize {
+        self.rects.len()
+    }
+
+    /// Returns true if the neighborhood is empty
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// Returns the largest absolute offset covered by the spans
+    pub fn radius(&self) -> usize {
+        self.rects
+            .iter()
+            .flat_map(|&(x0, y0, x1, y1)| [x0, y0, x1, y1])
+            .map(isize::unsigned_abs)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Counts the alive neighbors of a cell using a summed-area table
+    ///
+    /// # Arguments
+    /// * `table` - Table built with a padding of at least [`RowSpans::radius`]
+    /// * `grid` - The grid the table was built from
+    /// * `pos` - Cell whose neighbors are counted
+    pub fn count(&self, table: &SummedAreaTable, grid: &DenseGrid, pos: Position) -> usize {
+        let x = pos.x as isize;
+        let y = pos.y as isize;
+        let mut count: usize = self
+            .rects
+            .iter()
+            .map(|&(x0, y0, x1, y1)| table.rect_sum(x + x0, y + y0, x + x1, y + y1))
+            .sum();
+
+        if self.excludes_center && grid.get(pos).map(|s| s.is_alive()).unwrap_or(false) {
+            count -= 1;
+        }
+        count
+    }
+}
@@ -0,0 +1,317 @@
+use crate::core::cell::{CellState, Position};
+use crate::core::dense_grid::DenseGrid;
+use crate::core::grid::Grid;
+use crate::error::{Error, Result};
+
+/// Longest line written by [`RlePattern::to_rle`], as in Golly
+const MAX_LINE_LENGTH: usize = 70;
+
+/// Largest number of cells [`RlePattern::parse`] accepts in a pattern
+///
+/// RLE often arrives from the clipboard, the network or other programs, so
+/// headers and run counts are checked against this before any memory is
+/// set aside for them.
+pub const MAX_PATTERN_CELLS: usize = 1 << 26;
+
+/// A pattern in run-length encoded (RLE) form, as used by Golly and LifeWiki
+///
+/// The text starts with optional `#` lines (`#N` name, `#O` author, `#C`
+/// comments), then a header such as `x = 3, y = 3, rule = B3/S23`, then the
+/// cells: `b` or `.` is dead, `o` is alive, `A` to `X` are states 1 to 24
+/// and `p` to `y` prefix higher states. Each token may be preceded by a run
+/// count, `$` ends a row and `!` ends the pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RlePattern {
+    /// Cells of the pattern
+    pub grid: DenseGrid,
+    /// Rule from the header, e.g. `B3/S23` or `B3/S23:T30,20`
+    pub rule: Option<String>,
+    /// Name from the `#N` line
+    pub name: Option<String>,
+    /// Author from the `#O` line
+    pub author: Option<String>,
+    /// Text of the `#C` lines
+    pub comments: Vec<String>,
+}
+
+impl RlePattern {
+    /// Wraps a grid in a pattern with no rule or metadata
+    pub fn new(grid: DenseGrid) -> Self {
+        Self {
+            grid,
+            rule: None,
+            name: None,
+            author: None,
+            comments: Vec::new(),
+        }
+    }
+
+    /// Parses RLE text
+    ///
+    /// The grid is as large as the header says, or larger if the cells do
+    /// not fit. Without a header it is just large enough for the cells.
+    ///
+    /// # Errors
+    /// Returns an error for a malformed header, an unknown cell character, a
+    /// state above 255, or a pattern of more than [`MAX_PATTERN_CELLS`]
+    /// cells
+    pub fn parse(text: &str) -> Result<Self> {
+        Self::parse_with_limit(text, MAX_PATTERN_CELLS)
+    }
+
+    /// Parses RLE text into a pattern of at most `max_cells` cells
+    ///
+    /// The size is checked before the grid is allocated.
+    ///
+    /// # Arguments
+    /// * `text` - RLE text
+    /// * `max_cells` - Largest number of cells accepted, at most
+    ///   [`MAX_PATTERN_CELLS`]
+    ///
+    /// # Errors
+    /// As [`parse`](Self::parse), with `max_cells` as the size limit
+    pub fn parse_with_limit(text: &str, max_cells: usize) -> Result<Self> {
+        let max_cells = max_cells.min(MAX_PATTERN_CELLS);
+        let mut pattern = Self::new(DenseGrid::new(1, 1)?);
+        let mut size = (0, 0);
+        let mut body = String::new();
+
+        for line in text.lines() {
+            let line = line.trim();
+            if let Some(comment) = line.strip_prefix('#') {
+                let mut chars = comment.chars();
+                let kind = chars.next().unwrap_or(' ');
+                let value = chars.as_str().trim().to_string();
+                match kind {
+                    'N' => pattern.name = Some(value),
+                    'O' => pattern.author = Some(value),
+                    'C' | 'c' => pattern.comments.push(value),
+                    _ => {}
+                }
+            } else if body.is_empty() && line.starts_with('x') {
+                size = Self::parse_header(line, &mut pattern.rule)?;
+            } else {
+                body.push_str(line);
+                if line.contains('!') {
+                    break;
+                }
+            }
+        }
+
+        let runs = Self::parse_runs(&body)?;
+        let width = runs.iter().map(|&(pos, run, _)| pos.x + run).max().unwrap_or(0).max(size.0);
+        let height = runs.iter().map(|&(pos, _, _)| pos.y + 1).max().unwrap_or(0).max(size.1);
+        if width.checked_mul(height).is_none_or(|cells| cells > max_cells) {
+            return Err(Error::PatternParseError(format!(
+                "RLE pattern of {}x{} cells is larger than {} cells",
+                width, height, max_cells
+            )));
+        }
+        pattern.grid = DenseGrid::new(width.max(1), height.max(1))?;
+        for (pos, run, state) in runs {
+            pattern.grid.fill_region(pos, run, 1, state);
+        }
+        Ok(pattern)
+    }
+
+    /// Parses a header line such as `x = 3, y = 3, rule = B3/S23`
+    ///
+    /// The rule is always last and runs to the end of the line, since rules
+    /// such as `R5,C0,M1,S34..58,B34..45,NM` or `B3/S23:T30,20` contain commas.
+    fn parse_header(line: &str, rule: &mut Option<String>) -> Result<(usize, usize)> {
+        let malformed = |field: &str| {
+            Error::PatternParseError(format!("malformed RLE header field '{}'", field.trim()))
+        };
+        let (fields, rule_field) = match line.find("rule") {
+            Some(index) => (&line[..index], Some(&line[index..])),
+            None => (line, None),
+        };
+        if let Some(field) = rule_field {
+            let (_, value) = field.split_once('=').ok_or_else(|| malformed(field))?;
+            *rule = Some(value.trim().to_string());
+        }
+
+        let mut size = (0, 0);
+        for field in fields.split(',').filter(|field| !field.trim().is_empty()) {
+            let (key, value) = field.split_once('=').ok_or_else(|| malformed(field))?;
+            let value = value.trim();
+            let dimension = || {
+                value.parse::<usize>().map_err(|_| {
+                    Error::PatternParseError(format!("invalid RLE dimension '{}'", value))
+                })
+            };
+            match key.trim() {
+                "x" => size.0 = dimension()?,
+                "y" => size.1 = dimension()?,
+                _ => {}
+            }
+        }
+        Ok(size)
+    }
+
+    /// Decodes the run-length encoded cells into runs of live cells, each
+    /// given by its first cell and length
+    fn parse_runs(body: &str) -> Result<Vec<(Position, usize, CellState)>> {
+        let mut runs = Vec::new();
+        let (mut x, mut y) = (0, 0);
+        let mut count = String::new();
+        let mut prefix = None;
+
+        for c in body.chars() {
+            if c.is_ascii_digit() {
+                count.push(c);
+                continue;
+            }
+            if c.is_whitespace() {
+                continue;
+            }
+            let run = match count.parse::<usize>() {
+                _ if count.is_empty() => 1,
+                Ok(run) if run <= MAX_PATTERN_CELLS => run,
+                _ => {
+                    return Err(Error::PatternParseError(format!(
+                        "RLE run of {} cells is longer than {} cells",
+                        count, MAX_PATTERN_CELLS
+                    )))
+                }
+            };
+            count.clear();
+
+            let state = match (prefix.take(), c) {
+                (None, '!') => break,
+                (None, '$') => {
+                    y = checked_extent(y, run)?;
+                    x = 0;
+                    continue;
+                }
+                (None, 'b' | '.') => 0,
+                (None, 'o') => 1,
+                (None, 'p'..='y') => {
+                    prefix = Some(c);
+                    // Keep the run count for the letter that follows
+                    count = run.to_string();
+                    continue;
+                }
+                (prefix, 'A'..='X') => {
+                    let high = prefix.map_or(0, |p| (p as u32 - 'p' as u32 + 1) * 24);
+                    let state = high + (c as u32 - 'A' as u32 + 1);
+                    u8::try_from(state).map_err(|_| {
+                        Error::PatternParseError(format!("RLE state {} is above 255", state))
+                    })?
+                }
+                _ => {
+                    return Err(Error::PatternParseError(format!(
+                        "unexpected character '{}' in RLE",
+                        c
+                    )))
+                }
+            };
+            let end = checked_extent(x, run)?;
+            if state != 0 && run > 0 {
+                runs.push((Position::new(x, y), run, CellState::from_index(state)));
+            }
+            x = end;
+        }
+        Ok(runs)
+    }
+
+    /// Writes the pattern as RLE text
+    ///
+    /// Two-state grids use `b` and `o`; grids with more states use `.` and
+    /// letters. Lines are at most 70 characters long.
+    pub fn to_rle(&self) -> String {
+        let mut text = String::new();
+        if let Some(name) = &self.name {
+            text.push_str(&format!("#N {}\n", name));
+        }
+        if let Some(author) = &self.author {
+            text.push_str(&format!("#O {}\n", author));
+        }
+        for comment in &self.comments {
+            text.push_str(&format!("#C {}\n", comment));
+        }
+        let (width, height) = (self.grid.width(), self.grid.height());
+        text.push_str(&format!("x = {}, y = {}", width, height));
+        if let Some(rule) = &self.rule {
+            text.push_str(&format!(", rule = {}", rule));
+        }
+        text.push('\n');
+
+        let multi_state = self.grid.iter().any(|(_, state)| state.index() > 1);
+        let mut tokens = Vec::new();
+        let mut pending_rows = 0;
+        for y in 0..height {
+            let row: Vec<u8> = (0..width)
+                .map(|x| self.grid.get(Position::new(x, y)).map_or(0, |s| s.index()))
+                .collect();
+            let end = row.iter().rposition(|&state| state != 0).map_or(0, |i| i + 1);
+            if end == 0 {
+                pending_rows += 1;
+                continue;
+            }
+            // Rows are separated by `$`, and blank rows add to the count
+            let rows = pending_rows + usize::from(!tokens.is_empty());
+            if rows > 0 {
+                tokens.push(Self::run(rows, "$"));
+            }
+            pending_rows = 0;
+
+            let mut x = 0;
+            while x < end {
+                let state = row[x];
+                let run = row[x..end].iter().take_while(|&&s| s == state).count();
+                tokens.push(Self::run(run, &Self::state_token(state, multi_state)));
+                x += run;
+            }
+        }
+        tokens.push("!".to_string());
+
+        let mut line = String::new();
+        for token in tokens {
+            if line.len() + token.len() > MAX_LINE_LENGTH {
+                text.push_str(&line);
+                text.push('\n');
+                line.clear();
+            }
+            line.push_str(&token);
+        }
+        text.push_str(&line);
+        text.push('\n');
+        text
+    }
+
+    /// Formats a token with its run count
+    fn run(count: usize, token: &str) -> String {
+        if count == 1 {
+            token.to_string()
+        } else {
+            format!("{}{}", count, token)
+        }
+    }
+
+    /// Returns the RLE token of a state
+    fn state_token(state: u8, multi_state: bool) -> String {
+        match (state, multi_state) {
+            (0, false) => "b".to_string(),
+            (_, false) => "o".to_string(),
+            (0, true) => ".".to_string(),
+            (state, true) => {
+                let (high, low) = ((state - 1) / 24, (state - 1) % 24);
+                let letter = (b'A' + low) as char;
+                if high == 0 {
+                    letter.to_string()
+                } else {
+                    format!("{}{}", (b'p' + high - 1) as char, letter)
+                }
+            }
+        }
+    }
+}
+
+/// Adds a run to a coordinate, failing once the pattern
+/// could no longer fit in [`MAX_PATTERN_CELLS`]
+fn checked_extent(start: usize, run: usize) -> Result<usize> {
+    start.checked_add(run).filter(|&end| end <= MAX_PATTERN_CELLS).ok_or_else(|| {
+        Error::PatternParseError(format!("RLE pattern is larger than {} cells", MAX_PATTERN_CELLS))
+    })
+}
@@ -0,0 +1,71 @@
+use crate::core::cell::CellState;
+use crate::core::rules::trait_def::Rule;
+
+/// Brian's Brain rule
+///
+/// A three-state rule over the Moore neighborhood, in which almost every
+/// pattern explodes into gliders:
+/// - An off cell starts firing if exactly two of its neighbors are firing
+/// - A firing cell becomes refractory
+/// - A refractory cell turns off
+///
+/// Firing cells are state 1 ([`CellState::Alive`]), so the alive neighbor
+/// count is the number of firing neighbors.
+#[derive(Debug, Clone, Copy)]
+pub struct BriansBrainRule;
+
+impl BriansBrainRule {
+    /// Resting cell
+    pub const OFF: CellState = CellState::Dead;
+    /// Firing cell
+    pub const FIRING: CellState = CellState::Alive;
+    /// Refractory cell, which cannot fire in the next generation
+    pub const REFRACTORY: CellState = CellState::State(2);
+
+    /// Creates a new Brian's Brain rule
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for BriansBrainRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rule for BriansBrainRule {
+    fn apply(&self, current_state: CellState, alive_neighbors: usize) -> CellState {
+        match (current_state, alive_neighbors) {
+            (Self::OFF, 2) => Self::FIRING,
+            (Self::FIRING, _) => Self::REFRACTORY,
+            _ => Self::OFF,
+        }
+    }
+
+    fn states(&self) -> u8 {
+        3
+    }
+
+    fn state_name(&self, state: u8) -> String {
+        match state {
+            0 => "Off",
+            1 => "Firing",
+            2 => "Refractory",
+            _ => "Unused",
+        }
+        .to_string()
+    }
+
+    fn palette(&self) -> Option<&[[u8; 3]]> {
+        Some(&[[0, 0, 0], [255, 255, 255], [40, 90, 220]])
+    }
+
+    fn name(&self) -> &str {
+        "Brian's Brain"
+    }
+
+    fn description(&self) -> &str {
+        "B2/S/3 - Off cells fire on 2 firing neighbors, firing cells become refractory"
+    }
+}
@@ -0,0 +1,354 @@
+use crate::core::cell::CellState;
+use crate::core::rules::trait_def::{Neighbors, Rule};
+use crate::error::{Error, Result};
+
+/// Order in which Hensel letters are written for each neighbor count
+const LETTER_ORDER: &str = "cekainyqjrtwz";
+
+/// Representative configuration of each transition for counts 1 to 4
+///
+/// Neighbors are numbered clockwise from north: N=0, NE=1, E=2, SE=3, S=4,
+/// SW=5, W=6, NW=7. Counts 5 to 7 use the complement of the configuration
+/// with the same letter and 8 - n neighbors.
+const TRANSITIONS: [(usize, char, &[usize]); 33] = [
+    (1, 'c', &[1]),
+    (1, 'e', &[0]),
+    (2, 'c', &[1, 3]),
+    (2, 'e', &[0, 2]),
+    (2, 'k', &[0, 3]),
+    (2, 'a', &[0, 1]),
+    (2, 'i', &[0, 4]),
+    (2, 'n', &[1, 5]),
+    (3, 'c', &[1, 3, 5]),
+    (3, 'e', &[0, 2, 4]),
+    (3, 'k', &[0, 2, 5]),
+    (3, 'a', &[0, 1, 2]),
+    (3, 'i', &[0, 1, 7]),
+    (3, 'n', &[0, 1, 3]),
+    (3, 'y', &[0, 3, 5]),
+    (3, 'q', &[0, 1, 5]),
+    (3, 'j', &[0, 1, 6]),
+    (3, 'r', &[0, 1, 4]),
+    (4, 'c', &[1, 3, 5, 7]),
+    (4, 'e', &[0, 2, 4, 6]),
+    (4, 'k', &[0, 1, 3, 6]),
+    (4, 'a', &[0, 1, 2, 3]),
+    (4, 'i', &[0, 1, 3, 4]),
+    (4, 'n', &[0, 1, 3, 7]),
+    (4, 'y', &[0, 1, 3, 5]),
+    (4, 'q', &[0, 1, 2, 5]),
+    (4, 'j', &[0, 1, 4, 6]),
+    (4, 'r', &[0, 1, 2, 4]),
+    (4, 't', &[0, 3, 4, 5]),
+    (4, 'w', &[1, 2, 4, 5]),
+    (4, 'z', &[0, 1, 4, 5]),
+    // Counts 0 and 8 have a single configuration and no letter
+    (0, ' ', &[]),
+    (8, ' ', &[0, 1, 2, 3, 4, 5, 6, 7]),
+];
+
+/// Position on the clockwise ring of each [`MooreNeighborhood`] offset
+///
+/// [`MooreNeighborhood`]: crate::core::moore_neighborhood::MooreNeighborhood
+const MOORE_TO_RING: [usize; 8] = [7, 0, 1, 6, 2, 5, 4, 3];
+
+/// Isotropic non-totalistic rule in Hensel notation
+///
+/// A cell's next state depends on the arrangement of its live neighbors up
+/// to rotation and reflection, not only on their number. Each neighbor
+/// count is refined by letters, e.g. `2a` (two adjacent neighbors, one
+/// orthogonal and one diagonal) or `3i` (a full side). There are 51 such
+/// transitions in the Moore neighborhood.
+///
+/// Rule strings list birth and survival conditions, where letters select
+/// transitions and a `-` excludes them:
+/// ```text
+/// B3/S23          Conway's Life (outer totalistic)
+/// B2-a/S12        births on any 2 except 2a
+/// B3/S2-i34q      tlife
+/// ```
+///
+/// The rule reads the neighbor configuration from [`Neighbors::mask`], which
+/// must follow the offset order of
+/// [`MooreNeighborhood`](crate::core::moore_neighborhood::MooreNeighborhood).
+#[derive(Debug, Clone)]
+pub struct HenselRule {
+    /// Birth condition indexed by Moore neighbor mask
+    birth: [bool; 256],
+    /// Survival condition indexed by Moore neighbor mask
+    survival: [bool; 256],
+    spec: String,
+}
+
+impl HenselRule {
+    /// Parses a rule string such as `B2-a/S12` or `B3/S2-i34q`
+    ///
+    /// `S23/B3` ordering and the MCell-style `23/3` (survival/birth) form are
+    /// also accepted.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        let (birth_text, survival_text) = split_birth_survival(spec)?;
+        let birth_set = parse_conditions(birth_text)?;
+        let survival_set = parse_conditions(survival_text)?;
+
+        let classes = classify_ring_masks();
+        let mut birth = [false; 256];
+        let mut survival = [false; 256];
+        for moore_mask in 0..256usize {
+            let (count, letter) = classes[moore_to_ring(moore_mask as u64) as usize];
+            birth[moore_mask] = birth_set.contains(&(count, letter));
+            survival[moore_mask] = survival_set.contains(&(count, letter));
+        }
+
+        let spec = format!(
+            "B{}/S{}",
+            format_conditions(&birth_set),
+            format_conditions(&survival_set)
+        );
+        Ok(Self {
+            birth,
+            survival,
+            spec,
+        })
+    }
+
+    /// Returns the names of all transitions, from `0` and `1c` up to `8`
+    pub fn transitions() -> Vec<String> {
+        (0..=8)
+            .flat_map(|count| {
+                letters_for(count)
+                    .into_iter()
+                    .map(move |letter| format!("{}{}", count, letter).trim_end().to_string())
+            })
+            .collect()
+    }
+
+    /// Returns true if the rule only depends on the neighbor count
+    pub fn is_totalistic(&self) -> bool {
+        let classes = classify_ring_masks();
+        let mut by_count = [[None::<bool>; 2]; 9];
+        for moore_mask in 0..256usize {
+            let (count, _) = classes[moore_to_ring(moore_mask as u64) as usize];
+            for (i, table) in [&self.birth, &self.survival].into_iter().enumerate() {
+                match by_count[count][i] {
+                    None => by_count[count][i] = Some(table[moore_mask]),
+                    Some(value) if value != table[moore_mask] => return false,
+                    Some(_) => {}
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns the next state for a live/dead cell with the given Moore mask
+    fn next(&self, current_state: CellState, mask: u64) -> CellState {
+        let table = if current_state.is_alive() {
+            &self.survival
+        } else {
+            &self.birth
+        };
+        if table[(mask & 0xff) as usize] {
+            CellState::Alive
+        } else {
+            CellState::Dead
+        }
+    }
+}
+
+impl Rule for HenselRule {
+    /// Applies the rule from a count alone
+    ///
+    /// A count is only enough when every configuration with that count has
+    /// the same outcome; otherwise the cell is treated as if none of the
+    /// count's transitions applied. [`Rule::apply_neighbors`] uses the full
+    /// configuration and should be preferred.
+    fn apply(&self, current_state: CellState, alive_neighbors: usize) -> CellState {
+        let table = if current_state.is_alive() {
+            &self.survival
+        } else {
+            &self.birth
+        };
+        let all = (0..256usize)
+            .filter(|mask| mask.count_ones() as usize == alive_neighbors)
+            .all(|mask| table[mask]);
+        if all && alive_neighbors <= 8 {
+            CellState::Alive
+        } else {
+            CellState::Dead
+        }
+    }
+
+    fn apply_neighbors(&self, current_state: CellState, neighbors: &Neighbors) -> CellState {
+        self.next(current_state, neighbors.mask)
+    }
+
+    fn needs_configuration(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "Isotropic non-totalistic"
+    }
+
+    fn description(&self) -> &str {
+        &self.spec
+    }
+}
+
+/// Converts a mask in Moore offset order to a mask in clockwise ring order
+fn moore_to_ring(mask: u64) -> u8 {
+    MOORE_TO_RING
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| mask >> i & 1 == 1)
+        .fold(0u8, |ring, (_, &bit)| ring | 1 << bit)
+}
+
+/// Applies one of the 8 symmetries of the square to a ring mask
+///
+/// Rotations move the ring by two positions; reflections mirror it.
+fn transform_ring(mask: u8, rotation: usize, reflect: bool) -> u8 {
+    (0..8)
+        .filter(|i| mask >> i & 1 == 1)
+        .map(|i| {
+            let rotated = (i + 2 * rotation) % 8;
+            if reflect {
+                (8 - rotated) % 8
+            } else {
+                rotated
+            }
+        })
+        .fold(0u8, |acc, bit| acc | 1 << bit)
+}
+
+/// Assigns every ring mask its neighbor count and Hensel letter
+fn classify_ring_masks() -> [(usize, char); 256] {
+    let mut classes = [(0usize, ' '); 256];
+    let mut assign = |mask: u8, count: usize, letter: char| {
+        for rotation in 0..4 {
+            for reflect in [false, true] {
+                classes[transform_ring(mask, rotation, reflect) as usize] = (count, letter);
+            }
+        }
+    };
+
+    for &(count, letter, cells) in TRANSITIONS.iter() {
+        let mask = cells.iter().fold(0u8, |acc, &bit| acc | 1 << bit);
+        assign(mask, count, letter);
+        if (1..4).contains(&count) {
+            assign(!mask, 8 - count, letter);
+        }
+    }
+    classes
+}
+
+/// Returns the letters defined for a neighbor count, in standard order
+fn letters_for(count: usize) -> Vec<char> {
+    let mut letters: Vec<char> = TRANSITIONS
+        .iter()
+        .filter(|(c, _, _)| *c == count.min(8 - count))
+        .map(|(_, letter, _)| *letter)
+        .collect();
+    letters.sort_by_key(|l| LETTER_ORDER.find(*l));
+    letters
+}
+
+/// Splits a rule string into its birth and survival parts
+fn split_birth_survival(spec: &str) -> Result<(&str, &str)> {
+    let (first, second) = spec
+        .split_once('/')
+        .ok_or_else(|| Error::InvalidRuleFormat(format!("rule '{}' has no '/'", spec)))?;
+    let lead = |s: &str| s.chars().next().map(|c| c.to_ascii_uppercase());
+
+    match (lead(first), lead(second)) {
+        (Some('B'), Some('S')) => Ok((&first[1..], &second[1..])),
+        (Some('S'), Some('B')) => Ok((&second[1..], &first[1..])),
+        (Some('B'), None) => Ok((&first[1..], "")),
+        (first_lead, second_lead)
+            if !matches!(first_lead, Some('B' | 'S')) && !matches!(second_lead, Some('B' | 'S')) =>
+        {
+            // MCell order: survival/birth
+            Ok((second, first))
+        }
+        _ => Err(Error::InvalidRuleFormat(format!(
+            "expected B.../S... in rule '{}'",
+            spec
+        ))),
+    }
+}
+
+/// Parses a condition list such as `2-i34q` into (count, letter) pairs
+fn parse_conditions(text: &str) -> Result<Vec<(usize, char)>> {
+    let mut set = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let count = c.to_digit(10).filter(|&d| d <= 8).ok_or_else(|| {
+            Error::InvalidRuleFormat(format!("unexpected '{}' in condition '{}'", c, text))
+        })? as usize;
+
+        let negate = chars.next_if_eq(&'-').is_some();
+        let mut letters = Vec::new();
+        while let Some(letter) = chars.next_if(|l| l.is_ascii_alphabetic()) {
+            letters.push(letter.to_ascii_lowercase());
+        }
+
+        let available = letters_for(count);
+        if let Some(bad) = letters.iter().find(|l| !available.contains(l)) {
+            return Err(Error::InvalidRuleFormat(format!(
+                "transition '{}{}' does not exist",
+                count, bad
+            )));
+        }
+        if negate && letters.is_empty() {
+            return Err(Error::InvalidRuleFormat(format!(
+                "'-' after {} must be followed by letters",
+                count
+            )));
+        }
+
+        let selected: Vec<char> = if letters.is_empty() {
+            available
+        } else if negate {
+            available.into_iter().filter(|l| !letters.contains(l)).collect()
+        } else {
+            letters
+        };
+        for letter in selected {
+            if !set.contains(&(count, letter)) {
+                set.push((count, letter));
+            }
+        }
+    }
+
+    Ok(set)
+}
+
+/// Writes a condition set back in canonical Hensel notation
+fn format_conditions(set: &[(usize, char)]) -> String {
+    let mut out = String::new();
+    for count in 0..=8 {
+        let available = letters_for(count);
+        let included: Vec<char> = available
+            .iter()
+            .copied()
+            .filter(|l| set.contains(&(count, *l)))
+            .collect();
+
+        if included.is_empty() {
+            continue;
+        }
+        out.push_str(&count.to_string());
+        if included.len() == available.len() {
+            continue;
+        }
+        let excluded: Vec<char> = available.into_iter().filter(|l| !included.contains(l)).collect();
+        if excluded.len() < included.len() {
+            out.push('-');
+            out.extend(excluded);
+        } else {
+            out.extend(included);
+        }
+    }
+    out
+}
@@ -0,0 +1,36 @@
+use crate::core::cell::CellState;
+use crate::core::rules::trait_def::Rule;
+
+/// Rule that leaves every cell unchanged
+///
+/// Useful when all the activity comes from agents such as Langton's ant,
+/// which read and write cells themselves.
+#[derive(Debug, Clone, Copy)]
+pub struct IdentityRule;
+
+impl IdentityRule {
+    /// Creates a new identity rule
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for IdentityRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rule for IdentityRule {
+    fn apply(&self, current_state: CellState, _alive_neighbors: usize) -> CellState {
+        current_state
+    }
+
+    fn name(&self) -> &str {
+        "Identity"
+    }
+
+    fn description(&self) -> &str {
+        "Every cell keeps its state"
+    }
+}
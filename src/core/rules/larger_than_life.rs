@@ -0,0 +1,269 @@
+use std::ops::RangeInclusive;
+
+use crate::core::cell::CellState;
+use crate::core::circular_neighborhood::CircularNeighborhood;
+use crate::core::hexagonal_neighborhood::HexagonalNeighborhood;
+use crate::core::moore_neighborhood::MooreNeighborhood;
+use crate::core::neighborhood::Neighborhood;
+use crate::core::rules::trait_def::Rule;
+use crate::core::von_neumann_neighborhood::VonNeumannNeighborhood;
+use crate::error::{Error, Result};
+
+/// Largest range accepted by [`LtLRule::parse`], matching Golly's limit
+pub const MAX_LTL_RANGE: usize = 500;
+
+/// Neighborhood shape used by a Larger than Life rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LtLNeighborhood {
+    /// Square of side 2r+1 (`NM`)
+    Moore,
+    /// Diamond of Manhattan radius r (`NN`)
+    VonNeumann,
+    /// Disc of radius r + 0.5 (`NC`)
+    Circular,
+    /// Cells within hexagonal distance r (`NH`)
+    Hexagonal,
+}
+
+impl LtLNeighborhood {
+    /// Returns the Golly suffix letter for the neighborhood
+    pub fn code(&self) -> char {
+        match self {
+            LtLNeighborhood::Moore => 'M',
+            LtLNeighborhood::VonNeumann => 'N',
+            LtLNeighborhood::Circular => 'C',
+            LtLNeighborhood::Hexagonal => 'H',
+        }
+    }
+
+    fn from_code(code: &str) -> Result<Self> {
+        match code.to_ascii_uppercase().as_str() {
+            "M" => Ok(LtLNeighborhood::Moore),
+            "N" => Ok(LtLNeighborhood::VonNeumann),
+            "C" => Ok(LtLNeighborhood::Circular),
+            "H" => Ok(LtLNeighborhood::Hexagonal),
+            other => Err(Error::InvalidRuleFormat(format!(
+                "unknown Larger than Life neighborhood 'N{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Larger than Life rule
+///
+/// A two-state totalistic rule over a range-r neighborhood. A dead cell is
+/// born when its neighbor count lies in the birth interval and a live cell
+/// survives when its count lies in the survival interval. With `M1` the
+/// cell itself is included in the count.
+///
+/// Rules are written in Golly's notation, e.g. Bosco's Rule:
+/// ```text
+/// R5,C0,M1,S34..58,B34..45,NM
+/// ```
+#[derive(Debug, Clone)]
+pub struct LtLRule {
+    range: usize,
+    include_middle: bool,
+    survival: RangeInclusive<usize>,
+    birth: RangeInclusive<usize>,
+    neighborhood: LtLNeighborhood,
+    spec: String,
+}
+
+impl LtLRule {
+    /// Creates a Larger than Life rule
+    ///
+    /// # Arguments
+    /// * `range` - Neighborhood radius r
+    /// * `include_middle` - Whether the cell counts towards its own total
+    /// * `survival` - Counts for which a live cell stays alive
+    /// * `birth` - Counts for which a dead cell becomes alive
+    /// * `neighborhood` - Shape of the neighborhood
+    pub fn new(
+        range: usize,
+        include_middle: bool,
+        survival: RangeInclusive<usize>,
+        birth: RangeInclusive<usize>,
+        neighborhood: LtLNeighborhood,
+    ) -> Self {
+        let spec = format!(
+            "R{},C0,M{},S{}..{},B{}..{},N{}",
+            range,
+            include_middle as u8,
+            survival.start(),
+            survival.end(),
+            birth.start(),
+            birth.end(),
+            neighborhood.code()
+        );
+        Self {
+            range,
+            include_middle,
+            survival,
+            birth,
+            neighborhood,
+            spec,
+        }
+    }
+
+    /// Parses a rule in Golly's `Rr,Cc,Mm,Smin..max,Bmin..max,Nn` notation
+    ///
+    /// The older `r,bmin,bmax,smin,smax` notation is also accepted and, as in
+    /// Golly, counts the middle cell.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        let parts: Vec<&str> = spec.split(',').map(str::trim).collect();
+
+        if parts.len() == 5 && parts.iter().all(|p| p.chars().all(|c| c.is_ascii_digit())) {
+            let value = |i: usize| parse_number(parts[i]);
+            let range = check_range(value(0)?)?;
+            return Ok(Self::new(
+                range,
+                true,
+                value(3)?..=value(4)?,
+                value(1)?..=value(2)?,
+                LtLNeighborhood::Moore,
+            ));
+        }
+
+        let mut range = None;
+        let mut include_middle = false;
+        let mut survival = None;
+        let mut birth = None;
+        let mut neighborhood = LtLNeighborhood::Moore;
+
+        for part in parts {
+            let mut chars = part.chars();
+            let key = chars.next().map(|c| c.to_ascii_uppercase());
+            let value = chars.as_str();
+            match key {
+                Some('R') => range = Some(check_range(parse_number(value)?)?),
+                Some('C') => {
+                    let states = parse_number(value)?;
+                    if states > 2 {
+                        return Err(Error::InvalidRuleFormat(format!(
+                            "Larger than Life rules with {} states are not supported",
+                            states
+                        )));
+                    }
+                }
+                Some('M') => match value {
+                    "0" => include_middle = false,
+                    "1" => include_middle = true,
+                    _ => {
+                        return Err(Error::InvalidRuleFormat(format!(
+                            "middle flag must be M0 or M1, got '{}'",
+                            part
+                        )))
+                    }
+                },
+                Some('S') => survival = Some(parse_interval(value)?),
+                Some('B') => birth = Some(parse_interval(value)?),
+                Some('N') => neighborhood = LtLNeighborhood::from_code(value)?,
+                _ => {
+                    return Err(Error::InvalidRuleFormat(format!(
+                        "unexpected Larger than Life term '{}'",
+                        part
+                    )))
+                }
+            }
+        }
+
+        let missing = |what: &str| {
+            Error::InvalidRuleFormat(format!("Larger than Life rule '{}' has no {}", spec, what))
+        };
+        Ok(Self::new(
+            range.ok_or_else(|| missing("range (R)"))?,
+            include_middle,
+            survival.ok_or_else(|| missing("survival interval (S)"))?,
+            birth.ok_or_else(|| missing("birth interval (B)"))?,
+            neighborhood,
+        ))
+    }
+
+    /// Returns the neighborhood radius r
+    pub fn range(&self) -> usize {
+        self.range
+    }
+
+    /// Returns true if the cell is counted in its own total
+    pub fn include_middle(&self) -> bool {
+        self.include_middle
+    }
+
+    /// Returns the survival interval
+    pub fn survival(&self) -> &RangeInclusive<usize> {
+        &self.survival
+    }
+
+    /// Returns the birth interval
+    pub fn birth(&self) -> &RangeInclusive<usize> {
+        &self.birth
+    }
+
+    /// Returns the neighborhood shape
+    pub fn neighborhood_kind(&self) -> LtLNeighborhood {
+        self.neighborhood
+    }
+
+    /// Builds the neighborhood this rule is defined over
+    pub fn neighborhood(&self) -> Box<dyn Neighborhood> {
+        match self.neighborhood {
+            LtLNeighborhood::Moore => Box::new(MooreNeighborhood::with_radius(self.range)),
+            LtLNeighborhood::VonNeumann => Box::new(VonNeumannNeighborhood::with_radius(self.range)),
+            LtLNeighborhood::Circular => Box::new(CircularNeighborhood::new(self.range)),
+            LtLNeighborhood::Hexagonal => Box::new(HexagonalNeighborhood::with_radius(self.range)),
+        }
+    }
+}
+
+impl Rule for LtLRule {
+    fn apply(&self, current_state: CellState, alive_neighbors: usize) -> CellState {
+        let count = alive_neighbors + (self.include_middle && current_state.is_alive()) as usize;
+        let next_alive = if current_state.is_alive() {
+            self.survival.contains(&count)
+        } else {
+            self.birth.contains(&count)
+        };
+        if next_alive {
+            CellState::Alive
+        } else {
+            CellState::Dead
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Larger than Life"
+    }
+
+    fn description(&self) -> &str {
+        &self.spec
+    }
+}
+
+fn parse_number(text: &str) -> Result<usize> {
+    text.parse()
+        .map_err(|_| Error::InvalidRuleFormat(format!("expected a number, got '{}'", text)))
+}
+
+fn check_range(range: usize) -> Result<usize> {
+    if range == 0 || range > MAX_LTL_RANGE {
+        return Err(Error::InvalidRuleFormat(format!(
+            "range must be between 1 and {}, got {}",
+            MAX_LTL_RANGE, range
+        )));
+    }
+    Ok(range)
+}
+
+/// Parses `min..max` or a single count
+fn parse_interval(text: &str) -> Result<RangeInclusive<usize>> {
+    match text.split_once("..") {
+        Some((lo, hi)) => Ok(parse_number(lo)?..=parse_number(hi)?),
+        None => {
+            let value = parse_number(text)?;
+            Ok(value..=value)
+        }
+    }
+}
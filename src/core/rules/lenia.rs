@@ -0,0 +1,60 @@
+use crate::core::rules::trait_def::ContinuousRule;
+
+/// Lenia update rule
+///
+/// The potential `u` of a cell is the kernel-weighted average of its
+/// neighborhood. A Gaussian growth function maps it to a rate between -1
+/// and 1:
+/// ```text
+/// G(u) = 2 exp(-(u - μ)² / 2σ²) - 1
+/// ```
+/// and the cell moves by `dt * G(u)`, clipped to [0, 1].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LeniaRule {
+    /// Potential at which growth is strongest (μ)
+    pub mu: f64,
+    /// Width of the growth peak (σ)
+    pub sigma: f64,
+    /// Time step, the reciprocal of Lenia's T
+    pub dt: f64,
+}
+
+impl LeniaRule {
+    /// Creates a Lenia rule
+    ///
+    /// # Arguments
+    /// * `mu` - Growth centre μ
+    /// * `sigma` - Growth width σ
+    /// * `dt` - Time step
+    pub fn new(mu: f64, sigma: f64, dt: f64) -> Self {
+        Self { mu, sigma, dt }
+    }
+
+    /// Returns the parameters of Orbium, Lenia's best-known glider, which
+    /// uses a single ring of radius 13
+    pub fn orbium() -> Self {
+        Self::new(0.15, 0.015, 0.1)
+    }
+
+    /// Returns the growth rate for a potential
+    pub fn growth(&self, potential: f64) -> f64 {
+        let d = (potential - self.mu) / self.sigma;
+        2.0 * (-d * d / 2.0).exp() - 1.0
+    }
+}
+
+impl Default for LeniaRule {
+    fn default() -> Self {
+        Self::orbium()
+    }
+}
+
+impl ContinuousRule for LeniaRule {
+    fn apply(&self, value: f64, potential: f64) -> f64 {
+        (value + self.dt * self.growth(potential)).clamp(0.0, 1.0)
+    }
+
+    fn name(&self) -> &str {
+        "Lenia"
+    }
+}
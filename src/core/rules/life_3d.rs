@@ -0,0 +1,278 @@
+use crate::core::cell::CellState;
+use crate::core::moore_neighborhood_3d::MooreNeighborhood3D;
+use crate::core::neighborhood_3d::Neighborhood3D;
+use crate::core::rules::trait_def::Rule;
+use crate::core::von_neumann_neighborhood_3d::VonNeumannNeighborhood3D;
+use crate::error::{Error, Result};
+
+/// Neighborhood used by a 3D totalistic rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Life3DNeighborhood {
+    /// The 26 cells of the surrounding cube (`M`)
+    Moore,
+    /// The 6 cells sharing a face (`N`)
+    VonNeumann,
+}
+
+impl Life3DNeighborhood {
+    /// Returns the suffix letter for the neighborhood
+    pub fn code(&self) -> char {
+        match self {
+            Life3DNeighborhood::Moore => 'M',
+            Life3DNeighborhood::VonNeumann => 'N',
+        }
+    }
+
+    /// Returns the number of neighbors
+    pub fn size(&self) -> usize {
+        match self {
+            Life3DNeighborhood::Moore => 26,
+            Life3DNeighborhood::VonNeumann => 6,
+        }
+    }
+}
+
+/// Totalistic rule for three-dimensional grids
+///
+/// A dead cell is born when its number of live neighbors is in the birth
+/// set and a live cell survives when its count is in the survival set.
+/// With more than two states, a live cell that does not survive decays
+/// through states 2, 3, ... before dying, as in Generations rules, and only
+/// state 1 counts as a live neighbor.
+///
+/// Rules are written as survival/birth/states/neighborhood, with counts
+/// separated by commas and runs written as ranges:
+/// ```text
+/// 13-26/13-14,17-19/2/M    Clouds 1
+/// 4/4/5/M                  445
+/// ```
+/// Bays' four-digit notation is also accepted: `4555` survives on 4 to 5
+/// live neighbors and is born on 5 to 5.
+#[derive(Debug, Clone)]
+pub struct Life3DRule {
+    survival: u32,
+    birth: u32,
+    states: u8,
+    neighborhood: Life3DNeighborhood,
+    spec: String,
+}
+
+impl Life3DRule {
+    /// Creates a 3D rule
+    ///
+    /// # Arguments
+    /// * `survival` - Counts for which a live cell stays alive
+    /// * `birth` - Counts for which a dead cell becomes alive
+    /// * `states` - Number of states, at least 2
+    /// * `neighborhood` - Neighborhood the counts are taken over
+    ///
+    /// # Errors
+    /// Returns an error if a count exceeds the size of the neighborhood or
+    /// there are fewer than two states
+    pub fn new(
+        survival: &[usize],
+        birth: &[usize],
+        states: u8,
+        neighborhood: Life3DNeighborhood,
+    ) -> Result<Self> {
+        if states < 2 {
+            return Err(Error::InvalidRuleFormat(format!(
+                "a 3D rule needs at least 2 states, got {}",
+                states
+            )));
+        }
+        let survival = count_mask(survival, neighborhood)?;
+        let birth = count_mask(birth, neighborhood)?;
+        let spec = format!(
+            "{}/{}/{}/{}",
+            mask_to_text(survival),
+            mask_to_text(birth),
+            states,
+            neighborhood.code()
+        );
+        Ok(Self {
+            survival,
+            birth,
+            states,
+            neighborhood,
+            spec,
+        })
+    }
+
+    /// Returns Life 4555: survival on 4-5, birth on 5
+    pub fn life_4555() -> Self {
+        Self::new(&[4, 5], &[5], 2, Life3DNeighborhood::Moore).expect("valid 3D rule")
+    }
+
+    /// Returns Clouds 1: survival on 13-26, birth on 13-14 and 17-19
+    pub fn clouds() -> Self {
+        let survival: Vec<usize> = (13..=26).collect();
+        Self::new(&survival, &[13, 14, 17, 18, 19], 2, Life3DNeighborhood::Moore)
+            .expect("valid 3D rule")
+    }
+
+    /// Parses a rule in survival/birth/states/neighborhood notation, Bays'
+    /// four-digit notation, or by name (`Clouds`)
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        let named: String =
+            spec.to_ascii_uppercase().chars().filter(char::is_ascii_alphanumeric).collect();
+        if named == "CLOUDS" || named == "CLOUDS1" {
+            return Ok(Self::clouds());
+        }
+
+        if spec.len() == 4 && spec.chars().all(|c| c.is_ascii_digit()) {
+            let digit = |i: usize| (spec.as_bytes()[i] - b'0') as usize;
+            let survival: Vec<usize> = (digit(0)..=digit(1)).collect();
+            let birth: Vec<usize> = (digit(2)..=digit(3)).collect();
+            return Self::new(&survival, &birth, 2, Life3DNeighborhood::Moore);
+        }
+
+        let parts: Vec<&str> = spec.split('/').map(str::trim).collect();
+        if parts.len() < 2 || parts.len() > 4 {
+            return Err(Error::InvalidRuleFormat(format!(
+                "expected survival/birth/states/neighborhood, got '{}'",
+                spec
+            )));
+        }
+        let states = match parts.get(2) {
+            Some(states) => states.parse().map_err(|_| {
+                Error::InvalidRuleFormat(format!("invalid number of states '{}'", states))
+            })?,
+            None => 2,
+        };
+        let neighborhood = match parts.get(3).map(|n| n.to_ascii_uppercase()).as_deref() {
+            None | Some("M") => Life3DNeighborhood::Moore,
+            Some("N") | Some("VN") => Life3DNeighborhood::VonNeumann,
+            Some(other) => {
+                return Err(Error::InvalidRuleFormat(format!(
+                    "unknown 3D neighborhood '{}'",
+                    other
+                )))
+            }
+        };
+        let survival = parse_counts(parts[0], neighborhood)?;
+        let birth = parse_counts(parts[1], neighborhood)?;
+        Self::new(&survival, &birth, states, neighborhood)
+    }
+
+    /// Returns true if a live cell with `count` live neighbors survives
+    pub fn survives(&self, count: usize) -> bool {
+        count < 32 && self.survival >> count & 1 == 1
+    }
+
+    /// Returns true if a dead cell with `count` live neighbors is born
+    pub fn is_born(&self, count: usize) -> bool {
+        count < 32 && self.birth >> count & 1 == 1
+    }
+
+    /// Returns the rule in survival/birth/states/neighborhood notation
+    pub fn spec(&self) -> &str {
+        &self.spec
+    }
+
+    /// Returns the neighborhood shape
+    pub fn neighborhood_kind(&self) -> Life3DNeighborhood {
+        self.neighborhood
+    }
+
+    /// Builds the neighborhood this rule is defined over
+    pub fn neighborhood(&self) -> Box<dyn Neighborhood3D> {
+        match self.neighborhood {
+            Life3DNeighborhood::Moore => Box::new(MooreNeighborhood3D::new()),
+            Life3DNeighborhood::VonNeumann => Box::new(VonNeumannNeighborhood3D::new()),
+        }
+    }
+}
+
+impl Rule for Life3DRule {
+    fn apply(&self, current_state: CellState, alive_neighbors: usize) -> CellState {
+        match current_state {
+            CellState::Dead if self.is_born(alive_neighbors) => CellState::Alive,
+            CellState::Dead => CellState::Dead,
+            CellState::Alive if self.survives(alive_neighbors) => CellState::Alive,
+            // Cells that fail to survive decay through the remaining states
+            state => {
+                let next = state.index() + 1;
+                if next < self.states {
+                    CellState::State(next)
+                } else {
+                    CellState::Dead
+                }
+            }
+        }
+    }
+
+    fn states(&self) -> u8 {
+        self.states
+    }
+
+    fn name(&self) -> &str {
+        "3D Life"
+    }
+
+    fn description(&self) -> &str {
+        &self.spec
+    }
+}
+
+/// Builds a bit set of neighbor counts, checking them against the neighborhood
+fn count_mask(counts: &[usize], neighborhood: Life3DNeighborhood) -> Result<u32> {
+    counts.iter().try_fold(0u32, |mask, &count| {
+        Ok(mask | 1 << check_count(count, neighborhood)?)
+    })
+}
+
+/// Returns a neighbor count if the neighborhood has that many neighbors
+fn check_count(count: usize, neighborhood: Life3DNeighborhood) -> Result<usize> {
+    if count > neighborhood.size() {
+        return Err(Error::InvalidRuleFormat(format!(
+            "count {} exceeds the {} neighbors of the {} neighborhood",
+            count,
+            neighborhood.size(),
+            neighborhood.code()
+        )));
+    }
+    Ok(count)
+}
+
+/// Parses a comma-separated list of counts and `min-max` runs, checking
+/// them against the neighborhood before any run is expanded
+fn parse_counts(text: &str, neighborhood: Life3DNeighborhood) -> Result<Vec<usize>> {
+    let number = |text: &str| {
+        let count = text.trim().parse::<usize>().map_err(|_| {
+            Error::InvalidRuleFormat(format!("expected a neighbor count, got '{}'", text))
+        })?;
+        check_count(count, neighborhood)
+    };
+    let mut counts = Vec::new();
+    for item in text.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+        match item.split_once('-') {
+            Some((min, max)) => counts.extend(number(min)?..=number(max)?),
+            None => counts.push(number(item)?),
+        }
+    }
+    Ok(counts)
+}
+
+/// Writes a bit set of counts as a list of counts and runs
+fn mask_to_text(mask: u32) -> String {
+    let mut items = Vec::new();
+    let mut count = 0;
+    while count < 32 {
+        if mask >> count & 1 == 0 {
+            count += 1;
+            continue;
+        }
+        let start = count;
+        while count < 32 && mask >> count & 1 == 1 {
+            count += 1;
+        }
+        items.push(if count - 1 == start {
+            start.to_string()
+        } else {
+            format!("{}-{}", start, count - 1)
+        });
+    }
+    items.join(",")
+}
@@ -1,5 +1,29 @@
 pub mod trait_def;
 pub mod conway;
+pub mod wolfram;
+pub mod larger_than_life;
+pub mod weighted_life;
+pub mod hensel;
+pub mod life_3d;
+pub mod lenia;
+pub mod wireworld;
+pub mod brians_brain;
+pub mod rule_table;
+pub mod identity;
+pub mod stochastic;
+pub mod parse;
 
-pub use trait_def::Rule;
-pub use conway::ConwayRule;
\ No newline at end of file
+pub use trait_def::{ContinuousRule, Neighbors, Rule, Rule1D};
+pub use brians_brain::BriansBrainRule;
+pub use conway::ConwayRule;
+pub use hensel::HenselRule;
+pub use identity::IdentityRule;
+pub use larger_than_life::{LtLNeighborhood, LtLRule};
+pub use lenia::LeniaRule;
+pub use life_3d::{Life3DNeighborhood, Life3DRule};
+pub use parse::{parse_rule, ParsedRule};
+pub use rule_table::{RuleTable, TableNeighborhood, TableSymmetry};
+pub use stochastic::StochasticRule;
+pub use weighted_life::WeightedLifeRule;
+pub use wireworld::WireworldRule;
+pub use wolfram::{TotalisticCodeRule, WolframRule};
\ No newline at end of file
@@ -0,0 +1,97 @@
+use crate::core::moore_neighborhood::MooreNeighborhood;
+use crate::core::neighborhood::Neighborhood;
+use crate::core::rules::brians_brain::BriansBrainRule;
+use crate::core::rules::conway::ConwayRule;
+use crate::core::rules::hensel::HenselRule;
+use crate::core::rules::larger_than_life::LtLRule;
+use crate::core::rules::rule_table::RuleTable;
+use crate::core::rules::trait_def::Rule;
+use crate::core::rules::weighted_life::WeightedLifeRule;
+use crate::core::rules::wireworld::WireworldRule;
+use crate::error::{Error, Result};
+
+/// A rule parsed from text together with the neighborhood it is defined over
+pub struct ParsedRule {
+    /// Rule to apply for evolution
+    pub rule: Box<dyn Rule>,
+    /// Neighborhood the rule expects
+    pub neighborhood: Box<dyn Neighborhood>,
+}
+
+/// Parses a rule from any of the supported notations
+///
+/// Recognised forms:
+/// * `Life` or `Conway` - Conway's Game of Life
+/// * `Wireworld`, `Brian's Brain` or `Langton's Loops` - named multi-state rules
+/// * Life-like and isotropic non-totalistic rules, e.g. `B3/S23` or `B2-a/S12`
+/// * Larger than Life, e.g. `R5,C0,M1,S34..58,B34..45,NM`
+/// * MCell Weighted Life, e.g. `NW1,NN1,NE1,WW1,ME0,EE1,SW1,SS1,SE1,HI0,RS2,RS3,RB3`
+/// * Golly rule tables, starting with `@RULE` or `@TABLE`
+pub fn parse_rule(spec: &str) -> Result<ParsedRule> {
+    let spec = spec.trim();
+    let upper = spec.to_ascii_uppercase();
+
+    if spec.starts_with('@') {
+        let rule = RuleTable::parse(spec)?;
+        return Ok(ParsedRule {
+            neighborhood: rule.neighborhood(),
+            rule: Box::new(rule),
+        });
+    }
+
+    // Named rules ignore case, spaces and apostrophes
+    let named: String = upper.chars().filter(char::is_ascii_alphanumeric).collect();
+    match named.as_str() {
+        "LIFE" | "CONWAY" => {
+            return Ok(ParsedRule {
+                rule: Box::new(ConwayRule::new()),
+                neighborhood: Box::new(MooreNeighborhood::new()),
+            })
+        }
+        "WIREWORLD" => {
+            return Ok(ParsedRule {
+                rule: Box::new(WireworldRule::new()),
+                neighborhood: Box::new(MooreNeighborhood::new()),
+            })
+        }
+        "BRIANSBRAIN" => {
+            return Ok(ParsedRule {
+                rule: Box::new(BriansBrainRule::new()),
+                neighborhood: Box::new(MooreNeighborhood::new()),
+            })
+        }
+        "LANGTONSLOOPS" => {
+            let rule = RuleTable::langtons_loops();
+            return Ok(ParsedRule {
+                neighborhood: rule.neighborhood(),
+                rule: Box::new(rule),
+            });
+        }
+        _ => {}
+    }
+
+    if spec.contains('/') {
+        return Ok(ParsedRule {
+            rule: Box::new(HenselRule::parse(spec)?),
+            neighborhood: Box::new(MooreNeighborhood::new()),
+        });
+    }
+
+    if upper.contains("RS") || upper.contains("RB") || upper.starts_with("NW") {
+        let rule = WeightedLifeRule::parse(spec)?;
+        return Ok(ParsedRule {
+            neighborhood: Box::new(rule.neighborhood()),
+            rule: Box::new(rule),
+        });
+    }
+
+    if upper.starts_with('R') || upper.contains(',') {
+        let rule = LtLRule::parse(spec)?;
+        return Ok(ParsedRule {
+            neighborhood: rule.neighborhood(),
+            rule: Box::new(rule),
+        });
+    }
+
+    Err(Error::InvalidRuleFormat(format!("unrecognised rule '{}'", spec)))
+}
@@ -0,0 +1,511 @@
+use std::collections::HashMap;
+
+use crate::core::cell::{CellState, Position};
+use crate::core::dense_grid::DenseGrid;
+use crate::core::grid::Grid;
+use crate::core::moore_neighborhood::MooreNeighborhood;
+use crate::core::neighborhood::Neighborhood;
+use crate::core::rules::trait_def::{Neighbors, Rule};
+use crate::core::von_neumann_neighborhood::VonNeumannNeighborhood;
+use crate::error::{Error, Result};
+
+/// Langton's self-reproducing loops, as a rule table
+const LANGTONS_LOOPS: &str = include_str!("langtons_loops.table");
+
+/// Neighbor offsets in rule table order: N, E, S, W
+const VON_NEUMANN_ORDER: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Neighbor offsets in rule table order: N, NE, E, SE, S, SW, W, NW
+const MOORE_ORDER: [(isize, isize); 8] =
+    [(0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)];
+
+/// Neighborhood a rule table is written for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableNeighborhood {
+    /// Four orthogonal neighbors
+    VonNeumann,
+    /// Eight surrounding neighbors
+    Moore,
+}
+
+impl TableNeighborhood {
+    /// Returns the neighbor offsets in the order transitions list them
+    pub fn order(&self) -> &'static [(isize, isize)] {
+        match self {
+            TableNeighborhood::VonNeumann => &VON_NEUMANN_ORDER,
+            TableNeighborhood::Moore => &MOORE_ORDER,
+        }
+    }
+}
+
+/// Symmetries under which each transition of a rule table also applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableSymmetry {
+    /// Transitions apply only as written
+    None,
+    /// Left-right mirror images
+    ReflectHorizontal,
+    /// Rotations by 90 degrees
+    Rotate4,
+    /// Rotations by 90 degrees and their mirror images
+    Rotate4Reflect,
+    /// Rotations by 45 degrees (Moore only)
+    Rotate8,
+    /// Rotations by 45 degrees and their mirror images (Moore only)
+    Rotate8Reflect,
+    /// Any arrangement of the same neighbor states
+    Permute,
+}
+
+impl TableSymmetry {
+    fn parse(name: &str, neighborhood: TableNeighborhood) -> Result<Self> {
+        let symmetry = match name {
+            "none" => TableSymmetry::None,
+            "reflect_horizontal" => TableSymmetry::ReflectHorizontal,
+            "rotate4" => TableSymmetry::Rotate4,
+            "rotate4reflect" => TableSymmetry::Rotate4Reflect,
+            "rotate8" => TableSymmetry::Rotate8,
+            "rotate8reflect" => TableSymmetry::Rotate8Reflect,
+            "permute" => TableSymmetry::Permute,
+            _ => {
+                return Err(Error::InvalidRuleFormat(format!("unknown symmetries '{}'", name)))
+            }
+        };
+        let rotate8 = matches!(symmetry, TableSymmetry::Rotate8 | TableSymmetry::Rotate8Reflect);
+        if rotate8 && neighborhood == TableNeighborhood::VonNeumann {
+            return Err(Error::InvalidRuleFormat(format!(
+                "symmetries '{}' need the Moore neighborhood",
+                name
+            )));
+        }
+        Ok(symmetry)
+    }
+}
+
+/// Multi-state rule given by an explicit transition table
+///
+/// Tables use the `@TABLE` format of Golly `.rule` files: a header giving
+/// the number of states, the neighborhood and the symmetries, optional
+/// variables, then one transition per line listing the state of the cell,
+/// the states of its neighbors clockwise from north, and the new state:
+/// ```text
+/// @RULE Example
+/// @TABLE
+/// n_states:3
+/// neighborhood:vonNeumann
+/// symmetries:rotate4
+/// var a={0,1,2}
+/// 0,1,a,a,0,2
+/// ```
+/// A variable used more than once in a transition takes the same value
+/// everywhere. Tables with fewer than ten states may write transitions as
+/// plain digit strings such as `000012`. When several transitions match, the
+/// first one wins, and cells no transition matches keep their state. An
+/// optional `@COLORS` section gives the palette as `state r g b` lines.
+#[derive(Debug, Clone)]
+pub struct RuleTable {
+    name: String,
+    states: u8,
+    neighborhood: TableNeighborhood,
+    symmetry: TableSymmetry,
+    /// Next state keyed by the cell state followed by the canonical
+    /// neighbor states
+    transitions: HashMap<Vec<u8>, u8>,
+    /// Number of transitions as written, before symmetry and variable expansion
+    rules: usize,
+    /// Index into the neighborhood's offsets of each neighbor in table order
+    offset_order: Vec<usize>,
+    palette: Option<Vec<[u8; 3]>>,
+}
+
+impl RuleTable {
+    /// Parses a rule table from the contents of a `.rule` file
+    ///
+    /// Text without an `@TABLE` line is read as the body of a table.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut name = "Rule table".to_string();
+        let mut section = if text.contains("@TABLE") { "" } else { "@TABLE" };
+        let mut table = Vec::new();
+        let mut colors = Vec::new();
+
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line.starts_with('@') {
+                let mut words = line.split_whitespace();
+                section = words.next().unwrap_or("");
+                if section == "@RULE" {
+                    if let Some(rule_name) = words.next() {
+                        name = rule_name.to_string();
+                    }
+                }
+                continue;
+            }
+            match section {
+                "@TABLE" => table.push(line),
+                "@COLORS" => colors.push(line),
+                _ => {}
+            }
+        }
+
+        let mut rule = Self::parse_table(&table)?;
+        rule.name = name;
+        if !colors.is_empty() {
+            rule.palette = Some(parse_colors(&colors, rule.states)?);
+        }
+        Ok(rule)
+    }
+
+    /// Loads a rule table from a `.rule` or `.table` file
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let text = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| Error::IoError(format!("{}: {}", path.as_ref().display(), e)))?;
+        Self::parse(&text)
+    }
+
+    /// Returns Langton's self-reproducing loops
+    ///
+    /// An eight-state von Neumann rule in which a loop of sheath (state 2)
+    /// carrying a genome of signals builds a copy of itself every 151
+    /// generations. [`RuleTable::LANGTONS_LOOP`] is the initial loop.
+    pub fn langtons_loops() -> Self {
+        Self::parse(LANGTONS_LOOPS).expect("built-in Langton's loops table is valid")
+    }
+
+    /// Langton's initial loop, for use with [`RuleTable::pattern`]
+    pub const LANGTONS_LOOP: &'static str = "\
+.22222222
+2170140142
+2022222202
+272....212
+212....212
+202....212
+272....212
+21222222122222
+207107107111112
+.2222222222222";
+
+    /// Builds a grid from rows of state digits
+    ///
+    /// Each character is one cell: digits give the state, and `.` or a space
+    /// is state 0. The grid is as wide as the longest line.
+    pub fn pattern(text: &str) -> Result<DenseGrid> {
+        let lines: Vec<&str> = text.lines().collect();
+        let width = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+        let mut grid = DenseGrid::new(width, lines.len())?;
+
+        for (y, line) in lines.iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let state = match c {
+                    '.' | ' ' => 0,
+                    _ => c.to_digit(10).ok_or_else(|| {
+                        Error::PatternParseError(format!("unexpected character '{}' in pattern", c))
+                    })? as u8,
+                };
+                grid.set(Position::new(x, y), CellState::from_index(state))?;
+            }
+        }
+        Ok(grid)
+    }
+
+    /// Returns the neighborhood the table is written for
+    pub fn neighborhood_kind(&self) -> TableNeighborhood {
+        self.neighborhood
+    }
+
+    /// Returns the symmetries of the table
+    pub fn symmetry(&self) -> TableSymmetry {
+        self.symmetry
+    }
+
+    /// Returns the number of transitions as written in the table
+    pub fn len(&self) -> usize {
+        self.rules
+    }
+
+    /// Returns true if the table has no transitions
+    pub fn is_empty(&self) -> bool {
+        self.rules == 0
+    }
+
+    /// Builds the neighborhood this rule is defined over
+    pub fn neighborhood(&self) -> Box<dyn Neighborhood> {
+        match self.neighborhood {
+            TableNeighborhood::VonNeumann => Box::new(VonNeumannNeighborhood::new()),
+            TableNeighborhood::Moore => Box::new(MooreNeighborhood::new()),
+        }
+    }
+
+    /// Looks up the transition for a cell
+    ///
+    /// # Arguments
+    /// * `center` - State of the cell
+    /// * `neighbors` - Neighbor states clockwise from north
+    ///
+    /// # Returns
+    /// The new state, or `None` if no transition matches
+    pub fn transition(&self, center: u8, neighbors: &[u8]) -> Option<u8> {
+        if neighbors.len() != self.neighborhood.order().len() {
+            return None;
+        }
+        self.transitions.get(&self.key(center, neighbors)).copied()
+    }
+
+    /// Builds the lookup key: the centre followed by the smallest
+    /// arrangement of the neighbors under the table's symmetries
+    fn key(&self, center: u8, neighbors: &[u8]) -> Vec<u8> {
+        let n = neighbors.len();
+        let rotation_step = match (self.symmetry, self.neighborhood) {
+            (TableSymmetry::Rotate4 | TableSymmetry::Rotate4Reflect, TableNeighborhood::Moore) => 2,
+            (TableSymmetry::Rotate4 | TableSymmetry::Rotate4Reflect, _)
+            | (TableSymmetry::Rotate8 | TableSymmetry::Rotate8Reflect, _) => 1,
+            _ => n,
+        };
+        let reflect = matches!(
+            self.symmetry,
+            TableSymmetry::ReflectHorizontal
+                | TableSymmetry::Rotate4Reflect
+                | TableSymmetry::Rotate8Reflect
+        );
+
+        let mut best: Vec<u8> = neighbors.to_vec();
+        if self.symmetry == TableSymmetry::Permute {
+            best.sort_unstable();
+        } else {
+            for shift in (0..n).step_by(rotation_step) {
+                let rotated: Vec<u8> = (0..n).map(|i| neighbors[(i + shift) % n]).collect();
+                if rotated < best {
+                    best = rotated.clone();
+                }
+                if reflect {
+                    // Mirror about the north-south axis
+                    let mirrored: Vec<u8> = (0..n).map(|i| rotated[(n - i) % n]).collect();
+                    if mirrored < best {
+                        best = mirrored;
+                    }
+                }
+            }
+        }
+
+        let mut key = Vec::with_capacity(n + 1);
+        key.push(center);
+        key.extend(best);
+        key
+    }
+
+    fn parse_table(lines: &[&str]) -> Result<Self> {
+        let mut states = None;
+        let mut neighborhood = None;
+        let mut symmetry_name = "none".to_string();
+        let mut variables: HashMap<String, Vec<u8>> = HashMap::new();
+        let mut rows = Vec::new();
+
+        for &line in lines {
+            if let Some(value) = line.strip_prefix("n_states:") {
+                let count: u8 = value.trim().parse().map_err(|_| {
+                    Error::InvalidRuleFormat(format!("invalid number of states '{}'", value))
+                })?;
+                if count < 2 {
+                    return Err(Error::InvalidRuleFormat("a rule needs at least 2 states".into()));
+                }
+                states = Some(count);
+            } else if let Some(value) = line.strip_prefix("neighborhood:") {
+                neighborhood = Some(match value.trim() {
+                    "vonNeumann" => TableNeighborhood::VonNeumann,
+                    "Moore" => TableNeighborhood::Moore,
+                    other => {
+                        return Err(Error::InvalidRuleFormat(format!(
+                            "unsupported rule table neighborhood '{}'",
+                            other
+                        )))
+                    }
+                });
+            } else if let Some(value) = line.strip_prefix("symmetries:") {
+                symmetry_name = value.trim().to_string();
+            } else if let Some(definition) = line.strip_prefix("var ") {
+                let (var, values) = parse_variable(definition, &variables)?;
+                variables.insert(var, values);
+            } else {
+                rows.push(line);
+            }
+        }
+
+        let states = states
+            .ok_or_else(|| Error::InvalidRuleFormat("rule table has no n_states line".into()))?;
+        let neighborhood = neighborhood
+            .ok_or_else(|| Error::InvalidRuleFormat("rule table has no neighborhood line".into()))?;
+        let symmetry = TableSymmetry::parse(&symmetry_name, neighborhood)?;
+
+        let mut rule = Self {
+            name: String::new(),
+            states,
+            neighborhood,
+            symmetry,
+            transitions: HashMap::new(),
+            rules: rows.len(),
+            offset_order: Vec::new(),
+            palette: None,
+        };
+        // Neighborhood offsets are listed row by row; tables go clockwise from north
+        let offsets = rule.neighborhood();
+        rule.offset_order = neighborhood
+            .order()
+            .iter()
+            .map(|offset| offsets.offsets().iter().position(|o| o == offset).unwrap_or(0))
+            .collect();
+
+        let width = neighborhood.order().len() + 2;
+        for row in rows {
+            let tokens: Vec<&str> = if row.contains(',') {
+                row.split(',').map(str::trim).collect()
+            } else {
+                row.matches(|_| true).collect()
+            };
+            if tokens.len() != width {
+                return Err(Error::InvalidRuleFormat(format!(
+                    "transition '{}' should have {} entries",
+                    row, width
+                )));
+            }
+            for values in expand(&tokens, &variables, states)? {
+                let key = rule.key(values[0], &values[1..width - 1]);
+                rule.transitions.entry(key).or_insert(values[width - 1]);
+            }
+        }
+
+        Ok(rule)
+    }
+}
+
+impl Rule for RuleTable {
+    /// A count is not enough to look up a transition, so the cell keeps its
+    /// state; [`Rule::apply_neighbors`] does the lookup
+    fn apply(&self, current_state: CellState, _alive_neighbors: usize) -> CellState {
+        current_state
+    }
+
+    fn apply_neighbors(&self, current_state: CellState, neighbors: &Neighbors) -> CellState {
+        if neighbors.states.len() != self.offset_order.len() {
+            return current_state;
+        }
+        let clockwise: Vec<u8> =
+            self.offset_order.iter().map(|&i| neighbors.states[i].index()).collect();
+
+        self.transition(current_state.index(), &clockwise)
+            .map_or(current_state, CellState::from_index)
+    }
+
+    fn needs_configuration(&self) -> bool {
+        true
+    }
+
+    fn states(&self) -> u8 {
+        self.states
+    }
+
+    fn palette(&self) -> Option<&[[u8; 3]]> {
+        self.palette.as_deref()
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "Transition table"
+    }
+}
+
+/// Parses `name={a,b,...}`, where entries are states or earlier variables
+fn parse_variable(
+    definition: &str,
+    variables: &HashMap<String, Vec<u8>>,
+) -> Result<(String, Vec<u8>)> {
+    let invalid = || Error::InvalidRuleFormat(format!("invalid variable 'var {}'", definition));
+    let (name, values) = definition.split_once('=').ok_or_else(invalid)?;
+    let values = values
+        .trim()
+        .strip_prefix('{')
+        .and_then(|v| v.strip_suffix('}'))
+        .ok_or_else(invalid)?;
+
+    let mut states = Vec::new();
+    for value in values.split(',').map(str::trim) {
+        match variables.get(value) {
+            Some(existing) => states.extend(existing),
+            None => states.push(value.parse().map_err(|_| invalid())?),
+        }
+    }
+    Ok((name.trim().to_string(), states))
+}
+
+/// Expands a transition's variables into every concrete transition
+///
+/// Each distinct variable is bound once, so repeated uses share a value.
+fn expand(tokens: &[&str], variables: &HashMap<String, Vec<u8>>, states: u8) -> Result<Vec<Vec<u8>>> {
+    let mut names: Vec<&str> = Vec::new();
+    for &token in tokens {
+        if variables.contains_key(token) && !names.contains(&token) {
+            names.push(token);
+        }
+    }
+
+    let mut bindings: Vec<Vec<u8>> = vec![Vec::new()];
+    for name in &names {
+        bindings = bindings
+            .into_iter()
+            .flat_map(|binding| {
+                variables[*name].iter().map(move |&value| {
+                    let mut next = binding.clone();
+                    next.push(value);
+                    next
+                })
+            })
+            .collect();
+    }
+
+    bindings
+        .into_iter()
+        .map(|binding| {
+            tokens
+                .iter()
+                .map(|&token| {
+                    let value = match names.iter().position(|&name| name == token) {
+                        Some(i) => binding[i],
+                        None => token.parse().map_err(|_| {
+                            Error::InvalidRuleFormat(format!("unknown state or variable '{}'", token))
+                        })?,
+                    };
+                    if value >= states {
+                        return Err(Error::InvalidRuleFormat(format!(
+                            "state {} is out of range for a {}-state rule",
+                            value, states
+                        )));
+                    }
+                    Ok(value)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Parses `state r g b` lines into a palette, defaulting unlisted states to grey
+fn parse_colors(lines: &[&str], states: u8) -> Result<Vec<[u8; 3]>> {
+    let mut palette = vec![[128, 128, 128]; states as usize];
+    palette[0] = [0, 0, 0];
+    for line in lines {
+        let values: Vec<u8> = line
+            .split_whitespace()
+            .map(|v| v.parse())
+            .collect::<std::result::Result<_, _>>()
+            .map_err(|_| Error::InvalidRuleFormat(format!("invalid colour line '{}'", line)))?;
+        if let [state, r, g, b] = values[..] {
+            if let Some(color) = palette.get_mut(state as usize) {
+                *color = [r, g, b];
+            }
+        }
+    }
+    Ok(palette)
+}
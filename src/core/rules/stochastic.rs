@@ -0,0 +1,132 @@
+use rand::{Rng, RngCore};
+
+use crate::core::cell::CellState;
+use crate::core::neighborhood::Neighborhood;
+use crate::core::rules::trait_def::{Neighbors, Rule};
+use crate::error::{Error, Result};
+
+/// Probabilistic version of another rule
+///
+/// The wrapped rule decides what may happen to each cell, and chance decides
+/// whether it does: a dead cell the rule would bring to life is born with
+/// `birth_probability`, and a live cell the rule would keep alive survives
+/// with `survival_probability`. All other transitions happen as the wrapped
+/// rule says. With both probabilities at 1.0 the rule behaves exactly like
+/// the one it wraps.
+pub struct StochasticRule {
+    inner: Box<dyn Rule>,
+    birth_probability: f64,
+    survival_probability: f64,
+    description: String,
+}
+
+impl StochasticRule {
+    /// Wraps a rule with birth and survival probabilities
+    ///
+    /// # Arguments
+    /// * `inner` - Rule deciding which transitions are possible
+    /// * `birth_probability` - Chance that a possible birth happens
+    /// * `survival_probability` - Chance that a possible survival happens
+    ///
+    /// # Errors
+    /// Returns an error if a probability is outside 0.0 to 1.0
+    pub fn new(
+        inner: Box<dyn Rule>,
+        birth_probability: f64,
+        survival_probability: f64,
+    ) -> Result<Self> {
+        for probability in [birth_probability, survival_probability] {
+            if !(0.0..=1.0).contains(&probability) {
+                return Err(Error::InvalidRuleFormat(format!(
+                    "probability {} is not between 0 and 1",
+                    probability
+                )));
+            }
+        }
+        let description = format!(
+            "{} with birth p={} and survival p={}",
+            inner.description(),
+            birth_probability,
+            survival_probability
+        );
+        Ok(Self {
+            inner,
+            birth_probability,
+            survival_probability,
+            description,
+        })
+    }
+
+    /// Returns the wrapped rule
+    pub fn inner(&self) -> &dyn Rule {
+        self.inner.as_ref()
+    }
+
+    /// Returns the chance that a possible birth happens
+    pub fn birth_probability(&self) -> f64 {
+        self.birth_probability
+    }
+
+    /// Returns the chance that a possible survival happens
+    pub fn survival_probability(&self) -> f64 {
+        self.survival_probability
+    }
+}
+
+impl Rule for StochasticRule {
+    /// Applies the wrapped rule without chance
+    fn apply(&self, current_state: CellState, alive_neighbors: usize) -> CellState {
+        self.inner.apply(current_state, alive_neighbors)
+    }
+
+    fn apply_neighbors(&self, current_state: CellState, neighbors: &Neighbors) -> CellState {
+        self.inner.apply_neighbors(current_state, neighbors)
+    }
+
+    fn apply_random(
+        &self,
+        current_state: CellState,
+        neighbors: &Neighbors,
+        rng: &mut dyn RngCore,
+    ) -> CellState {
+        let next = self.inner.apply_random(current_state, neighbors, rng);
+        let probability = match (current_state, next) {
+            (CellState::Dead, next) if !next.is_dead() => self.birth_probability,
+            (CellState::Alive, CellState::Alive) => self.survival_probability,
+            _ => return next,
+        };
+        if probability >= 1.0 || rng.random::<f64>() < probability {
+            next
+        } else {
+            CellState::Dead
+        }
+    }
+
+    fn needs_configuration(&self) -> bool {
+        self.inner.needs_configuration()
+    }
+
+    fn check_neighborhood(&self, neighborhood: &dyn Neighborhood) -> Result<()> {
+        self.inner.check_neighborhood(neighborhood)
+    }
+
+    fn states(&self) -> u8 {
+        self.inner.states()
+    }
+
+    fn state_name(&self, state: u8) -> String {
+        self.inner.state_name(state)
+    }
+
+    fn palette(&self) -> Option<&[[u8; 3]]> {
+        self.inner.palette()
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+}
@@ -1,4 +1,44 @@
+use rand::RngCore;
+
 use crate::core::cell::CellState;
+use crate::core::neighborhood::Neighborhood;
+use crate::error::Result;
+
+/// Summary of a cell's neighborhood, passed to [`Rule::apply_neighbors`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Neighbors<'a> {
+    /// Number of alive neighbors
+    pub alive: usize,
+    /// Sum of the weights of the alive neighbors
+    ///
+    /// Equal to `alive` for unweighted neighborhoods.
+    pub weighted_sum: i64,
+    /// Configuration of the alive neighbors as a bitmask
+    ///
+    /// Bit `i` is set when the neighbor at `offsets()[i]` is alive. Only the
+    /// first 64 offsets are represented, and the mask is left at 0 unless the
+    /// rule reports [`Rule::needs_configuration`].
+    pub mask: u64,
+    /// State of every neighbor, in `offsets()` order
+    ///
+    /// Neighbors beyond the grid edge are reported as the boundary's
+    /// [`outside_state`](crate::core::boundary::Boundary::outside_state). Like
+    /// `mask`, this is left empty unless the rule reports
+    /// [`Rule::needs_configuration`].
+    pub states: &'a [CellState],
+}
+
+impl Neighbors<'_> {
+    /// Creates a summary for an unweighted neighborhood
+    pub fn new(alive: usize) -> Self {
+        Self {
+            alive,
+            weighted_sum: alive as i64,
+            mask: 0,
+            states: &[],
+        }
+    }
+}
 
 /// Trait defining the interface for cellular automata rules
 pub trait Rule: Send + Sync {
@@ -12,6 +52,74 @@ pub trait Rule: Send + Sync {
     /// The next state of the cell
     fn apply(&self, current_state: CellState, alive_neighbors: usize) -> CellState;
 
+    /// Applies the rule given a full summary of the neighborhood
+    ///
+    /// The default implementation forwards the alive neighbor count to
+    /// [`Rule::apply`]; rules that need the weighted sum or the neighbor
+    /// configuration override it.
+    fn apply_neighbors(&self, current_state: CellState, neighbors: &Neighbors) -> CellState {
+        self.apply(current_state, neighbors.alive)
+    }
+
+    /// Applies the rule with access to a random number generator
+    ///
+    /// This is what [`Automata`](crate::core::automata::Automata) calls,
+    /// passing its own seeded generator so that runs can be replayed. The
+    /// default implementation ignores the generator and calls
+    /// [`Rule::apply_neighbors`]; stochastic rules override it.
+    fn apply_random(
+        &self,
+        current_state: CellState,
+        neighbors: &Neighbors,
+        _rng: &mut dyn RngCore,
+    ) -> CellState {
+        self.apply_neighbors(current_state, neighbors)
+    }
+
+    /// Returns true if the rule reads the neighbor configuration from
+    /// [`Neighbors::mask`] or [`Neighbors::states`]
+    ///
+    /// Such rules are always evaluated one neighbor at a time, never with
+    /// prefix-sum counting.
+    fn needs_configuration(&self) -> bool {
+        false
+    }
+
+    /// Checks that the rule gives the right transitions on a neighborhood
+    ///
+    /// [`Automata`](crate::core::automata::Automata) calls this before it
+    /// switches lattice or neighborhood by name. The default implementation
+    /// accepts every neighborhood; rules tabulated for a range of neighbor
+    /// sums override it.
+    ///
+    /// # Errors
+    /// Returns an error if the neighborhood gives sums the rule cannot handle
+    fn check_neighborhood(&self, _neighborhood: &dyn Neighborhood) -> Result<()> {
+        Ok(())
+    }
+
+    /// Returns the number of states a cell can take
+    ///
+    /// State 0 is [`CellState::Dead`], state 1 is [`CellState::Alive`] and
+    /// higher states are [`CellState::State`].
+    fn states(&self) -> u8 {
+        2
+    }
+
+    /// Returns the display name of a state
+    fn state_name(&self, state: u8) -> String {
+        match state {
+            0 => "Dead".to_string(),
+            1 => "Alive".to_string(),
+            n => format!("State {}", n),
+        }
+    }
+
+    /// Returns the RGB colour of each state, if the rule has its own palette
+    fn palette(&self) -> Option<&[[u8; 3]]> {
+        None
+    }
+
     /// Returns the name of the rule
     fn name(&self) -> &str;
 
@@ -19,4 +127,49 @@ pub trait Rule: Send + Sync {
     fn description(&self) -> &str {
         "No description available"
     }
-}
\ No newline at end of file
+}
+
+/// Trait defining the interface for one-dimensional cellular automata rules
+///
+/// Unlike [`Rule`], a one-dimensional rule sees the full window of cells
+/// around the cell being updated, so it can implement both Wolfram's
+/// elementary rule numbers and totalistic codes.
+pub trait Rule1D: Send + Sync {
+    /// Applies the rule to a window of `2 * radius + 1` cell states
+    ///
+    /// # Arguments
+    /// * `window` - Cell states from left to right, centred on the updated cell
+    ///
+    /// # Returns
+    /// The next state of the centre cell
+    fn apply(&self, window: &[u8]) -> u8;
+
+    /// Returns the neighborhood radius of the rule
+    fn radius(&self) -> usize;
+
+    /// Returns the number of states (colours) a cell can take
+    fn states(&self) -> u8;
+
+    /// Returns the name of the rule
+    fn name(&self) -> &str;
+}
+
+/// Trait defining the interface for continuous-state rules
+///
+/// Cells hold a real value, and the rule sees the cell's value together
+/// with its potential: the kernel-weighted sum of its neighborhood computed
+/// by [`ContinuousAutomata`](crate::core::continuous_automata::ContinuousAutomata).
+pub trait ContinuousRule: Send + Sync {
+    /// Applies the rule to a single cell
+    ///
+    /// # Arguments
+    /// * `value` - Current value of the cell
+    /// * `potential` - Kernel-weighted sum of the neighborhood
+    ///
+    /// # Returns
+    /// The next value of the cell
+    fn apply(&self, value: f64, potential: f64) -> f64;
+
+    /// Returns the name of the rule
+    fn name(&self) -> &str;
+}
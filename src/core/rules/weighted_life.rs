@@ -0,0 +1,194 @@
+use crate::core::cell::CellState;
+use crate::core::custom_neighborhood::CustomNeighborhood;
+use crate::core::rules::trait_def::{Neighbors, Rule};
+use crate::error::{Error, Result};
+
+/// Offsets of the nine weight terms, in `NW,NN,NE,WW,ME,EE,SW,SS,SE` order
+const TERMS: [(&str, (isize, isize)); 9] = [
+    ("NW", (-1, -1)),
+    ("NN", (0, -1)),
+    ("NE", (1, -1)),
+    ("WW", (-1, 0)),
+    ("ME", (0, 0)),
+    ("EE", (1, 0)),
+    ("SW", (-1, 1)),
+    ("SS", (0, 1)),
+    ("SE", (1, 1)),
+];
+
+/// Weighted Life rule
+///
+/// Each cell of the 3x3 block, including the cell itself, carries an integer
+/// weight. A dead cell is born when the sum of the weights of its live
+/// neighbors is one of the birth sums, and a live cell survives when the sum
+/// is one of the survival sums.
+///
+/// Rules use the MCell Weighted Life notation found in `#GAME Weighted Life`
+/// pattern files, e.g. Conway's Life written as:
+/// ```text
+/// NW1,NN1,NE1,WW1,ME0,EE1,SW1,SS1,SE1,HI0,RS2,RS3,RB3
+/// ```
+#[derive(Debug, Clone)]
+pub struct WeightedLifeRule {
+    weights: [i32; 9],
+    survival: Vec<i64>,
+    birth: Vec<i64>,
+    spec: String,
+}
+
+impl WeightedLifeRule {
+    /// Creates a Weighted Life rule
+    ///
+    /// # Arguments
+    /// * `weights` - Weights in `NW,NN,NE,WW,ME,EE,SW,SS,SE` order
+    /// * `survival` - Weighted sums for which a live cell stays alive
+    /// * `birth` - Weighted sums for which a dead cell becomes alive
+    pub fn new(weights: [i32; 9], survival: Vec<i64>, birth: Vec<i64>) -> Self {
+        let mut terms: Vec<String> = TERMS
+            .iter()
+            .zip(weights)
+            .map(|((name, _), w)| format!("{}{}", name, w))
+            .collect();
+        terms.push("HI0".to_string());
+        terms.extend(survival.iter().map(|s| format!("RS{}", s)));
+        terms.extend(birth.iter().map(|b| format!("RB{}", b)));
+
+        Self {
+            weights,
+            survival,
+            birth,
+            spec: terms.join(","),
+        }
+    }
+
+    /// Parses a rule in MCell Weighted Life notation
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut weights = [0i32; 9];
+        let mut survival = Vec::new();
+        let mut birth = Vec::new();
+
+        for term in spec.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            if term.len() < 3 || !term.is_char_boundary(2) {
+                return Err(Error::InvalidRuleFormat(format!(
+                    "unexpected Weighted Life term '{}'",
+                    term
+                )));
+            }
+            let (key, value) = term.split_at(2);
+            let key = key.to_ascii_uppercase();
+            let value: i64 = value.parse().map_err(|_| {
+                Error::InvalidRuleFormat(format!("expected a number in '{}'", term))
+            })?;
+
+            match key.as_str() {
+                "RS" => survival.push(value),
+                "RB" => birth.push(value),
+                "HI" if value == 0 => {}
+                "HI" => {
+                    return Err(Error::InvalidRuleFormat(format!(
+                        "Weighted Life history states (HI{}) are not supported",
+                        value
+                    )))
+                }
+                _ => {
+                    let index = TERMS.iter().position(|(name, _)| *name == key).ok_or_else(|| {
+                        Error::InvalidRuleFormat(format!("unknown Weighted Life term '{}'", term))
+                    })?;
+                    weights[index] = i32::try_from(value).map_err(|_| {
+                        Error::InvalidRuleFormat(format!("weight out of range in '{}'", term))
+                    })?;
+                }
+            }
+        }
+
+        if weights.iter().all(|&w| w == 0) {
+            return Err(Error::InvalidRuleFormat(
+                "Weighted Life rule has no non-zero weights".to_string(),
+            ));
+        }
+
+        Ok(Self::new(weights, survival, birth))
+    }
+
+    /// Imports a rule from the contents of an MCell Weighted Life file
+    ///
+    /// The rule is taken from the `#RULE` lines; a `#GAME` line, if present,
+    /// must name Weighted Life.
+    pub fn import(text: &str) -> Result<Self> {
+        let mut rule = String::new();
+        for line in text.lines().map(str::trim) {
+            if let Some(game) = line.strip_prefix("#GAME") {
+                let game = game.trim();
+                if !game.eq_ignore_ascii_case("Weighted Life") {
+                    return Err(Error::PatternParseError(format!(
+                        "expected a Weighted Life file, found game '{}'",
+                        game
+                    )));
+                }
+            } else if let Some(terms) = line.strip_prefix("#RULE") {
+                if !rule.is_empty() {
+                    rule.push(',');
+                }
+                rule.push_str(terms.trim());
+            }
+        }
+
+        if rule.is_empty() {
+            return Err(Error::PatternParseError("file has no #RULE line".to_string()));
+        }
+        Self::parse(&rule)
+    }
+
+    /// Returns the weights in `NW,NN,NE,WW,ME,EE,SW,SS,SE` order
+    pub fn weights(&self) -> [i32; 9] {
+        self.weights
+    }
+
+    /// Returns the weighted sums for which a live cell survives
+    pub fn survival(&self) -> &[i64] {
+        &self.survival
+    }
+
+    /// Returns the weighted sums for which a dead cell is born
+    pub fn birth(&self) -> &[i64] {
+        &self.birth
+    }
+
+    /// Builds the weighted neighborhood this rule is defined over
+    pub fn neighborhood(&self) -> CustomNeighborhood {
+        let weighted = TERMS
+            .iter()
+            .zip(self.weights)
+            .filter(|(_, w)| *w != 0)
+            .map(|((_, offset), w)| (*offset, w))
+            .collect();
+        CustomNeighborhood::from_weighted_offsets(weighted).with_name("Weighted Life")
+    }
+}
+
+impl Rule for WeightedLifeRule {
+    fn apply(&self, current_state: CellState, alive_neighbors: usize) -> CellState {
+        self.apply_neighbors(current_state, &Neighbors::new(alive_neighbors))
+    }
+
+    fn apply_neighbors(&self, current_state: CellState, neighbors: &Neighbors) -> CellState {
+        let sums = if current_state.is_alive() {
+            &self.survival
+        } else {
+            &self.birth
+        };
+        if sums.contains(&neighbors.weighted_sum) {
+            CellState::Alive
+        } else {
+            CellState::Dead
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Weighted Life"
+    }
+
+    fn description(&self) -> &str {
+        &self.spec
+    }
+}
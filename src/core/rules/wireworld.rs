@@ -0,0 +1,139 @@
+use crate::core::cell::{CellState, Position};
+use crate::core::dense_grid::DenseGrid;
+use crate::core::grid::Grid;
+use crate::core::rules::trait_def::Rule;
+use crate::error::{Error, Result};
+
+/// Wireworld rule
+///
+/// A four-state rule over the Moore neighborhood for simulating digital
+/// circuits. Electrons travel along conductors as a head followed by a tail:
+/// - An electron head becomes an electron tail
+/// - An electron tail becomes a conductor
+/// - A conductor becomes an electron head if one or two of its neighbors
+///   are heads, and stays a conductor otherwise
+/// - Empty cells stay empty
+///
+/// Heads are state 1 ([`CellState::Alive`]), so the alive neighbor count is
+/// the number of neighboring heads.
+#[derive(Debug, Clone, Copy)]
+pub struct WireworldRule;
+
+impl WireworldRule {
+    /// Empty space
+    pub const EMPTY: CellState = CellState::Dead;
+    /// Electron head
+    pub const HEAD: CellState = CellState::Alive;
+    /// Electron tail
+    pub const TAIL: CellState = CellState::State(2);
+    /// Conductor (wire)
+    pub const CONDUCTOR: CellState = CellState::State(3);
+
+    // The example circuits have an empty margin so that no boundary
+    // condition can affect them.
+
+    /// Clock: an electron circling a six-cell loop sends a pulse along the
+    /// wire to the right every 6 generations
+    pub const CLOCK: &'static str = "\
+............
+..tH........
+.#..#######.
+..##........
+............";
+
+    /// Diode: an electron entering from the left passes through to the right
+    pub const DIODE: &'static str = "\
+.................
+.......##........
+.tH#####.#######.
+.......##........
+.................";
+
+    /// The same diode fed from the right: the electron is blocked
+    pub const DIODE_REVERSED: &'static str = "\
+.................
+.......##........
+.#######.#####Ht.
+.......##........
+.................";
+
+    /// Creates a new Wireworld rule
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Builds a grid from an ASCII circuit
+    ///
+    /// `.` and spaces are empty, `#` is a conductor, `H` an electron head and
+    /// `t` an electron tail. The grid is as wide as the longest line; shorter
+    /// lines are padded with empty cells.
+    pub fn circuit(text: &str) -> Result<DenseGrid> {
+        let lines: Vec<&str> = text.lines().collect();
+        let width = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+        let mut grid = DenseGrid::new(width, lines.len())?;
+
+        for (y, line) in lines.iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let state = match c {
+                    '.' | ' ' => Self::EMPTY,
+                    '#' => Self::CONDUCTOR,
+                    'H' => Self::HEAD,
+                    't' => Self::TAIL,
+                    _ => {
+                        return Err(Error::PatternParseError(format!(
+                            "unexpected character '{}' in Wireworld circuit",
+                            c
+                        )))
+                    }
+                };
+                grid.set(Position::new(x, y), state)?;
+            }
+        }
+        Ok(grid)
+    }
+}
+
+impl Default for WireworldRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rule for WireworldRule {
+    fn apply(&self, current_state: CellState, alive_neighbors: usize) -> CellState {
+        match current_state {
+            Self::HEAD => Self::TAIL,
+            Self::TAIL => Self::CONDUCTOR,
+            Self::CONDUCTOR if (1..=2).contains(&alive_neighbors) => Self::HEAD,
+            Self::CONDUCTOR => Self::CONDUCTOR,
+            _ => Self::EMPTY,
+        }
+    }
+
+    fn states(&self) -> u8 {
+        4
+    }
+
+    fn state_name(&self, state: u8) -> String {
+        match state {
+            0 => "Empty",
+            1 => "Electron head",
+            2 => "Electron tail",
+            3 => "Conductor",
+            _ => "Unused",
+        }
+        .to_string()
+    }
+
+    fn palette(&self) -> Option<&[[u8; 3]]> {
+        Some(&[[0, 0, 0], [60, 120, 255], [255, 80, 40], [255, 200, 0]])
+    }
+
+    fn name(&self) -> &str {
+        "Wireworld"
+    }
+
+    fn description(&self) -> &str {
+        "Heads become tails, tails become wire, wire with 1 or 2 head neighbors becomes a head"
+    }
+}
@@ -0,0 +1,180 @@
+use crate::core::rules::trait_def::Rule1D;
+use crate::error::{Error, Result};
+
+/// Largest radius supported by [`WolframRule`]
+///
+/// A radius-3 rule has 2^7 = 128 neighborhood configurations, which is the
+/// most a `u128` rule number can encode.
+pub const MAX_WOLFRAM_RADIUS: usize = 3;
+
+/// Largest radius supported by [`TotalisticCodeRule`]
+///
+/// A two-colour radius-31 rule has 64 window sums, one for each binary
+/// digit of a `u64` code; wider windows only add sums that map to 0.
+pub const MAX_TOTALISTIC_RADIUS: usize = 31;
+
+/// Wolfram rule for two-state one-dimensional automata
+///
+/// The rule number is read as a lookup table: bit `i` of the number gives the
+/// next state for the neighborhood whose cells, read left to right as a
+/// binary number, equal `i`. With radius 1 this yields the classic 256
+/// elementary automata (Rule 30, Rule 90, Rule 110, ...).
+#[derive(Debug, Clone)]
+pub struct WolframRule {
+    number: u128,
+    radius: usize,
+    name: String,
+}
+
+impl WolframRule {
+    /// Creates an elementary (radius 1) rule from a rule number 0-255
+    pub fn new(number: u8) -> Self {
+        Self {
+            number: number as u128,
+            radius: 1,
+            name: format!("Rule {}", number),
+        }
+    }
+
+    /// Creates a two-state rule with a larger radius
+    ///
+    /// # Arguments
+    /// * `number` - Rule number, must be below 2^(2^(2 * radius + 1))
+    /// * `radius` - Neighborhood radius, at most [`MAX_WOLFRAM_RADIUS`]
+    pub fn with_radius(number: u128, radius: usize) -> Result<Self> {
+        if radius > MAX_WOLFRAM_RADIUS {
+            return Err(Error::InvalidRuleFormat(format!(
+                "radius {} exceeds the maximum of {}",
+                radius, MAX_WOLFRAM_RADIUS
+            )));
+        }
+
+        let configurations = 1u32 << (2 * radius + 1);
+        if configurations < 128 && number >> configurations != 0 {
+            return Err(Error::InvalidRuleFormat(format!(
+                "rule number {} is too large for radius {}",
+                number, radius
+            )));
+        }
+
+        Ok(Self {
+            number,
+            radius,
+            name: format!("Rule {} (r={})", number, radius),
+        })
+    }
+
+    /// Returns the rule number
+    pub fn number(&self) -> u128 {
+        self.number
+    }
+}
+
+impl Rule1D for WolframRule {
+    fn apply(&self, window: &[u8]) -> u8 {
+        let index = window
+            .iter()
+            .fold(0u32, |acc, &state| (acc << 1) | (state != 0) as u32);
+        ((self.number >> index) & 1) as u8
+    }
+
+    fn radius(&self) -> usize {
+        self.radius
+    }
+
+    fn states(&self) -> u8 {
+        2
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Totalistic k-colour rule identified by a Wolfram code
+///
+/// The next state depends only on the sum of the states in the window.
+/// Digit `s` of the code written in base `k` gives the next state for
+/// a window sum of `s`, following the numbering used in
+/// *A New Kind of Science*.
+#[derive(Debug, Clone)]
+pub struct TotalisticCodeRule {
+    code: u64,
+    colors: u8,
+    radius: usize,
+    /// Next state indexed by window sum
+    table: Vec<u8>,
+    name: String,
+}
+
+impl TotalisticCodeRule {
+    /// Creates a totalistic rule from a code number
+    ///
+    /// # Arguments
+    /// * `code` - Wolfram code, must be below k^((2r+1)(k-1)+1)
+    /// * `colors` - Number of cell states `k` (2 or more)
+    /// * `radius` - Neighborhood radius `r`, at most [`MAX_TOTALISTIC_RADIUS`]
+    pub fn new(code: u64, colors: u8, radius: usize) -> Result<Self> {
+        if colors < 2 {
+            return Err(Error::InvalidRuleFormat(format!(
+                "totalistic rules need at least 2 colours, got {}",
+                colors
+            )));
+        }
+        if radius > MAX_TOTALISTIC_RADIUS {
+            return Err(Error::InvalidRuleFormat(format!(
+                "radius {} exceeds the maximum of {}",
+                radius, MAX_TOTALISTIC_RADIUS
+            )));
+        }
+
+        // At most 63 * 254 + 1 sums, given the bounds above
+        let sums = (2 * radius + 1) * (colors as usize - 1) + 1;
+        let limit = (colors as u128).checked_pow(sums as u32);
+        if matches!(limit, Some(limit) if (code as u128) >= limit) {
+            return Err(Error::InvalidRuleFormat(format!(
+                "code {} is too large for k={}, r={}",
+                code, colors, radius
+            )));
+        }
+
+        let mut table = Vec::with_capacity(sums);
+        let mut remaining = code;
+        for _ in 0..sums {
+            table.push((remaining % colors as u64) as u8);
+            remaining /= colors as u64;
+        }
+
+        Ok(Self {
+            code,
+            colors,
+            radius,
+            table,
+            name: format!("Code {} (k={}, r={})", code, colors, radius),
+        })
+    }
+
+    /// Returns the code number
+    pub fn code(&self) -> u64 {
+        self.code
+    }
+}
+
+impl Rule1D for TotalisticCodeRule {
+    fn apply(&self, window: &[u8]) -> u8 {
+        let sum: usize = window.iter().map(|&state| state as usize).sum();
+        self.table.get(sum).copied().unwrap_or(0)
+    }
+
+    fn radius(&self) -> usize {
+        self.radius
+    }
+
+    fn states(&self) -> u8 {
+        self.colors
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
@@ -0,0 +1,59 @@
+use crate::core::boundary::{crossings, Boundary, EdgePair};
+use crate::core::cell::Position;
+
+/// Shifted torus boundary - a torus whose wrap is offset along one pair of
+/// edges
+///
+/// Leaving through the bottom edge at column x re-enters through the top
+/// edge at column x + shift (for [`EdgePair::TopBottom`]); leaving through
+/// the right edge at row y re-enters through the left edge at row y + shift
+/// (for [`EdgePair::LeftRight`]). Moving the other way undoes the shift. A
+/// shift of 0 is an ordinary torus.
+#[derive(Debug, Clone, Copy)]
+pub struct ShiftedTorusBoundary {
+    shifted: EdgePair,
+    shift: isize,
+}
+
+impl ShiftedTorusBoundary {
+    /// Creates a shifted torus boundary
+    ///
+    /// # Arguments
+    /// * `shifted` - Pair of edges whose wrap is offset
+    /// * `shift` - Number of cells the wrap is offset by
+    pub fn new(shifted: EdgePair, shift: isize) -> Self {
+        Self { shifted, shift }
+    }
+
+    /// Returns the pair of edges whose wrap is offset
+    pub fn shifted(&self) -> EdgePair {
+        self.shifted
+    }
+
+    /// Returns the number of cells the wrap is offset by
+    pub fn shift(&self) -> isize {
+        self.shift
+    }
+}
+
+impl Boundary for ShiftedTorusBoundary {
+    fn wrap(&self, x: isize, y: isize, width: usize, height: usize) -> Option<Position> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        let position = match self.shifted {
+            EdgePair::TopBottom => {
+                let (y_crossings, wrapped_y) = crossings(y, height);
+                let (_, wrapped_x) = crossings(x + y_crossings * self.shift, width);
+                Position::new(wrapped_x, wrapped_y)
+            }
+            EdgePair::LeftRight => {
+                let (x_crossings, wrapped_x) = crossings(x, width);
+                let (_, wrapped_y) = crossings(y + x_crossings * self.shift, height);
+                Position::new(wrapped_x, wrapped_y)
+            }
+        };
+        Some(position)
+    }
+}
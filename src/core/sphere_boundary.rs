@@ -0,0 +1,48 @@
+use crate::core::boundary::Boundary;
+use crate::core::cell::Position;
+
+/// Sphere boundary - adjacent edges of a square grid joined together
+///
+/// The top edge is joined to the left edge and the bottom edge to the right
+/// edge, as in Golly: moving up from column x enters row x from the left,
+/// and moving down from column x enters row x from the right. Positions
+/// beyond a corner, or beyond the edges of a grid that is not square, have
+/// no cell.
+#[derive(Debug, Clone, Copy)]
+pub struct SphereBoundary;
+
+impl SphereBoundary {
+    /// Creates a new sphere boundary
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for SphereBoundary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Boundary for SphereBoundary {
+    fn wrap(&self, x: isize, y: isize, width: usize, height: usize) -> Option<Position> {
+        let size = width as isize;
+        let inside = |c: isize| (0..size).contains(&c);
+        if inside(x) && (0..height as isize).contains(&y) {
+            return Some(Position::new(x as usize, y as usize));
+        }
+        if width != height {
+            return None;
+        }
+
+        // Exactly one coordinate may be off the grid, by less than its size
+        let (x, y) = match (inside(x), inside(y)) {
+            (true, false) if y < 0 && y >= -size => (-1 - y, x),
+            (true, false) if y >= size && y < 2 * size => (2 * size - 1 - y, x),
+            (false, true) if x < 0 && x >= -size => (y, -1 - x),
+            (false, true) if x >= size && x < 2 * size => (y, 2 * size - 1 - x),
+            _ => return None,
+        };
+        (inside(x) && inside(y)).then(|| Position::new(x as usize, y as usize))
+    }
+}
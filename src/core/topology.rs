@@ -0,0 +1,217 @@
+use std::fmt;
+
+use crate::core::boundary::{Boundary, EdgePair};
+use crate::core::cross_surface_boundary::CrossSurfaceBoundary;
+use crate::core::dead_boundary::DeadBoundary;
+use crate::core::klein_bottle_boundary::KleinBottleBoundary;
+use crate::core::shifted_torus_boundary::ShiftedTorusBoundary;
+use crate::core::sphere_boundary::SphereBoundary;
+use crate::core::toroidal_boundary::ToroidalBoundary;
+use crate::error::{Error, Result};
+
+/// A bounded grid in Golly's notation, such as `T30,20`, `K30*,20` or `S30`
+///
+/// Golly appends the topology to a rule after a colon, e.g. `B3/S23:T30,20`,
+/// so an RLE header carries both the grid size and how its edges are joined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Topology {
+    /// `P w,h` - a plane surrounded by dead cells
+    Plane { width: usize, height: usize },
+    /// `T w,h` - a torus, optionally shifted as in `T30+5,20` or `T30,20-2`
+    ///
+    /// A shift after the width offsets the wrap across the top and bottom
+    /// edges, a shift after the height the wrap across the left and right
+    /// edges, see [`ShiftedTorusBoundary`].
+    Torus {
+        width: usize,
+        height: usize,
+        shifted: EdgePair,
+        shift: isize,
+    },
+    /// `K w*,h` or `K w,h*` - a Klein bottle
+    ///
+    /// The star marks the twisted pair of edges: after the width for the top
+    /// and bottom edges, after the height for the left and right edges.
+    KleinBottle {
+        width: usize,
+        height: usize,
+        twisted: EdgePair,
+    },
+    /// `C w,h` - a cross-surface (real projective plane)
+    CrossSurface { width: usize, height: usize },
+    /// `S n` - a sphere made from an n by n grid
+    Sphere { size: usize },
+}
+
+/// One side of a `w,h` size: its length, whether it is starred and its shift
+struct Side {
+    length: usize,
+    starred: bool,
+    shift: isize,
+}
+
+impl Topology {
+    /// Parses a topology such as `T30,20`, `T30+5,20`, `K30*,20`, `C30,20`
+    /// or `S30`
+    ///
+    /// # Arguments
+    /// * `spec` - Topology in Golly's notation, without the leading colon
+    ///
+    /// # Errors
+    /// Returns an error for unknown letters, malformed sizes, zero
+    /// (unbounded) sizes, and stars or shifts the topology does not allow
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        let invalid = |reason: &str| {
+            Error::InvalidRuleFormat(format!("topology '{}': {}", spec, reason))
+        };
+        let mut chars = spec.chars();
+        let letter = chars.next().ok_or_else(|| invalid("empty"))?.to_ascii_uppercase();
+        let size = chars.as_str().trim();
+
+        if letter == 'S' {
+            let size = size.parse::<usize>().map_err(|_| invalid("expected a size"))?;
+            if size == 0 {
+                return Err(invalid("unbounded grids are not supported"));
+            }
+            return Ok(Topology::Sphere { size });
+        }
+
+        let (width, height) = size.split_once(',').ok_or_else(|| invalid("expected 'w,h'"))?;
+        let width = Self::parse_side(width).ok_or_else(|| invalid("malformed width"))?;
+        let height = Self::parse_side(height).ok_or_else(|| invalid("malformed height"))?;
+        if width.length == 0 || height.length == 0 {
+            return Err(invalid("unbounded grids are not supported"));
+        }
+        let shifted = width.shift != 0 || height.shift != 0;
+        let invalid_star = || invalid("stars and shifts need a torus or Klein bottle");
+        let (w, h) = (width.length, height.length);
+
+        match letter {
+            'P' | 'C' if width.starred || height.starred || shifted => Err(invalid_star()),
+            'P' => Ok(Topology::Plane { width: w, height: h }),
+            'C' => Ok(Topology::CrossSurface { width: w, height: h }),
+            'T' if width.starred || height.starred => Err(invalid("a torus has no twisted edges")),
+            'T' if width.shift != 0 && height.shift != 0 => {
+                Err(invalid("only one pair of edges can be shifted"))
+            }
+            'T' if height.shift != 0 => Ok(Topology::Torus {
+                width: w,
+                height: h,
+                shifted: EdgePair::LeftRight,
+                shift: height.shift,
+            }),
+            'T' => Ok(Topology::Torus {
+                width: w,
+                height: h,
+                shifted: EdgePair::TopBottom,
+                shift: width.shift,
+            }),
+            'K' if shifted => Err(invalid("shifted Klein bottles are not supported")),
+            'K' if width.starred == height.starred => Err(invalid("exactly one side needs a '*'")),
+            'K' => Ok(Topology::KleinBottle {
+                width: w,
+                height: h,
+                twisted: if width.starred { EdgePair::TopBottom } else { EdgePair::LeftRight },
+            }),
+            _ => Err(invalid("expected P, T, K, C or S")),
+        }
+    }
+
+    /// Parses one side of a `w,h` size, e.g. `30`, `30*` or `30+5`
+    fn parse_side(text: &str) -> Option<Side> {
+        let text = text.trim();
+        let digits = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+        let length = text[..digits].parse().ok()?;
+        let mut rest = &text[digits..];
+        let starred = rest.starts_with('*');
+        if starred {
+            rest = &rest[1..];
+        }
+        let shift = if rest.is_empty() {
+            0
+        } else if rest.starts_with('+') || rest.starts_with('-') {
+            rest.parse().ok()?
+        } else {
+            return None;
+        };
+        Some(Side { length, starred, shift })
+    }
+
+    /// Splits a rule such as `B3/S23:T30,20` into the rule and its topology
+    ///
+    /// Rule tables, which start with `@`, are returned unchanged since they
+    /// may contain colons of their own.
+    ///
+    /// # Errors
+    /// Returns an error if the text after the colon is not a valid topology
+    pub fn split_rule(spec: &str) -> Result<(&str, Option<Topology>)> {
+        let trimmed = spec.trim();
+        if trimmed.starts_with('@') {
+            return Ok((spec, None));
+        }
+        match trimmed.rsplit_once(':') {
+            Some((rule, topology)) => Ok((rule.trim(), Some(Self::parse(topology)?))),
+            None => Ok((spec, None)),
+        }
+    }
+
+    /// Returns the width of the grid
+    pub fn width(&self) -> usize {
+        match *self {
+            Topology::Plane { width, .. }
+            | Topology::Torus { width, .. }
+            | Topology::KleinBottle { width, .. }
+            | Topology::CrossSurface { width, .. } => width,
+            Topology::Sphere { size } => size,
+        }
+    }
+
+    /// Returns the height of the grid
+    pub fn height(&self) -> usize {
+        match *self {
+            Topology::Plane { height, .. }
+            | Topology::Torus { height, .. }
+            | Topology::KleinBottle { height, .. }
+            | Topology::CrossSurface { height, .. } => height,
+            Topology::Sphere { size } => size,
+        }
+    }
+
+    /// Returns the boundary that joins the grid edges this way
+    pub fn boundary(&self) -> Box<dyn Boundary> {
+        match *self {
+            Topology::Plane { .. } => Box::new(DeadBoundary::new()),
+            Topology::Torus { shift: 0, .. } => Box::new(ToroidalBoundary::new()),
+            Topology::Torus { shifted, shift, .. } => {
+                Box::new(ShiftedTorusBoundary::new(shifted, shift))
+            }
+            Topology::KleinBottle { twisted, .. } => Box::new(KleinBottleBoundary::new(twisted)),
+            Topology::CrossSurface { .. } => Box::new(CrossSurfaceBoundary::new()),
+            Topology::Sphere { .. } => Box::new(SphereBoundary::new()),
+        }
+    }
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Topology::Plane { width, height } => write!(f, "P{},{}", width, height),
+            Topology::Torus { width, height, shift: 0, .. } => write!(f, "T{},{}", width, height),
+            Topology::Torus { width, height, shifted: EdgePair::TopBottom, shift } => {
+                write!(f, "T{}{:+},{}", width, shift, height)
+            }
+            Topology::Torus { width, height, shifted: EdgePair::LeftRight, shift } => {
+                write!(f, "T{},{}{:+}", width, height, shift)
+            }
+            Topology::KleinBottle { width, height, twisted: EdgePair::TopBottom } => {
+                write!(f, "K{}*,{}", width, height)
+            }
+            Topology::KleinBottle { width, height, twisted: EdgePair::LeftRight } => {
+                write!(f, "K{},{}*", width, height)
+            }
+            Topology::CrossSurface { width, height } => write!(f, "C{},{}", width, height),
+            Topology::Sphere { size } => write!(f, "S{}", size),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::core::boundary::Boundary;
-use crate::core::cell::Position;
+use crate::core::boundary_3d::Boundary3D;
+use crate::core::cell::{Position, Position3D};
 
 /// Toroidal boundary - edges wrap around to opposite sides
 ///
@@ -8,6 +9,8 @@ use crate::core::cell::Position;
 /// - Moving off the left edge wraps to the right edge
 /// - Moving off the top edge wraps to the bottom edge
 /// - Moving off the bottom edge wraps to the top edge
+///
+/// On three-dimensional grids the front and back faces wrap as well.
 #[derive(Debug, Clone, Copy)]
 pub struct ToroidalBoundary;
 
@@ -40,4 +43,26 @@ impl Boundary for ToroidalBoundary {
 
         Some(Position::new(wrapped_x as usize, wrapped_y as usize))
     }
-}
\ No newline at end of file
+}
+
+impl Boundary3D for ToroidalBoundary {
+    fn wrap(
+        &self,
+        x: isize,
+        y: isize,
+        z: isize,
+        width: usize,
+        height: usize,
+        depth: usize,
+    ) -> Option<Position3D> {
+        if width == 0 || height == 0 || depth == 0 {
+            return None;
+        }
+
+        Some(Position3D::new(
+            x.rem_euclid(width as isize) as usize,
+            y.rem_euclid(height as isize) as usize,
+            z.rem_euclid(depth as isize) as usize,
+        ))
+    }
+}
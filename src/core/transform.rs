@@ -0,0 +1,93 @@
+use crate::core::cell::Position;
+
+/// One of the eight symmetries of a rectangle: the rotations by multiples
+/// of 90 degrees and the four reflections
+///
+/// Transforms act on whole grids through
+/// [`DenseGrid::transform`](crate::core::dense_grid::DenseGrid::transform).
+/// Rotations and reflections across a diagonal swap width and height.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Transform {
+    /// Leaves the grid unchanged
+    #[default]
+    Identity,
+    /// Quarter turn clockwise
+    Rotate90,
+    /// Half turn
+    Rotate180,
+    /// Quarter turn anticlockwise
+    Rotate270,
+    /// Mirror left to right
+    FlipHorizontal,
+    /// Mirror top to bottom
+    FlipVertical,
+    /// Mirror across the main diagonal (transpose)
+    FlipDiagonal,
+    /// Mirror across the anti-diagonal
+    FlipAntiDiagonal,
+}
+
+impl Transform {
+    /// Every transform, identity first
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+        Transform::FlipDiagonal,
+        Transform::FlipAntiDiagonal,
+    ];
+
+    /// Returns the display name of the transform
+    pub fn name(&self) -> &str {
+        match self {
+            Transform::Identity => "Identity",
+            Transform::Rotate90 => "Rotate 90°",
+            Transform::Rotate180 => "Rotate 180°",
+            Transform::Rotate270 => "Rotate 270°",
+            Transform::FlipHorizontal => "Flip horizontally",
+            Transform::FlipVertical => "Flip vertically",
+            Transform::FlipDiagonal => "Flip diagonally",
+            Transform::FlipAntiDiagonal => "Flip anti-diagonally",
+        }
+    }
+
+    /// Returns true if the transform swaps width and height
+    pub fn swaps_axes(&self) -> bool {
+        matches!(
+            self,
+            Transform::Rotate90
+                | Transform::Rotate270
+                | Transform::FlipDiagonal
+                | Transform::FlipAntiDiagonal
+        )
+    }
+
+    /// Returns the transform that undoes this one
+    pub fn inverse(&self) -> Transform {
+        match self {
+            Transform::Rotate90 => Transform::Rotate270,
+            Transform::Rotate270 => Transform::Rotate90,
+            other => *other,
+        }
+    }
+
+    /// Maps a cell of a `width` by `height` grid to its place in the
+    /// transformed grid
+    pub fn apply(&self, pos: Position, width: usize, height: usize) -> Position {
+        let (x, y) = (pos.x, pos.y);
+        let (right, bottom) = (width - 1 - x, height - 1 - y);
+        match self {
+            Transform::Identity => Position::new(x, y),
+            Transform::Rotate90 => Position::new(bottom, x),
+            Transform::Rotate180 => Position::new(right, bottom),
+            Transform::Rotate270 => Position::new(y, right),
+            Transform::FlipHorizontal => Position::new(right, y),
+            Transform::FlipVertical => Position::new(x, bottom),
+            Transform::FlipDiagonal => Position::new(y, x),
+            Transform::FlipAntiDiagonal => Position::new(bottom, right),
+        }
+    }
+}
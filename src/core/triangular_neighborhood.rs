@@ -0,0 +1,86 @@
+use crate::core::cell::Position;
+use crate::core::neighborhood::Neighborhood;
+
+/// Neighborhood on a triangular lattice
+///
+/// Triangles are stored row by row on the square grid, alternating in
+/// orientation: the cell at (x, y) points up when `x + y` is even and down
+/// otherwise. Neighbors to the left and right are half a triangle away, so
+/// a row of `w` cells is `(w + 1) / 2` triangles wide.
+///
+/// The edge neighborhood holds the three triangles sharing an edge; the
+/// vertex neighborhood holds all twelve triangles sharing a corner. For an
+/// up triangle (`^`) they are:
+/// ```text
+///   . X X X .        . . . . .
+///   X X ^ X X        . X ^ X .
+///   X X X X X        . . X . .
+///   vertex (12)      edge (3)
+/// ```
+/// Down triangles use the same pattern flipped vertically. Toroidal
+/// wrapping keeps orientations consistent only when the grid has an even
+/// width and height.
+#[derive(Debug, Clone)]
+pub struct TriangularNeighborhood {
+    up: Vec<(isize, isize)>,
+    down: Vec<(isize, isize)>,
+    name: String,
+}
+
+impl TriangularNeighborhood {
+    /// Creates the edge neighborhood (3 neighbors)
+    pub fn new() -> Self {
+        Self::from_up(vec![(-1, 0), (1, 0), (0, 1)], "Triangular (3 neighbors)")
+    }
+
+    /// Creates the vertex neighborhood (12 neighbors)
+    pub fn with_vertices() -> Self {
+        let mut up = vec![(-1, -1), (0, -1), (1, -1), (-2, 0), (-1, 0), (1, 0), (2, 0)];
+        up.extend((-2..=2).map(|dx| (dx, 1)));
+        Self::from_up(up, "Triangular (12 neighbors)")
+    }
+
+    /// Builds the neighborhood from the offsets of an up triangle
+    fn from_up(up: Vec<(isize, isize)>, name: &str) -> Self {
+        let down = up.iter().map(|&(dx, dy)| (dx, -dy)).collect();
+        Self {
+            up,
+            down,
+            name: name.to_string(),
+        }
+    }
+
+    /// Returns true if the triangle at a position points up
+    pub fn points_up(pos: Position) -> bool {
+        (pos.x + pos.y).is_multiple_of(2)
+    }
+}
+
+impl Default for TriangularNeighborhood {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Neighborhood for TriangularNeighborhood {
+    /// Returns the offsets of an up triangle
+    fn offsets(&self) -> &[(isize, isize)] {
+        &self.up
+    }
+
+    fn offsets_at(&self, pos: Position) -> &[(isize, isize)] {
+        if Self::points_up(pos) {
+            &self.up
+        } else {
+            &self.down
+        }
+    }
+
+    fn is_uniform(&self) -> bool {
+        false
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
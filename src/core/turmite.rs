@@ -0,0 +1,406 @@
+use crate::core::boundary::Boundary;
+use crate::core::cell::{CellState, Position};
+use crate::core::dense_grid::DenseGrid;
+use crate::core::grid::Grid;
+use crate::error::{Error, Result};
+
+/// Direction an ant is facing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Heading {
+    /// Towards row 0
+    #[default]
+    North,
+    /// Towards increasing x
+    East,
+    /// Towards increasing y
+    South,
+    /// Towards column 0
+    West,
+}
+
+impl Heading {
+    /// Returns the (dx, dy) of one step in this direction
+    pub fn offset(&self) -> (isize, isize) {
+        match self {
+            Heading::North => (0, -1),
+            Heading::East => (1, 0),
+            Heading::South => (0, 1),
+            Heading::West => (-1, 0),
+        }
+    }
+
+    /// Returns the heading after a turn
+    pub fn turn(&self, turn: Turn) -> Self {
+        let quarter_turns = match turn {
+            Turn::None => 0,
+            Turn::Right => 1,
+            Turn::UTurn => 2,
+            Turn::Left => 3,
+        };
+        let index = (*self as usize + quarter_turns) % 4;
+        [Heading::North, Heading::East, Heading::South, Heading::West][index]
+    }
+}
+
+/// Turn made by an ant, relative to its heading
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Turn {
+    /// Keep going straight
+    None,
+    /// Turn 90 degrees clockwise
+    Right,
+    /// Turn around
+    UTurn,
+    /// Turn 90 degrees anticlockwise
+    Left,
+}
+
+impl Turn {
+    /// Returns the turn for a code of the turmite notation
+    /// (1 = none, 2 = right, 4 = u-turn, 8 = left)
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            1 => Some(Turn::None),
+            2 => Some(Turn::Right),
+            4 => Some(Turn::UTurn),
+            8 => Some(Turn::Left),
+            _ => None,
+        }
+    }
+
+    /// Returns the code of the turn in the turmite notation
+    pub fn code(&self) -> u32 {
+        match self {
+            Turn::None => 1,
+            Turn::Right => 2,
+            Turn::UTurn => 4,
+            Turn::Left => 8,
+        }
+    }
+}
+
+/// One entry of a turmite's state table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    /// Colour written to the cell under the ant
+    pub write: u8,
+    /// Turn made before moving
+    pub turn: Turn,
+    /// Internal state of the ant afterwards
+    pub next_state: u8,
+}
+
+/// State table of a turmite
+///
+/// For each internal state and each colour of the cell under the ant, the
+/// table gives the colour to write, the turn to make and the next internal
+/// state. Colour 0 is a dead cell, colour 1 a live cell and higher colours
+/// are [`CellState::State`] values.
+///
+/// Tables use the common turmite notation of Golly and Ed Pegg Jr., listing
+/// `{write, turn, next state}` for each colour within each state, with turns
+/// coded 1 (none), 2 (right), 4 (u-turn) and 8 (left). Langton's ant is:
+/// ```text
+/// {{{1, 2, 0}, {0, 8, 0}}}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurmiteRule {
+    /// `table[state][colour]`
+    table: Vec<Vec<Transition>>,
+}
+
+impl TurmiteRule {
+    /// Creates a turmite from its state table
+    ///
+    /// # Arguments
+    /// * `table` - Transitions indexed by internal state, then by colour
+    pub fn new(table: Vec<Vec<Transition>>) -> Result<Self> {
+        let colors = table.first().map(Vec::len).unwrap_or(0);
+        if colors < 2 {
+            return Err(Error::InvalidRuleFormat(
+                "a turmite needs at least one state and two colours".to_string(),
+            ));
+        }
+        if table.iter().any(|state| state.len() != colors) {
+            return Err(Error::InvalidRuleFormat(format!(
+                "every turmite state must list {} colours",
+                colors
+            )));
+        }
+        for transition in table.iter().flatten() {
+            if transition.write as usize >= colors {
+                return Err(Error::InvalidRuleFormat(format!(
+                    "turmite writes colour {} but has only {} colours",
+                    transition.write, colors
+                )));
+            }
+            if transition.next_state as usize >= table.len() {
+                return Err(Error::InvalidRuleFormat(format!(
+                    "turmite moves to state {} but has only {} states",
+                    transition.next_state,
+                    table.len()
+                )));
+            }
+        }
+        Ok(Self { table })
+    }
+
+    /// Returns Langton's ant: turn right on colour 0, left on colour 1,
+    /// flipping the colour each time
+    pub fn langtons_ant() -> Self {
+        Self::from_turns("RL").expect("RL is a valid ant")
+    }
+
+    /// Creates a single-state ant from one turn letter per colour
+    ///
+    /// Letters are `R`, `L`, `N` (no turn) and `U` (u-turn). On a cell of
+    /// colour `c` the ant makes the `c`-th turn and writes colour `c + 1`,
+    /// wrapping round to 0, so `RL` is Langton's ant and `LLRR` a symmetric
+    /// multi-colour ant.
+    pub fn from_turns(turns: &str) -> Result<Self> {
+        let turns: Vec<Turn> = turns
+            .trim()
+            .chars()
+            .map(|c| match c.to_ascii_uppercase() {
+                'N' => Ok(Turn::None),
+                'R' => Ok(Turn::Right),
+                'U' => Ok(Turn::UTurn),
+                'L' => Ok(Turn::Left),
+                _ => Err(Error::InvalidRuleFormat(format!("unknown ant turn '{}'", c))),
+            })
+            .collect::<Result<_>>()?;
+        if turns.len() > u8::MAX as usize {
+            return Err(Error::InvalidRuleFormat("too many ant colours".to_string()));
+        }
+
+        let colors = turns.len();
+        let state = turns
+            .iter()
+            .enumerate()
+            .map(|(color, &turn)| Transition {
+                write: ((color + 1) % colors.max(1)) as u8,
+                turn,
+                next_state: 0,
+            })
+            .collect();
+        Self::new(vec![state])
+    }
+
+    /// Parses a turmite in the `{{{write, turn, next}, ...}, ...}` notation
+    ///
+    /// Square brackets are accepted in place of braces, so JSON arrays parse
+    /// as well.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let tokens = tokenize(spec)?;
+        let mut pos = 0;
+        let tree = parse_list(&tokens, &mut pos)?;
+        if pos != tokens.len() {
+            return Err(Error::InvalidRuleFormat(
+                "unexpected text after turmite specification".to_string(),
+            ));
+        }
+
+        let invalid = |what: &str| {
+            Error::InvalidRuleFormat(format!("turmite specification should be {}", what))
+        };
+        let states = tree.as_list().ok_or_else(|| invalid("a list of states"))?;
+        let table = states
+            .iter()
+            .map(|state| {
+                let colors = state.as_list().ok_or_else(|| invalid("a list of colours per state"))?;
+                colors
+                    .iter()
+                    .map(|entry| {
+                        let numbers = entry
+                            .as_list()
+                            .and_then(|items| items.iter().map(Node::as_number).collect::<Option<Vec<_>>>())
+                            .filter(|numbers| numbers.len() == 3)
+                            .ok_or_else(|| invalid("{write, turn, next state} triples"))?;
+                        let turn = Turn::from_code(numbers[1]).ok_or_else(|| {
+                            Error::InvalidRuleFormat(format!(
+                                "turn code must be 1, 2, 4 or 8, got {}",
+                                numbers[1]
+                            ))
+                        })?;
+                        let small = |n: u32| {
+                            u8::try_from(n).map_err(|_| invalid("small colour and state numbers"))
+                        };
+                        Ok(Transition {
+                            write: small(numbers[0])?,
+                            turn,
+                            next_state: small(numbers[2])?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::new(table)
+    }
+
+    /// Returns the number of internal states
+    pub fn states(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Returns the number of cell colours the turmite reads and writes
+    pub fn colors(&self) -> usize {
+        self.table[0].len()
+    }
+
+    /// Returns the transition for an internal state and cell colour
+    ///
+    /// Colours the turmite does not know are read as colour 0.
+    pub fn transition(&self, state: u8, color: u8) -> Transition {
+        let row = &self.table[state as usize % self.table.len()];
+        row.get(color as usize).copied().unwrap_or(row[0])
+    }
+
+    /// Returns the rule in turmite notation
+    pub fn spec(&self) -> String {
+        let states: Vec<String> = self
+            .table
+            .iter()
+            .map(|state| {
+                let colors: Vec<String> = state
+                    .iter()
+                    .map(|t| format!("{{{}, {}, {}}}", t.write, t.turn.code(), t.next_state))
+                    .collect();
+                format!("{{{}}}", colors.join(", "))
+            })
+            .collect();
+        format!("{{{}}}", states.join(", "))
+    }
+}
+
+/// A turmite walking over the grid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ant {
+    /// Cell the ant is standing on
+    pub position: Position,
+    /// Direction the ant will move in
+    pub heading: Heading,
+    /// Current internal state
+    pub state: u8,
+    /// State table the ant follows
+    pub rule: TurmiteRule,
+}
+
+impl Ant {
+    /// Creates an ant in internal state 0
+    pub fn new(position: Position, heading: Heading, rule: TurmiteRule) -> Self {
+        Self {
+            position,
+            heading,
+            state: 0,
+            rule,
+        }
+    }
+
+    /// Moves the ant one step
+    ///
+    /// The ant reads the colour under it, writes the new colour, turns and
+    /// moves forward one cell. If the boundary has no cell ahead, the ant
+    /// stays where it is.
+    pub fn step(&mut self, grid: &mut DenseGrid, boundary: &dyn Boundary) {
+        let color = grid.get(self.position).map(|s| s.index()).unwrap_or(0);
+        let transition = self.rule.transition(self.state, color);
+        let _ = grid.set(self.position, CellState::from_index(transition.write));
+        self.heading = self.heading.turn(transition.turn);
+        self.state = transition.next_state;
+
+        let (dx, dy) = self.heading.offset();
+        let x = self.position.x as isize + dx;
+        let y = self.position.y as isize + dy;
+        if let Some(next) = boundary.wrap(x, y, grid.width(), grid.height()) {
+            self.position = next;
+        }
+    }
+}
+
+/// Token of the turmite notation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    Number(u32),
+}
+
+/// Nested list of numbers
+enum Node {
+    List(Vec<Node>),
+    Number(u32),
+}
+
+impl Node {
+    fn as_list(&self) -> Option<&[Node]> {
+        match self {
+            Node::List(items) => Some(items),
+            Node::Number(_) => None,
+        }
+    }
+
+    fn as_number(&self) -> Option<u32> {
+        match self {
+            Node::Number(n) => Some(*n),
+            Node::List(_) => None,
+        }
+    }
+}
+
+fn tokenize(spec: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = spec.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '[' => tokens.push(Token::Open),
+            '}' | ']' => tokens.push(Token::Close),
+            ',' => {}
+            c if c.is_whitespace() => {}
+            c if c.is_ascii_digit() => {
+                let mut value = c.to_digit(10).unwrap_or(0);
+                while let Some(d) = chars.peek().and_then(|d| d.to_digit(10)) {
+                    value = value.saturating_mul(10).saturating_add(d);
+                    chars.next();
+                }
+                tokens.push(Token::Number(value));
+            }
+            _ => {
+                return Err(Error::InvalidRuleFormat(format!(
+                    "unexpected character '{}' in turmite specification",
+                    c
+                )))
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_list(tokens: &[Token], pos: &mut usize) -> Result<Node> {
+    match tokens.get(*pos) {
+        Some(Token::Number(n)) => {
+            *pos += 1;
+            Ok(Node::Number(*n))
+        }
+        Some(Token::Open) => {
+            *pos += 1;
+            let mut items = Vec::new();
+            loop {
+                match tokens.get(*pos) {
+                    Some(Token::Close) => {
+                        *pos += 1;
+                        return Ok(Node::List(items));
+                    }
+                    Some(_) => items.push(parse_list(tokens, pos)?),
+                    None => {
+                        return Err(Error::InvalidRuleFormat(
+                            "unbalanced braces in turmite specification".to_string(),
+                        ))
+                    }
+                }
+            }
+        }
+        _ => Err(Error::InvalidRuleFormat(
+            "turmite specification should start with '{'".to_string(),
+        )),
+    }
+}
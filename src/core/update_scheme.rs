@@ -0,0 +1,41 @@
+/// Order in which [`Automata`](crate::core::automata::Automata) updates cells
+///
+/// Every scheme except [`UpdateScheme::Synchronous`] and
+/// [`UpdateScheme::LineByLine`] draws from the automaton's seeded random
+/// number generator, so runs with the same seed are identical.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum UpdateScheme {
+    /// Every cell is updated at once from the previous generation
+    #[default]
+    Synchronous,
+    /// Each cell is updated with probability `alpha`, all from the previous
+    /// generation; the others keep their state (α-asynchronous updating)
+    Asynchronous {
+        /// Probability that a cell is updated, between 0.0 and 1.0
+        alpha: f64,
+    },
+    /// Cells are updated one at a time in a fresh random order every
+    /// generation, each seeing the updates made before it
+    RandomSequential,
+    /// Cells are updated one at a time row by row, left to right and top to
+    /// bottom, each seeing the updates made before it
+    LineByLine,
+}
+
+impl UpdateScheme {
+    /// Returns the display name of the scheme
+    pub fn name(&self) -> &str {
+        match self {
+            UpdateScheme::Synchronous => "Synchronous",
+            UpdateScheme::Asynchronous { .. } => "α-asynchronous",
+            UpdateScheme::RandomSequential => "Random sequential",
+            UpdateScheme::LineByLine => "Line by line",
+        }
+    }
+
+    /// Returns true if cells see the updates of earlier cells in the same
+    /// generation
+    pub fn is_in_place(&self) -> bool {
+        matches!(self, UpdateScheme::RandomSequential | UpdateScheme::LineByLine)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::neighborhood::Neighborhood;
+use crate::core::neighborhood::{offsets_where, Neighborhood};
 
 /// Von Neumann neighborhood - includes only orthogonally adjacent cells
 ///
@@ -9,22 +9,32 @@ use crate::core::neighborhood::Neighborhood;
 ///   X
 /// ```
 /// Where O is the center cell and X are neighbors
-#[derive(Debug, Clone, Copy)]
+///
+/// With a larger radius `r` the neighborhood is the diamond of cells
+/// within Manhattan distance `r`.
+#[derive(Debug, Clone)]
 pub struct VonNeumannNeighborhood {
-    offsets: [(isize, isize); 4],
+    offsets: Vec<(isize, isize)>,
+    radius: usize,
+    name: String,
 }
 
 impl VonNeumannNeighborhood {
     /// Creates a new Von Neumann neighborhood
     pub fn new() -> Self {
-        Self {
-            offsets: [
-                (0, -1),  // Top
-                (-1, 0),  // Left
-                (1, 0),   // Right
-                (0, 1),   // Bottom
-            ],
-        }
+        Self::with_radius(1)
+    }
+
+    /// Creates a Von Neumann neighborhood of the given range
+    pub fn with_radius(radius: usize) -> Self {
+        let r = radius as isize;
+        let offsets = offsets_where(radius, |dx, dy| dx.abs() + dy.abs() <= r);
+        let name = if radius == 1 {
+            "Von Neumann (4 neighbors)".to_string()
+        } else {
+            format!("Von Neumann (r={}, {} neighbors)", radius, offsets.len())
+        };
+        Self { offsets, radius, name }
     }
 }
 
@@ -40,6 +50,10 @@ impl Neighborhood for VonNeumannNeighborhood {
     }
 
     fn name(&self) -> &str {
-        "Von Neumann (4 neighbors)"
+        &self.name
     }
-}
\ No newline at end of file
+
+    fn radius(&self) -> usize {
+        self.radius
+    }
+}
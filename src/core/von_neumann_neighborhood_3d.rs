@@ -0,0 +1,32 @@
+use crate::core::neighborhood_3d::Neighborhood3D;
+
+/// Offsets of the face neighbors, in z, y, x order
+const FACE_OFFSETS: [(isize, isize, isize); 6] =
+    [(0, 0, -1), (0, -1, 0), (-1, 0, 0), (1, 0, 0), (0, 1, 0), (0, 0, 1)];
+
+/// Three-dimensional von Neumann neighborhood - the 6 cells sharing a face
+#[derive(Debug, Clone, Copy)]
+pub struct VonNeumannNeighborhood3D;
+
+impl VonNeumannNeighborhood3D {
+    /// Creates a new 3D von Neumann neighborhood
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for VonNeumannNeighborhood3D {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Neighborhood3D for VonNeumannNeighborhood3D {
+    fn offsets(&self) -> &[(isize, isize, isize)] {
+        &FACE_OFFSETS
+    }
+
+    fn name(&self) -> &str {
+        "3D von Neumann (6 neighbors)"
+    }
+}
@@ -1,5 +1,6 @@
 use crate::core::boundary::Boundary;
-use crate::core::cell::Position;
+use crate::core::boundary_3d::Boundary3D;
+use crate::core::cell::{Position, Position3D};
 
 /// Walled boundary - coordinates are clamped to grid edges
 ///
@@ -9,6 +10,13 @@ use crate::core::cell::Position;
 /// - Coordinates beyond the bottom edge are clamped to the bottom row
 /// - Coordinates beyond the top edge are clamped to the top row
 /// - Negative coordinates return None (out of bounds)
+///
+/// On three-dimensional grids the z coordinate is treated the same way.
+///
+/// Clamping makes the cells along the right and bottom edges count as
+/// their own neighbors, and the left and top edges behave differently. For
+/// a plane surrounded by dead cells use
+/// [`DeadBoundary`](crate::core::dead_boundary::DeadBoundary).
 #[derive(Debug, Clone, Copy)]
 pub struct WalledBoundary;
 
@@ -42,4 +50,22 @@ impl Boundary for WalledBoundary {
 
         Some(Position::new(clamped_x, clamped_y))
     }
-}
\ No newline at end of file
+}
+
+impl Boundary3D for WalledBoundary {
+    fn wrap(
+        &self,
+        x: isize,
+        y: isize,
+        z: isize,
+        width: usize,
+        height: usize,
+        depth: usize,
+    ) -> Option<Position3D> {
+        if depth == 0 || z < 0 {
+            return None;
+        }
+        let pos = Boundary::wrap(self, x, y, width, height)?;
+        Some(Position3D::new(pos.x, pos.y, (z as usize).min(depth - 1)))
+    }
+}
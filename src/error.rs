@@ -18,6 +18,9 @@ pub enum Error {
     /// I/O error
     IoError(String),
     
+    /// Script that failed to compile or run
+    ScriptError(String),
+    
     /// Generic error with message
     Other(String),
 }
@@ -38,6 +41,7 @@ impl fmt::Display for Error {
             Error::InvalidRuleFormat(msg) => write!(f, "Invalid rule format: {}", msg),
             Error::PatternParseError(msg) => write!(f, "Pattern parse error: {}", msg),
             Error::IoError(msg) => write!(f, "I/O error: {}", msg),
+            Error::ScriptError(msg) => write!(f, "Script error: {}", msg),
             Error::Other(msg) => write!(f, "{}", msg),
         }
     }
@@ -0,0 +1,297 @@
+use std::ffi::c_char;
+use std::slice;
+
+use crate::core::automata::Automata;
+use crate::core::cell::{CellState, Position};
+use crate::core::grid::Grid;
+use crate::core::rle::RlePattern;
+use crate::error::Error;
+use crate::ffi::status::CellularityStatus;
+use crate::ffi::{guard, str_arg, Failure};
+
+/// Pass as a coordinate of [`cellularity_automaton_load_rle`] to centre the
+/// pattern along that axis
+pub const CELLULARITY_CENTER: usize = usize::MAX;
+
+/// An automaton owned by C code, created by [`cellularity_automaton_new`]
+/// and released by [`cellularity_automaton_free`]
+pub struct CellularityAutomaton {
+    automata: Automata,
+}
+
+/// Borrows the automaton behind a handle
+///
+/// # Safety
+/// `handle` must be null or come from [`cellularity_automaton_new`] and not
+/// have been freed
+unsafe fn automaton<'a>(handle: *const CellularityAutomaton) -> Result<&'a Automata, Failure> {
+    handle
+        .as_ref()
+        .map(|handle| &handle.automata)
+        .ok_or_else(|| Failure::new(CellularityStatus::NullPointer, "automaton is null"))
+}
+
+/// Mutably borrows the automaton behind a handle
+///
+/// # Safety
+/// As for [`automaton`], and no other reference to it may be live
+unsafe fn automaton_mut<'a>(
+    handle: *mut CellularityAutomaton,
+) -> Result<&'a mut Automata, Failure> {
+    handle
+        .as_mut()
+        .map(|handle| &mut handle.automata)
+        .ok_or_else(|| Failure::new(CellularityStatus::NullPointer, "automaton is null"))
+}
+
+/// Creates an automaton
+///
+/// `rule` takes any notation the engine parses, such as `B3/S23`,
+/// `Wireworld` or `B3/S23:T80,60`; a topology after the rule sets the size
+/// and edges. `boundary` is `torus`, `plane`, `walled`, `mirror`, `alive`,
+/// `klein`, `cross` or `sphere`, and null means `torus`.
+///
+/// # Safety
+/// `rule` and `boundary` must be null or NUL-terminated strings, and `out`
+/// must be null or point to writable memory for a handle
+#[no_mangle]
+pub unsafe extern "C" fn cellularity_automaton_new(
+    rule: *const c_char,
+    width: usize,
+    height: usize,
+    boundary: *const c_char,
+    out: *mut *mut CellularityAutomaton,
+) -> CellularityStatus {
+    guard(|| {
+        if out.is_null() {
+            return Err(Failure::new(CellularityStatus::NullPointer, "out is null"));
+        }
+        let rule = str_arg(rule, "rule")?;
+        let boundary = if boundary.is_null() { "torus" } else { str_arg(boundary, "boundary")? };
+        let automata = Automata::from_spec(rule, width, height, boundary)?;
+        *out = Box::into_raw(Box::new(CellularityAutomaton { automata }));
+        Ok(())
+    })
+}
+
+/// Releases an automaton; null is ignored
+///
+/// # Safety
+/// `handle` must be null or come from [`cellularity_automaton_new`] and not
+/// have been freed already
+#[no_mangle]
+pub unsafe extern "C" fn cellularity_automaton_free(handle: *mut CellularityAutomaton) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Returns the width of the grid, or 0 for a null handle
+///
+/// # Safety
+/// `handle` must be null or a live handle
+#[no_mangle]
+pub unsafe extern "C" fn cellularity_automaton_width(handle: *const CellularityAutomaton) -> usize {
+    automaton(handle).map_or(0, |automata| automata.grid().width())
+}
+
+/// Returns the height of the grid, or 0 for a null handle
+///
+/// # Safety
+/// `handle` must be null or a live handle
+#[no_mangle]
+pub unsafe extern "C" fn cellularity_automaton_height(
+    handle: *const CellularityAutomaton,
+) -> usize {
+    automaton(handle).map_or(0, |automata| automata.grid().height())
+}
+
+/// Returns the current generation, or 0 for a null handle
+///
+/// # Safety
+/// `handle` must be null or a live handle
+#[no_mangle]
+pub unsafe extern "C" fn cellularity_automaton_generation(
+    handle: *const CellularityAutomaton,
+) -> u64 {
+    automaton(handle).map_or(0, |automata| automata.generation())
+}
+
+/// Returns the number of live cells, or 0 for a null handle
+///
+/// # Safety
+/// `handle` must be null or a live handle
+#[no_mangle]
+pub unsafe extern "C" fn cellularity_automaton_population(
+    handle: *const CellularityAutomaton,
+) -> usize {
+    automaton(handle).map_or(0, |automata| automata.grid().count_alive())
+}
+
+/// Reads the state number of one cell (0 dead, 1 alive, higher for
+/// multi-state rules)
+///
+/// # Safety
+/// `handle` must be null or a live handle, and `state` null or writable
+#[no_mangle]
+pub unsafe extern "C" fn cellularity_automaton_get_cell(
+    handle: *const CellularityAutomaton,
+    x: usize,
+    y: usize,
+    state: *mut u8,
+) -> CellularityStatus {
+    guard(|| {
+        let automata = automaton(handle)?;
+        if state.is_null() {
+            return Err(Failure::new(CellularityStatus::NullPointer, "state is null"));
+        }
+        *state = automata.grid().get(Position::new(x, y))?.index();
+        Ok(())
+    })
+}
+
+/// Sets the state number of one cell
+///
+/// # Safety
+/// `handle` must be null or a live handle
+#[no_mangle]
+pub unsafe extern "C" fn cellularity_automaton_set_cell(
+    handle: *mut CellularityAutomaton,
+    x: usize,
+    y: usize,
+    state: u8,
+) -> CellularityStatus {
+    guard(|| {
+        let automata = automaton_mut(handle)?;
+        automata.grid_mut().set(Position::new(x, y), CellState::from_index(state))?;
+        Ok(())
+    })
+}
+
+/// Advances `steps` generations
+///
+/// # Safety
+/// `handle` must be null or a live handle
+#[no_mangle]
+pub unsafe extern "C" fn cellularity_automaton_step(
+    handle: *mut CellularityAutomaton,
+    steps: u64,
+) -> CellularityStatus {
+    guard(|| {
+        let automata = automaton_mut(handle)?;
+        for _ in 0..steps {
+            automata.step();
+        }
+        Ok(())
+    })
+}
+
+/// Kills every cell and goes back to generation 0
+///
+/// # Safety
+/// `handle` must be null or a live handle
+#[no_mangle]
+pub unsafe extern "C" fn cellularity_automaton_reset(
+    handle: *mut CellularityAutomaton,
+) -> CellularityStatus {
+    guard(|| {
+        automaton_mut(handle)?.reset();
+        Ok(())
+    })
+}
+
+/// Copies the state number of every cell, row by row, into a buffer of
+/// exactly width × height bytes
+///
+/// # Safety
+/// `handle` must be null or a live handle, and `buffer` null or valid for
+/// `length` writable bytes
+#[no_mangle]
+pub unsafe extern "C" fn cellularity_automaton_read_grid(
+    handle: *const CellularityAutomaton,
+    buffer: *mut u8,
+    length: usize,
+) -> CellularityStatus {
+    guard(|| {
+        let automata = automaton(handle)?;
+        let buffer = grid_buffer(automata, buffer, length)?;
+        automata.grid().copy_states_to(slice::from_raw_parts_mut(buffer, length))?;
+        Ok(())
+    })
+}
+
+/// Sets every cell from a buffer of width × height state numbers, row by
+/// row
+///
+/// # Safety
+/// `handle` must be null or a live handle, and `buffer` null or valid for
+/// `length` readable bytes
+#[no_mangle]
+pub unsafe extern "C" fn cellularity_automaton_write_grid(
+    handle: *mut CellularityAutomaton,
+    buffer: *const u8,
+    length: usize,
+) -> CellularityStatus {
+    guard(|| {
+        let automata = automaton_mut(handle)?;
+        let buffer = grid_buffer(automata, buffer.cast_mut(), length)?;
+        automata.grid_mut().copy_states_from(slice::from_raw_parts(buffer, length))?;
+        Ok(())
+    })
+}
+
+/// Checks that a caller's buffer is present and holds one byte per cell
+fn grid_buffer(automata: &Automata, buffer: *mut u8, length: usize) -> Result<*mut u8, Failure> {
+    let cells = automata.grid().width() * automata.grid().height();
+    if buffer.is_null() {
+        return Err(Failure::new(CellularityStatus::NullPointer, "buffer is null"));
+    }
+    if length != cells {
+        return Err(Failure::new(
+            CellularityStatus::BufferSize,
+            format!("buffer holds {} bytes, the grid has {} cells", length, cells),
+        ));
+    }
+    Ok(buffer)
+}
+
+/// Places an RLE pattern with its top-left cell at (`x`, `y`), centring it
+/// along any axis given as [`CELLULARITY_CENTER`]
+///
+/// Cells past the edges are dropped. With `clear` the rest of the grid is
+/// killed first; otherwise the pattern's live cells are drawn over it.
+///
+/// # Safety
+/// `handle` must be null or a live handle, and `rle` null or a
+/// NUL-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn cellularity_automaton_load_rle(
+    handle: *mut CellularityAutomaton,
+    rle: *const c_char,
+    x: usize,
+    y: usize,
+    clear: bool,
+) -> CellularityStatus {
+    guard(|| {
+        let automata = automaton_mut(handle)?;
+        let pattern = RlePattern::parse(str_arg(rle, "rle")?)?.grid;
+        let grid = automata.grid_mut();
+        let place = |coordinate: usize, size: usize, pattern_size: usize| match coordinate {
+            CELLULARITY_CENTER => size.saturating_sub(pattern_size) / 2,
+            coordinate => coordinate,
+        };
+        let (width, height) = (grid.width(), grid.height());
+        let origin = Position::new(
+            place(x, width, pattern.width()),
+            place(y, height, pattern.height()),
+        );
+        if !origin.is_within_bounds(width, height) {
+            return Err(Error::OutOfBounds { x: origin.x, y: origin.y, width, height }.into());
+        }
+        if clear {
+            grid.clear();
+        }
+        grid.stamp(&pattern, origin);
+        Ok(())
+    })
+}
@@ -0,0 +1,82 @@
+//! C API for embedding the engine, built with the `ffi` feature
+//!
+//! Every function is `extern "C"` and works on opaque
+//! [`CellularityAutomaton`](automaton::CellularityAutomaton) handles.
+//! Fallible functions return a [`CellularityStatus`](status::CellularityStatus)
+//! and leave a message for [`cellularity_last_error`]; no panic crosses the
+//! boundary. The header is committed as `include/cellularity.h`; building
+//! with the feature generates it again in `OUT_DIR`, and with
+//! `CELLULARITY_WRITE_HEADER` set also copies it over the committed one.
+//! The library itself comes from
+//! `cargo rustc --release --lib --features ffi --crate-type cdylib` (or
+//! `staticlib`).
+
+pub mod automaton;
+pub mod status;
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::error::Error;
+use status::CellularityStatus;
+
+thread_local! {
+    /// Message of the latest failure on this thread
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// A failed call: the code to return and the message to leave behind
+struct Failure {
+    status: CellularityStatus,
+    message: String,
+}
+
+impl Failure {
+    fn new(status: CellularityStatus, message: impl Into<String>) -> Self {
+        Self { status, message: message.into() }
+    }
+}
+
+impl From<Error> for Failure {
+    fn from(err: Error) -> Self {
+        Self::new(CellularityStatus::from(&err), err.to_string())
+    }
+}
+
+/// Runs the body of an API function, turning errors and panics into a
+/// status and recording the message
+fn guard(body: impl FnOnce() -> Result<(), Failure>) -> CellularityStatus {
+    let outcome = panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|_| {
+        Err(Failure::new(CellularityStatus::Panic, "the engine panicked"))
+    });
+    let (status, message) = match outcome {
+        Ok(()) => (CellularityStatus::Ok, String::new()),
+        Err(failure) => (failure.status, failure.message),
+    };
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+    status
+}
+
+/// Reads a NUL-terminated UTF-8 string argument
+///
+/// # Safety
+/// `text` must be null or point to a NUL-terminated string
+unsafe fn str_arg<'a>(text: *const c_char, name: &str) -> Result<&'a str, Failure> {
+    if text.is_null() {
+        return Err(Failure::new(CellularityStatus::NullPointer, format!("{} is null", name)));
+    }
+    CStr::from_ptr(text)
+        .to_str()
+        .map_err(|_| Failure::new(CellularityStatus::InvalidUtf8, format!("{} is not UTF-8", name)))
+}
+
+/// Returns the message of the latest failed call on this thread, or an
+/// empty string if the latest call succeeded
+///
+/// The string stays valid until the next API call on the same thread.
+#[no_mangle]
+pub extern "C" fn cellularity_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ptr())
+}
@@ -0,0 +1,73 @@
+use std::ffi::c_char;
+
+use crate::error::Error;
+
+/// Result code returned by every fallible function of the C API
+///
+/// The first codes mirror the variants of [`Error`]; the rest report misuse
+/// of the API itself. [`cellularity_last_error`](super::cellularity_last_error)
+/// describes the latest failure in words.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellularityStatus {
+    /// Success
+    Ok = 0,
+    /// [`Error::InvalidDimensions`]
+    InvalidDimensions = 1,
+    /// [`Error::OutOfBounds`]
+    OutOfBounds = 2,
+    /// [`Error::InvalidRuleFormat`]
+    InvalidRule = 3,
+    /// [`Error::PatternParseError`]
+    PatternParse = 4,
+    /// [`Error::IoError`]
+    Io = 5,
+    /// [`Error::Other`] or [`Error::ScriptError`]
+    Other = 6,
+    /// A required pointer argument was null
+    NullPointer = 7,
+    /// A string argument was not valid UTF-8
+    InvalidUtf8 = 8,
+    /// A buffer did not hold one byte per cell
+    BufferSize = 9,
+    /// The engine panicked; the automaton should be freed
+    Panic = 10,
+}
+
+impl From<&Error> for CellularityStatus {
+    fn from(err: &Error) -> Self {
+        match err {
+            Error::InvalidDimensions { .. } => CellularityStatus::InvalidDimensions,
+            Error::OutOfBounds { .. } => CellularityStatus::OutOfBounds,
+            Error::InvalidRuleFormat(_) => CellularityStatus::InvalidRule,
+            Error::PatternParseError(_) => CellularityStatus::PatternParse,
+            Error::IoError(_) => CellularityStatus::Io,
+            Error::ScriptError(_) | Error::Other(_) => CellularityStatus::Other,
+        }
+    }
+}
+
+impl CellularityStatus {
+    /// Returns the name of the code as a NUL-terminated string
+    pub fn name(&self) -> &'static [u8] {
+        match self {
+            CellularityStatus::Ok => b"ok\0",
+            CellularityStatus::InvalidDimensions => b"invalid dimensions\0",
+            CellularityStatus::OutOfBounds => b"out of bounds\0",
+            CellularityStatus::InvalidRule => b"invalid rule\0",
+            CellularityStatus::PatternParse => b"pattern parse error\0",
+            CellularityStatus::Io => b"I/O error\0",
+            CellularityStatus::Other => b"error\0",
+            CellularityStatus::NullPointer => b"null pointer\0",
+            CellularityStatus::InvalidUtf8 => b"invalid UTF-8\0",
+            CellularityStatus::BufferSize => b"wrong buffer size\0",
+            CellularityStatus::Panic => b"panic\0",
+        }
+    }
+}
+
+/// Returns a short, static description of a status code
+#[no_mangle]
+pub extern "C" fn cellularity_status_name(status: CellularityStatus) -> *const c_char {
+    status.name().as_ptr().cast()
+}
@@ -0,0 +1,169 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::core::cell::{CellState, Position};
+use crate::core::dense_grid::DenseGrid;
+use crate::core::grid::Grid;
+use crate::error::{Error, Result};
+
+/// Colour drawn for states past the end of a palette, as in the grid view
+const MISSING_COLOR: [u8; 3] = [255, 0, 255];
+
+/// How grids are drawn into images: cell size, colours and cropping
+///
+/// Colours follow the grid view: a rule's own palette when there is one,
+/// otherwise `alive_color` for every live state and `dead_color` for dead
+/// cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameStyle {
+    /// Width and height of a cell in pixels
+    pub cell_size: usize,
+    /// Colour of live cells when there is no palette
+    pub alive_color: [u8; 3],
+    /// Colour of dead cells when there is no palette
+    pub dead_color: [u8; 3],
+    /// Colour of each state, indexed by state number
+    pub palette: Option<Vec<[u8; 3]>>,
+    /// Region to draw as its top-left cell, width and height, or the whole grid
+    pub crop: Option<(Position, usize, usize)>,
+}
+
+impl FrameStyle {
+    /// Creates a style with 4 pixel cells in the default app colours
+    pub fn new() -> Self {
+        Self {
+            cell_size: 4,
+            alive_color: [60, 220, 120],
+            dead_color: [30, 30, 35],
+            palette: None,
+            crop: None,
+        }
+    }
+
+    /// Returns the colour of a cell state
+    pub fn color(&self, state: CellState) -> [u8; 3] {
+        match &self.palette {
+            Some(palette) => palette.get(state.index() as usize).copied().unwrap_or(MISSING_COLOR),
+            None if state.is_dead() => self.dead_color,
+            None => self.alive_color,
+        }
+    }
+
+    /// Returns the colour of every state, indexed by state number
+    pub fn color_table(&self) -> Vec<[u8; 3]> {
+        (0..=u8::MAX).map(|index| self.color(CellState::from_index(index))).collect()
+    }
+
+    /// Returns the region of a grid that is drawn
+    ///
+    /// # Errors
+    /// Returns an error if the crop is empty or extends past the grid
+    pub fn region<G: Grid + ?Sized>(&self, grid: &G) -> Result<(Position, usize, usize)> {
+        match self.crop {
+            Some((origin, width, height)) => {
+                if width == 0 || height == 0 {
+                    return Err(Error::InvalidDimensions { width, height });
+                }
+                let far = Position::new(origin.x + width - 1, origin.y + height - 1);
+                grid.get(far)?;
+                Ok((origin, width, height))
+            }
+            None => Ok((Position::new(0, 0), grid.width(), grid.height())),
+        }
+    }
+
+    /// Returns the width and height in pixels of a drawn grid
+    pub fn image_size(&self, grid: &DenseGrid) -> Result<(usize, usize)> {
+        let (_, width, height) = self.region(grid)?;
+        Ok((width * self.cell_size, height * self.cell_size))
+    }
+
+    /// Draws a grid as one byte per pixel holding the state number of the
+    /// cell, row by row
+    ///
+    /// The bytes index into [`color_table`](Self::color_table).
+    pub fn render_indexed(&self, grid: &DenseGrid) -> Result<Vec<u8>> {
+        if self.cell_size == 0 {
+            return Err(Error::Other("cell size must be at least one pixel".to_string()));
+        }
+        let (origin, width, height) = self.region(grid)?;
+        let mut pixels = Vec::with_capacity(width * height * self.cell_size * self.cell_size);
+        for y in origin.y..origin.y + height {
+            let row: Vec<u8> = (origin.x..origin.x + width)
+                .flat_map(|x| {
+                    let state = grid.get(Position::new(x, y)).unwrap_or_default();
+                    std::iter::repeat_n(state.index(), self.cell_size)
+                })
+                .collect();
+            for _ in 0..self.cell_size {
+                pixels.extend_from_slice(&row);
+            }
+        }
+        Ok(pixels)
+    }
+
+    /// Draws a grid as three bytes per pixel (RGB), row by row
+    pub fn render_rgb(&self, grid: &DenseGrid) -> Result<Vec<u8>> {
+        let colors = self.color_table();
+        let indexed = self.render_indexed(grid)?;
+        Ok(indexed.iter().flat_map(|&index| colors[index as usize]).collect())
+    }
+
+    /// Writes a grid as an RGB PNG image
+    pub fn write_png<W: Write>(&self, grid: &DenseGrid, writer: W) -> Result<()> {
+        let (width, height) = self.image_size(grid)?;
+        let too_large = || Error::InvalidDimensions { width, height };
+        let png_width = u32::try_from(width).map_err(|_| too_large())?;
+        let png_height = u32::try_from(height).map_err(|_| too_large())?;
+        let pixels = self.render_rgb(grid)?;
+
+        let png_error = |err: png::EncodingError| Error::IoError(err.to_string());
+        let mut encoder = png::Encoder::new(writer, png_width, png_height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(png_error)?;
+        writer.write_image_data(&pixels).map_err(png_error)?;
+        writer.finish().map_err(png_error)
+    }
+
+    /// Writes a grid to a PBM file if the path ends in `.pbm`, otherwise
+    /// to a PNG file
+    pub fn save(&self, grid: &DenseGrid, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let file =
+            File::create(path).map_err(|e| Error::IoError(format!("{}: {}", path.display(), e)))?;
+        let writer = BufWriter::new(file);
+        if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("pbm")) {
+            self.write_pbm(grid, writer)
+        } else {
+            self.write_png(grid, writer)
+        }
+    }
+
+    /// Writes a grid as a plain (ASCII) PBM bitmap, with every cell that is
+    /// not dead in black
+    ///
+    /// Colours and palettes are ignored; the cell size and crop apply.
+    pub fn write_pbm<W: Write>(&self, grid: &DenseGrid, mut writer: W) -> Result<()> {
+        let (width, height) = self.image_size(grid)?;
+        let pixels = self.render_indexed(grid)?;
+        let mut text = format!("P1\n{} {}\n", width, height);
+        for row in pixels.chunks_exact(width) {
+            // Netpbm asks for lines of at most 70 characters
+            for line in row.chunks(35) {
+                let bits: Vec<&str> =
+                    line.iter().map(|&state| if state == 0 { "0" } else { "1" }).collect();
+                text.push_str(&bits.join(" "));
+                text.push('\n');
+            }
+        }
+        writer.write_all(text.as_bytes()).map_err(|e| Error::IoError(e.to_string()))
+    }
+}
+
+impl Default for FrameStyle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
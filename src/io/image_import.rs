@@ -0,0 +1,128 @@
+use std::path::Path;
+
+use crate::core::cell::{CellState, Position};
+use crate::core::dense_grid::DenseGrid;
+use crate::core::grid::Grid;
+use crate::error::{Error, Result};
+use crate::io::frame_style::FrameStyle;
+use crate::io::raster::Raster;
+
+/// Settings for turning an image into a grid
+///
+/// Each cell covers a `scale` by `scale` block of pixels and takes their
+/// average colour. Without a palette, cells darker than `threshold` are
+/// alive, so black drawings on white come out as live cells. With a palette
+/// each cell takes the state whose colour is nearest, which maps
+/// multi-colour images onto multi-state rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageImport {
+    /// Brightness (0 to 255) below which a cell is alive
+    pub threshold: u8,
+    /// Width and height in pixels of the block each cell covers
+    pub scale: usize,
+    /// Makes light cells alive instead of dark ones
+    pub invert: bool,
+    /// Colour of each state, indexed by state number
+    pub palette: Option<Vec<[u8; 3]>>,
+}
+
+impl ImageImport {
+    /// Creates settings with a threshold of 128, one pixel per cell and no
+    /// palette
+    pub fn new() -> Self {
+        Self {
+            threshold: 128,
+            scale: 1,
+            invert: false,
+            palette: None,
+        }
+    }
+
+    /// Reads an image file into a grid
+    pub fn open(&self, path: impl AsRef<Path>) -> Result<DenseGrid> {
+        self.to_grid(&Raster::open(path)?)
+    }
+
+    /// Converts an image into a grid, rounding its size up to whole cells
+    ///
+    /// # Errors
+    /// Returns an error if the scale is zero, the palette is empty or the
+    /// image has no pixels
+    pub fn to_grid(&self, image: &Raster) -> Result<DenseGrid> {
+        if self.scale == 0 {
+            return Err(Error::Other("scale must be at least one pixel per cell".to_string()));
+        }
+        if self.palette.as_ref().is_some_and(|palette| palette.is_empty()) {
+            return Err(Error::Other("palette has no colours".to_string()));
+        }
+        let width = image.width.div_ceil(self.scale);
+        let height = image.height.div_ceil(self.scale);
+        let mut grid = DenseGrid::new(width, height)?;
+        for y in 0..height {
+            for x in 0..width {
+                let color = self.block_color(image, x * self.scale, y * self.scale);
+                grid.set(Position::new(x, y), self.state(color))?;
+            }
+        }
+        Ok(grid)
+    }
+
+    /// Returns the style that draws grids as these settings read them back:
+    /// black live cells on white (or the reverse when inverted), or the
+    /// palette, with `scale` pixels per cell
+    pub fn export_style(&self) -> FrameStyle {
+        let (alive, dead) = if self.invert { ([255; 3], [0; 3]) } else { ([0; 3], [255; 3]) };
+        FrameStyle {
+            cell_size: self.scale,
+            alive_color: alive,
+            dead_color: dead,
+            palette: self.palette.clone(),
+            crop: None,
+        }
+    }
+
+    /// Returns the state of a cell of the given colour
+    pub fn state(&self, color: [u8; 3]) -> CellState {
+        match &self.palette {
+            Some(palette) => {
+                let distance = |entry: &[u8; 3]| -> u32 {
+                    (0..3).map(|i| u32::from(color[i].abs_diff(entry[i])).pow(2)).sum()
+                };
+                let nearest = (0..palette.len()).min_by_key(|&i| distance(&palette[i]));
+                CellState::from_index(nearest.unwrap_or(0).min(255) as u8)
+            }
+            None => {
+                let [r, g, b] = color.map(u32::from);
+                let brightness = (299 * r + 587 * g + 114 * b) / 1000;
+                if (brightness < u32::from(self.threshold)) != self.invert {
+                    CellState::Alive
+                } else {
+                    CellState::Dead
+                }
+            }
+        }
+    }
+
+    /// Averages the colour of the block of pixels starting at a corner,
+    /// clipped to the image
+    fn block_color(&self, image: &Raster, left: usize, top: usize) -> [u8; 3] {
+        let right = (left + self.scale).min(image.width);
+        let bottom = (top + self.scale).min(image.height);
+        let mut sums = [0u64; 3];
+        for y in top..bottom {
+            for x in left..right {
+                for (sum, value) in sums.iter_mut().zip(image.pixel(x, y)) {
+                    *sum += u64::from(value);
+                }
+            }
+        }
+        let count = ((right - left) * (bottom - top)).max(1) as u64;
+        sums.map(|sum| (sum / count) as u8)
+    }
+}
+
+impl Default for ImageImport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
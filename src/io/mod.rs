@@ -0,0 +1,5 @@
+pub mod frame_style;
+pub mod recorder;
+pub mod raster;
+pub mod image_import;
+pub mod svg_export;
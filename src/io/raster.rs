@@ -0,0 +1,188 @@
+use std::io::Cursor;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+
+/// Bytes every PNG file starts with
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// A decoded image as RGB pixels, row by row
+///
+/// PNG images of any colour type are read, with transparent pixels blended
+/// onto white, as are the Netpbm formats: PBM (`P1`, `P4`), PGM (`P2`,
+/// `P5`) and PPM (`P3`, `P6`) with up to 8 bits per channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Raster {
+    /// Width in pixels
+    pub width: usize,
+    /// Height in pixels
+    pub height: usize,
+    /// Colour of each pixel, row by row
+    pub pixels: Vec<[u8; 3]>,
+}
+
+impl Raster {
+    /// Reads an image file, telling PNG from Netpbm by its contents
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .map_err(|e| Error::IoError(format!("{}: {}", path.display(), e)))?;
+        Self::decode(&bytes)
+    }
+
+    /// Decodes a PNG or Netpbm image
+    ///
+    /// # Errors
+    /// Returns an error for other formats, malformed or truncated images and
+    /// Netpbm images with more than 8 bits per channel
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        if bytes.starts_with(PNG_SIGNATURE) {
+            Self::decode_png(bytes)
+        } else if bytes.first() == Some(&b'P') {
+            Self::decode_netpbm(bytes)
+        } else {
+            Err(Error::PatternParseError("expected a PNG, PBM, PGM or PPM image".to_string()))
+        }
+    }
+
+    /// Returns the colour of a pixel
+    pub fn pixel(&self, x: usize, y: usize) -> [u8; 3] {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Decodes a PNG image into RGB
+    fn decode_png(bytes: &[u8]) -> Result<Self> {
+        let png_error = |err: png::DecodingError| Error::PatternParseError(err.to_string());
+        let mut decoder = png::Decoder::new(Cursor::new(bytes));
+        decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+        let mut reader = decoder.read_info().map_err(png_error)?;
+        let size = reader
+            .output_buffer_size()
+            .ok_or_else(|| Error::PatternParseError("PNG image is too large".to_string()))?;
+        let mut buffer = vec![0; size];
+        let info = reader.next_frame(&mut buffer).map_err(png_error)?;
+        let (width, height) = (info.width as usize, info.height as usize);
+
+        let channels = info.color_type.samples();
+        let blend = |value: u8, alpha: u8| {
+            let (value, alpha) = (u32::from(value), u32::from(alpha));
+            ((value * alpha + 255 * (255 - alpha)) / 255) as u8
+        };
+        let pixels = buffer[..info.buffer_size()]
+            .chunks_exact(info.line_size)
+            .flat_map(|line| line[..width * channels].chunks_exact(channels))
+            .map(|sample| match *sample {
+                [gray] => [gray; 3],
+                [gray, alpha] => [blend(gray, alpha); 3],
+                [r, g, b] => [r, g, b],
+                [r, g, b, alpha] => [blend(r, alpha), blend(g, alpha), blend(b, alpha)],
+                _ => [255; 3],
+            })
+            .collect();
+        Ok(Self { width, height, pixels })
+    }
+
+    /// Decodes a PBM, PGM or PPM image, in plain (ASCII) or raw form
+    fn decode_netpbm(bytes: &[u8]) -> Result<Self> {
+        let invalid = |reason: &str| Error::PatternParseError(format!("Netpbm image: {}", reason));
+        let kind = match bytes.get(1) {
+            Some(&digit @ b'1'..=b'6') => digit - b'0',
+            _ => return Err(invalid("expected P1 to P6")),
+        };
+        let mut tokens = NetpbmTokens { bytes, offset: 2 };
+        let width = tokens.next_number("width")?;
+        let height = tokens.next_number("height")?;
+        let bitmap = kind == 1 || kind == 4;
+        let max_value = if bitmap { 1 } else { tokens.next_number("maximum value")? };
+        if max_value == 0 || max_value > 255 {
+            return Err(invalid("only 1 to 255 levels per channel are supported"));
+        }
+        let channels = if kind == 3 || kind == 6 { 3 } else { 1 };
+        let count = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(channels))
+            .ok_or_else(|| invalid("too large"))?;
+
+        let samples: Vec<usize> = match kind {
+            // PBM bits are set for black and packed eight to a byte, rows padded
+            4 => {
+                let start = tokens.offset + 1;
+                let row_bytes = width.div_ceil(8);
+                let end = row_bytes
+                    .checked_mul(height)
+                    .and_then(|size| size.checked_add(start))
+                    .ok_or_else(|| invalid("too large"))?;
+                let raster = bytes.get(start..end).ok_or_else(|| invalid("truncated"))?;
+                let bit = |row: &[u8], x: usize| usize::from(row[x / 8] >> (7 - x % 8) & 1);
+                raster
+                    .chunks_exact(row_bytes.max(1))
+                    .flat_map(|row| (0..width).map(move |x| bit(row, x)))
+                    .collect()
+            }
+            5 | 6 => {
+                let start = tokens.offset + 1;
+                let end = start.checked_add(count).ok_or_else(|| invalid("too large"))?;
+                let raster = bytes.get(start..end).ok_or_else(|| invalid("truncated"))?;
+                raster.iter().map(|&value| usize::from(value)).collect()
+            }
+            // P1 samples may be written without spaces between them
+            1 => {
+                let digits = bytes[tokens.offset..].iter().filter(|c| matches!(c, b'0' | b'1'));
+                digits.take(count).map(|&c| usize::from(c - b'0')).collect()
+            }
+            _ => (0..count).map(|_| tokens.next_number("sample")).collect::<Result<_>>()?,
+        };
+        if samples.len() < count {
+            return Err(invalid("truncated"));
+        }
+
+        let level = |sample: usize| (sample.min(max_value) * 255 / max_value) as u8;
+        let pixels = match channels {
+            3 => samples
+                .chunks_exact(3)
+                .map(|rgb| [level(rgb[0]), level(rgb[1]), level(rgb[2])])
+                .collect(),
+            _ if bitmap => samples.iter().map(|&bit| [if bit == 1 { 0 } else { 255 }; 3]).collect(),
+            _ => samples.iter().map(|&gray| [level(gray); 3]).collect(),
+        };
+        Ok(Self { width, height, pixels })
+    }
+}
+
+/// Splits a Netpbm header into whitespace-separated tokens, skipping
+/// `#` comments
+struct NetpbmTokens<'a> {
+    bytes: &'a [u8],
+    /// Position just after the last token read
+    offset: usize,
+}
+
+impl<'a> NetpbmTokens<'a> {
+    /// Reads a token as a whole number
+    fn next_number(&mut self, name: &str) -> Result<usize> {
+        self.next_token()
+            .and_then(|token| std::str::from_utf8(token).ok()?.parse::<usize>().ok())
+            .ok_or_else(|| {
+                Error::PatternParseError(format!("Netpbm image: malformed {}", name))
+            })
+    }
+
+    fn next_token(&mut self) -> Option<&'a [u8]> {
+        loop {
+            match self.bytes.get(self.offset)? {
+                b'#' => {
+                    while self.bytes.get(self.offset).is_some_and(|&c| c != b'\n') {
+                        self.offset += 1;
+                    }
+                }
+                c if c.is_ascii_whitespace() => self.offset += 1,
+                _ => break,
+            }
+        }
+        let start = self.offset;
+        while self.bytes.get(self.offset).is_some_and(|c| !c.is_ascii_whitespace()) {
+            self.offset += 1;
+        }
+        Some(&self.bytes[start..self.offset])
+    }
+}
@@ -0,0 +1,146 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::core::automata::Automata;
+use crate::core::dense_grid::DenseGrid;
+use crate::error::{Error, Result};
+use crate::io::frame_style::FrameStyle;
+
+/// Records the evolution of an automaton as an animated GIF or a sequence
+/// of PNG images
+///
+/// Frames are plain grids, so they can come from [`capture`](Self::capture)
+/// or be collected one generation at a time, as the app does while running.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recorder {
+    /// Cell size, colours and cropping of each frame
+    pub style: FrameStyle,
+    /// Number of generations captured by [`capture`](Self::capture)
+    pub frames: usize,
+    /// Time each GIF frame is shown, in milliseconds
+    ///
+    /// GIF delays are stored in hundredths of a second, so this is rounded
+    /// down to a multiple of 10.
+    pub frame_delay_ms: u32,
+}
+
+impl Recorder {
+    /// Creates a recorder for 100 frames shown 100 ms each
+    pub fn new() -> Self {
+        Self {
+            style: FrameStyle::new(),
+            frames: 100,
+            frame_delay_ms: 100,
+        }
+    }
+
+    /// Steps an automaton, copying its grid before each step
+    ///
+    /// The first frame is the current generation, so the automaton ends
+    /// `frames - 1` generations later.
+    pub fn capture(&self, automata: &mut Automata) -> Vec<DenseGrid> {
+        let mut frames = Vec::with_capacity(self.frames);
+        for frame in 0..self.frames {
+            if frame > 0 {
+                automata.step();
+            }
+            frames.push(automata.grid().clone());
+        }
+        frames
+    }
+
+    /// Writes frames as a looping animated GIF
+    ///
+    /// Cell states index straight into the GIF palette, so colours are
+    /// exact.
+    ///
+    /// # Errors
+    /// Returns an error if there are no frames, the frames differ in size,
+    /// the image is larger than 65535 pixels a side, or writing fails
+    pub fn write_gif<W: Write>(&self, frames: &[DenseGrid], writer: W) -> Result<()> {
+        let (width, height) = self.frame_size(frames)?;
+        let too_large = || Error::InvalidDimensions { width, height };
+        let gif_width = u16::try_from(width).map_err(|_| too_large())?;
+        let gif_height = u16::try_from(height).map_err(|_| too_large())?;
+        let palette: Vec<u8> = self.style.color_table().concat();
+
+        let gif_error = |err: gif::EncodingError| Error::IoError(err.to_string());
+        let mut encoder =
+            gif::Encoder::new(writer, gif_width, gif_height, &palette).map_err(gif_error)?;
+        encoder.set_repeat(gif::Repeat::Infinite).map_err(gif_error)?;
+        let delay = u16::try_from(self.frame_delay_ms / 10).unwrap_or(u16::MAX);
+        for grid in frames {
+            let frame = gif::Frame {
+                width: gif_width,
+                height: gif_height,
+                delay,
+                buffer: self.style.render_indexed(grid)?.into(),
+                ..gif::Frame::default()
+            };
+            encoder.write_frame(&frame).map_err(gif_error)?;
+        }
+        Ok(())
+    }
+
+    /// Writes each frame to a numbered PNG file, e.g. `frame_0007.png`,
+    /// creating the directory if needed
+    ///
+    /// # Returns
+    /// The paths of the files written, in order
+    pub fn write_png_sequence(
+        &self,
+        frames: &[DenseGrid],
+        directory: impl AsRef<Path>,
+    ) -> Result<Vec<PathBuf>> {
+        self.frame_size(frames)?;
+        let directory = directory.as_ref();
+        std::fs::create_dir_all(directory)
+            .map_err(|e| Error::IoError(format!("{}: {}", directory.display(), e)))?;
+        let digits = frames.len().to_string().len().max(4);
+        let mut paths = Vec::with_capacity(frames.len());
+        for (index, grid) in frames.iter().enumerate() {
+            let path = directory.join(format!("frame_{:0digits$}.png", index, digits = digits));
+            let file = File::create(&path)
+                .map_err(|e| Error::IoError(format!("{}: {}", path.display(), e)))?;
+            self.style.write_png(grid, BufWriter::new(file))?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    /// Writes frames as a GIF if the path ends in `.gif`, otherwise as a
+    /// PNG sequence in the directory at `path`
+    ///
+    /// # Returns
+    /// The paths of the files written
+    pub fn save(&self, frames: &[DenseGrid], path: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+        let path = path.as_ref();
+        let is_gif = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("gif"));
+        if !is_gif {
+            return self.write_png_sequence(frames, path);
+        }
+        let file =
+            File::create(path).map_err(|e| Error::IoError(format!("{}: {}", path.display(), e)))?;
+        self.write_gif(frames, BufWriter::new(file))?;
+        Ok(vec![path.to_path_buf()])
+    }
+
+    /// Returns the image size shared by every frame
+    fn frame_size(&self, frames: &[DenseGrid]) -> Result<(usize, usize)> {
+        let first = frames.first().ok_or_else(|| Error::Other("no frames to write".to_string()))?;
+        let size = self.style.image_size(first)?;
+        for grid in frames {
+            if self.style.image_size(grid)? != size {
+                return Err(Error::Other("frames differ in size".to_string()));
+            }
+        }
+        Ok(size)
+    }
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,259 @@
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::core::cell::Position;
+use crate::core::grid::Grid;
+use crate::error::{Error, Result};
+use crate::io::frame_style::FrameStyle;
+
+/// Draws grid regions as SVG vector images for papers and slides
+///
+/// Dead cells form the background and every other cell is a square in the
+/// colour [`FrameStyle::color`] gives it, which matches the grid view.
+/// Several generations can be drawn side by side as a spacetime stack with
+/// [`stack`](Self::stack).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SvgExport {
+    /// Cell size in pixels, colours and the region drawn
+    pub style: FrameStyle,
+    /// Draws thin lines between all cells
+    pub grid_lines: bool,
+    /// Outlines every cell that is not dead
+    pub cell_borders: bool,
+    /// Numbers the columns and rows, and the generations of a stack
+    pub labels: bool,
+    /// Distance in cells between numbered columns and rows
+    pub label_step: usize,
+    /// Cells outlined in `highlight_color`, in grid coordinates
+    pub highlights: Vec<Position>,
+    /// Colour of highlight outlines
+    pub highlight_color: [u8; 3],
+    /// Colour of grid lines, cell borders and labels
+    pub line_color: [u8; 3],
+    /// Space between the generations of a stack, in cells
+    pub gap: usize,
+}
+
+impl SvgExport {
+    /// Creates an exporter with 10 pixel cells and grid lines
+    pub fn new() -> Self {
+        Self {
+            style: FrameStyle {
+                cell_size: 10,
+                ..FrameStyle::new()
+            },
+            grid_lines: true,
+            cell_borders: false,
+            labels: false,
+            label_step: 5,
+            highlights: Vec::new(),
+            highlight_color: [250, 200, 60],
+            line_color: [128, 128, 128],
+            gap: 2,
+        }
+    }
+
+    /// Draws one grid
+    pub fn to_svg<G: Grid>(&self, grid: &G) -> Result<String> {
+        self.stack(std::slice::from_ref(grid))
+    }
+
+    /// Draws several generations of the same grid side by side, left to
+    /// right
+    ///
+    /// # Errors
+    /// Returns an error if there are no frames, the cell size is zero or
+    /// the region does not fit in every frame
+    pub fn stack<G: Grid>(&self, frames: &[G]) -> Result<String> {
+        let cell = self.style.cell_size;
+        if cell == 0 {
+            return Err(Error::Other("cell size must be at least one pixel".to_string()));
+        }
+        let first = frames.first().ok_or_else(|| Error::Other("no grids to draw".to_string()))?;
+        let (origin, width, height) = self.style.region(first)?;
+        let font_size = (cell * 6 / 10).max(8);
+        let margin = if self.labels { font_size * 3 } else { 0 };
+        let panel_width = width * cell;
+        let stride = panel_width + self.gap * cell;
+        let total_width = margin + stride * (frames.len() - 1) + panel_width + 1;
+        let total_height = margin + height * cell + 1;
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+             viewBox=\"0 0 {w} {h}\" shape-rendering=\"crispEdges\">",
+            w = total_width,
+            h = total_height,
+        );
+        for (index, grid) in frames.iter().enumerate() {
+            if self.style.region(grid)? != (origin, width, height) {
+                return Err(Error::Other("region does not fit every grid".to_string()));
+            }
+            let left = margin + index * stride;
+            self.draw_panel(&mut svg, grid, (origin, width, height), left, margin)?;
+            if self.labels {
+                let region = (origin, width, height);
+                self.draw_labels(&mut svg, region, left, margin, font_size, index == 0);
+                if frames.len() > 1 {
+                    let _ = writeln!(
+                        svg,
+                        "  <text x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\" \
+                         font-family=\"sans-serif\" fill=\"{}\">t = {}</text>",
+                        left + panel_width / 2,
+                        font_size,
+                        font_size,
+                        hex(self.line_color),
+                        index,
+                    );
+                }
+            }
+        }
+        svg.push_str("</svg>\n");
+        Ok(svg)
+    }
+
+    /// Writes one grid to an SVG file
+    pub fn save<G: Grid>(&self, grid: &G, path: impl AsRef<Path>) -> Result<()> {
+        self.save_stack(std::slice::from_ref(grid), path)
+    }
+
+    /// Writes a spacetime stack to an SVG file
+    pub fn save_stack<G: Grid>(&self, frames: &[G], path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.stack(frames)?)
+            .map_err(|e| Error::IoError(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Draws the cells, grid lines and highlights of one grid with its
+    /// top-left corner at (`left`, `top`)
+    fn draw_panel<G: Grid>(
+        &self,
+        svg: &mut String,
+        grid: &G,
+        (origin, width, height): (Position, usize, usize),
+        left: usize,
+        top: usize,
+    ) -> Result<()> {
+        let cell = self.style.cell_size;
+        let dead = hex(self.style.color(Default::default()));
+        let _ = writeln!(
+            svg,
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+            left,
+            top,
+            width * cell,
+            height * cell,
+            dead,
+        );
+
+        let border = if self.cell_borders {
+            format!(" stroke=\"{}\" stroke-width=\"1\"", hex(self.line_color))
+        } else {
+            String::new()
+        };
+        for y in 0..height {
+            for x in 0..width {
+                let state = grid.get(Position::new(origin.x + x, origin.y + y))?;
+                if state.is_dead() {
+                    continue;
+                }
+                let _ = writeln!(
+                    svg,
+                    "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"{}/>",
+                    left + x * cell,
+                    top + y * cell,
+                    cell,
+                    cell,
+                    hex(self.style.color(state)),
+                    border,
+                );
+            }
+        }
+
+        if self.grid_lines {
+            let mut path = String::new();
+            for x in 0..=width {
+                let _ = write!(path, "M{} {}v{}", left + x * cell, top, height * cell);
+            }
+            for y in 0..=height {
+                let _ = write!(path, "M{} {}h{}", left, top + y * cell, width * cell);
+            }
+            let _ = writeln!(
+                svg,
+                "  <path d=\"{}\" stroke=\"{}\" stroke-width=\"0.5\" fill=\"none\"/>",
+                path,
+                hex(self.line_color),
+            );
+        }
+
+        let inside = |pos: &&Position| {
+            (origin.x..origin.x + width).contains(&pos.x)
+                && (origin.y..origin.y + height).contains(&pos.y)
+        };
+        for pos in self.highlights.iter().filter(inside) {
+            let _ = writeln!(
+                svg,
+                "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" \
+                 stroke=\"{}\" stroke-width=\"2\"/>",
+                left + (pos.x - origin.x) * cell + 1,
+                top + (pos.y - origin.y) * cell + 1,
+                cell.saturating_sub(2),
+                cell.saturating_sub(2),
+                hex(self.highlight_color),
+            );
+        }
+        Ok(())
+    }
+
+    /// Numbers every `label_step`th column above the panel and, if `rows`
+    /// is set, every `label_step`th row to its left, in grid coordinates
+    fn draw_labels(
+        &self,
+        svg: &mut String,
+        (origin, width, height): (Position, usize, usize),
+        left: usize,
+        top: usize,
+        font_size: usize,
+        rows: bool,
+    ) {
+        let cell = self.style.cell_size;
+        let step = self.label_step.max(1);
+        let color = hex(self.line_color);
+        for x in (0..width).filter(|x| (origin.x + x) % step == 0) {
+            let _ = writeln!(
+                svg,
+                "  <text x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\" \
+                 font-family=\"sans-serif\" fill=\"{}\">{}</text>",
+                left + x * cell + cell / 2,
+                top - font_size / 2,
+                font_size,
+                color,
+                origin.x + x,
+            );
+        }
+        for y in (0..height).filter(|y| rows && (origin.y + y) % step == 0) {
+            let _ = writeln!(
+                svg,
+                "  <text x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"end\" \
+                 dominant-baseline=\"central\" font-family=\"sans-serif\" fill=\"{}\">{}</text>",
+                left - font_size / 2,
+                top + y * cell + cell / 2,
+                font_size,
+                color,
+                origin.y + y,
+            );
+        }
+    }
+}
+
+impl Default for SvgExport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats a colour as `#rrggbb`
+fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
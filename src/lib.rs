@@ -1,21 +1,72 @@
+pub mod cli;
 pub mod core;
 pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod io;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+#[cfg(feature = "server")]
+pub mod server;
 pub mod utils;
 pub mod ui;
 
 // Re-export commonly used types
 pub use core::{
     automata::Automata,
-    boundary::Boundary,
-    cell::{CellState, Position},
+    automata_3d::Automata3D,
+    boundary::{Boundary, EdgePair},
+    boundary_3d::Boundary3D,
+    cell::{CellState, Position, Position3D},
+    circular_neighborhood::CircularNeighborhood,
+    constant_boundary::ConstantBoundary,
+    continuous_automata::ContinuousAutomata,
+    cross_surface_boundary::CrossSurfaceBoundary,
+    convolution::Convolution,
+    custom_neighborhood::CustomNeighborhood,
+    dead_boundary::DeadBoundary,
     dense_grid::DenseGrid,
+    dense_grid_3d::{Axis, DenseGrid3D},
+    elementary_automata::{EdgeCondition, ElementaryAutomata, SpacetimeDiagram},
+    float_grid::FloatGrid,
     grid::Grid,
+    hexagonal_neighborhood::HexagonalNeighborhood,
+    kernel_neighborhood::KernelNeighborhood,
+    klein_bottle_boundary::KleinBottleBoundary,
+    lattice::Lattice,
+    margolus::{BlockRule, MargolusAutomata},
+    mirror_boundary::MirrorBoundary,
+    mixed_boundary::{EdgeBehavior, MixedBoundary},
     moore_neighborhood::MooreNeighborhood,
+    moore_neighborhood_3d::MooreNeighborhood3D,
     neighborhood::Neighborhood,
-    rules::{ConwayRule, Rule},
+    neighborhood_3d::Neighborhood3D,
+    pattern_library::{LibraryPattern, PatternCategory, PatternLibrary},
+    prefix_sum::{RowSpans, SummedAreaTable},
+    rules::{
+        parse_rule, BriansBrainRule, ContinuousRule, ConwayRule, HenselRule, LtLNeighborhood,
+        LtLRule, Neighbors, IdentityRule, LeniaRule, Life3DNeighborhood, Life3DRule, ParsedRule,
+        Rule, Rule1D, RuleTable, StochasticRule, TableNeighborhood, TableSymmetry,
+        TotalisticCodeRule, WeightedLifeRule, WireworldRule, WolframRule,
+    },
+    rle::RlePattern,
+    shifted_torus_boundary::ShiftedTorusBoundary,
+    sphere_boundary::SphereBoundary,
     toroidal_boundary::ToroidalBoundary,
+    topology::Topology,
+    transform::Transform,
+    triangular_neighborhood::TriangularNeighborhood,
+    turmite::{Ant, Heading, Transition, Turn, TurmiteRule},
+    update_scheme::UpdateScheme,
     von_neumann_neighborhood::VonNeumannNeighborhood,
+    von_neumann_neighborhood_3d::VonNeumannNeighborhood3D,
     walled_boundary::WalledBoundary,
 };
 pub use error::{Error, Result};
+pub use io::{
+    frame_style::FrameStyle, image_import::ImageImport, raster::Raster, recorder::Recorder,
+    svg_export::SvgExport,
+};
 pub use ui::{ControlPanel, ControlAction};
\ No newline at end of file
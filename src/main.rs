@@ -1,7 +1,15 @@
-use eframe::NativeOptions;
-
+#[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result<()> {
-    let options = NativeOptions::default();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        if let Err(err) = cellularity::cli::run(&args) {
+            eprintln!("error: {}", err);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let options = eframe::NativeOptions::default();
     eframe::run_native(
         "Cellularity",
         options,
@@ -13,3 +21,37 @@ fn main() -> eframe::Result<()> {
         }),
     )
 }
+
+/// Starts the app on the `cellularity_canvas` element of `index.html`
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    use eframe::wasm_bindgen::JsCast;
+
+    wasm_bindgen_futures::spawn_local(async {
+        let document = eframe::web_sys::window()
+            .and_then(|window| window.document())
+            .expect("the app runs in a web page");
+        let canvas = document
+            .get_element_by_id("cellularity_canvas")
+            .and_then(|element| element.dyn_into::<eframe::web_sys::HtmlCanvasElement>().ok())
+            .expect("index.html has a canvas with id cellularity_canvas");
+
+        let started = eframe::WebRunner::new()
+            .start(
+                canvas,
+                eframe::WebOptions::default(),
+                Box::new(|cc| Ok(Box::new(cellularity::ui::app::CellularityApp::new(cc)))),
+            )
+            .await;
+
+        // Replace the loading message with the outcome
+        if let Some(loading) = document.get_element_by_id("loading") {
+            match started {
+                Ok(()) => loading.remove(),
+                Err(err) => {
+                    loading.set_inner_html(&format!("Cellularity failed to start: {:?}", err))
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,311 @@
+use std::path::Path;
+
+use numpy::ndarray::ArrayView2;
+use numpy::PyArray2;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+use pyo3::PyTypeInfo;
+
+use crate::cli::automata_for;
+use crate::core::automata::Automata;
+use crate::core::boundary::parse_boundary;
+use crate::core::cell::Position;
+use crate::core::grid::Grid;
+use crate::core::rle::RlePattern;
+use crate::core::rules::parse_rule;
+use crate::core::topology::Topology;
+use crate::error::{Error, Result};
+use crate::python::boundary::PyBoundary;
+use crate::python::neighborhood::PyNeighborhood;
+use crate::python::rule::PyRule;
+
+/// An automaton driven from Python, as `cellularity.Automata(64, 64, "B3/S23")`
+///
+/// [`grid`](Self::grid) is a NumPy `uint8` array of state numbers, shape
+/// `(height, width)`, that views the automaton's own cells without copying
+/// them: it changes in place as the automaton steps, and writing to it
+/// changes the automaton's cells. The grid keeps its size and its storage for the
+/// life of the automaton, so the array never goes stale.
+#[pyclass(name = "Automata", module = "cellularity")]
+pub struct PyAutomata {
+    automata: Automata,
+    rule: String,
+    boundary: String,
+}
+
+impl PyAutomata {
+    /// Wraps an automaton
+    pub fn wrap(automata: Automata, rule: String, boundary: String) -> Self {
+        Self { automata, rule, boundary }
+    }
+
+    /// Returns the wrapped automaton
+    pub fn automata(&self) -> &Automata {
+        &self.automata
+    }
+
+    /// Stamps a pattern onto the grid
+    fn stamp(&mut self, pattern: &RlePattern, x: Option<usize>, y: Option<usize>, clear: bool) {
+        let grid = self.automata.grid_mut();
+        let x = x.unwrap_or(grid.width().saturating_sub(pattern.grid.width()) / 2);
+        let y = y.unwrap_or(grid.height().saturating_sub(pattern.grid.height()) / 2);
+        if clear {
+            grid.clear();
+        }
+        grid.stamp(&pattern.grid, Position::new(x, y));
+    }
+}
+
+/// Reads a rule, boundary or neighborhood given as text or as the matching
+/// class
+fn spec_of<T: PyTypeInfo>(value: &Bound<'_, PyAny>, what: &str) -> PyResult<String> {
+    if value.is_instance_of::<PyString>() || value.is_instance_of::<T>() {
+        Ok(value.str()?.to_string())
+    } else {
+        Err(PyTypeError::new_err(format!("{} must be a str or {}", what, T::NAME)))
+    }
+}
+
+/// Returns the topology of a rule such as `B3/S23:T30,20`, or `boundary`
+/// for a rule without one
+fn edges_of(rule: &str, boundary: &str) -> Result<String> {
+    Ok(match Topology::split_rule(rule)?.1 {
+        Some(topology) => topology.to_string(),
+        None => boundary.to_string(),
+    })
+}
+
+/// Reads an RLE file
+fn read_pattern(path: &str) -> Result<RlePattern> {
+    let text =
+        std::fs::read_to_string(path).map_err(|e| Error::IoError(format!("{}: {}", path, e)))?;
+    RlePattern::parse(&text)
+}
+
+#[pymethods]
+impl PyAutomata {
+    /// Builds an automaton from a rule, boundary and neighborhood, each
+    /// given as text or as a `Rule`, `Boundary` or `Neighborhood`
+    ///
+    /// A rule with a topology, such as `B3/S23:K40*,30`, sets the size and
+    /// boundary itself.
+    #[new]
+    #[pyo3(signature = (width=64, height=64, rule=None, boundary=None, neighborhood=None, seed=0))]
+    fn new(
+        width: usize,
+        height: usize,
+        rule: Option<&Bound<'_, PyAny>>,
+        boundary: Option<&Bound<'_, PyAny>>,
+        neighborhood: Option<&Bound<'_, PyAny>>,
+        seed: u64,
+    ) -> PyResult<Self> {
+        let rule = rule.map(|rule| spec_of::<PyRule>(rule, "rule")).transpose()?;
+        let rule = rule.unwrap_or_else(|| "B3/S23".to_string());
+        let boundary = boundary.map(|b| spec_of::<PyBoundary>(b, "boundary")).transpose()?;
+        let boundary = boundary.unwrap_or_else(|| "torus".to_string());
+        let mut automata = Automata::from_spec(&rule, width, height, &boundary)?;
+        if let Some(neighborhood) = neighborhood {
+            automata.set_neighborhood_by_name(&spec_of::<PyNeighborhood>(
+                neighborhood,
+                "neighborhood",
+            )?)?;
+        }
+        automata.set_seed(seed);
+        let boundary = edges_of(&rule, &boundary)?;
+        Ok(Self::wrap(automata, rule, boundary))
+    }
+
+    /// Loads an RLE file into a new automaton with room around the pattern
+    ///
+    /// The rule defaults to the one in the file's header, then to B3/S23.
+    /// Without a topology in the rule, the grid is the pattern plus `margin`
+    /// dead cells on each side.
+    #[staticmethod]
+    #[pyo3(signature = (path, rule=None, margin=16))]
+    fn from_file(path: &str, rule: Option<&Bound<'_, PyAny>>, margin: usize) -> PyResult<Self> {
+        let pattern = read_pattern(path)?;
+        let rule = match rule {
+            Some(rule) => spec_of::<PyRule>(rule, "rule")?,
+            None => pattern.rule.clone().unwrap_or_else(|| "B3/S23".to_string()),
+        };
+        let automata = automata_for(&rule, &pattern.grid, margin)?;
+        let boundary = edges_of(&rule, "plane")?;
+        Ok(Self::wrap(automata, rule, boundary))
+    }
+
+    /// State numbers of the cells as a `(height, width)` array viewing the
+    /// automaton's own storage
+    #[getter]
+    fn grid<'py>(this: Bound<'py, Self>) -> Bound<'py, PyArray2<u8>> {
+        let owner = this.clone().into_any();
+        let automata = this.borrow();
+        let grid = automata.automata.grid();
+        let view = ArrayView2::from_shape((grid.height(), grid.width()), grid.states())
+            .expect("the grid holds one state per cell");
+        // SAFETY: the grid is never resized and steps copy into it rather
+        // than swapping buffers, so its storage lives as long as the
+        // automaton, which the view keeps alive
+        unsafe { PyArray2::borrow_from_array(&view, owner) }
+    }
+
+    #[getter]
+    fn width(&self) -> usize {
+        self.automata.grid().width()
+    }
+
+    #[getter]
+    fn height(&self) -> usize {
+        self.automata.grid().height()
+    }
+
+    #[getter]
+    fn generation(&self) -> u64 {
+        self.automata.generation()
+    }
+
+    /// Number of live cells
+    #[getter]
+    fn population(&self) -> usize {
+        self.automata.grid().count_alive()
+    }
+
+    #[getter]
+    fn rule(&self) -> PyResult<PyRule> {
+        PyRule::new(&self.rule)
+    }
+
+    #[getter]
+    fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    #[getter]
+    fn neighborhood(&self) -> &str {
+        self.automata.neighborhood().name()
+    }
+
+    /// Seed of the random number generator used by stochastic rules
+    #[getter]
+    fn seed(&self) -> u64 {
+        self.automata.seed()
+    }
+
+    #[setter]
+    fn set_seed(&mut self, seed: u64) {
+        self.automata.set_seed(seed);
+    }
+
+    /// Advances one generation
+    fn step(&mut self) {
+        self.step_n(1);
+    }
+
+    /// Advances `steps` generations
+    fn step_n(&mut self, steps: usize) {
+        self.automata.step_n(steps);
+    }
+
+    /// Clears the grid, goes back to generation 0 and restarts the random
+    /// number generator from the seed
+    fn reset(&mut self) {
+        self.automata.reset();
+    }
+
+    /// Kills every cell, keeping the generation
+    fn clear(&mut self) {
+        self.automata.grid_mut().clear();
+    }
+
+    /// Makes each cell alive with the given probability
+    #[pyo3(signature = (probability=0.5))]
+    fn randomize(&mut self, probability: f64) {
+        let (width, height) = (self.width(), self.height());
+        let grid = self.automata.grid_mut();
+        grid.randomize_region(Position::new(0, 0), width, height, probability);
+    }
+
+    /// Switches to another rule, with the neighborhood it expects
+    fn set_rule(&mut self, rule: &Bound<'_, PyAny>) -> PyResult<()> {
+        let spec = spec_of::<PyRule>(rule, "rule")?;
+        let (rule, topology) = Topology::split_rule(&spec)?;
+        if topology.is_some() {
+            return Err(PyValueError::new_err(
+                "a topology sets the grid size, so it can only be given to a new Automata",
+            ));
+        }
+        let parsed = parse_rule(rule)?;
+        self.automata.set_rule(parsed.rule);
+        self.automata.set_neighborhood(parsed.neighborhood);
+        self.rule = spec;
+        Ok(())
+    }
+
+    /// Switches to another edge behavior
+    fn set_boundary(&mut self, boundary: &Bound<'_, PyAny>) -> PyResult<()> {
+        let name = spec_of::<PyBoundary>(boundary, "boundary")?;
+        self.automata.set_boundary(parse_boundary(&name)?);
+        self.boundary = name;
+        Ok(())
+    }
+
+    /// Switches to another neighborhood
+    fn set_neighborhood(&mut self, neighborhood: &Bound<'_, PyAny>) -> PyResult<()> {
+        let name = spec_of::<PyNeighborhood>(neighborhood, "neighborhood")?;
+        self.automata.set_neighborhood_by_name(&name)?;
+        Ok(())
+    }
+
+    /// Places RLE text on the grid, centred unless `x` and `y` give its
+    /// top-left cell, replacing the grid unless `clear` is false
+    #[pyo3(signature = (rle, x=None, y=None, clear=true))]
+    fn place(
+        &mut self,
+        rle: &str,
+        x: Option<usize>,
+        y: Option<usize>,
+        clear: bool,
+    ) -> PyResult<()> {
+        self.stamp(&RlePattern::parse(rle)?, x, y, clear);
+        Ok(())
+    }
+
+    /// Places the pattern of an RLE file on the grid, as [`place`](Self::place)
+    #[pyo3(signature = (path, x=None, y=None, clear=true))]
+    fn load(
+        &mut self,
+        path: &str,
+        x: Option<usize>,
+        y: Option<usize>,
+        clear: bool,
+    ) -> PyResult<()> {
+        self.stamp(&read_pattern(path)?, x, y, clear);
+        Ok(())
+    }
+
+    /// Returns the grid as RLE with the rule in the header
+    #[pyo3(name = "to_rle")]
+    fn rle(&self) -> String {
+        let mut pattern = RlePattern::new(self.automata.grid().clone());
+        pattern.rule = Some(self.rule.clone());
+        pattern.to_rle()
+    }
+
+    /// Writes the grid to an RLE file
+    fn save(&self, path: &str) -> PyResult<()> {
+        let rle = self.rle();
+        std::fs::write(Path::new(path), rle)
+            .map_err(|e| Error::IoError(format!("{}: {}", path, e)))?;
+        Ok(())
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Automata({}x{}, rule='{}', generation={})",
+            self.width(),
+            self.height(),
+            self.rule,
+            self.automata.generation()
+        )
+    }
+}
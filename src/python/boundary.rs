@@ -0,0 +1,36 @@
+use pyo3::prelude::*;
+
+use crate::core::boundary::parse_boundary;
+
+/// An edge behavior, as `cellularity.Boundary("klein")`
+///
+/// Accepts the names [`parse_boundary`] does: `torus`, `plane`, `walled`,
+/// `mirror`, `alive`, `klein`, `cross` and `sphere`.
+#[pyclass(name = "Boundary", module = "cellularity", frozen)]
+#[derive(Debug, Clone)]
+pub struct PyBoundary {
+    name: String,
+}
+
+#[pymethods]
+impl PyBoundary {
+    #[new]
+    pub fn new(name: &str) -> PyResult<Self> {
+        parse_boundary(name)?;
+        Ok(Self { name: name.trim().to_ascii_lowercase() })
+    }
+
+    /// Name of the edge behavior
+    #[getter]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Boundary('{}')", self.name)
+    }
+
+    fn __str__(&self) -> &str {
+        &self.name
+    }
+}
@@ -0,0 +1,34 @@
+//! Python bindings for the simulation core, built with the `python` feature
+//!
+//! The extension module is named `cellularity`; build it with
+//! [maturin](https://www.maturin.rs) as described in `pyproject.toml`.
+
+pub mod automata;
+pub mod boundary;
+pub mod neighborhood;
+pub mod rule;
+
+use pyo3::exceptions::{PyIndexError, PyOSError, PyValueError};
+use pyo3::prelude::*;
+
+use crate::error::Error;
+
+impl From<Error> for PyErr {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::IoError(_) => PyOSError::new_err(err.to_string()),
+            Error::OutOfBounds { .. } => PyIndexError::new_err(err.to_string()),
+            _ => PyValueError::new_err(err.to_string()),
+        }
+    }
+}
+
+/// The `cellularity` Python module
+#[pymodule]
+pub fn cellularity(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<automata::PyAutomata>()?;
+    module.add_class::<boundary::PyBoundary>()?;
+    module.add_class::<neighborhood::PyNeighborhood>()?;
+    module.add_class::<rule::PyRule>()?;
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use pyo3::prelude::*;
+
+use crate::core::lattice::Lattice;
+use crate::core::neighborhood::parse_neighborhood;
+
+/// A neighborhood, as `cellularity.Neighborhood("circular:3")`
+///
+/// Accepts `moore`, `von_neumann` and `circular:<radius>`, plus
+/// `hexagonal` and `triangular`, which also change the lattice of the
+/// automaton they are given to.
+#[pyclass(name = "Neighborhood", module = "cellularity", frozen)]
+#[derive(Debug, Clone)]
+pub struct PyNeighborhood {
+    name: String,
+    size: usize,
+}
+
+#[pymethods]
+impl PyNeighborhood {
+    #[new]
+    pub fn new(name: &str) -> PyResult<Self> {
+        let name = name.trim().to_ascii_lowercase();
+        let neighborhood = match name.as_str() {
+            "hexagonal" => Lattice::Hexagonal.neighborhood(),
+            "triangular" => Lattice::Triangular.neighborhood(),
+            _ => parse_neighborhood(&name)?,
+        };
+        Ok(Self { size: neighborhood.offsets().len(), name })
+    }
+
+    /// Name of the neighborhood
+    #[getter]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Number of neighbors each cell has
+    #[getter]
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Neighborhood('{}')", self.name)
+    }
+
+    fn __str__(&self) -> &str {
+        &self.name
+    }
+}
@@ -0,0 +1,65 @@
+use pyo3::prelude::*;
+
+use crate::core::rules::parse_rule;
+use crate::core::topology::Topology;
+
+/// A rule, as `cellularity.Rule("B36/S23")`
+///
+/// Any notation [`parse_rule`] accepts works, optionally followed by a
+/// Golly topology such as `B3/S23:T80,60`. The rule is checked when it is
+/// built, so a typo fails here rather than when an automaton is made.
+#[pyclass(name = "Rule", module = "cellularity", frozen)]
+#[derive(Debug, Clone)]
+pub struct PyRule {
+    spec: String,
+    name: String,
+    states: u8,
+    description: String,
+}
+
+#[pymethods]
+impl PyRule {
+    #[new]
+    pub fn new(spec: &str) -> PyResult<Self> {
+        let (rule, _) = Topology::split_rule(spec)?;
+        let parsed = parse_rule(rule)?;
+        Ok(Self {
+            spec: spec.trim().to_string(),
+            name: parsed.rule.name().to_string(),
+            states: parsed.rule.states(),
+            description: parsed.rule.description().to_string(),
+        })
+    }
+
+    /// The rule as it was given
+    #[getter]
+    pub fn spec(&self) -> &str {
+        &self.spec
+    }
+
+    /// Display name of the rule
+    #[getter]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Number of states a cell can take
+    #[getter]
+    fn states(&self) -> u8 {
+        self.states
+    }
+
+    /// One-line description of the rule
+    #[getter]
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Rule('{}')", self.spec)
+    }
+
+    fn __str__(&self) -> &str {
+        &self.spec
+    }
+}
@@ -0,0 +1,42 @@
+//! Rules, neighborhoods and automation written in [Rhai](https://rhai.rs),
+//! built with the `scripting` feature
+//!
+//! A script is a set of functions, each optional:
+//!
+//! ```text
+//! fn name() { "HighLife" }
+//! fn description() { "B36/S23, written out by hand" }
+//! fn states() { 2 }
+//! fn apply(state, alive) {
+//!     if state == 0 { alive == 3 || alive == 6 } else { alive == 2 || alive == 3 }
+//! }
+//! fn neighborhood() { "moore" }
+//! fn run(sim) {
+//!     while sim.population >= 100 { sim.step(); }
+//!     sim.stamp("bo$2bo$3o!");
+//! }
+//! ```
+//!
+//! `neighborhood` returns a name or a list of `[dx, dy]` or
+//! `[dx, dy, weight]` offsets. On a weighted neighborhood `alive` is the
+//! sum of the weights of the alive neighbors rather than their count.
+//!
+//! `apply` and `neighborhood` make a [`ScriptRule`](script_rule::ScriptRule)
+//! and a neighborhood, installed by [`Script::install`](script::Script::install);
+//! `run` drives an automaton through a [`Sim`](sim::Sim) handle, started by
+//! [`Script::run`](script::Script::run).
+
+pub mod script;
+pub mod script_rule;
+pub mod sim;
+
+use rhai::EvalAltResult;
+
+use crate::error::Error;
+
+/// Lets functions called from scripts fail with an engine error
+impl From<Error> for Box<EvalAltResult> {
+    fn from(err: Error) -> Self {
+        err.to_string().into()
+    }
+}
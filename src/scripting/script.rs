@@ -0,0 +1,337 @@
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::path::Path;
+use std::rc::Rc;
+
+use rhai::{Array, CallFnOptions, Dynamic, Engine, FuncArgs, Scope, AST};
+
+use crate::core::automata::Automata;
+use crate::core::custom_neighborhood::CustomNeighborhood;
+use crate::core::dead_boundary::DeadBoundary;
+use crate::core::lattice::Lattice;
+use crate::core::moore_neighborhood::MooreNeighborhood;
+use crate::core::neighborhood::{parse_neighborhood, Neighborhood};
+use crate::core::rules::IdentityRule;
+use crate::error::{Error, Result};
+use crate::scripting::script_rule::ScriptRule;
+use crate::scripting::sim::Sim;
+
+/// Default number of operations a script may perform per call before it is
+/// stopped, so a loop that never ends cannot hang the app
+///
+/// `run(sim)` may also step at most this many generations.
+pub const DEFAULT_MAX_OPERATIONS: u64 = 1_000_000;
+
+/// A neighborhood returned by a script's `neighborhood()` function
+enum ScriptNeighborhood {
+    /// A name [`Automata::set_neighborhood_by_name`] reads
+    Named(String),
+    /// An explicit list of offsets
+    Custom(CustomNeighborhood),
+}
+
+/// A compiled Rhai script defining a rule, a neighborhood, an automation,
+/// or any mix of them
+///
+/// Statements outside functions run once, when the script is loaded. See
+/// the [module documentation](crate::scripting) for the functions a script
+/// can define.
+pub struct Script {
+    engine: Engine,
+    ast: AST,
+    /// Name of the script, used in error messages and as the default rule
+    /// name
+    name: String,
+    /// Lines printed by the script since the last run
+    output: Rc<RefCell<Vec<String>>>,
+}
+
+impl Script {
+    /// Compiles a script and runs its top-level statements
+    ///
+    /// # Arguments
+    /// * `source` - Rhai source code
+    pub fn parse(source: &str) -> Result<Self> {
+        Self::compile(source, "script")
+    }
+
+    /// Reads and compiles a script file, named after the file
+    ///
+    /// # Arguments
+    /// * `path` - Path to a `.rhai` file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| Error::IoError(format!("{}: {}", path.display(), e)))?;
+        let name = path.file_stem().map_or_else(
+            || path.display().to_string(),
+            |stem| stem.to_string_lossy().into_owned(),
+        );
+        Self::compile(&source, &name)
+    }
+
+    fn compile(source: &str, name: &str) -> Result<Self> {
+        let output = Rc::new(RefCell::new(Vec::new()));
+        let mut engine = Engine::new();
+        engine.set_max_operations(DEFAULT_MAX_OPERATIONS);
+        Sim::register(&mut engine);
+        let printed = Rc::clone(&output);
+        engine.on_print(move |text| printed.borrow_mut().push(text.to_string()));
+        let printed = Rc::clone(&output);
+        engine.on_debug(move |text, _, _| printed.borrow_mut().push(text.to_string()));
+
+        let ast = engine.compile(source).map_err(|e| script_error(name, e))?;
+        engine.run_ast(&ast).map_err(|e| script_error(name, e))?;
+        Ok(Self {
+            engine,
+            ast,
+            name: name.to_string(),
+            output,
+        })
+    }
+
+    /// Returns the name of the script
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets how many operations a script may perform in one call, and how
+    /// many generations `run(sim)` may step; 0 means no limit
+    pub fn set_max_operations(&mut self, operations: u64) {
+        self.engine.set_max_operations(operations);
+    }
+
+    /// Returns true if the script has an `apply(state, alive)` function
+    pub fn defines_rule(&self) -> bool {
+        self.defines("apply", 2)
+    }
+
+    /// Returns true if the script has a `neighborhood()` function
+    pub fn defines_neighborhood(&self) -> bool {
+        self.defines("neighborhood", 0)
+    }
+
+    /// Returns true if the script has a `run(sim)` function
+    pub fn defines_automation(&self) -> bool {
+        self.defines("run", 1)
+    }
+
+    fn defines(&self, name: &str, params: usize) -> bool {
+        self.ast.iter_functions().any(|f| f.name == name && f.params.len() == params)
+    }
+
+    /// Calls a function of the script
+    fn call(&self, name: &str, args: impl FuncArgs) -> Result<Dynamic> {
+        let options = CallFnOptions::new().eval_ast(false);
+        self.engine
+            .call_fn_with_options(options, &mut Scope::new(), &self.ast, name, args)
+            .map_err(|e| script_error(&self.name, e))
+    }
+
+    /// Calls a function of the script that takes no arguments and returns
+    /// text
+    fn call_text(&self, name: &str) -> Result<String> {
+        let value = self.call(name, ())?;
+        let kind = value.type_name();
+        value.into_string().map_err(|_| {
+            script_error(&self.name, format!("{}() returned {}, not a string", name, kind))
+        })
+    }
+
+    /// Builds the script's rule for an unweighted neighborhood of
+    /// `neighbors` cells
+    ///
+    /// `name()`, `description()` and `states()` are optional and default to
+    /// the script's name, a generic description and 2 states. `apply` may
+    /// return a state number or a boolean.
+    pub fn rule(&self, neighbors: usize) -> Result<ScriptRule> {
+        self.weighted_rule(&vec![1; neighbors])
+    }
+
+    /// Builds the script's rule for a neighborhood with the given weights,
+    /// passing `apply` the weighted sum of the alive neighbors
+    ///
+    /// # Arguments
+    /// * `weights` - Weight of each neighbor, as
+    ///   [`Neighborhood::weights`] returns them
+    pub fn weighted_rule(&self, weights: &[i32]) -> Result<ScriptRule> {
+        if !self.defines_rule() {
+            return Err(script_error(&self.name, "no apply(state, alive) function"));
+        }
+        let name = if self.defines("name", 0) {
+            self.call_text("name")?
+        } else {
+            self.name.clone()
+        };
+        let description = if self.defines("description", 0) {
+            self.call_text("description")?
+        } else {
+            format!("Rule defined by the script {}", self.name)
+        };
+        let states = if self.defines("states", 0) {
+            let states = self.call("states", ())?;
+            let number = states.as_int().ok().and_then(|n| u8::try_from(n).ok());
+            number.ok_or_else(|| script_error(&self.name, format!("states() returned {}", states)))?
+        } else {
+            2
+        };
+        ScriptRule::tabulate(name, description, states, weights, |state, alive| {
+            let next = self.call("apply", (state as i64, alive))?;
+            let number = match next.as_bool() {
+                Ok(alive) => Some(alive as i64),
+                Err(_) => next.as_int().ok(),
+            };
+            number.and_then(|number| u8::try_from(number).ok()).ok_or_else(|| {
+                let message = format!("apply({}, {}) returned {}", state, alive, next);
+                script_error(&self.name, message)
+            })
+        })
+    }
+
+    /// Reads the script's `neighborhood()` function
+    fn neighborhood(&self) -> Result<ScriptNeighborhood> {
+        let value = self.call("neighborhood", ())?;
+        if value.is_string() {
+            let name = value.into_string().expect("checked to be a string");
+            return Ok(ScriptNeighborhood::Named(name));
+        }
+        let invalid = || {
+            script_error(
+                &self.name,
+                "neighborhood() must return a name or an array of [dx, dy] or \
+                 [dx, dy, weight] offsets",
+            )
+        };
+        let offsets = value.try_cast::<Array>().ok_or_else(invalid)?;
+        let weighted = offsets
+            .into_iter()
+            .map(|offset| {
+                let numbers = offset.try_cast::<Array>().ok_or_else(invalid)?;
+                let numbers: Vec<i64> = numbers
+                    .iter()
+                    .map(|number| number.as_int().map_err(|_| invalid()))
+                    .collect::<Result<_>>()?;
+                match numbers[..] {
+                    [dx, dy] => Ok(((dx as isize, dy as isize), 1)),
+                    [dx, dy, weight] => Ok(((dx as isize, dy as isize), weight as i32)),
+                    _ => Err(invalid()),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let neighborhood = CustomNeighborhood::from_weighted_offsets(weighted);
+        Ok(ScriptNeighborhood::Custom(neighborhood.with_name(self.name.clone())))
+    }
+
+    /// Installs the script's neighborhood and rule on an automaton
+    ///
+    /// The rule is built for the neighborhood the automaton ends up with,
+    /// including its weights. Nothing changes if the script fails.
+    pub fn install(&self, automata: &mut Automata) -> Result<()> {
+        if !self.defines_rule() && !self.defines_neighborhood() {
+            return Err(script_error(
+                &self.name,
+                "no apply(state, alive) or neighborhood() function",
+            ));
+        }
+        let neighborhood =
+            if self.defines_neighborhood() { Some(self.neighborhood()?) } else { None };
+        let rule = if self.defines_rule() {
+            let weights = match &neighborhood {
+                Some(ScriptNeighborhood::Named(name)) => {
+                    weights_of(named_neighborhood(name)?.as_ref())
+                }
+                Some(ScriptNeighborhood::Custom(custom)) => weights_of(custom),
+                None => weights_of(automata.neighborhood()),
+            };
+            Some(self.weighted_rule(&weights)?)
+        } else {
+            None
+        };
+
+        match neighborhood {
+            Some(ScriptNeighborhood::Named(name)) => {
+                // The new rule goes in first, as the old one may not accept
+                // the new neighborhood
+                let previous = rule.map(|rule| automata.replace_rule(Box::new(rule)));
+                if let Err(err) = automata.set_neighborhood_by_name(&name) {
+                    if let Some(previous) = previous {
+                        automata.set_rule(previous);
+                    }
+                    return Err(err);
+                }
+            }
+            Some(ScriptNeighborhood::Custom(custom)) => {
+                if rule.is_none() {
+                    automata.rule().check_neighborhood(&custom)?;
+                }
+                // The rule is set aside while passing through the square
+                // lattice's own neighborhood, which it need not accept
+                let previous = automata.replace_rule(Box::new(IdentityRule::new()));
+                automata
+                    .set_lattice(Lattice::Square)
+                    .expect("any grid suits the square lattice");
+                automata.set_neighborhood(Box::new(custom));
+                automata.set_rule(rule.map_or(previous, |rule| Box::new(rule)));
+            }
+            None => {
+                if let Some(rule) = rule {
+                    automata.set_rule(Box::new(rule));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Calls the script's `run(sim)` function on an automaton
+    ///
+    /// Returns the lines the script printed. The automaton keeps whatever
+    /// the script did before an error.
+    pub fn run(&self, automata: &mut Automata) -> Result<Vec<String>> {
+        if !self.defines_automation() {
+            return Err(script_error(&self.name, "no run(sim) function"));
+        }
+        self.output.borrow_mut().clear();
+        let shared = Rc::new(RefCell::new(std::mem::replace(automata, placeholder())));
+        let sim = Sim::new(Rc::clone(&shared), self.engine.max_operations());
+        let result = self.call("run", (sim,));
+        *automata = Rc::try_unwrap(shared)
+            .map(RefCell::into_inner)
+            .unwrap_or_else(|shared| shared.replace(placeholder()));
+        result.map(|_| self.output.take())
+    }
+}
+
+/// Wraps a Rhai parse or evaluation error
+fn script_error(name: &str, err: impl Display) -> Error {
+    Error::ScriptError(format!("{}: {}", name, err))
+}
+
+/// Builds a neighborhood from a name as
+/// [`Automata::set_neighborhood_by_name`] reads it
+fn named_neighborhood(name: &str) -> Result<Box<dyn Neighborhood>> {
+    match name.trim().to_ascii_lowercase().as_str() {
+        "hexagonal" => Ok(Lattice::Hexagonal.neighborhood()),
+        "triangular" => Ok(Lattice::Triangular.neighborhood()),
+        _ => parse_neighborhood(name),
+    }
+}
+
+/// Returns the weight of each neighbor, 1 for an unweighted neighborhood
+fn weights_of(neighborhood: &dyn Neighborhood) -> Vec<i32> {
+    match neighborhood.weights() {
+        Some(weights) => weights.to_vec(),
+        None => vec![1; neighborhood.offsets().len()],
+    }
+}
+
+/// Stands in for an automaton while a script holds it
+fn placeholder() -> Automata {
+    Automata::new(
+        1,
+        1,
+        Box::new(IdentityRule::new()),
+        Box::new(DeadBoundary::new()),
+        Box::new(MooreNeighborhood::new()),
+    )
+    .expect("a 1x1 grid is valid")
+}
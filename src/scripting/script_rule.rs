@@ -0,0 +1,146 @@
+use std::ops::RangeInclusive;
+
+use crate::core::cell::CellState;
+use crate::core::neighborhood::Neighborhood;
+use crate::core::rules::{Neighbors, Rule};
+use crate::error::{Error, Result};
+
+/// Largest number of neighbor sums a [`ScriptRule`] tabulates, so that
+/// large weights cannot make a script run for ages
+pub const MAX_SUMS: usize = 4096;
+
+/// A totalistic rule whose transitions come from a script's `apply`
+/// function
+///
+/// The script is called once for every state and neighbor sum when the
+/// rule is built, so `apply` must depend only on its arguments; stepping
+/// then reads the table and never calls back into the script. On a
+/// weighted neighborhood the sum is the neighbors'
+/// [`weighted_sum`](Neighbors::weighted_sum), otherwise it is the alive
+/// count.
+#[derive(Debug, Clone)]
+pub struct ScriptRule {
+    name: String,
+    description: String,
+    states: u8,
+    /// Smallest and largest neighbor sum in the table
+    sums: RangeInclusive<i64>,
+    /// Next state for each current state and neighbor sum, indexed by
+    /// `state * sums.count() + (sum - sums.start())`
+    table: Vec<CellState>,
+}
+
+impl ScriptRule {
+    /// Builds the transition table by calling `apply` for every state and
+    /// every neighbor sum the weights allow
+    ///
+    /// # Arguments
+    /// * `name` - Name of the rule
+    /// * `description` - Description of the rule
+    /// * `states` - Number of states, from 2 to 255
+    /// * `weights` - Weight of each neighbor of the neighborhood the rule
+    ///   runs on, all 1 for an unweighted one
+    /// * `apply` - Next state given the current state and the neighbor sum
+    pub fn tabulate(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        states: u8,
+        weights: &[i32],
+        mut apply: impl FnMut(u8, i64) -> Result<u8>,
+    ) -> Result<Self> {
+        if states < 2 {
+            return Err(Error::ScriptError(format!(
+                "a rule needs at least 2 states, got {}",
+                states
+            )));
+        }
+        let sums = sum_range(weights);
+        let (low, high) = (*sums.start(), *sums.end());
+        if high - low >= MAX_SUMS as i64 {
+            return Err(Error::ScriptError(format!(
+                "neighbor sums from {} to {} are more than {} to tabulate",
+                low, high, MAX_SUMS
+            )));
+        }
+        let mut table = Vec::with_capacity(states as usize * sums.clone().count());
+        for state in 0..states {
+            for alive in sums.clone() {
+                let next = apply(state, alive)?;
+                if next >= states {
+                    return Err(Error::ScriptError(format!(
+                        "apply({}, {}) returned {}, but the rule has {} states",
+                        state, alive, next, states
+                    )));
+                }
+                table.push(CellState::from_index(next));
+            }
+        }
+        Ok(Self {
+            name: name.into(),
+            description: description.into(),
+            states,
+            sums,
+            table,
+        })
+    }
+
+    /// Returns the smallest and largest neighbor sum the table covers
+    ///
+    /// [`check_neighborhood`](Rule::check_neighborhood) refuses
+    /// neighborhoods whose sums fall outside it.
+    pub fn sums(&self) -> RangeInclusive<i64> {
+        self.sums.clone()
+    }
+}
+
+/// Returns the smallest and largest sum of a subset of the weights
+fn sum_range(weights: &[i32]) -> RangeInclusive<i64> {
+    let low: i64 = weights.iter().map(|&w| w.min(0) as i64).sum();
+    let high: i64 = weights.iter().map(|&w| w.max(0) as i64).sum();
+    low..=high
+}
+
+impl Rule for ScriptRule {
+    fn apply(&self, current_state: CellState, alive_neighbors: usize) -> CellState {
+        self.apply_neighbors(current_state, &Neighbors::new(alive_neighbors))
+    }
+
+    fn apply_neighbors(&self, current_state: CellState, neighbors: &Neighbors) -> CellState {
+        let (low, high) = (*self.sums.start(), *self.sums.end());
+        let state = current_state.index().min(self.states - 1) as usize;
+        let sum = neighbors.weighted_sum.clamp(low, high);
+        self.table[state * (high - low + 1) as usize + (sum - low) as usize]
+    }
+
+    fn check_neighborhood(&self, neighborhood: &dyn Neighborhood) -> Result<()> {
+        let sums = match neighborhood.weights() {
+            Some(weights) => sum_range(weights),
+            None => 0..=neighborhood.offsets().len() as i64,
+        };
+        if sums.start() < self.sums.start() || sums.end() > self.sums.end() {
+            return Err(Error::ScriptError(format!(
+                "{} was tabulated for neighbor sums {} to {}, not {} to {} as on {}; \
+                 install the script again",
+                self.name,
+                self.sums.start(),
+                self.sums.end(),
+                sums.start(),
+                sums.end(),
+                neighborhood.name()
+            )));
+        }
+        Ok(())
+    }
+
+    fn states(&self) -> u8 {
+        self.states
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+}
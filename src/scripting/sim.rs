@@ -0,0 +1,135 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use rhai::{Engine, EvalAltResult};
+
+use crate::core::automata::Automata;
+use crate::core::cell::{CellState, Position};
+use crate::core::grid::Grid;
+use crate::core::rle::RlePattern;
+use crate::core::rules::parse_rule;
+
+/// Result of a function called from a script
+type ScriptResult<T> = std::result::Result<T, Box<EvalAltResult>>;
+
+/// The automaton as an automation script sees it, passed to `fn run(sim)`
+///
+/// Scripts read `sim.width`, `sim.height`, `sim.generation` and
+/// `sim.population`, and call `sim.step()`, `sim.step(n)`,
+/// `sim.get(x, y)`, `sim.set(x, y, state)`, `sim.clear()`,
+/// `sim.randomize(p)`, `sim.stamp(rle)` (centred), `sim.stamp(rle, x, y)`
+/// and `sim.set_rule(rule)`.
+///
+/// A call to `sim.step(n)` is a single script operation, so the
+/// generations a script steps are limited separately, by the same budget.
+#[derive(Clone)]
+pub struct Sim {
+    automata: Rc<RefCell<Automata>>,
+    /// Generations the script may still step; `None` means no limit
+    steps_left: Rc<Cell<Option<u64>>>,
+}
+
+impl Sim {
+    /// Shares an automaton with a script
+    ///
+    /// # Arguments
+    /// * `automata` - The automaton the script drives
+    /// * `max_steps` - Generations the script may step in all; 0 means no
+    ///   limit
+    pub fn new(automata: Rc<RefCell<Automata>>, max_steps: u64) -> Self {
+        let steps_left = (max_steps > 0).then_some(max_steps);
+        Self {
+            automata,
+            steps_left: Rc::new(Cell::new(steps_left)),
+        }
+    }
+
+    /// Registers the `Sim` type and its methods with an engine
+    pub fn register(engine: &mut Engine) {
+        engine
+            .register_type_with_name::<Sim>("Sim")
+            .register_get("width", |sim: &mut Sim| sim.automata.borrow().grid().width() as i64)
+            .register_get("height", |sim: &mut Sim| sim.automata.borrow().grid().height() as i64)
+            .register_get("generation", |sim: &mut Sim| sim.automata.borrow().generation() as i64)
+            .register_get("population", |sim: &mut Sim| {
+                sim.automata.borrow().grid().count_alive() as i64
+            })
+            .register_fn("step", |sim: &mut Sim| sim.step_n(1))
+            .register_fn("step", Sim::step_n)
+            .register_fn("get", Sim::get)
+            .register_fn("set", Sim::set)
+            .register_fn("clear", |sim: &mut Sim| sim.automata.borrow_mut().grid_mut().clear())
+            .register_fn("randomize", Sim::randomize)
+            .register_fn("stamp", |sim: &mut Sim, rle: &str| sim.stamp(rle, None))
+            .register_fn("stamp", |sim: &mut Sim, rle: &str, x: i64, y: i64| {
+                sim.stamp(rle, Some((x, y)))
+            })
+            .register_fn("set_rule", Sim::set_rule);
+    }
+
+    fn step_n(&mut self, steps: i64) -> ScriptResult<()> {
+        let steps = usize::try_from(steps).map_err(|_| format!("cannot step {} times", steps))?;
+        if let Some(left) = self.steps_left.get() {
+            let left = left.checked_sub(steps as u64).ok_or_else(|| {
+                format!("cannot step {} more times: too many steps", steps)
+            })?;
+            self.steps_left.set(Some(left));
+        }
+        self.automata.borrow_mut().step_n(steps);
+        Ok(())
+    }
+
+    fn get(&mut self, x: i64, y: i64) -> ScriptResult<i64> {
+        let state = self.automata.borrow().grid().get(position(x, y)?)?;
+        Ok(state.index() as i64)
+    }
+
+    fn set(&mut self, x: i64, y: i64, state: i64) -> ScriptResult<()> {
+        let state = u8::try_from(state).map_err(|_| format!("invalid state {}", state))?;
+        let mut automata = self.automata.borrow_mut();
+        automata.grid_mut().set(position(x, y)?, CellState::from_index(state))?;
+        Ok(())
+    }
+
+    fn randomize(&mut self, probability: f64) {
+        let mut automata = self.automata.borrow_mut();
+        let grid = automata.grid_mut();
+        let (width, height) = (grid.width(), grid.height());
+        grid.randomize_region(Position::new(0, 0), width, height, probability);
+    }
+
+    /// Draws the live cells of an RLE pattern, centred unless given its
+    /// top-left cell
+    fn stamp(&mut self, rle: &str, at: Option<(i64, i64)>) -> ScriptResult<()> {
+        let pattern = RlePattern::parse(rle)?.grid;
+        let mut automata = self.automata.borrow_mut();
+        let grid = automata.grid_mut();
+        let origin = match at {
+            Some((x, y)) => position(x, y)?,
+            None => Position::new(
+                grid.width().saturating_sub(pattern.width()) / 2,
+                grid.height().saturating_sub(pattern.height()) / 2,
+            ),
+        };
+        grid.stamp(&pattern, origin);
+        Ok(())
+    }
+
+    /// Switches to a rule in any notation [`parse_rule`] reads, with its
+    /// neighborhood
+    fn set_rule(&mut self, rule: &str) -> ScriptResult<()> {
+        let parsed = parse_rule(rule)?;
+        let mut automata = self.automata.borrow_mut();
+        automata.set_rule(parsed.rule);
+        automata.set_neighborhood(parsed.neighborhood);
+        Ok(())
+    }
+}
+
+/// Converts script coordinates, which may be negative, to a position
+fn position(x: i64, y: i64) -> ScriptResult<Position> {
+    match (usize::try_from(x), usize::try_from(y)) {
+        (Ok(x), Ok(y)) => Ok(Position::new(x, y)),
+        _ => Err(format!("invalid cell ({}, {})", x, y).into()),
+    }
+}
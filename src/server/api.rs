@@ -0,0 +1,307 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::core::cell::{CellState, Position};
+use crate::core::dense_grid::DenseGrid;
+use crate::core::grid::Grid;
+use crate::core::rle::RlePattern;
+use crate::server::session::{check_size, Session, SessionConfig, MAX_CELLS};
+
+/// Largest number of generations one step request may run
+pub const MAX_STEPS: u64 = 1_000_000;
+
+/// Largest number of cell updates, generations times grid cells, one step
+/// request may run
+///
+/// Requests are answered one at a time, so this keeps a single request from
+/// stalling every other session and stream for long.
+pub const MAX_CELL_UPDATES: u64 = 1 << 28;
+
+/// Largest number of sessions that may exist at once
+pub const MAX_SESSIONS: usize = 64;
+
+/// An HTTP response produced by [`Api::handle`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    /// HTTP status code
+    pub status: u16,
+    /// Value of the `Content-Type` header
+    pub content_type: &'static str,
+    /// Response body
+    pub body: String,
+}
+
+impl Response {
+    /// Builds a JSON response
+    pub fn json(status: u16, value: &Value) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body: value.to_string(),
+        }
+    }
+
+    /// Builds a JSON error response of the form `{"error": "..."}`
+    pub fn error(status: u16, message: impl Into<String>) -> Self {
+        Self::json(status, &json!({ "error": message.into() }))
+    }
+
+    /// Builds a plain text response
+    pub fn text(status: u16, body: String) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body,
+        }
+    }
+}
+
+/// A grid as JSON: its size and the state number of every cell, row by row
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct GridJson {
+    width: usize,
+    height: usize,
+    cells: Vec<Vec<u8>>,
+}
+
+/// The JSON API behind the server, independent of any socket
+///
+/// Sessions are numbered from 1 and live until they are deleted:
+///
+/// | Method   | Path                        | Action                               |
+/// |----------|-----------------------------|--------------------------------------|
+/// | `GET`    | `/sessions`                 | List sessions                        |
+/// | `POST`   | `/sessions`                 | Create a session from a [`SessionConfig`] |
+/// | `GET`    | `/sessions/{id}`            | Describe a session                   |
+/// | `DELETE` | `/sessions/{id}`            | Delete a session                     |
+/// | `PUT`    | `/sessions/{id}/pattern`    | Upload RLE or a JSON grid            |
+/// | `POST`   | `/sessions/{id}/step`       | Run `?steps=N` generations (default 1) |
+/// | `POST`   | `/sessions/{id}/reset`      | Clear the grid and go to generation 0 |
+/// | `GET`    | `/sessions/{id}/population` | Generation and live cell count       |
+/// | `GET`    | `/sessions/{id}/grid`       | Download as `?format=json` or `rle`  |
+///
+/// Patterns are centred unless `?x=` and `?y=` give their top-left cell,
+/// and replace the grid unless `?clear=false`. At most [`MAX_SESSIONS`]
+/// sessions exist at once, and one step request runs at most
+/// [`MAX_CELL_UPDATES`] cell updates.
+#[derive(Default)]
+pub struct Api {
+    sessions: BTreeMap<u64, Session>,
+    next_id: u64,
+}
+
+impl Api {
+    /// Creates an API with no sessions
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of open sessions
+    pub fn session_count(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Returns a session by id
+    pub fn session(&self, id: u64) -> Option<&Session> {
+        self.sessions.get(&id)
+    }
+
+    /// Returns a session by id, for changing it
+    pub fn session_mut(&mut self, id: u64) -> Option<&mut Session> {
+        self.sessions.get_mut(&id)
+    }
+
+    /// Answers one request
+    ///
+    /// # Arguments
+    /// * `method` - HTTP method, e.g. `GET`
+    /// * `url` - Path with an optional query string, e.g. `/sessions/1/step?steps=10`
+    /// * `body` - Request body
+    pub fn handle(&mut self, method: &str, url: &str, body: &str) -> Response {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let query = parse_query(query);
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        match (method, segments.as_slice()) {
+            ("GET", []) => Response::json(200, &json!({ "sessions": "/sessions" })),
+            ("GET", ["sessions"]) => {
+                let list: Vec<Value> =
+                    self.sessions.iter().map(|(&id, session)| session.info(id)).collect();
+                Response::json(200, &Value::Array(list))
+            }
+            ("POST", ["sessions"]) => self.create(body),
+            (_, ["sessions", id, rest @ ..]) => {
+                let Some(id) = id.parse::<u64>().ok().filter(|id| self.sessions.contains_key(id))
+                else {
+                    return Response::error(404, format!("no session '{}'", id));
+                };
+                if let ("DELETE", []) = (method, rest) {
+                    self.sessions.remove(&id);
+                    return Response::json(200, &json!({ "deleted": id }));
+                }
+                let session = self.sessions.get_mut(&id).expect("session exists");
+                Self::handle_session(session, id, method, rest, &query, body)
+            }
+            _ => Response::error(404, format!("no endpoint {} {}", method, path)),
+        }
+    }
+
+    /// Creates a session from a JSON config, or the default one for an
+    /// empty body
+    fn create(&mut self, body: &str) -> Response {
+        if self.sessions.len() >= MAX_SESSIONS {
+            return Response::error(
+                429,
+                format!("at most {} sessions; delete one first", MAX_SESSIONS),
+            );
+        }
+        let config = if body.trim().is_empty() {
+            Ok(SessionConfig::default())
+        } else {
+            serde_json::from_str::<SessionConfig>(body)
+        };
+        let session = match config {
+            Ok(config) => Session::new(config),
+            Err(err) => return Response::error(400, format!("invalid session config: {}", err)),
+        };
+        match session {
+            Ok(session) => {
+                self.next_id += 1;
+                let id = self.next_id;
+                let info = session.info(id);
+                self.sessions.insert(id, session);
+                Response::json(201, &info)
+            }
+            Err(err) => Response::error(400, err.to_string()),
+        }
+    }
+
+    /// Answers a request addressed to one session
+    fn handle_session(
+        session: &mut Session,
+        id: u64,
+        method: &str,
+        rest: &[&str],
+        query: &BTreeMap<String, String>,
+        body: &str,
+    ) -> Response {
+        match (method, rest) {
+            ("GET", []) => Response::json(200, &session.info(id)),
+            ("GET", ["population"]) => Response::json(200, &session.population()),
+            ("POST", ["step"]) => {
+                let grid = session.automata.grid();
+                let cells = (grid.width() * grid.height()) as u64;
+                let max_steps = MAX_STEPS.min(MAX_CELL_UPDATES / cells);
+                let steps = match query.get("steps").map(|steps| steps.parse::<u64>()) {
+                    None => 1,
+                    Some(Ok(steps)) if steps <= max_steps => steps,
+                    Some(_) => {
+                        return Response::error(
+                            400,
+                            format!("steps must be 0 to {} for this grid size", max_steps),
+                        )
+                    }
+                };
+                for _ in 0..steps {
+                    session.automata.step();
+                }
+                Response::json(200, &session.population())
+            }
+            ("POST", ["reset"]) => {
+                session.automata.reset();
+                Response::json(200, &session.population())
+            }
+            ("PUT" | "POST", ["pattern"]) => match Self::upload(session, query, body) {
+                Ok(()) => Response::json(200, &session.info(id)),
+                Err(message) => Response::error(400, message),
+            },
+            ("GET", ["grid"]) => match query.get("format").map(String::as_str) {
+                None | Some("json") => Response::json(200, &grid_json(session.automata.grid())),
+                Some("rle") => {
+                    let mut pattern = RlePattern::new(session.automata.grid().clone());
+                    pattern.rule = Some(session.config.rule.clone());
+                    Response::text(200, pattern.to_rle())
+                }
+                Some(other) => Response::error(400, format!("unknown format '{}'", other)),
+            },
+            _ => Response::error(404, format!("no endpoint {} for sessions", method)),
+        }
+    }
+
+    /// Places an uploaded RLE pattern or JSON grid on a session's grid
+    fn upload(
+        session: &mut Session,
+        query: &BTreeMap<String, String>,
+        body: &str,
+    ) -> Result<(), String> {
+        let pattern = if body.trim_start().starts_with('{') {
+            let grid: GridJson = serde_json::from_str(body).map_err(|e| e.to_string())?;
+            grid_from_json(&grid)?
+        } else {
+            RlePattern::parse_with_limit(body, MAX_CELLS).map_err(|e| e.to_string())?.grid
+        };
+
+        let grid = session.automata.grid();
+        let coordinate = |name: &str, centred: usize| match query.get(name) {
+            Some(value) => {
+                value.parse::<usize>().map_err(|_| format!("invalid {} '{}'", name, value))
+            }
+            None => Ok(centred),
+        };
+        let x = coordinate("x", grid.width().saturating_sub(pattern.width()) / 2)?;
+        let y = coordinate("y", grid.height().saturating_sub(pattern.height()) / 2)?;
+        let clear = query.get("clear").is_none_or(|value| value != "false");
+
+        let grid = session.automata.grid_mut();
+        if clear {
+            grid.clear();
+        }
+        grid.stamp(&pattern, Position::new(x, y));
+        Ok(())
+    }
+}
+
+/// Encodes a grid as `{"width", "height", "cells"}` with rows of state
+/// numbers
+fn grid_json(grid: &DenseGrid) -> Value {
+    let cells: Vec<Vec<u8>> = (0..grid.height())
+        .map(|y| {
+            (0..grid.width())
+                .map(|x| grid.get(Position::new(x, y)).map_or(0, |state| state.index()))
+                .collect()
+        })
+        .collect();
+    json!({ "width": grid.width(), "height": grid.height(), "cells": cells })
+}
+
+/// Decodes a grid sent in the form [`grid_json`] produces
+///
+/// The size is checked against the cells and [`MAX_CELLS`] before the grid
+/// is allocated.
+fn grid_from_json(json: &GridJson) -> Result<DenseGrid, String> {
+    if json.cells.len() != json.height || json.cells.iter().any(|row| row.len() != json.width) {
+        return Err("cells must have 'height' rows of 'width' states".to_string());
+    }
+    check_size(json.width, json.height).map_err(|e| e.to_string())?;
+    let mut grid = DenseGrid::new(json.width, json.height).map_err(|e| e.to_string())?;
+    for (y, row) in json.cells.iter().enumerate() {
+        for (x, &state) in row.iter().enumerate() {
+            grid.set(Position::new(x, y), CellState::from_index(state)).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(grid)
+}
+
+/// Splits a query string such as `steps=10&format=rle` into its pairs
+fn parse_query(query: &str) -> BTreeMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key.to_string(), value.to_string())
+        })
+        .collect()
+}
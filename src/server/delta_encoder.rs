@@ -0,0 +1,91 @@
+use crate::core::dense_grid::DenseGrid;
+use crate::core::grid::Grid;
+use crate::server::stream_frame::StreamFrame;
+
+/// Turns successive grids of a simulation into the frames of a stream
+///
+/// Each delta is taken against the last grid actually sent, not the last
+/// generation, so a sender that skips generations for a slow receiver
+/// still produces a correct stream: the skipped changes fold into the next
+/// delta. A frame is sent whenever the grid or the generation changed, so
+/// edits that keep the generation, such as an uploaded pattern, still
+/// reach receivers. A keyframe is sent first, after every
+/// `keyframe_interval` deltas, when asked for, when the generation went
+/// back (after a reset), and whenever it would be smaller than the delta.
+#[derive(Debug, Clone)]
+pub struct DeltaEncoder {
+    /// Deltas sent between keyframes
+    pub keyframe_interval: usize,
+    last_sent: Option<(u64, DenseGrid)>,
+    deltas_since_keyframe: usize,
+    keyframe_requested: bool,
+}
+
+impl DeltaEncoder {
+    /// Creates an encoder that sends a keyframe at least every
+    /// `keyframe_interval` frames
+    ///
+    /// # Arguments
+    /// * `keyframe_interval` - Deltas between keyframes, 0 to only send
+    ///   keyframes when needed
+    pub fn new(keyframe_interval: usize) -> Self {
+        Self {
+            keyframe_interval,
+            last_sent: None,
+            deltas_since_keyframe: 0,
+            keyframe_requested: false,
+        }
+    }
+
+    /// Makes the next frame a keyframe, e.g. when a receiver lost track
+    pub fn request_keyframe(&mut self) {
+        self.keyframe_requested = true;
+    }
+
+    /// Returns the generation of the last frame sent
+    pub fn last_generation(&self) -> Option<u64> {
+        self.last_sent.as_ref().map(|(generation, _)| *generation)
+    }
+
+    /// Returns the frame bringing a receiver to `grid`, or None if it is
+    /// already there: same generation and same cells
+    ///
+    /// # Arguments
+    /// * `grid` - Current grid of the simulation
+    /// * `generation` - Generation the grid is at
+    pub fn encode(&mut self, grid: &DenseGrid, generation: u64) -> Option<StreamFrame> {
+        let delta = match &self.last_sent {
+            _ if self.keyframe_requested => None,
+            Some((sent, previous)) if *sent == generation && previous == grid => return None,
+            Some((sent, previous)) if *sent <= generation => {
+                StreamFrame::delta(previous, grid, generation).ok()
+            }
+            _ => None,
+        };
+        let due =
+            self.keyframe_interval > 0 && self.deltas_since_keyframe >= self.keyframe_interval;
+        let frame = match delta {
+            // A change costs about two bytes against roughly one per run of
+            // a keyframe, so deltas touching most cells are not worth it
+            Some(StreamFrame::Delta { ref changes, .. })
+                if !due && changes.len() * 4 < grid.width() * grid.height() =>
+            {
+                self.deltas_since_keyframe += 1;
+                delta.expect("matched a delta")
+            }
+            _ => {
+                self.deltas_since_keyframe = 0;
+                self.keyframe_requested = false;
+                StreamFrame::Keyframe { generation, grid: grid.clone() }
+            }
+        };
+        self.last_sent = Some((generation, grid.clone()));
+        Some(frame)
+    }
+}
+
+impl Default for DeltaEncoder {
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
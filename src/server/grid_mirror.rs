@@ -0,0 +1,68 @@
+use crate::core::dense_grid::DenseGrid;
+use crate::core::grid::Grid;
+use crate::error::{Error, Result};
+use crate::server::stream_frame::StreamFrame;
+
+/// A copy of a remote grid kept up to date by applying [`StreamFrame`]s
+///
+/// Deltas are only accepted once a keyframe has arrived, and must follow
+/// the generation the mirror is at.
+#[derive(Debug, Clone, Default)]
+pub struct GridMirror {
+    grid: Option<DenseGrid>,
+    generation: u64,
+}
+
+impl GridMirror {
+    /// Creates a mirror waiting for its first keyframe
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the mirrored grid, or None before the first keyframe
+    pub fn grid(&self) -> Option<&DenseGrid> {
+        self.grid.as_ref()
+    }
+
+    /// Returns the generation of the last frame applied
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Brings the grid to the frame's generation
+    ///
+    /// # Errors
+    /// Returns an error for a delta before any keyframe, a delta going back
+    /// in time, or changes outside the grid. The mirror is left unchanged,
+    /// and a fresh keyframe puts it back in sync.
+    pub fn apply(&mut self, frame: &StreamFrame) -> Result<()> {
+        match frame {
+            StreamFrame::Keyframe { generation, grid } => {
+                self.grid = Some(grid.clone());
+                self.generation = *generation;
+            }
+            StreamFrame::Delta { generation, changes, .. } => {
+                let Some(grid) = &mut self.grid else {
+                    return Err(Error::Other("delta received before a keyframe".to_string()));
+                };
+                if *generation < self.generation {
+                    return Err(Error::Other(format!(
+                        "delta for generation {} after generation {}",
+                        generation, self.generation
+                    )));
+                }
+                let (width, height) = (grid.width(), grid.height());
+                if let Some((pos, _)) =
+                    changes.iter().find(|(pos, _)| !pos.is_within_bounds(width, height))
+                {
+                    return Err(Error::OutOfBounds { x: pos.x, y: pos.y, width, height });
+                }
+                for &(pos, state) in changes {
+                    grid.set(pos, state)?;
+                }
+                self.generation = *generation;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use std::io::Read;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use crate::error::{Error, Result};
+use crate::server::api::{Api, Response};
+
+/// Largest request body the server reads, in bytes
+const MAX_BODY: u64 = 16 * 1024 * 1024;
+
+/// A local HTTP server answering requests with an [`Api`]
+///
+/// Requests are handled one at a time, in the order they arrive.
+pub struct Server {
+    http: tiny_http::Server,
+    api: Arc<Mutex<Api>>,
+}
+
+impl Server {
+    /// Starts listening on an address such as `127.0.0.1:8080`
+    ///
+    /// Port 0 picks a free port; see [`local_addr`](Self::local_addr).
+    pub fn bind(addr: &str) -> Result<Self> {
+        Self::with_api(addr, Arc::new(Mutex::new(Api::new())))
+    }
+
+    /// Starts listening on an address, serving sessions shared with others
+    /// such as a [`StreamServer`](crate::server::stream_server::StreamServer)
+    pub fn with_api(addr: &str, api: Arc<Mutex<Api>>) -> Result<Self> {
+        let http = tiny_http::Server::http(addr)
+            .map_err(|e| Error::IoError(format!("{}: {}", addr, e)))?;
+        Ok(Self { http, api })
+    }
+
+    /// Returns the sessions the server answers for
+    pub fn api(&self) -> Arc<Mutex<Api>> {
+        Arc::clone(&self.api)
+    }
+
+    /// Returns the address the server listens on
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.http.server_addr().to_ip()
+    }
+
+    /// Answers requests until the process ends or [`stop`](Self::stop) is
+    /// called
+    pub fn run(&self) {
+        for request in self.http.incoming_requests() {
+            self.respond(request);
+        }
+    }
+
+    /// Makes [`run`](Self::run) return once the current request is done
+    pub fn stop(&self) {
+        self.http.unblock();
+    }
+
+    /// Reads one request, hands it to the API and sends the answer
+    fn respond(&self, mut request: tiny_http::Request) {
+        let mut body = String::new();
+        let read = request.as_reader().take(MAX_BODY).read_to_string(&mut body);
+        let response = match read {
+            Ok(_) => {
+                let method = request.method().as_str().to_ascii_uppercase();
+                let mut api = self.api.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                api.handle(&method, request.url(), &body)
+            }
+            Err(err) => Response::error(400, format!("unreadable body: {}", err)),
+        };
+        let content_type =
+            tiny_http::Header::from_bytes("Content-Type", response.content_type).expect("valid");
+        let reply = tiny_http::Response::from_string(response.body)
+            .with_status_code(response.status)
+            .with_header(content_type);
+        // The client may already have gone; there is nobody left to tell
+        let _ = request.respond(reply);
+    }
+}
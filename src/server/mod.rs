@@ -0,0 +1,8 @@
+pub mod api;
+pub mod delta_encoder;
+pub mod grid_mirror;
+pub mod http_server;
+pub mod session;
+pub mod stream_client;
+pub mod stream_frame;
+pub mod stream_server;
@@ -0,0 +1,152 @@
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::core::automata::Automata;
+use crate::core::grid::Grid;
+use crate::core::topology::Topology;
+use crate::error::{Error, Result};
+
+pub use crate::core::boundary::parse_boundary;
+pub use crate::core::neighborhood::parse_neighborhood;
+
+/// Largest number of cells a session grid or uploaded pattern may have
+///
+/// Sizes come from clients, so they are checked against this before any
+/// memory is set aside for them.
+pub const MAX_CELLS: usize = 1 << 24;
+
+/// Checks a grid size sent by a client against [`MAX_CELLS`]
+///
+/// # Errors
+/// Returns an error if the grid would have more than [`MAX_CELLS`] cells
+pub fn check_size(width: usize, height: usize) -> Result<()> {
+    if width.checked_mul(height).is_none_or(|cells| cells > MAX_CELLS) {
+        return Err(Error::Other(format!(
+            "a {}x{} grid is larger than {} cells",
+            width, height, MAX_CELLS
+        )));
+    }
+    Ok(())
+}
+
+/// Settings for a new session, as sent to `POST /sessions`
+///
+/// Every field is optional: the default is Conway's Game of Life on a 64 by
+/// 64 torus with the rule's own neighborhood.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SessionConfig {
+    /// Rule in any notation [`parse_rule`] accepts, optionally followed by a
+    /// Golly topology such as `B3/S23:K40*,30`, which sets the size and
+    /// boundary
+    pub rule: String,
+    /// Grid width, ignored if the rule has a topology
+    pub width: usize,
+    /// Grid height, ignored if the rule has a topology
+    pub height: usize,
+    /// Edge behavior, see [`parse_boundary`]; ignored if the rule has a
+    /// topology
+    pub boundary: String,
+    /// Neighborhood replacing the rule's own, see [`parse_neighborhood`]
+    pub neighborhood: Option<String>,
+    /// Seed for stochastic rules
+    pub seed: u64,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            rule: "B3/S23".to_string(),
+            width: 64,
+            height: 64,
+            boundary: "torus".to_string(),
+            neighborhood: None,
+            seed: 0,
+        }
+    }
+}
+
+/// One automaton driven through the server, with the settings it was made
+/// from
+pub struct Session {
+    /// The automaton being run
+    pub automata: Automata,
+    /// Settings the session was created with
+    pub config: SessionConfig,
+    /// When the next timed step is due, while the session is being run
+    next_tick: Option<Instant>,
+}
+
+impl Session {
+    /// Builds the automaton a config describes
+    ///
+    /// # Errors
+    /// Returns an error for unknown rules, boundaries or neighborhoods, for
+    /// sizes the lattice does not allow and for grids of more than
+    /// [`MAX_CELLS`] cells
+    pub fn new(config: SessionConfig) -> Result<Self> {
+        match Topology::split_rule(&config.rule)?.1 {
+            Some(topology) => check_size(topology.width(), topology.height())?,
+            None => check_size(config.width, config.height)?,
+        }
+        let mut automata =
+            Automata::from_spec(&config.rule, config.width, config.height, &config.boundary)?;
+        if let Some(name) = &config.neighborhood {
+            automata.set_neighborhood_by_name(name)?;
+        }
+        automata.set_seed(config.seed);
+        Ok(Self {
+            automata,
+            config,
+            next_tick: None,
+        })
+    }
+
+    /// Runs the generations that fell due since the last call, one every
+    /// `interval`
+    ///
+    /// Every caller shares the same clock, so a session watched by several
+    /// streams still runs at one speed. At most `max_steps` generations are
+    /// run at once; a session that falls further behind skips ahead in time.
+    ///
+    /// # Returns
+    /// The number of generations run
+    pub fn tick(&mut self, now: Instant, interval: Duration, max_steps: u64) -> u64 {
+        let due = *self.next_tick.get_or_insert(now);
+        let mut steps = 0;
+        let mut next = due;
+        while next <= now && steps < max_steps {
+            self.automata.step();
+            steps += 1;
+            next += interval;
+        }
+        self.next_tick = Some(if next <= now { now + interval } else { next });
+        steps
+    }
+
+    /// Returns the generation and number of live cells
+    pub fn population(&self) -> Value {
+        json!({
+            "generation": self.automata.generation(),
+            "population": self.automata.grid().count_alive(),
+        })
+    }
+
+    /// Describes the session for `GET /sessions/{id}`
+    pub fn info(&self, id: u64) -> Value {
+        let grid = self.automata.grid();
+        json!({
+            "id": id,
+            "rule": self.config.rule,
+            "boundary": self.config.boundary,
+            "neighborhood": self.automata.neighborhood().name(),
+            "lattice": self.automata.lattice().name(),
+            "width": grid.width(),
+            "height": grid.height(),
+            "generation": self.automata.generation(),
+            "population": grid.count_alive(),
+        })
+    }
+}
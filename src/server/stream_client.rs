@@ -0,0 +1,92 @@
+use std::net::TcpStream;
+
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Message, WebSocket};
+
+use crate::error::{Error, Result};
+use crate::server::grid_mirror::GridMirror;
+use crate::server::stream_frame::StreamFrame;
+
+/// Connects to a [`StreamServer`](crate::server::stream_server::StreamServer)
+/// and mirrors the session it streams
+pub struct StreamClient {
+    socket: WebSocket<MaybeTlsStream<TcpStream>>,
+    url: String,
+    mirror: GridMirror,
+}
+
+impl StreamClient {
+    /// Connects to a stream URL such as `ws://127.0.0.1:8081/sessions/1`
+    pub fn connect(url: &str) -> Result<Self> {
+        let (socket, _) =
+            tungstenite::connect(url).map_err(|e| Error::IoError(format!("{}: {}", url, e)))?;
+        Ok(Self {
+            socket,
+            url: url.to_string(),
+            mirror: GridMirror::new(),
+        })
+    }
+
+    /// Returns the mirrored session
+    pub fn mirror(&self) -> &GridMirror {
+        &self.mirror
+    }
+
+    /// Waits for the next frame
+    ///
+    /// # Returns
+    /// None once the server has closed the stream
+    pub fn next_frame(&mut self) -> Result<Option<StreamFrame>> {
+        loop {
+            let message = match self.socket.read() {
+                Ok(message) => message,
+                Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => {
+                    return Ok(None)
+                }
+                Err(e) => return Err(self.error(e)),
+            };
+            match message {
+                Message::Text(text) => return StreamFrame::from_json(&text).map(Some),
+                Message::Binary(bytes) => return StreamFrame::from_bytes(&bytes).map(Some),
+                Message::Close(_) => return Ok(None),
+                _ => {}
+            }
+        }
+    }
+
+    /// Waits for the next frame and applies it to the mirror
+    ///
+    /// A frame the mirror cannot apply makes the client ask for a keyframe
+    /// before the error is returned, so later calls get back in sync.
+    ///
+    /// # Returns
+    /// False once the server has closed the stream
+    pub fn update(&mut self) -> Result<bool> {
+        let Some(frame) = self.next_frame()? else {
+            return Ok(false);
+        };
+        if let Err(err) = self.mirror.apply(&frame) {
+            self.request_keyframe()?;
+            return Err(err);
+        }
+        Ok(true)
+    }
+
+    /// Asks the server to send a keyframe next
+    pub fn request_keyframe(&mut self) -> Result<()> {
+        self.socket.send(Message::text("keyframe")).map_err(|e| self.error(e))
+    }
+
+    /// Closes the stream
+    pub fn close(mut self) -> Result<()> {
+        self.socket.close(None).map_err(|e| self.error(e))?;
+        // Read until the server acknowledges the close
+        while self.socket.read().is_ok() {}
+        Ok(())
+    }
+
+    /// Wraps a WebSocket error with the stream URL
+    fn error(&self, err: tungstenite::Error) -> Error {
+        Error::IoError(format!("{}: {}", self.url, err))
+    }
+}
@@ -0,0 +1,289 @@
+use serde_json::{json, Value};
+
+use crate::core::cell::{CellState, Position};
+use crate::core::dense_grid::DenseGrid;
+use crate::core::grid::Grid;
+use crate::core::rle::RlePattern;
+use crate::error::{Error, Result};
+use crate::server::session::MAX_CELLS;
+
+/// Tag of a keyframe in the binary encoding
+const KEYFRAME_TAG: u8 = 0;
+/// Tag of a delta in the binary encoding
+const DELTA_TAG: u8 = 1;
+
+/// One message of a generation stream: a whole grid, or the cells that
+/// changed since the previous message
+///
+/// Frames are encoded as JSON text or as compact binary. In JSON a
+/// keyframe carries the grid as RLE and a delta lists the cells `born`,
+/// the cells that `died` and, for multi-state rules, the cells `changed`
+/// to another state. Both carry the grid size:
+///
+/// ```json
+/// {"type": "keyframe", "generation": 0, "width": 3, "height": 1, "rle": "..."}
+/// {"type": "delta", "generation": 1, "width": 3, "height": 1, "born": [[0, 0]]}
+/// ```
+///
+/// The binary form starts with a tag byte (0 keyframe, 1 delta) and the
+/// generation, width and height as LEB128 varints. A keyframe follows with
+/// runs of `(state byte, varint length)` covering the grid row by row; a
+/// delta with the number of changes, then for each change the distance in
+/// cells from the previous change (varint, in row-major order) and the new
+/// state byte.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreamFrame {
+    /// The whole grid
+    Keyframe { generation: u64, grid: DenseGrid },
+    /// Cells that changed since the previous frame, in row-major order
+    Delta {
+        generation: u64,
+        width: usize,
+        height: usize,
+        changes: Vec<(Position, CellState)>,
+    },
+}
+
+impl StreamFrame {
+    /// Builds the delta that turns `previous` into `current`
+    ///
+    /// # Errors
+    /// Returns an error if the grids differ in size
+    pub fn delta(previous: &DenseGrid, current: &DenseGrid, generation: u64) -> Result<Self> {
+        let (width, height) = (current.width(), current.height());
+        if (previous.width(), previous.height()) != (width, height) {
+            return Err(Error::InvalidDimensions { width, height });
+        }
+        let changes = previous
+            .iter()
+            .zip(current.iter())
+            .filter(|((_, before), (_, after))| before != after)
+            .map(|(_, change)| change)
+            .collect();
+        Ok(StreamFrame::Delta { generation, width, height, changes })
+    }
+
+    /// Returns the generation the frame brings the grid to
+    pub fn generation(&self) -> u64 {
+        match *self {
+            StreamFrame::Keyframe { generation, .. } | StreamFrame::Delta { generation, .. } => {
+                generation
+            }
+        }
+    }
+
+    /// Encodes the frame as JSON
+    pub fn to_json(&self) -> String {
+        match self {
+            StreamFrame::Keyframe { generation, grid } => json!({
+                "type": "keyframe",
+                "generation": generation,
+                "width": grid.width(),
+                "height": grid.height(),
+                "rle": RlePattern::new(grid.clone()).to_rle(),
+            })
+            .to_string(),
+            StreamFrame::Delta { generation, width, height, changes } => {
+                let cells = |wanted: fn(CellState) -> bool| -> Vec<[usize; 2]> {
+                    changes
+                        .iter()
+                        .filter(|(_, state)| wanted(*state))
+                        .map(|(pos, _)| [pos.x, pos.y])
+                        .collect()
+                };
+                let mut frame = json!({
+                    "type": "delta",
+                    "generation": generation,
+                    "width": width,
+                    "height": height,
+                    "born": cells(|state| state.is_alive()),
+                    "died": cells(|state| state.is_dead()),
+                });
+                let changed: Vec<[usize; 3]> = changes
+                    .iter()
+                    .filter(|(_, state)| matches!(state, CellState::State(_)))
+                    .map(|(pos, state)| [pos.x, pos.y, state.index() as usize])
+                    .collect();
+                if !changed.is_empty() {
+                    frame["changed"] = json!(changed);
+                }
+                frame.to_string()
+            }
+        }
+    }
+
+    /// Decodes a frame written by [`to_json`](Self::to_json)
+    pub fn from_json(text: &str) -> Result<Self> {
+        let invalid = |reason: &str| Error::PatternParseError(format!("stream frame: {}", reason));
+        let frame: Value = serde_json::from_str(text).map_err(|e| invalid(&e.to_string()))?;
+        let generation = frame["generation"].as_u64().ok_or_else(|| invalid("no generation"))?;
+        match frame["type"].as_str() {
+            Some("keyframe") => {
+                let rle = frame["rle"].as_str().ok_or_else(|| invalid("no rle"))?;
+                Ok(StreamFrame::Keyframe { generation, grid: RlePattern::parse(rle)?.grid })
+            }
+            Some("delta") => {
+                let size = |key: &str| frame[key].as_u64().ok_or_else(|| invalid(key));
+                let (width, height) = (size("width")? as usize, size("height")? as usize);
+                let mut changes = Vec::new();
+                let lists = [
+                    ("born", Some(CellState::Alive)),
+                    ("died", Some(CellState::Dead)),
+                    ("changed", None),
+                ];
+                for (key, fixed) in lists {
+                    let Some(cells) = frame[key].as_array() else {
+                        continue;
+                    };
+                    for cell in cells {
+                        let number = |i: usize| cell[i].as_u64().ok_or_else(|| invalid(key));
+                        let pos = Position::new(number(0)? as usize, number(1)? as usize);
+                        let state = match fixed {
+                            Some(state) => state,
+                            None => CellState::from_index(
+                                u8::try_from(number(2)?).map_err(|_| invalid("state above 255"))?,
+                            ),
+                        };
+                        changes.push((pos, state));
+                    }
+                }
+                changes.sort_by_key(|(pos, _)| (pos.y, pos.x));
+                Ok(StreamFrame::Delta { generation, width, height, changes })
+            }
+            _ => Err(invalid("unknown type")),
+        }
+    }
+
+    /// Encodes the frame in the compact binary form
+    ///
+    /// # Errors
+    /// Returns an error for a delta whose changes are not inside its grid
+    /// and in row-major order, which the binary form cannot express
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        match self {
+            StreamFrame::Keyframe { generation, grid } => {
+                bytes.push(KEYFRAME_TAG);
+                for value in [*generation, grid.width() as u64, grid.height() as u64] {
+                    write_varint(&mut bytes, value);
+                }
+                let states: Vec<u8> = grid.iter().map(|(_, state)| state.index()).collect();
+                for run in states.chunk_by(|a, b| a == b) {
+                    bytes.push(run[0]);
+                    write_varint(&mut bytes, run.len() as u64);
+                }
+            }
+            StreamFrame::Delta { generation, width, height, changes } => {
+                bytes.push(DELTA_TAG);
+                for value in [*generation, *width as u64, *height as u64, changes.len() as u64] {
+                    write_varint(&mut bytes, value);
+                }
+                let mut previous = 0;
+                for (pos, state) in changes {
+                    let inside = pos.x < *width && pos.y < *height;
+                    let index = if inside { (pos.y * width + pos.x) as u64 } else { 0 };
+                    if !inside || index < previous {
+                        return Err(Error::Other(format!(
+                            "delta change at ({}, {}) is outside the {}x{} grid or out of order",
+                            pos.x, pos.y, width, height
+                        )));
+                    }
+                    write_varint(&mut bytes, index - previous);
+                    bytes.push(state.index());
+                    previous = index;
+                }
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Decodes a frame written by [`to_bytes`](Self::to_bytes)
+    ///
+    /// # Errors
+    /// Returns an error for malformed input, or a grid of more than
+    /// [`MAX_CELLS`] cells, which no session can hold
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = ByteReader { bytes, offset: 1 };
+        let tag = *bytes.first().ok_or_else(|| reader.error("empty"))?;
+        let generation = reader.varint()?;
+        let width = usize::try_from(reader.varint()?).unwrap_or(usize::MAX);
+        let height = usize::try_from(reader.varint()?).unwrap_or(usize::MAX);
+        let cells = width
+            .checked_mul(height)
+            .filter(|&cells| cells <= MAX_CELLS)
+            .ok_or_else(|| reader.error("grid larger than a session can hold"))?;
+        match tag {
+            KEYFRAME_TAG => {
+                let mut grid = DenseGrid::new(width, height)?;
+                let mut index = 0;
+                while index < cells {
+                    let state = CellState::from_index(reader.byte()?);
+                    let run = reader.varint()? as usize;
+                    if run == 0 || run > cells - index {
+                        return Err(reader.error("run past the end of the grid"));
+                    }
+                    for cell in index..index + run {
+                        grid.set(Position::new(cell % width, cell / width), state)?;
+                    }
+                    index += run;
+                }
+                Ok(StreamFrame::Keyframe { generation, grid })
+            }
+            DELTA_TAG => {
+                let count = reader.varint()? as usize;
+                let mut changes = Vec::with_capacity(count.min(bytes.len()));
+                let mut index: usize = 0;
+                for _ in 0..count {
+                    let step = usize::try_from(reader.varint()?).ok();
+                    index = step
+                        .and_then(|step| index.checked_add(step))
+                        .filter(|&index| index < cells)
+                        .ok_or_else(|| reader.error("change past the end of the grid"))?;
+                    let state = CellState::from_index(reader.byte()?);
+                    changes.push((Position::new(index % width, index / width), state));
+                }
+                Ok(StreamFrame::Delta { generation, width, height, changes })
+            }
+            _ => Err(reader.error("unknown tag")),
+        }
+    }
+}
+
+/// Appends an unsigned LEB128 varint
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// Reads bytes and varints from a binary frame
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl ByteReader<'_> {
+    fn error(&self, reason: &str) -> Error {
+        Error::PatternParseError(format!("binary stream frame: {}", reason))
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        let byte = *self.bytes.get(self.offset).ok_or_else(|| self.error("truncated"))?;
+        self.offset += 1;
+        Ok(byte)
+    }
+
+    fn varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(self.error("varint too long"))
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::{Message, WebSocket};
+
+use crate::error::{Error, Result};
+use crate::server::api::Api;
+use crate::server::delta_encoder::DeltaEncoder;
+
+/// Most generations a running stream catches up on at once
+const MAX_CATCH_UP: u64 = 64;
+
+/// Slowest stream interval, in milliseconds
+const MAX_INTERVAL_MS: u64 = 60_000;
+
+/// What a client asked for in the WebSocket URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct StreamRequest {
+    session: u64,
+    binary: bool,
+    keyframe_interval: usize,
+    run: bool,
+    interval: Duration,
+}
+
+impl StreamRequest {
+    /// Parses a path such as `/sessions/1?format=binary&run=true`
+    fn parse(path: &str, query: Option<&str>) -> std::result::Result<Self, String> {
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let session = match segments.as_slice() {
+            ["sessions", id] | ["sessions", id, "stream"] => {
+                id.parse().map_err(|_| format!("no session '{}'", id))?
+            }
+            _ => return Err(format!("no stream at {}; use /sessions/{{id}}", path)),
+        };
+        let mut request = Self {
+            session,
+            binary: false,
+            keyframe_interval: DeltaEncoder::default().keyframe_interval,
+            run: false,
+            interval: Duration::from_millis(50),
+        };
+        let pairs = query.unwrap_or("").split('&').filter_map(|pair| pair.split_once('='));
+        for (key, value) in pairs.collect::<BTreeMap<_, _>>() {
+            let number =
+                || value.parse::<u64>().map_err(|_| format!("invalid {} '{}'", key, value));
+            match key {
+                "format" if value == "json" => request.binary = false,
+                "format" if value == "binary" => request.binary = true,
+                "format" => return Err(format!("unknown format '{}'", value)),
+                "keyframe" => request.keyframe_interval = number()? as usize,
+                "run" => request.run = value == "true" || value == "1",
+                "interval" => {
+                    request.interval = Duration::from_millis(number()?.clamp(1, MAX_INTERVAL_MS))
+                }
+                _ => return Err(format!("unknown option '{}'", key)),
+            }
+        }
+        Ok(request)
+    }
+}
+
+/// A WebSocket server streaming the sessions of an [`Api`] generation by
+/// generation
+///
+/// Clients connect to `ws://host:port/sessions/{id}` and receive a
+/// [`StreamFrame`](crate::server::stream_frame::StreamFrame) whenever the
+/// session's grid changes: a keyframe first, then deltas. Options go in the
+/// query string:
+///
+/// * `format=json` (default) sends text frames, `format=binary` the compact
+///   binary encoding
+/// * `keyframe=N` sends a keyframe after every N deltas (default 100, 0 for
+///   only when needed)
+/// * `run=true` steps the session on its own, one generation per interval
+/// * `interval=ms` sets how often the session is checked or stepped
+///   (default 50)
+///
+/// A client sending the text `keyframe` gets a keyframe next. Each client
+/// is served by its own thread and frames are written as fast as it reads
+/// them: while it lags, changes pile up into the next delta instead of
+/// queueing, so slow clients see fewer, larger frames.
+pub struct StreamServer {
+    listener: TcpListener,
+    api: Arc<Mutex<Api>>,
+}
+
+impl StreamServer {
+    /// Starts listening on an address such as `127.0.0.1:8081`
+    ///
+    /// # Arguments
+    /// * `addr` - Address to listen on; port 0 picks a free port
+    /// * `api` - Sessions to stream, usually shared with an HTTP
+    ///   [`Server`](crate::server::http_server::Server)
+    pub fn bind(addr: &str, api: Arc<Mutex<Api>>) -> Result<Self> {
+        let listener =
+            TcpListener::bind(addr).map_err(|e| Error::IoError(format!("{}: {}", addr, e)))?;
+        Ok(Self { listener, api })
+    }
+
+    /// Returns the address the server listens on
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.listener.local_addr().ok()
+    }
+
+    /// Accepts clients until the process ends, streaming to each on its own
+    /// thread
+    pub fn run(&self) {
+        for stream in self.listener.incoming().flatten() {
+            let api = Arc::clone(&self.api);
+            thread::spawn(move || {
+                // A client that fails the handshake or hangs up is simply gone
+                let _ = serve(stream, &api);
+            });
+        }
+    }
+}
+
+/// Completes the WebSocket handshake and streams one session to the client
+// The handshake callback's error type is set by tungstenite
+#[allow(clippy::result_large_err)]
+fn serve(stream: TcpStream, api: &Mutex<Api>) -> tungstenite::Result<()> {
+    let mut request = None;
+    let mut socket = tungstenite::accept_hdr(stream, |http: &Request, response: Response| {
+        let parsed = StreamRequest::parse(http.uri().path(), http.uri().query());
+        let found = parsed.and_then(|parsed| {
+            let api = api.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            match api.session(parsed.session) {
+                Some(_) => Ok(parsed),
+                None => Err(format!("no session '{}'", parsed.session)),
+            }
+        });
+        match found {
+            Ok(parsed) => {
+                request = Some(parsed);
+                Ok(response)
+            }
+            Err(message) => {
+                let mut error = ErrorResponse::new(Some(message));
+                *error.status_mut() = tungstenite::http::StatusCode::NOT_FOUND;
+                Err(error)
+            }
+        }
+    })
+    .map_err(|e| tungstenite::Error::Io(std::io::Error::other(e.to_string())))?;
+    let request = request.expect("handshake accepted a request");
+    socket.get_mut().set_read_timeout(Some(request.interval))?;
+    stream_session(&mut socket, api, &request)
+}
+
+/// Sends frames until the client leaves or the session is deleted
+fn stream_session(
+    socket: &mut WebSocket<TcpStream>,
+    api: &Mutex<Api>,
+    request: &StreamRequest,
+) -> tungstenite::Result<()> {
+    let mut encoder = DeltaEncoder::new(request.keyframe_interval);
+    loop {
+        let frame = {
+            let mut api = api.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let Some(session) = api.session_mut(request.session) else {
+                socket.close(None)?;
+                return socket.flush();
+            };
+            if request.run {
+                session.tick(Instant::now(), request.interval, MAX_CATCH_UP);
+            }
+            encoder.encode(session.automata.grid(), session.automata.generation())
+        };
+        if let Some(frame) = frame {
+            let message = if request.binary {
+                // Frames from the encoder always match the session's grid
+                let bytes = frame.to_bytes().map_err(|e| std::io::Error::other(e.to_string()))?;
+                Message::binary(bytes)
+            } else {
+                Message::text(frame.to_json())
+            };
+            socket.send(message)?;
+        }
+
+        // Waiting for the client doubles as the pause between frames
+        match socket.read() {
+            Ok(Message::Text(text)) if text.trim() == "keyframe" => encoder.request_keyframe(),
+            Ok(Message::Close(_)) => return socket.flush(),
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
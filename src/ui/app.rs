@@ -3,22 +3,161 @@ use eframe::egui::Color32;
 
 use crate::core::cell::{CellState, Position};
 use crate::core::automata::Automata;
+use crate::core::automata_3d::Automata3D;
+use crate::core::continuous_automata::ContinuousAutomata;
+use crate::core::kernel_neighborhood::KernelNeighborhood;
+use crate::core::dense_grid_3d::{Axis, DenseGrid3D};
+use crate::core::dense_grid::DenseGrid;
 use crate::core::grid::Grid;
-use crate::core::rules::ConwayRule;
+use crate::core::lattice::Lattice;
+use crate::core::rle::RlePattern;
+use crate::core::pattern_library::{LibraryPattern, PatternCategory, PatternLibrary};
+use crate::core::elementary_automata::{EdgeCondition, ElementaryAutomata, SpacetimeDiagram};
+use crate::core::margolus::{BlockRule, MargolusAutomata};
+use crate::core::rules::{
+    parse_rule, ConwayRule, IdentityRule, LeniaRule, Life3DRule, RuleTable, StochasticRule,
+    Rule1D, TotalisticCodeRule, WireworldRule, WolframRule,
+};
+use crate::core::turmite::{Ant, Heading, TurmiteRule};
+use crate::core::update_scheme::UpdateScheme;
 use crate::core::toroidal_boundary::ToroidalBoundary;
+use crate::core::topology::Topology;
+use crate::core::transform::Transform;
+use crate::io::frame_style::FrameStyle;
+use crate::io::image_import::ImageImport;
+use crate::io::recorder::Recorder;
+use crate::io::svg_export::SvgExport;
+#[cfg(feature = "scripting")]
+use crate::scripting::script::Script;
+use crate::core::mixed_boundary::{EdgeBehavior, MixedBoundary};
+use crate::core::constant_boundary::ConstantBoundary;
 use crate::core::moore_neighborhood::MooreNeighborhood;
 
-use super::components::{grid_view::grid_view, ControlPanel, ControlAction};
+use super::components::grid_view::{
+    ant_overlay, grid_view, lattice_grid_view, palette_color, palette_from_rgb, palette_grid_view,
+    pattern_overlay, pixel_to_cell, selection_overlay, state_to_color,
+};
+use super::components::colormap::{colormap_image, Colormap};
+use super::components::voxel_view::isometric_image;
+use super::components::{ControlPanel, ControlAction};
+
+/// Which automaton the app is currently simulating
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SimulationMode {
+    /// Two-dimensional automaton on the main grid
+    Life,
+    /// One-dimensional automaton drawn as a spacetime diagram
+    Elementary,
+    /// Margolus block automaton
+    Block,
+    /// Three-dimensional automaton
+    Volume,
+    /// Continuous-state automaton (Lenia)
+    Continuous,
+}
+
+/// What clicking and dragging on the 2D grid does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditTool {
+    /// Set cells to the paint state
+    Paint,
+    /// Drag out a rectangle to copy, cut, clear or fill
+    Select,
+}
+
+/// How the three-dimensional automaton is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VolumeView {
+    /// One plane through the volume, editable
+    Slice,
+    /// Isometric voxel rendering of the whole volume
+    Isometric,
+}
 
 pub struct CellularityApp {
+    mode: SimulationMode,
     automata: Automata,
+    elementary: ElementaryAutomata,
+    spacetime: SpacetimeDiagram,
+    elementary_rule: u8,
+    /// Whether the 1D automaton runs `totalistic_code` instead of
+    /// `elementary_rule`
+    totalistic: bool,
+    /// Code, number of colours and radius of the totalistic 1D rule
+    totalistic_code: u64,
+    totalistic_colors: u8,
+    totalistic_radius: usize,
+    /// Error from the last totalistic code that could not be applied
+    elementary_error: Option<String>,
+    margolus: MargolusAutomata,
+    volume: Automata3D,
+    /// Rule text of the 3D automaton
+    volume_rule_text: String,
+    volume_view: VolumeView,
+    /// Axis and coordinate of the slice shown in [`VolumeView::Slice`]
+    slice_axis: Axis,
+    slice_index: usize,
+    /// Texture holding the last isometric rendering
+    voxel_texture: Option<egui::TextureHandle>,
+    lenia: ContinuousAutomata,
+    /// Parameters edited in the settings panel, applied to `lenia`
+    lenia_rule: LeniaRule,
+    lenia_radius: usize,
+    colormap: Colormap,
+    /// Texture holding the last colour-mapped rendering
+    lenia_texture: Option<egui::TextureHandle>,
+    /// Rule text entered in the settings panel
+    rule_text: String,
+    /// Error from the last attempt to apply `rule_text`
+    rule_error: Option<String>,
+    /// Grid size and edge joins in Golly's notation, e.g. `K50*,30`
+    topology_text: String,
+    /// Edge behaviors chosen in the settings panel
+    left_right_edges: EdgeBehavior,
+    top_bottom_edges: EdgeBehavior,
+    /// Chances that births and survivals allowed by the rule happen
+    birth_probability: f64,
+    survival_probability: f64,
+    /// State painted by clicking or dragging on the grid
+    paint_state: u8,
+    /// Library pattern placed by the next click on the grid
+    stamp: Option<&'static LibraryPattern>,
+    edit_tool: EditTool,
+    /// Corner where the selection drag started and the opposite corner
+    selection: Option<(Position, Position)>,
+    /// RLE of the last copied region, for the Paste button
+    clipboard: String,
+    /// Pattern following the pointer until it is dropped with a click
+    paste_preview: Option<DenseGrid>,
+    /// Set when a paste is dropped, so the rest of that click does not paint
+    paste_dropped: bool,
+    /// Generations captured since Record was pressed, until Stop
+    recording: Option<Vec<DenseGrid>>,
+    /// File (`.gif`) or directory (PNG sequence) the recording is saved to
+    record_path: String,
+    /// Outcome of the last recording
+    record_status: Option<String>,
+    /// Image file read by Import and written by Export
+    image_path: String,
+    /// Threshold, scale and inversion used for image import and export
+    image_import: ImageImport,
+    /// Outcome of the last image import or export
+    image_status: Option<String>,
+    /// Rhai script run by the Run script button
+    #[cfg(feature = "scripting")]
+    script_path: String,
+    /// Outcome and printed output of the last script run
+    #[cfg(feature = "scripting")]
+    script_status: Option<String>,
+    /// Turmite specification entered in the settings panel
+    turmite_text: String,
     control_panel: ControlPanel,
     cell_size: f32,
     alive_color: Color32,
     dead_color: Color32,
     // Simulation timing
-    last_update: std::time::Instant,
-    update_interval: std::time::Duration,
+    last_update: web_time::Instant,
+    update_interval: web_time::Duration,
 }
 
 impl CellularityApp {
@@ -32,19 +171,1184 @@ impl CellularityApp {
             Box::new(MooreNeighborhood::new()),
         ).expect("valid automata parameters");
 
-        // Seed a simple blinker pattern
-        let _ = automata.grid_mut().set(Position::new(10, 10), CellState::Alive);
-        let _ = automata.grid_mut().set(Position::new(11, 10), CellState::Alive);
-        let _ = automata.grid_mut().set(Position::new(12, 10), CellState::Alive);
+        // Seed a glider from the pattern library
+        if let Some(Ok(glider)) = PatternLibrary::get("Glider").map(|glider| glider.grid()) {
+            automata.grid_mut().stamp(&glider, Position::new(10, 10));
+        }
+
+        // One-dimensional automaton seeded with a single live cell
+        let elementary_rule = 30;
+        let mut elementary = ElementaryAutomata::new(
+            101,
+            Box::new(WolframRule::new(elementary_rule)),
+            EdgeCondition::Periodic,
+        ).expect("valid elementary automata parameters");
+        elementary.seed_center();
+        let mut spacetime = SpacetimeDiagram::new(elementary.width(), 60)
+            .expect("valid spacetime dimensions");
+        spacetime.push(elementary.cells());
+
+        // Block automaton on a torus, seeded at random
+        let mut margolus = MargolusAutomata::new(
+            50,
+            30,
+            BlockRule::critters(),
+            Box::new(ToroidalBoundary::new()),
+        ).expect("valid block automata parameters");
+        if let Ok(seed) = DenseGrid::new_random(50, 30, 0.2) {
+            *margolus.grid_mut() = seed;
+        }
+
+        // 3D automaton on a torus, filled with Clouds 1 noise
+        let clouds = Life3DRule::clouds();
+        let mut volume = Automata3D::new(
+            24,
+            24,
+            24,
+            Box::new(clouds.clone()),
+            Box::new(ToroidalBoundary::new()),
+            clouds.neighborhood(),
+        ).expect("valid 3D automata parameters");
+        if let Ok(seed) = DenseGrid3D::new_random(24, 24, 24, 0.5) {
+            *volume.grid_mut() = seed;
+        }
+
+        // Lenia with Orbium's parameters, seeded with a patch of noise
+        let lenia_rule = LeniaRule::orbium();
+        let lenia_radius = 13;
+        let mut lenia = ContinuousAutomata::new(
+            128,
+            128,
+            Box::new(lenia_rule),
+            KernelNeighborhood::ring(lenia_radius),
+        ).expect("valid continuous automata parameters");
+        lenia.seed_random_patch(Position::new(64, 64), 40);
 
         Self {
+            mode: SimulationMode::Life,
             automata,
+            elementary,
+            spacetime,
+            elementary_rule,
+            totalistic: false,
+            totalistic_code: 1635,
+            totalistic_colors: 3,
+            totalistic_radius: 1,
+            elementary_error: None,
+            margolus,
+            volume,
+            volume_rule_text: clouds.spec().to_string(),
+            volume_view: VolumeView::Isometric,
+            slice_axis: Axis::Z,
+            slice_index: 12,
+            voxel_texture: None,
+            lenia,
+            lenia_rule,
+            lenia_radius,
+            colormap: Colormap::default(),
+            lenia_texture: None,
+            rule_text: "R5,C0,M1,S34..58,B34..45,NM".to_string(),
+            rule_error: None,
+            topology_text: "T50,30".to_string(),
+            left_right_edges: EdgeBehavior::Wrap,
+            top_bottom_edges: EdgeBehavior::Wrap,
+            birth_probability: 1.0,
+            survival_probability: 1.0,
+            paint_state: 1,
+            stamp: None,
+            edit_tool: EditTool::Paint,
+            selection: None,
+            recording: None,
+            record_path: "recording.gif".to_string(),
+            record_status: None,
+            image_path: "pattern.png".to_string(),
+            image_import: ImageImport::new(),
+            image_status: None,
+            #[cfg(feature = "scripting")]
+            script_path: "rule.rhai".to_string(),
+            #[cfg(feature = "scripting")]
+            script_status: None,
+            clipboard: String::new(),
+            paste_preview: None,
+            paste_dropped: false,
+            turmite_text: "{{{1, 2, 0}, {0, 8, 0}}}".to_string(),
             control_panel: ControlPanel::new(0),
             cell_size: 16.0,
             alive_color: Color32::from_rgb(60, 220, 120),
             dead_color: Color32::from_rgb(30, 30, 35),
-            last_update: std::time::Instant::now(),
-            update_interval: std::time::Duration::from_millis(100), // 10 steps per second
+            last_update: web_time::Instant::now(),
+            update_interval: web_time::Duration::from_millis(100), // 10 steps per second
+        }
+    }
+}
+
+impl CellularityApp {
+    /// Advances the active automaton by one generation
+    fn step(&mut self) {
+        match self.mode {
+            SimulationMode::Life => {
+                self.automata.step();
+                if let Some(frames) = &mut self.recording {
+                    frames.push(self.automata.grid().clone());
+                }
+            }
+            SimulationMode::Block => self.margolus.step(),
+            SimulationMode::Volume => self.volume.step(),
+            SimulationMode::Continuous => self.lenia.step(),
+            SimulationMode::Elementary => {
+                self.elementary.step();
+                self.spacetime.push(self.elementary.cells());
+            }
+        }
+    }
+
+    /// Resets the active automaton to its initial state
+    fn reset(&mut self) {
+        match self.mode {
+            SimulationMode::Life => self.automata.reset(),
+            SimulationMode::Elementary => self.restart_elementary(),
+            SimulationMode::Block => self.randomize_margolus(),
+            SimulationMode::Volume => self.randomize_volume(),
+            SimulationMode::Continuous => self.randomize_lenia(),
+        }
+    }
+
+    /// Restarts the 3D automaton from random noise
+    fn randomize_volume(&mut self) {
+        let grid = self.volume.grid();
+        let (width, height, depth) = (grid.width(), grid.height(), grid.depth());
+        if let Ok(seed) = DenseGrid3D::new_random(width, height, depth, 0.5) {
+            self.volume.reset();
+            *self.volume.grid_mut() = seed;
+        }
+    }
+
+    /// Restarts Lenia from a patch of noise in the middle of the grid
+    fn randomize_lenia(&mut self) {
+        let grid = self.lenia.grid();
+        let center = Position::new(grid.width() / 2, grid.height() / 2);
+        self.lenia.reset();
+        self.lenia.seed_random_patch(center, 3 * self.lenia_radius);
+    }
+
+    /// Parses `volume_rule_text` and installs it on the 3D automaton
+    fn apply_volume_rule(&mut self) {
+        match Life3DRule::parse(&self.volume_rule_text) {
+            Ok(rule) => {
+                self.volume.set_neighborhood(rule.neighborhood());
+                self.volume.set_rule(Box::new(rule));
+                self.rule_error = None;
+            }
+            Err(err) => self.rule_error = Some(err.to_string()),
+        }
+    }
+
+    /// Restarts the block automaton from a random grid
+    fn randomize_margolus(&mut self) {
+        let grid = self.margolus.grid();
+        if let Ok(seed) = DenseGrid::new_random(grid.width(), grid.height(), 0.2) {
+            self.margolus.reset();
+            *self.margolus.grid_mut() = seed;
+        }
+    }
+
+    /// Restarts the one-dimensional automaton from a single live cell
+    fn restart_elementary(&mut self) {
+        self.elementary.reset();
+        self.elementary.seed_center();
+        self.spacetime.clear();
+        self.spacetime.push(self.elementary.cells());
+    }
+
+    /// Installs the Wolfram rule or totalistic code chosen in the settings
+    /// panel on the 1D automaton and restarts it
+    fn apply_elementary_rule(&mut self) {
+        let rule: Box<dyn Rule1D> = if self.totalistic {
+            match TotalisticCodeRule::new(
+                self.totalistic_code,
+                self.totalistic_colors,
+                self.totalistic_radius,
+            ) {
+                Ok(rule) => Box::new(rule),
+                Err(err) => {
+                    self.elementary_error = Some(err.to_string());
+                    return;
+                }
+            }
+        } else {
+            Box::new(WolframRule::new(self.elementary_rule))
+        };
+        self.elementary.set_rule(rule);
+        self.elementary_error = None;
+        self.restart_elementary();
+    }
+
+    /// Parses `rule_text` and installs it on the 2D automaton
+    fn apply_rule_text(&mut self) {
+        let (rule, topology) = match Topology::split_rule(&self.rule_text) {
+            Ok(split) => split,
+            Err(err) => {
+                self.rule_error = Some(err.to_string());
+                return;
+            }
+        };
+        let parsed = parse_rule(rule);
+        if let (Ok(_), Some(topology)) = (&parsed, topology) {
+            if !self.apply_topology(topology) {
+                return;
+            }
+        }
+        match parsed {
+            Ok(parsed) => {
+                let (birth, survival) = (self.birth_probability, self.survival_probability);
+                if birth < 1.0 || survival < 1.0 {
+                    match StochasticRule::new(parsed.rule, birth, survival) {
+                        Ok(rule) => self.automata.set_rule(Box::new(rule)),
+                        Err(err) => {
+                            self.rule_error = Some(err.to_string());
+                            return;
+                        }
+                    }
+                } else {
+                    self.automata.set_rule(parsed.rule);
+                }
+                // Other lattices keep their own neighborhood
+                if self.automata.lattice() == Lattice::Square {
+                    self.automata.set_neighborhood(parsed.neighborhood);
+                }
+                self.rule_error = None;
+            }
+            Err(err) => self.rule_error = Some(err.to_string()),
+        }
+        self.paint_state = 1;
+    }
+
+    /// Resizes the 2D grid and joins its edges as the topology describes
+    ///
+    /// Returns false, leaving the error in `rule_error`, if the grid cannot
+    /// take the new size.
+    fn apply_topology(&mut self, topology: Topology) -> bool {
+        if let Err(err) = self.automata.resize(topology.width(), topology.height()) {
+            self.rule_error = Some(err.to_string());
+            return false;
+        }
+        self.automata.set_boundary(topology.boundary());
+        self.topology_text = topology.to_string();
+        self.rule_error = None;
+        true
+    }
+
+    /// Places a library pattern centred on a cell
+    ///
+    /// The rule is left alone; picking the pattern in
+    /// [`show_pattern_library`](Self::show_pattern_library) already switched to it.
+    fn stamp_library_pattern(&mut self, pattern: &LibraryPattern, center: Position) {
+        let Ok(cells) = pattern.grid() else {
+            return;
+        };
+        let at = Position::new(
+            center.x.saturating_sub(cells.width() / 2),
+            center.y.saturating_sub(cells.height() / 2),
+        );
+        self.automata.grid_mut().stamp(&cells, at);
+    }
+
+    /// Returns the top-left cell, width and height of the selection
+    fn selection_region(&self) -> Option<(Position, usize, usize)> {
+        let (a, b) = self.selection?;
+        let min = Position::new(a.x.min(b.x), a.y.min(b.y));
+        Some((min, a.x.abs_diff(b.x) + 1, a.y.abs_diff(b.y) + 1))
+    }
+
+    /// Encodes the selected cells as RLE, stores them in the app clipboard
+    /// and returns the text for the system clipboard
+    fn copy_selection(&mut self) -> Option<String> {
+        let (origin, width, height) = self.selection_region()?;
+        let region = self.automata.grid().extract(origin, width, height).ok()?;
+        let mut pattern = RlePattern::new(region);
+        pattern.rule = Some(self.rule_text.clone());
+        self.clipboard = pattern.to_rle();
+        Some(self.clipboard.clone())
+    }
+
+    /// Sets every selected cell to one state
+    fn fill_selection(&mut self, state: CellState) {
+        if let Some((origin, width, height)) = self.selection_region() {
+            self.automata.grid_mut().fill_region(origin, width, height, state);
+        }
+    }
+
+    /// Selects a region by its top-left cell and size, clipped to the grid
+    fn select_region(&mut self, origin: Position, width: usize, height: usize) {
+        let grid = self.automata.grid();
+        let far = Position::new(
+            (origin.x + width - 1).min(grid.width() - 1),
+            (origin.y + height - 1).min(grid.height() - 1),
+        );
+        self.selection = Some((origin, far));
+    }
+
+    /// Selects the smallest rectangle around every live cell
+    fn select_pattern(&mut self) {
+        if let Some((origin, width, height)) = self.automata.grid().bounding_box() {
+            self.select_region(origin, width, height);
+        }
+    }
+
+    /// Shrinks the selection to the smallest rectangle around its live cells
+    fn crop_selection(&mut self) {
+        let Some((origin, width, height)) = self.selection_region() else {
+            return;
+        };
+        let Ok(region) = self.automata.grid().extract(origin, width, height) else {
+            return;
+        };
+        match region.bounding_box() {
+            Some((corner, width, height)) => {
+                let corner = Position::new(origin.x + corner.x, origin.y + corner.y);
+                self.select_region(corner, width, height);
+            }
+            None => self.selection = None,
+        }
+    }
+
+    /// Rotates or reflects the paste preview, or else the selected cells in
+    /// place, keeping the top-left corner of the selection fixed
+    fn transform_selection(&mut self, transform: Transform) {
+        if let Some(preview) = &mut self.paste_preview {
+            *preview = preview.transform(transform);
+            return;
+        }
+        let Some((origin, width, height)) = self.selection_region() else {
+            return;
+        };
+        let Ok(region) = self.automata.grid().extract(origin, width, height) else {
+            return;
+        };
+        let turned = region.transform(transform);
+        let grid = self.automata.grid_mut();
+        grid.fill_region(origin, width, height, CellState::Dead);
+        grid.stamp(&turned, origin);
+        self.select_region(origin, turned.width(), turned.height());
+    }
+
+    /// Moves the selected cells, and the selection with them, by an offset
+    ///
+    /// Nothing happens if the selection would leave the grid.
+    fn move_selection(&mut self, dx: isize, dy: isize) {
+        let Some((origin, width, height)) = self.selection_region() else {
+            return;
+        };
+        let grid = self.automata.grid();
+        let (Some(x), Some(y)) = (origin.x.checked_add_signed(dx), origin.y.checked_add_signed(dy))
+        else {
+            return;
+        };
+        if x + width > grid.width() || y + height > grid.height() {
+            return;
+        }
+        let Ok(region) = grid.extract(origin, width, height) else {
+            return;
+        };
+        let target = Position::new(x, y);
+        let grid = self.automata.grid_mut();
+        grid.fill_region(origin, width, height, CellState::Dead);
+        grid.stamp(&region, target);
+        self.select_region(target, width, height);
+    }
+
+    /// Returns the library pattern in the selection, if it holds one
+    fn identify_selection(&self) -> Option<&'static LibraryPattern> {
+        let (origin, width, height) = self.selection_region()?;
+        let region = self.automata.grid().extract(origin, width, height).ok()?;
+        PatternLibrary::identify(&region)
+    }
+
+    /// Returns a recorder drawing frames as the grid view does, cropped to
+    /// the selection if there is one
+    fn recorder(&self) -> Recorder {
+        let mut recorder = Recorder::new();
+        let style = &mut recorder.style;
+        style.cell_size = self.cell_size.round().max(1.0) as usize;
+        style.alive_color = [self.alive_color.r(), self.alive_color.g(), self.alive_color.b()];
+        style.dead_color = [self.dead_color.r(), self.dead_color.g(), self.dead_color.b()];
+        style.palette = self.automata.rule().palette().map(<[_]>::to_vec);
+        style.crop = self.selection_region();
+        recorder
+    }
+
+    /// Starts capturing every generation, or saves the frames captured so far
+    fn toggle_recording(&mut self) {
+        match self.recording.take() {
+            None => {
+                self.recording = Some(vec![self.automata.grid().clone()]);
+                self.record_status = None;
+            }
+            Some(frames) => {
+                let saved = self.recorder().save(&frames, &self.record_path);
+                self.record_status = Some(match saved {
+                    Ok(_) => format!("Saved {} frames to {}", frames.len(), self.record_path),
+                    Err(err) => err.to_string(),
+                });
+            }
+        }
+    }
+
+    /// Shows the Record button and where recordings are saved
+    fn show_record_settings(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Save to:");
+            ui.add_enabled(
+                self.recording.is_none(),
+                egui::TextEdit::singleline(&mut self.record_path).desired_width(140.0),
+            );
+        });
+        ui.horizontal(|ui| {
+            let label = if self.recording.is_some() { "⏹ Stop" } else { "⏺ Record" };
+            if ui.button(label).clicked() {
+                self.toggle_recording();
+            }
+            if let Some(frames) = &self.recording {
+                ui.label(format!("{} frames", frames.len()));
+            }
+        });
+        ui.label("A .gif path saves an animation, anything else a folder of PNGs");
+        if let Some(status) = &self.record_status {
+            ui.label(status);
+        }
+    }
+
+    /// Returns the image settings with the palette of the current rule
+    fn image_settings(&self) -> ImageImport {
+        ImageImport {
+            palette: self.automata.rule().palette().map(<[_]>::to_vec),
+            ..self.image_import.clone()
+        }
+    }
+
+    /// Replaces the grid with the image at `image_path`, centred, growing
+    /// the grid if the image does not fit
+    fn import_image(&mut self) -> Result<String, String> {
+        let image = self.image_settings().open(&self.image_path).map_err(|e| e.to_string())?;
+        let grid = self.automata.grid();
+        let width = grid.width().max(image.width());
+        let height = grid.height().max(image.height());
+        if (width, height) != (grid.width(), grid.height()) {
+            self.automata.resize(width, height).map_err(|e| e.to_string())?;
+        }
+        let origin = Position::new((width - image.width()) / 2, (height - image.height()) / 2);
+        let grid = self.automata.grid_mut();
+        grid.clear();
+        grid.stamp(&image, origin);
+        self.selection = None;
+        Ok(format!("Imported {}x{} cells", image.width(), image.height()))
+    }
+
+    /// Writes the grid, or the selection, to `image_path` as the import
+    /// settings would read it back, or as a vector image with grid lines if
+    /// the path ends in `.svg`
+    fn export_image(&self) -> Result<String, String> {
+        let mut style = self.image_settings().export_style();
+        style.crop = self.selection_region();
+        let grid = self.automata.grid();
+        let saved = if self.image_path.to_ascii_lowercase().ends_with(".svg") {
+            let mut svg = SvgExport::new();
+            svg.style = FrameStyle { cell_size: svg.style.cell_size, ..style };
+            svg.save(grid, &self.image_path)
+        } else {
+            style.save(grid, &self.image_path)
+        };
+        saved.map_err(|e| e.to_string())?;
+        Ok(format!("Saved {}", self.image_path))
+    }
+
+    /// Shows image import and export settings
+    fn show_image_settings(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Image:");
+            ui.text_edit_singleline(&mut self.image_path);
+        });
+        let settings = &mut self.image_import;
+        ui.add(egui::Slider::new(&mut settings.threshold, 0..=255).text("Threshold"));
+        ui.horizontal(|ui| {
+            ui.label("Pixels per cell:");
+            ui.add(egui::DragValue::new(&mut settings.scale).range(1..=64));
+            ui.checkbox(&mut settings.invert, "Light cells alive");
+        });
+        ui.horizontal(|ui| {
+            let (import, export) = (ui.button("Import").clicked(), ui.button("Export").clicked());
+            let result = match (import, export) {
+                (true, _) => Some(self.import_image()),
+                (_, true) => Some(self.export_image()),
+                _ => None,
+            };
+            if let Some(result) = result {
+                self.image_status = Some(result.unwrap_or_else(|err| err));
+            }
+        });
+        ui.label("PNG, PBM (.pbm) or, for export only, SVG (.svg)");
+        ui.label("Rules with their own colours import by palette");
+        if let Some(status) = &self.image_status {
+            ui.label(status);
+        }
+    }
+
+    /// Loads the script at `script_path`, installs its rule and
+    /// neighborhood and calls its `run(sim)` function, as the `script`
+    /// command does
+    #[cfg(feature = "scripting")]
+    fn run_script(&mut self) -> Result<String, String> {
+        let script = Script::load(&self.script_path).map_err(|e| e.to_string())?;
+        let mut report = Vec::new();
+        if script.defines_rule() || script.defines_neighborhood() {
+            script.install(&mut self.automata).map_err(|e| e.to_string())?;
+            self.rule_error = None;
+            report.push(format!(
+                "Installed {} on {}",
+                self.automata.rule().name(),
+                self.automata.neighborhood().name()
+            ));
+        }
+        if script.defines_automation() {
+            let printed = script.run(&mut self.automata).map_err(|e| e.to_string())?;
+            report.extend(printed);
+            report.push(format!("Ran to generation {}", self.automata.generation()));
+        }
+        if report.is_empty() {
+            return Err(format!(
+                "{} defines no apply(state, alive), neighborhood() or run(sim) function",
+                script.name()
+            ));
+        }
+        Ok(report.join("\n"))
+    }
+
+    /// Shows the script path and the Run script button
+    #[cfg(feature = "scripting")]
+    fn show_script_settings(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Script:");
+            ui.text_edit_singleline(&mut self.script_path);
+        });
+        if ui.button("Run script").clicked() {
+            self.script_status = Some(self.run_script().unwrap_or_else(|err| err));
+        }
+        ui.label("Rhai with apply(state, alive), neighborhood() or run(sim)");
+        if let Some(status) = &self.script_status {
+            ui.label(status);
+        }
+    }
+
+    /// Parses RLE text and floats it under the pointer until it is dropped
+    fn start_paste(&mut self, text: &str) {
+        match RlePattern::parse(text) {
+            Ok(pattern) => {
+                self.paste_preview = Some(pattern.grid);
+                self.stamp = None;
+                self.rule_error = None;
+            }
+            Err(err) => self.rule_error = Some(err.to_string()),
+        }
+    }
+
+    /// Handles clipboard events and the keys for selections and pasting
+    fn handle_edit_input(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        let events = ctx.input(|i| i.events.clone());
+        for event in events {
+            match event {
+                egui::Event::Copy => {
+                    if let Some(text) = self.copy_selection() {
+                        ctx.copy_text(text);
+                    }
+                }
+                egui::Event::Cut => {
+                    if let Some(text) = self.copy_selection() {
+                        ctx.copy_text(text);
+                        self.fill_selection(CellState::Dead);
+                    }
+                }
+                egui::Event::Paste(text) => self.start_paste(&text),
+                _ => {}
+            }
+        }
+
+        let (rotate, flip, escape, delete) = ctx.input(|i| {
+            (
+                i.key_pressed(egui::Key::R),
+                i.key_pressed(egui::Key::F),
+                i.key_pressed(egui::Key::Escape),
+                i.key_pressed(egui::Key::Delete),
+            )
+        });
+        if rotate {
+            self.transform_selection(Transform::Rotate90);
+        }
+        if flip {
+            self.transform_selection(Transform::FlipHorizontal);
+        }
+        let offset = ctx.input(|i| {
+            let key = |key, step| if i.key_pressed(key) { step } else { 0 };
+            (
+                key(egui::Key::ArrowRight, 1) - key(egui::Key::ArrowLeft, 1),
+                key(egui::Key::ArrowDown, 1) - key(egui::Key::ArrowUp, 1),
+            )
+        });
+        if offset != (0, 0) && self.paste_preview.is_none() {
+            self.move_selection(offset.0, offset.1);
+        }
+        if escape {
+            self.paste_preview = None;
+            self.selection = None;
+        }
+        if delete {
+            self.fill_selection(CellState::Dead);
+        }
+    }
+
+    /// Shows the selection tools and clipboard commands
+    fn show_edit_settings(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Tool:");
+            ui.radio_value(&mut self.edit_tool, EditTool::Paint, "Paint");
+            ui.radio_value(&mut self.edit_tool, EditTool::Select, "Select");
+        });
+        ui.horizontal_wrapped(|ui| {
+            let selected = self.selection.is_some();
+            if ui.add_enabled(selected, egui::Button::new("Copy")).clicked() {
+                if let Some(text) = self.copy_selection() {
+                    ui.ctx().copy_text(text);
+                }
+            }
+            if ui.add_enabled(selected, egui::Button::new("Cut")).clicked() {
+                if let Some(text) = self.copy_selection() {
+                    ui.ctx().copy_text(text);
+                    self.fill_selection(CellState::Dead);
+                }
+            }
+            let pasteable = !self.clipboard.is_empty();
+            if ui.add_enabled(pasteable, egui::Button::new("Paste")).clicked() {
+                let text = self.clipboard.clone();
+                self.start_paste(&text);
+            }
+            if ui.add_enabled(selected, egui::Button::new("Clear")).clicked() {
+                self.fill_selection(CellState::Dead);
+            }
+            if ui.add_enabled(selected, egui::Button::new("Random fill")).clicked() {
+                if let Some((origin, width, height)) = self.selection_region() {
+                    self.automata.grid_mut().randomize_region(origin, width, height, 0.3);
+                }
+            }
+            if ui.button("Select pattern").clicked() {
+                self.select_pattern();
+                self.edit_tool = EditTool::Select;
+            }
+        });
+
+        let previewing = self.paste_preview.is_some();
+        ui.add_enabled_ui(previewing || self.selection.is_some(), |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for transform in &Transform::ALL[1..] {
+                    if ui.small_button(transform.name()).clicked() {
+                        self.transform_selection(*transform);
+                    }
+                }
+            });
+        });
+        if previewing {
+            if ui.button("Cancel paste").clicked() {
+                self.paste_preview = None;
+            }
+            ui.label("Click the grid to drop the pattern (R rotates, F flips)");
+        } else if self.selection.is_some() {
+            ui.horizontal(|ui| {
+                ui.label("Move:");
+                for (label, dx, dy) in [("←", -1, 0), ("→", 1, 0), ("↑", 0, -1), ("↓", 0, 1)] {
+                    if ui.small_button(label).clicked() {
+                        self.move_selection(dx, dy);
+                    }
+                }
+                if ui.button("Crop selection").clicked() {
+                    self.crop_selection();
+                }
+            });
+            if let Some(pattern) = self.identify_selection() {
+                ui.label(format!("Selection holds a {}", pattern.name));
+            }
+        }
+    }
+
+    /// Lists the pattern library by category; clicking a pattern selects it
+    /// for stamping
+    fn show_pattern_library(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Patterns");
+        match self.stamp {
+            Some(pattern) => {
+                ui.label(format!("Click the grid to place {}", pattern.name));
+                ui.label(format!("by {}, rule {}", pattern.author, pattern.rule));
+                if let Some(period) = pattern.period {
+                    ui.label(format!("Period {}", period));
+                }
+                if ui.button("Cancel").clicked() {
+                    self.stamp = None;
+                }
+            }
+            None => {
+                ui.label("Pick a pattern, then click the grid to place it");
+            }
+        }
+        ui.separator();
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for category in PatternCategory::ALL {
+                egui::CollapsingHeader::new(category.name())
+                    .default_open(category == PatternCategory::Spaceship)
+                    .show(ui, |ui| {
+                        for pattern in PatternLibrary::by_category(category) {
+                            let selected = self.stamp == Some(pattern);
+                            if ui.selectable_label(selected, pattern.name).clicked() {
+                                self.stamp = (!selected).then_some(pattern);
+                                if !selected && self.rule_text != pattern.rule {
+                                    self.rule_text = pattern.rule.to_string();
+                                    self.apply_rule_text();
+                                }
+                            }
+                        }
+                    });
+            }
+        });
+    }
+
+    /// Switches to a named rule and draws an example pattern in the middle of the grid
+    fn load_example(&mut self, rule: &str, pattern: DenseGrid) {
+        self.rule_text = rule.to_string();
+        self.apply_rule_text();
+        self.automata.reset();
+
+        let grid = self.automata.grid_mut();
+        let ox = grid.width().saturating_sub(pattern.width()) / 2;
+        let oy = grid.height().saturating_sub(pattern.height()) / 2;
+        grid.stamp(&pattern, Position::new(ox, oy));
+    }
+
+    /// Parses `turmite_text` and adds an ant in the middle of the grid
+    fn add_ant(&mut self) {
+        let rule = TurmiteRule::parse(&self.turmite_text)
+            .or_else(|_| TurmiteRule::from_turns(&self.turmite_text));
+        match rule {
+            Ok(rule) => {
+                let grid = self.automata.grid();
+                let center = Position::new(grid.width() / 2, grid.height() / 2);
+                let _ = self.automata.add_ant(Ant::new(center, Heading::North, rule));
+                self.rule_error = None;
+            }
+            Err(err) => self.rule_error = Some(err.to_string()),
+        }
+    }
+
+    /// Shows the mode selector and mode-specific settings
+    fn show_settings(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Simulation");
+        ui.radio_value(&mut self.mode, SimulationMode::Life, "2D automaton");
+        ui.radio_value(&mut self.mode, SimulationMode::Elementary, "1D elementary");
+        ui.radio_value(&mut self.mode, SimulationMode::Block, "Margolus blocks");
+        ui.radio_value(&mut self.mode, SimulationMode::Volume, "3D automaton");
+        ui.radio_value(&mut self.mode, SimulationMode::Continuous, "Lenia");
+
+        if self.mode == SimulationMode::Life {
+            ui.separator();
+            ui.label(format!("Rule: {}", self.automata.rule().name()));
+            ui.label(self.automata.neighborhood().name());
+            ui.label("Rule (B/S, LtL or Weighted Life):");
+            let response = ui.text_edit_singleline(&mut self.rule_text);
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            ui.horizontal(|ui| {
+                if ui.button("Apply").clicked() || submitted {
+                    self.apply_rule_text();
+                }
+                if ui.button("Conway").clicked() {
+                    self.automata.set_rule(Box::new(ConwayRule::new()));
+                    let _ = self.automata.set_lattice(Lattice::Square);
+                    self.automata.set_neighborhood(Box::new(MooreNeighborhood::new()));
+                    self.rule_error = None;
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Lattice:");
+                for lattice in [Lattice::Square, Lattice::Hexagonal, Lattice::Triangular] {
+                    let selected = self.automata.lattice() == lattice;
+                    if ui.selectable_label(selected, lattice.name()).clicked() {
+                        if let Err(err) = self.automata.set_lattice(lattice) {
+                            self.rule_error = Some(err.to_string());
+                        }
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Topology:");
+                let response = ui.text_edit_singleline(&mut self.topology_text);
+                let submitted =
+                    response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if ui.button("Apply").clicked() || submitted {
+                    match Topology::parse(&self.topology_text) {
+                        Ok(topology) => {
+                            self.apply_topology(topology);
+                        }
+                        Err(err) => self.rule_error = Some(err.to_string()),
+                    }
+                }
+            });
+            ui.label("T w,h torus, K w*,h Klein bottle, C w,h cross-surface, S n sphere");
+            ui.horizontal(|ui| {
+                for (label, edges) in [
+                    ("Left/right", &mut self.left_right_edges),
+                    ("Top/bottom", &mut self.top_bottom_edges),
+                ] {
+                    egui::ComboBox::from_label(label)
+                        .selected_text(edges.name())
+                        .show_ui(ui, |ui| {
+                            for behavior in EdgeBehavior::ALL {
+                                ui.selectable_value(edges, behavior, behavior.name());
+                            }
+                        });
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.button("Apply edges").clicked() {
+                    let edges = MixedBoundary::new(self.left_right_edges, self.top_bottom_edges);
+                    self.automata.set_boundary(Box::new(edges));
+                    self.topology_text.clear();
+                }
+                if ui.button("Live border").clicked() {
+                    self.automata.set_boundary(Box::new(ConstantBoundary::new(CellState::Alive)));
+                    self.topology_text.clear();
+                }
+            });
+            if let Some(err) = &self.rule_error {
+                ui.colored_label(Color32::LIGHT_RED, err);
+            }
+
+            ui.separator();
+            self.show_update_settings(ui);
+
+            ui.separator();
+            self.show_edit_settings(ui);
+
+            ui.separator();
+            self.show_record_settings(ui);
+
+            ui.separator();
+            self.show_image_settings(ui);
+
+            #[cfg(feature = "scripting")]
+            {
+                ui.separator();
+                self.show_script_settings(ui);
+            }
+
+            ui.separator();
+            let rule = self.automata.rule();
+            let states = rule.states();
+            self.paint_state = self.paint_state.min(states - 1);
+            egui::ComboBox::from_label("Paint state")
+                .selected_text(rule.state_name(self.paint_state))
+                .show_ui(ui, |ui| {
+                    for state in 0..states {
+                        ui.selectable_value(&mut self.paint_state, state, rule.state_name(state));
+                    }
+                });
+
+            ui.label("Examples:");
+            ui.horizontal_wrapped(|ui| {
+                if ui.button("Wireworld clock").clicked() {
+                    if let Ok(clock) = WireworldRule::circuit(WireworldRule::CLOCK) {
+                        self.load_example("Wireworld", clock);
+                    }
+                }
+                if ui.button("Wireworld diode").clicked() {
+                    if let Ok(diode) = WireworldRule::circuit(WireworldRule::DIODE) {
+                        self.load_example("Wireworld", diode);
+                    }
+                }
+                if ui.button("Langton's loop").clicked() {
+                    if let Ok(loop_) = RuleTable::pattern(RuleTable::LANGTONS_LOOP) {
+                        self.load_example("Langton's Loops", loop_);
+                    }
+                }
+                if ui.button("Langton's ant").clicked() {
+                    self.automata.reset();
+                    self.automata.set_rule(Box::new(IdentityRule::new()));
+                    self.turmite_text = TurmiteRule::langtons_ant().spec();
+                    self.add_ant();
+                }
+            });
+
+            ui.separator();
+            ui.label(format!("Turmite ({} ants):", self.automata.ants().len()));
+            ui.text_edit_singleline(&mut self.turmite_text);
+            ui.horizontal(|ui| {
+                if ui.button("Add ant").clicked() {
+                    self.add_ant();
+                }
+                if ui.button("Clear ants").clicked() {
+                    self.automata.clear_ants();
+                }
+            });
+        }
+
+        if self.mode == SimulationMode::Block {
+            ui.separator();
+            ui.label(self.margolus.rule().spec());
+            ui.horizontal_wrapped(|ui| {
+                for (name, rule) in [
+                    ("Critters", BlockRule::critters()),
+                    ("Tron", BlockRule::tron()),
+                    ("Billiard balls", BlockRule::billiard_balls()),
+                ] {
+                    if ui.selectable_label(*self.margolus.rule() == rule, name).clicked() {
+                        self.margolus.set_rule(rule);
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.button("Randomize").clicked() {
+                    self.randomize_margolus();
+                }
+                let reversible = self.margolus.rule().is_reversible();
+                let can_step_back = reversible && self.margolus.generation() > 0;
+                if ui.add_enabled(can_step_back, egui::Button::new("Step back")).clicked() {
+                    let _ = self.margolus.step_back();
+                }
+            });
+        }
+
+        if self.mode == SimulationMode::Volume {
+            ui.separator();
+            ui.label(format!("Rule: {}", self.volume.rule().description()));
+            ui.label(self.volume.neighborhood().name());
+            ui.label("Rule (survival/birth/states/neighborhood):");
+            let response = ui.text_edit_singleline(&mut self.volume_rule_text);
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            ui.horizontal_wrapped(|ui| {
+                if ui.button("Apply").clicked() || submitted {
+                    self.apply_volume_rule();
+                }
+                for (name, spec) in [("4555", "4555"), ("Clouds 1", "Clouds"), ("445", "4/4/5/M")] {
+                    if ui.button(name).clicked() {
+                        self.volume_rule_text = spec.to_string();
+                        self.apply_volume_rule();
+                    }
+                }
+            });
+            if let Some(err) = &self.rule_error {
+                ui.colored_label(Color32::LIGHT_RED, err);
+            }
+            if ui.button("Randomize").clicked() {
+                self.randomize_volume();
+            }
+
+            ui.separator();
+            ui.radio_value(&mut self.volume_view, VolumeView::Isometric, "Isometric voxels");
+            ui.radio_value(&mut self.volume_view, VolumeView::Slice, "Slices");
+            if self.volume_view == VolumeView::Slice {
+                ui.horizontal(|ui| {
+                    ui.label("Axis:");
+                    ui.radio_value(&mut self.slice_axis, Axis::X, "X");
+                    ui.radio_value(&mut self.slice_axis, Axis::Y, "Y");
+                    ui.radio_value(&mut self.slice_axis, Axis::Z, "Z");
+                });
+                let grid = self.volume.grid();
+                let layers = match self.slice_axis {
+                    Axis::X => grid.width(),
+                    Axis::Y => grid.height(),
+                    Axis::Z => grid.depth(),
+                };
+                self.slice_index = self.slice_index.min(layers - 1);
+                ui.add(egui::Slider::new(&mut self.slice_index, 0..=layers - 1).text("Slice"));
+            }
+        }
+
+        if self.mode == SimulationMode::Continuous {
+            ui.separator();
+            ui.label(self.lenia.kernel().name());
+            ui.label(format!("Mass: {:.1}", self.lenia.grid().total()));
+            let rule = &mut self.lenia_rule;
+            let mut changed =
+                ui.add(egui::Slider::new(&mut rule.mu, 0.0..=0.5).text("μ")).changed();
+            changed |= ui
+                .add(egui::Slider::new(&mut rule.sigma, 0.001..=0.1).logarithmic(true).text("σ"))
+                .changed();
+            changed |= ui.add(egui::Slider::new(&mut rule.dt, 0.01..=1.0).text("dt")).changed();
+            if changed {
+                self.lenia.set_rule(Box::new(self.lenia_rule));
+            }
+            if ui.add(egui::Slider::new(&mut self.lenia_radius, 2..=30).text("R")).changed() {
+                self.lenia.set_kernel(KernelNeighborhood::ring(self.lenia_radius));
+            }
+            egui::ComboBox::from_label("Colour map")
+                .selected_text(self.colormap.name())
+                .show_ui(ui, |ui| {
+                    for colormap in Colormap::ALL {
+                        ui.selectable_value(&mut self.colormap, colormap, colormap.name());
+                    }
+                });
+            ui.horizontal(|ui| {
+                if ui.button("Randomize").clicked() {
+                    self.randomize_lenia();
+                }
+                if ui.button("Orbium").clicked() {
+                    self.lenia_rule = LeniaRule::orbium();
+                    self.lenia_radius = 13;
+                    self.lenia.set_rule(Box::new(self.lenia_rule));
+                    self.lenia.set_kernel(KernelNeighborhood::ring(self.lenia_radius));
+                }
+            });
+        }
+
+        if self.mode == SimulationMode::Elementary {
+            ui.separator();
+            let mut changed = false;
+            ui.horizontal(|ui| {
+                changed |= ui.radio_value(&mut self.totalistic, false, "Wolfram rule").changed();
+                changed |= ui.radio_value(&mut self.totalistic, true, "Totalistic code").changed();
+            });
+            if self.totalistic {
+                ui.horizontal(|ui| {
+                    changed |= ui
+                        .add(egui::DragValue::new(&mut self.totalistic_code).prefix("Code "))
+                        .changed();
+                    changed |= ui
+                        .add(
+                            egui::DragValue::new(&mut self.totalistic_colors)
+                                .range(2..=8)
+                                .prefix("k = "),
+                        )
+                        .changed();
+                    changed |= ui
+                        .add(
+                            egui::DragValue::new(&mut self.totalistic_radius)
+                                .range(1..=3)
+                                .prefix("r = "),
+                        )
+                        .changed();
+                });
+            } else {
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut self.elementary_rule)
+                            .range(0..=255)
+                            .prefix("Rule "),
+                    )
+                    .changed();
+            }
+            if changed {
+                self.apply_elementary_rule();
+            }
+            if let Some(err) = &self.elementary_error {
+                ui.colored_label(Color32::LIGHT_RED, err);
+            }
+        }
+    }
+
+    /// Shows the update order, rule probabilities and random seed of the 2D
+    /// automaton
+    fn show_update_settings(&mut self, ui: &mut egui::Ui) {
+        let mut scheme = self.automata.update_scheme();
+        let alpha = match scheme {
+            UpdateScheme::Asynchronous { alpha } => alpha,
+            _ => 0.5,
+        };
+        egui::ComboBox::from_label("Update order")
+            .selected_text(scheme.name())
+            .show_ui(ui, |ui| {
+                for option in [
+                    UpdateScheme::Synchronous,
+                    UpdateScheme::Asynchronous { alpha },
+                    UpdateScheme::RandomSequential,
+                    UpdateScheme::LineByLine,
+                ] {
+                    ui.selectable_value(&mut scheme, option, option.name());
+                }
+            });
+        if let UpdateScheme::Asynchronous { alpha } = &mut scheme {
+            ui.add(egui::Slider::new(alpha, 0.0..=1.0).text("α"));
+        }
+        self.automata.set_update_scheme(scheme);
+
+        let birth = egui::Slider::new(&mut self.birth_probability, 0.0..=1.0).text("Birth p");
+        let mut changed = ui.add(birth).changed();
+        let survival =
+            egui::Slider::new(&mut self.survival_probability, 0.0..=1.0).text("Survival p");
+        changed |= ui.add(survival).changed();
+        if changed {
+            self.apply_rule_text();
+        }
+
+        let mut seed = self.automata.seed();
+        ui.horizontal(|ui| {
+            ui.label("Seed:");
+            if ui.add(egui::DragValue::new(&mut seed)).changed() {
+                self.automata.set_seed(seed);
+            }
+            if ui.button("New seed").clicked() {
+                self.automata.set_seed(rand::random());
+            }
+        });
+    }
+
+    /// Draws Lenia through the selected colour map, four pixels per cell
+    fn show_lenia(&mut self, ui: &mut egui::Ui) {
+        let image = colormap_image(self.lenia.grid(), self.colormap);
+        let size = egui::vec2(image.size[0] as f32, image.size[1] as f32) * 4.0;
+        let texture = match &mut self.lenia_texture {
+            Some(texture) => {
+                texture.set(image, egui::TextureOptions::NEAREST);
+                texture
+            }
+            None => self.lenia_texture.insert(ui.ctx().load_texture(
+                "lenia",
+                image,
+                egui::TextureOptions::NEAREST,
+            )),
+        };
+        ui.image((texture.id(), size));
+    }
+
+    /// Draws the 3D automaton as a slice or as isometric voxels
+    fn show_volume(&mut self, ui: &mut egui::Ui) {
+        let (alive, dead) = (self.alive_color, self.dead_color);
+        match self.volume_view {
+            VolumeView::Slice => {
+                let (axis, index) = (self.slice_axis, self.slice_index);
+                let Ok(slice) = self.volume.grid().slice(axis, index) else {
+                    return;
+                };
+                if let Some(pos) = grid_view(ui, &slice, self.cell_size, alive, dead) {
+                    let voxel = DenseGrid3D::slice_position(axis, index, pos.x, pos.y);
+                    let _ = self.volume.grid_mut().set(voxel, CellState::Alive);
+                }
+            }
+            VolumeView::Isometric => {
+                let decaying = Color32::from_rgb(70, 110, 190);
+                let image = isometric_image(self.volume.grid(), 3, |state| {
+                    if state.is_alive() { alive } else { decaying }
+                });
+                let size = egui::vec2(image.size[0] as f32, image.size[1] as f32);
+                let texture = match &mut self.voxel_texture {
+                    Some(texture) => {
+                        texture.set(image, egui::TextureOptions::NEAREST);
+                        texture
+                    }
+                    None => self.voxel_texture.insert(ui.ctx().load_texture(
+                        "voxels",
+                        image,
+                        egui::TextureOptions::NEAREST,
+                    )),
+                };
+                ui.image((texture.id(), size));
+            }
         }
     }
 }
@@ -52,7 +1356,13 @@ impl CellularityApp {
 impl eframe::App for CellularityApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Update the generation in the control panel
-        self.control_panel.generation = self.automata.generation();
+        self.control_panel.generation = match self.mode {
+            SimulationMode::Life => self.automata.generation(),
+            SimulationMode::Elementary => self.elementary.generation(),
+            SimulationMode::Block => self.margolus.generation(),
+            SimulationMode::Volume => self.volume.generation(),
+            SimulationMode::Continuous => self.lenia.generation(),
+        };
 
         // Show the control panel in top panel
         egui::TopBottomPanel::top("top_bar").show(ctx, |ui| {
@@ -64,10 +1374,10 @@ impl eframe::App for CellularityApp {
                     self.control_panel.is_playing = false;
                 },
                 ControlAction::Step => {
-                    self.automata.step();
+                    self.step();
                 },
                 ControlAction::Reset => {
-                    self.automata.reset();
+                    self.reset();
                     self.control_panel.is_playing = false;
                 },
                 ControlAction::None => {},
@@ -78,17 +1388,122 @@ impl eframe::App for CellularityApp {
         if self.control_panel.is_playing {
             let elapsed = self.last_update.elapsed();
             if elapsed >= self.update_interval {
-                self.automata.step();
-                self.last_update = std::time::Instant::now();
+                self.step();
+                self.last_update = web_time::Instant::now();
                 
                 // Request repaint to keep the simulation running
                 ctx.request_repaint();
             }
         }
 
+        if self.mode == SimulationMode::Life {
+            self.handle_edit_input(ctx);
+        }
+
+        egui::SidePanel::left("settings").show(ctx, |ui| {
+            self.show_settings(ui);
+        });
+
+        if self.mode == SimulationMode::Life {
+            egui::SidePanel::right("patterns").show(ctx, |ui| {
+                self.show_pattern_library(ui);
+            });
+        }
+
         // Show the grid in central panel
         egui::CentralPanel::default().show(ctx, |ui| {
-            grid_view(ui, self.automata.grid(), self.cell_size, self.alive_color, self.dead_color);
+            match self.mode {
+                SimulationMode::Life => {
+                    let origin = ui.available_rect_before_wrap().min;
+                    let palette = self.automata.rule().palette().map(palette_from_rgb);
+                    let (alive, dead) = (self.alive_color, self.dead_color);
+                    let painted = lattice_grid_view(
+                        ui,
+                        self.automata.grid(),
+                        self.automata.lattice(),
+                        self.cell_size,
+                        |state| match &palette {
+                            Some(palette) => palette_color(state, palette),
+                            None => state_to_color(state, alive, dead),
+                        },
+                    );
+                    if self.automata.lattice() == Lattice::Square {
+                        let ants = self.automata.ants();
+                        let color = Color32::from_rgb(230, 60, 60);
+                        ant_overlay(ui, ants, self.cell_size, origin, color);
+                    }
+                    let grid = self.automata.grid();
+                    let (width, height) = (grid.width(), grid.height());
+                    let hovered = ui.input(|i| i.pointer.hover_pos()).and_then(|pointer| {
+                        let lattice = self.automata.lattice();
+                        pixel_to_cell(lattice, pointer, width, height, self.cell_size, origin)
+                    });
+                    let square = self.automata.lattice() == Lattice::Square;
+                    let highlight = Color32::from_rgb(250, 200, 60);
+                    if let (Some((min, max)), true) = (self.selection, square) {
+                        selection_overlay(ui, min, max, self.cell_size, origin, highlight);
+                    }
+                    if let (Some(preview), Some((x, y)), true) =
+                        (&self.paste_preview, hovered, square)
+                    {
+                        let at = Position::new(x, y);
+                        pattern_overlay(ui, preview, at, self.cell_size, origin, highlight);
+                    }
+
+                    let (pressed, down) =
+                        ui.input(|i| (i.pointer.primary_pressed(), i.pointer.primary_down()));
+                    self.paste_dropped &= down;
+                    if let Some(pos) = painted.filter(|_| !self.paste_dropped) {
+                        if self.paste_preview.is_some() {
+                            if let Some(preview) = self.paste_preview.take_if(|_| pressed) {
+                                self.automata.grid_mut().stamp(&preview, pos);
+                                self.paste_dropped = true;
+                            }
+                        } else if let Some(pattern) = self.stamp {
+                            if pressed {
+                                self.stamp_library_pattern(pattern, pos);
+                            }
+                        } else if self.edit_tool == EditTool::Select {
+                            let anchor = match self.selection {
+                                Some((anchor, _)) if !pressed => anchor,
+                                _ => pos,
+                            };
+                            self.selection = Some((anchor, pos));
+                        } else {
+                            let state = CellState::from_index(self.paint_state);
+                            let _ = self.automata.grid_mut().set(pos, state);
+                        }
+                    }
+                }
+                SimulationMode::Block => {
+                    let painted = grid_view(
+                        ui,
+                        self.margolus.grid(),
+                        self.cell_size,
+                        self.alive_color,
+                        self.dead_color,
+                    );
+                    if let Some(pos) = painted {
+                        let _ = self.margolus.grid_mut().set(pos, CellState::Alive);
+                    }
+                }
+                SimulationMode::Volume => self.show_volume(ui),
+                SimulationMode::Continuous => self.show_lenia(ui),
+                SimulationMode::Elementary => {
+                    // Spacetime diagram, one row per generation scrolling down
+                    let cell_size = (ui.available_width() / self.spacetime.grid().width() as f32)
+                        .clamp(2.0, self.cell_size);
+                    // States shade from the dead colour to the alive colour
+                    let states = self.elementary.rule().states().max(2);
+                    let palette: Vec<Color32> = (0..states)
+                        .map(|state| {
+                            let t = state as f32 / (states - 1) as f32;
+                            self.dead_color.lerp_to_gamma(self.alive_color, t)
+                        })
+                        .collect();
+                    palette_grid_view(ui, self.spacetime.grid(), cell_size, &palette);
+                }
+            }
         });
     }
 }
@@ -0,0 +1,66 @@
+use eframe::egui::{Color32, ColorImage};
+
+use crate::core::float_grid::FloatGrid;
+
+/// Colour maps for drawing continuous cell values
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Colormap {
+    /// Black to white
+    Grayscale,
+    /// Perceptually uniform dark blue, green and yellow
+    #[default]
+    Viridis,
+    /// Black through purple and orange to pale yellow
+    Inferno,
+}
+
+impl Colormap {
+    /// All colour maps, in menu order
+    pub const ALL: [Colormap; 3] = [Colormap::Grayscale, Colormap::Viridis, Colormap::Inferno];
+
+    /// Returns the display name of the colour map
+    pub fn name(&self) -> &str {
+        match self {
+            Colormap::Grayscale => "Grayscale",
+            Colormap::Viridis => "Viridis",
+            Colormap::Inferno => "Inferno",
+        }
+    }
+
+    /// Maps a value between 0.0 and 1.0 to a colour
+    ///
+    /// Values outside the range are clamped.
+    pub fn color(&self, value: f64) -> Color32 {
+        let stops: &[[u8; 3]] = match self {
+            Colormap::Grayscale => &[[0, 0, 0], [255, 255, 255]],
+            Colormap::Viridis => &[
+                [68, 1, 84],
+                [59, 82, 139],
+                [33, 145, 140],
+                [94, 201, 98],
+                [253, 231, 37],
+            ],
+            Colormap::Inferno => &[
+                [0, 0, 4],
+                [87, 16, 110],
+                [188, 55, 84],
+                [249, 142, 9],
+                [252, 255, 164],
+            ],
+        };
+        let position = value.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+        let index = (position.floor() as usize).min(stops.len() - 2);
+        let t = position - index as f64;
+        let channel = |c: usize| {
+            let (a, b) = (stops[index][c] as f64, stops[index + 1][c] as f64);
+            (a + (b - a) * t).round() as u8
+        };
+        Color32::from_rgb(channel(0), channel(1), channel(2))
+    }
+}
+
+/// Renders a continuous grid with one pixel per cell
+pub fn colormap_image(grid: &FloatGrid, colormap: Colormap) -> ColorImage {
+    let pixels = grid.values().iter().map(|&value| colormap.color(value)).collect();
+    ColorImage::new([grid.width(), grid.height()], pixels)
+}
@@ -65,6 +65,7 @@ pub enum ControlAction {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
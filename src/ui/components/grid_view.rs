@@ -1,47 +1,333 @@
 use eframe::egui;
-use eframe::egui::{Color32, Pos2, Rect, Vec2};
+use eframe::egui::{Color32, Pos2, Rect, Sense, Vec2};
 
-use crate::core::cell::CellState;
+use crate::core::cell::{CellState, Position};
 use crate::core::dense_grid::DenseGrid;
 use crate::core::grid::Grid;
+use crate::core::lattice::Lattice;
+use crate::core::turmite::{Ant, Heading};
 
+/// Draws a grid with one colour for live cells and one for dead cells
+///
+/// # Returns
+/// The cell under the pointer while the primary button is held, for editing
 pub fn grid_view(
     ui: &mut egui::Ui,
     grid: &DenseGrid,
     cell_size: f32,
     alive: Color32,
     dead: Color32,
-) {
+) -> Option<Position> {
+    lattice_grid_view(ui, grid, Lattice::Square, cell_size, |state| {
+        state_to_color(state, alive, dead)
+    })
+}
+
+/// Draws a grid of a multi-state rule with one colour per state
+///
+/// # Arguments
+/// * `palette` - Colour of each state, indexed by [`CellState::index`]
+///
+/// # Returns
+/// The cell under the pointer while the primary button is held, for editing
+pub fn palette_grid_view(
+    ui: &mut egui::Ui,
+    grid: &DenseGrid,
+    cell_size: f32,
+    palette: &[Color32],
+) -> Option<Position> {
+    lattice_grid_view(ui, grid, Lattice::Square, cell_size, |state| {
+        palette_color(state, palette)
+    })
+}
+
+/// Draws a grid on any lattice, as squares, hexagons or triangles
+///
+/// # Arguments
+/// * `lattice` - Shape of the cells, see [`cell_polygon`] for the layout
+/// * `cell_size` - Width of a square or hexagon, or side of a triangle
+/// * `color_of` - Colour of a cell in a given state
+///
+/// # Returns
+/// The cell under the pointer while the primary button is held, for editing
+pub fn lattice_grid_view(
+    ui: &mut egui::Ui,
+    grid: &DenseGrid,
+    lattice: Lattice,
+    cell_size: f32,
+    color_of: impl Fn(CellState) -> Color32,
+) -> Option<Position> {
     let available = ui.available_rect_before_wrap();
     let origin = available.min;
     let painter = ui.painter_at(available);
+    let (width, height) = (grid.width(), grid.height());
 
-    for y in 0..grid.height() as usize {
-        for x in 0..grid.width() as usize {
-            let state = grid
-                .get(crate::core::cell::Position::new(x, y))
-                .unwrap_or(CellState::Dead);
-            let color = state_to_color(state, alive, dead);
-            let rect = grid_to_rect(x as f32, y as f32, cell_size, origin);
-            painter.rect_filled(rect, 0.0, color);
+    for y in 0..height {
+        for x in 0..width {
+            let state = grid.get(Position::new(x, y)).unwrap_or(CellState::Dead);
+            let color = color_of(state);
+            if lattice == Lattice::Square {
+                let rect = grid_to_rect(x as f32, y as f32, cell_size, origin);
+                painter.rect_filled(rect, 0.0, color);
+            } else {
+                let points = cell_polygon(lattice, x, y, height, cell_size, origin);
+                painter.add(egui::Shape::convex_polygon(points, color, egui::Stroke::NONE));
+            }
         }
     }
 
     // reserve space so egui layout accounts for our drawing
-    let total_size = Vec2::new(grid.width() as f32 * cell_size, grid.height() as f32 * cell_size);
-    ui.allocate_space(total_size);
+    let total_size = lattice_extent(lattice, width, height, cell_size);
+    let (_, response) = ui.allocate_exact_size(total_size, Sense::click_and_drag());
+
+    if !response.is_pointer_button_down_on() {
+        return None;
+    }
+    let pointer = response.interact_pointer_pos()?;
+    let (x, y) = pixel_to_cell(lattice, pointer, width, height, cell_size, origin)?;
+    Some(Position::new(x, y))
 }
 
+/// Maps a two-state cell to its colour
+///
+/// Additional states of multi-state rules are drawn like live cells; use
+/// [`palette_color`] to tell them apart.
 pub fn state_to_color(state: CellState, alive: Color32, dead: Color32) -> Color32 {
     match state {
-        CellState::Alive => alive,
+        CellState::Alive | CellState::State(_) => alive,
         CellState::Dead => dead,
     }
 }
 
+/// Maps a cell to its colour in a per-state palette
+///
+/// States beyond the end of the palette are drawn in magenta so they stand out.
+pub fn palette_color(state: CellState, palette: &[Color32]) -> Color32 {
+    palette.get(state.index() as usize).copied().unwrap_or(Color32::from_rgb(255, 0, 255))
+}
+
+/// Converts a rule's RGB palette to egui colours
+pub fn palette_from_rgb(rgb: &[[u8; 3]]) -> Vec<Color32> {
+    rgb.iter().map(|&[r, g, b]| Color32::from_rgb(r, g, b)).collect()
+}
+
+/// Radius of a hexagon with the given width between parallel sides
+fn hex_radius(cell_size: f32) -> f32 {
+    cell_size / 3f32.sqrt()
+}
+
+/// Returns the corners of a cell on a lattice, clockwise from the top
+///
+/// Hexagons point up, with `cell_size` between opposite sides. Each row is
+/// shifted half a hexagon left of the one above, so that the cells sharing
+/// an edge are exactly those of
+/// [`HexagonalNeighborhood`](crate::core::hexagonal_neighborhood::HexagonalNeighborhood);
+/// the first row is indented so the last row starts at the left edge.
+///
+/// Triangles have sides of `cell_size` and alternate in orientation along
+/// each row, as described by
+/// [`TriangularNeighborhood`](crate::core::triangular_neighborhood::TriangularNeighborhood).
+///
+/// # Arguments
+/// * `height` - Number of rows in the grid, which fixes the hexagon indent
+pub fn cell_polygon(
+    lattice: Lattice,
+    x: usize,
+    y: usize,
+    height: usize,
+    cell_size: f32,
+    origin: Pos2,
+) -> Vec<Pos2> {
+    let (xf, yf) = (x as f32, y as f32);
+    match lattice {
+        Lattice::Square => {
+            let rect = grid_to_rect(xf, yf, cell_size, origin);
+            vec![rect.left_top(), rect.right_top(), rect.right_bottom(), rect.left_bottom()]
+        }
+        Lattice::Hexagonal => {
+            let radius = hex_radius(cell_size);
+            let indent = cell_size * height.saturating_sub(1) as f32 / 2.0;
+            let center = Pos2::new(
+                origin.x + indent + cell_size * (xf - yf / 2.0) + cell_size / 2.0,
+                origin.y + radius + 1.5 * radius * yf,
+            );
+            (0..6)
+                .map(|corner| {
+                    let angle = (corner as f32 * 60.0 - 90.0).to_radians();
+                    center + Vec2::new(angle.cos(), angle.sin()) * radius
+                })
+                .collect()
+        }
+        Lattice::Triangular => {
+            let row_height = cell_size * 3f32.sqrt() / 2.0;
+            let left = origin.x + xf * cell_size / 2.0;
+            let top = origin.y + yf * row_height;
+            let bottom = top + row_height;
+            let middle = left + cell_size / 2.0;
+            let right = left + cell_size;
+            if (x + y).is_multiple_of(2) {
+                vec![Pos2::new(middle, top), Pos2::new(right, bottom), Pos2::new(left, bottom)]
+            } else {
+                vec![Pos2::new(left, top), Pos2::new(right, top), Pos2::new(middle, bottom)]
+            }
+        }
+    }
+}
+
+/// Returns the size of the area covered by a grid on a lattice
+pub fn lattice_extent(lattice: Lattice, width: usize, height: usize, cell_size: f32) -> Vec2 {
+    let (w, h) = (width as f32, height as f32);
+    match lattice {
+        Lattice::Square => Vec2::new(w, h) * cell_size,
+        Lattice::Hexagonal => {
+            let radius = hex_radius(cell_size);
+            let rows = h.max(1.0) - 1.0;
+            Vec2::new(cell_size * (w + rows / 2.0), 2.0 * radius + 1.5 * radius * rows)
+        }
+        Lattice::Triangular => {
+            Vec2::new(cell_size * (w + 1.0) / 2.0, h * cell_size * 3f32.sqrt() / 2.0)
+        }
+    }
+}
+
+/// Returns the cell containing a screen position, the inverse of [`cell_polygon`]
+///
+/// Positions outside every cell of a `width` x `height` grid give `None`.
+pub fn pixel_to_cell(
+    lattice: Lattice,
+    pos: Pos2,
+    width: usize,
+    height: usize,
+    cell_size: f32,
+    origin: Pos2,
+) -> Option<(usize, usize)> {
+    // Estimate the row and column, then test the cells around the estimate
+    let (row, column) = match lattice {
+        Lattice::Square => {
+            let (x, y) = rect_to_cell(pos, cell_size, origin)?;
+            return (x < width && y < height).then_some((x, y));
+        }
+        Lattice::Hexagonal => {
+            let radius = hex_radius(cell_size);
+            let row = ((pos.y - origin.y - radius / 2.0) / (1.5 * radius)).floor();
+            let indent = cell_size * height.saturating_sub(1) as f32 / 2.0;
+            let column = (pos.x - origin.x - indent) / cell_size + row / 2.0;
+            (row, column.floor())
+        }
+        Lattice::Triangular => {
+            let row = ((pos.y - origin.y) / (cell_size * 3f32.sqrt() / 2.0)).floor();
+            (row, ((pos.x - origin.x) / (cell_size / 2.0)).floor())
+        }
+    };
+
+    for y in (row as isize - 1)..=(row as isize + 1) {
+        for x in (column as isize - 1)..=(column as isize + 1) {
+            if x < 0 || y < 0 || x as usize >= width || y as usize >= height {
+                continue;
+            }
+            let (x, y) = (x as usize, y as usize);
+            if contains(&cell_polygon(lattice, x, y, height, cell_size, origin), pos) {
+                return Some((x, y));
+            }
+        }
+    }
+    None
+}
+
+/// Returns true if a point lies inside a clockwise convex polygon
+fn contains(polygon: &[Pos2], point: Pos2) -> bool {
+    polygon.iter().zip(polygon.iter().cycle().skip(1)).all(|(&a, &b)| {
+        (b - a).x * (point - a).y - (b - a).y * (point - a).x >= 0.0
+    })
+}
+
+/// Draws ants as triangles pointing in their heading
+///
+/// Ants move on the square lattice only.
+///
+/// # Arguments
+/// * `origin` - Top-left corner of the grid, as used by [`grid_view`]
+pub fn ant_overlay(ui: &egui::Ui, ants: &[Ant], cell_size: f32, origin: Pos2, color: Color32) {
+    let painter = ui.painter();
+    for ant in ants {
+        let points = ant_triangle(ant.position, ant.heading, cell_size, origin);
+        painter.add(egui::Shape::convex_polygon(points.to_vec(), color, egui::Stroke::NONE));
+    }
+}
+
+/// Outlines a rectangle of cells, such as the current selection
+///
+/// # Arguments
+/// * `min` - Top-left cell of the rectangle
+/// * `max` - Bottom-right cell of the rectangle, inclusive
+/// * `origin` - Top-left corner of the grid, as used by [`grid_view`]
+pub fn selection_overlay(
+    ui: &egui::Ui,
+    min: Position,
+    max: Position,
+    cell_size: f32,
+    origin: Pos2,
+    color: Color32,
+) {
+    let rect = selection_rect(min, max, cell_size, origin);
+    ui.painter().rect_stroke(rect, 0.0, egui::Stroke::new(2.0, color), egui::StrokeKind::Outside);
+}
+
+/// Returns the screen rectangle covering the cells from `min` to `max`
+pub fn selection_rect(min: Position, max: Position, cell_size: f32, origin: Pos2) -> Rect {
+    let top_left = grid_to_rect(min.x as f32, min.y as f32, cell_size, origin);
+    let bottom_right = grid_to_rect(max.x as f32, max.y as f32, cell_size, origin);
+    top_left.union(bottom_right)
+}
+
+/// Draws the non-dead cells of a pattern translucently, as a preview of
+/// where it would be pasted
+///
+/// # Arguments
+/// * `at` - Cell the top-left corner of the pattern would land on
+/// * `origin` - Top-left corner of the grid, as used by [`grid_view`]
+pub fn pattern_overlay(
+    ui: &egui::Ui,
+    pattern: &DenseGrid,
+    at: Position,
+    cell_size: f32,
+    origin: Pos2,
+    color: Color32,
+) {
+    let painter = ui.painter();
+    let outline = selection_rect(
+        at,
+        Position::new(at.x + pattern.width() - 1, at.y + pattern.height() - 1),
+        cell_size,
+        origin,
+    );
+    painter.rect_stroke(outline, 0.0, egui::Stroke::new(1.0, color), egui::StrokeKind::Outside);
+    let fill = color.gamma_multiply(0.6);
+    for (pos, _) in pattern.iter().filter(|(_, state)| !state.is_dead()) {
+        let rect = grid_to_rect((at.x + pos.x) as f32, (at.y + pos.y) as f32, cell_size, origin);
+        painter.rect_filled(rect, 0.0, fill);
+    }
+}
+
+/// Returns the corners of the triangle marking an ant, tip first
+pub fn ant_triangle(position: Position, heading: Heading, cell_size: f32, origin: Pos2) -> [Pos2; 3] {
+    let rect = grid_to_rect(position.x as f32, position.y as f32, cell_size, origin);
+    let center = rect.center();
+    let (dx, dy) = heading.offset();
+    let forward = Vec2::new(dx as f32, dy as f32) * cell_size * 0.4;
+    let side = Vec2::new(-forward.y, forward.x);
+    [center + forward, center - forward + side, center - forward - side]
+}
+
 pub fn grid_to_rect(x: f32, y: f32, cell_size: f32, origin: Pos2) -> Rect {
     let min = Pos2::new(origin.x + x * cell_size, origin.y + y * cell_size);
     let max = Pos2::new(min.x + cell_size, min.y + cell_size);
     Rect { min, max }
 }
 
+/// Returns the cell containing a screen position, the inverse of [`grid_to_rect`]
+pub fn rect_to_cell(pos: Pos2, cell_size: f32, origin: Pos2) -> Option<(usize, usize)> {
+    let x = ((pos.x - origin.x) / cell_size).floor();
+    let y = ((pos.y - origin.y) / cell_size).floor();
+    (x >= 0.0 && y >= 0.0).then_some((x as usize, y as usize))
+}
@@ -1,4 +1,6 @@
 pub mod grid_view;
 pub mod control_panel;
+pub mod voxel_view;
+pub mod colormap;
 
 pub use control_panel::{ControlAction, ControlPanel};
@@ -0,0 +1,90 @@
+use eframe::egui::{Color32, ColorImage};
+
+use crate::core::cell::{CellState, Position3D};
+use crate::core::dense_grid_3d::DenseGrid3D;
+
+/// Returns the pixel size of the isometric image of a grid
+///
+/// Each voxel is drawn as a cube `4 * scale` pixels wide and tall.
+pub fn isometric_size(width: usize, height: usize, depth: usize, scale: usize) -> [usize; 2] {
+    let q = scale.max(1);
+    [
+        2 * q * (width + height),
+        q * (width + height) + 2 * q * depth,
+    ]
+}
+
+/// Returns the top-left corner of a voxel's sprite in the isometric image
+///
+/// The x axis runs down to the right, y down to the left and z straight up,
+/// so the viewer looks at the cube from the (+x, +y, +z) corner.
+pub fn isometric_origin(
+    pos: Position3D,
+    height: usize,
+    depth: usize,
+    scale: usize,
+) -> (usize, usize) {
+    let q = scale.max(1);
+    let x = 2 * q * (pos.x + height - 1 - pos.y);
+    let y = q * (pos.x + pos.y) + 2 * q * (depth - 1 - pos.z);
+    (x, y)
+}
+
+/// Renders the non-dead cells of a grid as isometric cubes on the CPU
+///
+/// Cubes are painted back to front, with the top face in the cell's colour
+/// and the two visible sides darkened. Empty space is transparent.
+///
+/// # Arguments
+/// * `scale` - Quarter of a cube's width in pixels
+/// * `color_of` - Colour of a cell in a given state
+pub fn isometric_image(
+    grid: &DenseGrid3D,
+    scale: usize,
+    color_of: impl Fn(CellState) -> Color32,
+) -> ColorImage {
+    let q = scale.max(1);
+    let size = isometric_size(grid.width(), grid.height(), grid.depth(), q);
+    let mut pixels = vec![Color32::TRANSPARENT; size[0] * size[1]];
+
+    let mut voxels: Vec<_> = grid.iter().filter(|(_, state)| !state.is_dead()).collect();
+    voxels.sort_by_key(|(pos, _)| pos.x + pos.y + pos.z);
+
+    for (pos, state) in voxels {
+        let color = color_of(state);
+        let faces = [color, shade(color, 0.75), shade(color, 0.55)];
+        let (left, top) = isometric_origin(pos, grid.height(), grid.depth(), q);
+        for v in 0..4 * q {
+            for u in 0..4 * q {
+                if let Some(face) = cube_face(u, v, q) {
+                    pixels[(top + v) * size[0] + left + u] = faces[face];
+                }
+            }
+        }
+    }
+    ColorImage::new(size, pixels)
+}
+
+/// Returns which face of a cube sprite covers a pixel: 0 for the top, 1 for
+/// the left side, 2 for the right side
+fn cube_face(u: usize, v: usize, q: usize) -> Option<usize> {
+    // Work in half pixels so the faces meet without gaps
+    let u2 = 2 * u as isize + 1;
+    let v2 = 2 * v as isize + 1;
+    let q2 = 2 * q as isize;
+    let from_edge = u2.min(4 * q2 - u2);
+    let top_below = q2 + from_edge / 2;
+    if (v2 - q2).abs() * 2 <= from_edge {
+        Some(0)
+    } else if v2 > top_below && v2 <= top_below + 2 * q2 {
+        Some(if u2 < 2 * q2 { 1 } else { 2 })
+    } else {
+        None
+    }
+}
+
+/// Darkens a colour by a factor
+fn shade(color: Color32, factor: f32) -> Color32 {
+    let scale = |c: u8| (c as f32 * factor) as u8;
+    Color32::from_rgb(scale(color.r()), scale(color.g()), scale(color.b()))
+}
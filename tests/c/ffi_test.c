@@ -0,0 +1,171 @@
+/*
+ * Exercises the C API through include/cellularity.h
+ *
+ * Build and run from the repository root with `make -C tests/c`.
+ */
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "cellularity.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                  \
+    do {                                                                  \
+        if (!(condition)) {                                               \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,        \
+                    __LINE__, #condition);                                \
+            failures++;                                                   \
+        }                                                                 \
+    } while (0)
+
+#define CHECK_STATUS(call, expected)                                      \
+    do {                                                                  \
+        CellularityStatus status_ = (call);                               \
+        if (status_ != (expected)) {                                      \
+            fprintf(stderr, "%s:%d: %s returned %s (%s)\n", __FILE__,     \
+                    __LINE__, #call, cellularity_status_name(status_),    \
+                    cellularity_last_error());                            \
+            failures++;                                                   \
+        }                                                                 \
+    } while (0)
+
+static void test_blinker(void) {
+    CellularityAutomaton *automaton = NULL;
+    CHECK_STATUS(cellularity_automaton_new("B3/S23", 5, 5, "plane", &automaton),
+                 CELLULARITY_STATUS_OK);
+    CHECK(automaton != NULL);
+    CHECK(cellularity_automaton_width(automaton) == 5);
+    CHECK(cellularity_automaton_height(automaton) == 5);
+
+    for (size_t x = 1; x <= 3; x++) {
+        CHECK_STATUS(cellularity_automaton_set_cell(automaton, x, 2, 1), CELLULARITY_STATUS_OK);
+    }
+    CHECK(cellularity_automaton_population(automaton) == 3);
+
+    CHECK_STATUS(cellularity_automaton_step(automaton, 1), CELLULARITY_STATUS_OK);
+    CHECK(cellularity_automaton_generation(automaton) == 1);
+
+    uint8_t state = 0;
+    CHECK_STATUS(cellularity_automaton_get_cell(automaton, 2, 1, &state), CELLULARITY_STATUS_OK);
+    CHECK(state == 1);
+    CHECK_STATUS(cellularity_automaton_get_cell(automaton, 1, 2, &state), CELLULARITY_STATUS_OK);
+    CHECK(state == 0);
+
+    uint8_t cells[25];
+    CHECK_STATUS(cellularity_automaton_read_grid(automaton, cells, sizeof cells),
+                 CELLULARITY_STATUS_OK);
+    for (size_t y = 0; y < 5; y++) {
+        for (size_t x = 0; x < 5; x++) {
+            CHECK(cells[y * 5 + x] == (x == 2 && y >= 1 && y <= 3));
+        }
+    }
+
+    cellularity_automaton_free(automaton);
+}
+
+static void test_grid_buffers(void) {
+    CellularityAutomaton *automaton = NULL;
+    CHECK_STATUS(cellularity_automaton_new("Wireworld", 4, 2, NULL, &automaton),
+                 CELLULARITY_STATUS_OK);
+
+    uint8_t written[8] = {3, 1, 2, 3, 0, 0, 0, 3};
+    CHECK_STATUS(cellularity_automaton_write_grid(automaton, written, sizeof written),
+                 CELLULARITY_STATUS_OK);
+    uint8_t read[8] = {0};
+    CHECK_STATUS(cellularity_automaton_read_grid(automaton, read, sizeof read),
+                 CELLULARITY_STATUS_OK);
+    CHECK(memcmp(written, read, sizeof read) == 0);
+
+    CHECK_STATUS(cellularity_automaton_read_grid(automaton, read, 7),
+                 CELLULARITY_STATUS_BUFFER_SIZE);
+    CHECK(strstr(cellularity_last_error(), "8 cells") != NULL);
+
+    CHECK_STATUS(cellularity_automaton_reset(automaton), CELLULARITY_STATUS_OK);
+    CHECK(cellularity_automaton_population(automaton) == 0);
+
+    cellularity_automaton_free(automaton);
+}
+
+static void test_load_rle(void) {
+    CellularityAutomaton *automaton = NULL;
+    CHECK_STATUS(cellularity_automaton_new("B3/S23", 20, 20, "torus", &automaton),
+                 CELLULARITY_STATUS_OK);
+
+    const char *glider = "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n";
+    CHECK_STATUS(cellularity_automaton_load_rle(automaton, glider, CELLULARITY_CENTER,
+                                                CELLULARITY_CENTER, true),
+                 CELLULARITY_STATUS_OK);
+    CHECK(cellularity_automaton_population(automaton) == 5);
+
+    uint8_t state = 0;
+    CHECK_STATUS(cellularity_automaton_get_cell(automaton, 9, 8, &state), CELLULARITY_STATUS_OK);
+    CHECK(state == 1);
+
+    /* A glider keeps its five cells on a torus */
+    CHECK_STATUS(cellularity_automaton_step(automaton, 80), CELLULARITY_STATUS_OK);
+    CHECK(cellularity_automaton_population(automaton) == 5);
+
+    CHECK_STATUS(cellularity_automaton_load_rle(automaton, "bo$2bo$3o!", 0, 0, false),
+                 CELLULARITY_STATUS_OK);
+    CHECK(cellularity_automaton_population(automaton) >= 5);
+
+    CHECK_STATUS(cellularity_automaton_load_rle(automaton, "3o!", 20, 0, true),
+                 CELLULARITY_STATUS_OUT_OF_BOUNDS);
+    CHECK_STATUS(cellularity_automaton_load_rle(automaton, "x = 1, y = 1\nq!", 0, 0, true),
+                 CELLULARITY_STATUS_PATTERN_PARSE);
+
+    cellularity_automaton_free(automaton);
+}
+
+static void test_errors(void) {
+    CellularityAutomaton *automaton = NULL;
+    CHECK_STATUS(cellularity_automaton_new("B9/S", 10, 10, NULL, &automaton),
+                 CELLULARITY_STATUS_INVALID_RULE);
+    CHECK(automaton == NULL);
+    CHECK(strlen(cellularity_last_error()) > 0);
+
+    CHECK_STATUS(cellularity_automaton_new("B3/S23", 0, 10, NULL, &automaton),
+                 CELLULARITY_STATUS_INVALID_DIMENSIONS);
+    CHECK_STATUS(cellularity_automaton_new("B3/S23", 10, 10, "donut", &automaton),
+                 CELLULARITY_STATUS_OTHER);
+    CHECK_STATUS(cellularity_automaton_new(NULL, 10, 10, NULL, &automaton),
+                 CELLULARITY_STATUS_NULL_POINTER);
+    CHECK_STATUS(cellularity_automaton_new("B3/S23", 10, 10, NULL, NULL),
+                 CELLULARITY_STATUS_NULL_POINTER);
+    CHECK_STATUS(cellularity_automaton_new("B3/S23\xff", 10, 10, NULL, &automaton),
+                 CELLULARITY_STATUS_INVALID_UTF8);
+
+    CHECK_STATUS(cellularity_automaton_new("B3/S23", 10, 10, NULL, &automaton),
+                 CELLULARITY_STATUS_OK);
+    CHECK(strlen(cellularity_last_error()) == 0);
+
+    uint8_t state = 0;
+    CHECK_STATUS(cellularity_automaton_get_cell(automaton, 10, 0, &state),
+                 CELLULARITY_STATUS_OUT_OF_BOUNDS);
+    CHECK_STATUS(cellularity_automaton_set_cell(automaton, 0, 10, 1),
+                 CELLULARITY_STATUS_OUT_OF_BOUNDS);
+    CHECK_STATUS(cellularity_automaton_get_cell(automaton, 0, 0, NULL),
+                 CELLULARITY_STATUS_NULL_POINTER);
+    CHECK_STATUS(cellularity_automaton_step(NULL, 1), CELLULARITY_STATUS_NULL_POINTER);
+    CHECK(cellularity_automaton_width(NULL) == 0);
+    CHECK(strcmp(cellularity_status_name(CELLULARITY_STATUS_OUT_OF_BOUNDS), "out of bounds") == 0);
+
+    cellularity_automaton_free(automaton);
+    cellularity_automaton_free(NULL);
+}
+
+int main(void) {
+    test_blinker();
+    test_grid_buffers();
+    test_load_rle();
+    test_errors();
+
+    if (failures > 0) {
+        fprintf(stderr, "%d check(s) failed\n", failures);
+        return EXIT_FAILURE;
+    }
+    printf("all C API checks passed\n");
+    return EXIT_SUCCESS;
+}
@@ -1,6 +1,12 @@
 #[path = "ui/grid_view_tests.rs"]
 mod grid_view_tests;
 
+#[path = "ui/voxel_view_tests.rs"]
+mod voxel_view_tests;
+
+#[path = "ui/colormap_tests.rs"]
+mod colormap_tests;
+
 #[path = "ui/smoke.rs"]
 mod smoke;
 
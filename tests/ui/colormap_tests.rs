@@ -0,0 +1,25 @@
+// Tests derived from src/ui/components/colormap.rs
+
+use cellularity::ui::components::colormap::{colormap_image, Colormap};
+use cellularity::{FloatGrid, Position};
+use eframe::egui::Color32;
+
+#[test]
+fn colormaps_span_their_stops() {
+    assert_eq!(Colormap::Grayscale.color(0.0), Color32::BLACK);
+    assert_eq!(Colormap::Grayscale.color(1.0), Color32::WHITE);
+    assert_eq!(Colormap::Grayscale.color(0.5), Color32::from_rgb(128, 128, 128));
+    assert_eq!(Colormap::Viridis.color(-1.0), Colormap::Viridis.color(0.0));
+    assert_eq!(Colormap::Viridis.color(1.0), Color32::from_rgb(253, 231, 37));
+    assert_eq!(Colormap::Inferno.color(2.0), Color32::from_rgb(252, 255, 164));
+}
+
+#[test]
+fn colormap_image_has_one_pixel_per_cell() {
+    let mut grid = FloatGrid::new(3, 2).unwrap();
+    grid.set(Position::new(2, 1), 1.0).unwrap();
+    let image = colormap_image(&grid, Colormap::Grayscale);
+    assert_eq!(image.size, [3, 2]);
+    assert_eq!(image.pixels[5], Color32::WHITE);
+    assert_eq!(image.pixels[0], Color32::BLACK);
+}
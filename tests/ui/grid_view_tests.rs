@@ -1,6 +1,11 @@
-use cellularity::ui::components::grid_view::{grid_to_rect, state_to_color};
-use cellularity::core::cell::CellState;
-use eframe::egui::{pos2, Color32};
+use cellularity::ui::components::grid_view::{
+    ant_triangle, cell_polygon, grid_to_rect, lattice_extent, palette_color, palette_from_rgb,
+    pixel_to_cell, rect_to_cell, selection_rect, state_to_color,
+};
+use cellularity::core::lattice::Lattice;
+use cellularity::core::cell::{CellState, Position};
+use cellularity::core::turmite::Heading;
+use eframe::egui::{pos2, Color32, Pos2};
 
 #[test]
 fn grid_to_rect_maps_cells_correctly() {
@@ -28,3 +33,93 @@ fn state_to_color_matches_mapping() {
     assert_eq!(state_to_color(CellState::Dead, alive, dead), dead);
 }
 
+
+#[test]
+fn rect_to_cell_inverts_grid_to_rect() {
+    let origin = pos2(2.5, 3.5);
+    let rect = grid_to_rect(4.0, 7.0, 8.0, origin);
+    assert_eq!(rect_to_cell(rect.center(), 8.0, origin), Some((4, 7)));
+    assert_eq!(rect_to_cell(rect.min, 8.0, origin), Some((4, 7)));
+    assert_eq!(rect_to_cell(pos2(0.0, 10.0), 8.0, origin), None);
+}
+
+#[test]
+fn palette_color_indexes_by_state() {
+    let palette = palette_from_rgb(&[[0, 0, 0], [0, 0, 255], [255, 0, 0], [255, 255, 0]]);
+    assert_eq!(palette_color(CellState::Dead, &palette), Color32::BLACK);
+    assert_eq!(palette_color(CellState::Alive, &palette), Color32::from_rgb(0, 0, 255));
+    assert_eq!(palette_color(CellState::State(3), &palette), Color32::from_rgb(255, 255, 0));
+    assert_eq!(palette_color(CellState::State(9), &palette), Color32::from_rgb(255, 0, 255));
+}
+
+#[test]
+fn ant_triangle_points_along_heading() {
+    let origin = pos2(0.0, 0.0);
+    let [tip, left, right] = ant_triangle(Position::new(1, 1), Heading::East, 10.0, origin);
+    let center = grid_to_rect(1.0, 1.0, 10.0, origin).center();
+    assert!(tip.x > center.x && tip.y == center.y);
+    assert!(left.x < center.x && right.x < center.x);
+    assert!(grid_to_rect(1.0, 1.0, 10.0, origin).contains(tip));
+}
+
+fn centroid(points: &[Pos2]) -> Pos2 {
+    let sum = points.iter().fold(pos2(0.0, 0.0), |sum, p| sum + p.to_vec2());
+    pos2(sum.x / points.len() as f32, sum.y / points.len() as f32)
+}
+
+fn shared_corners(a: &[Pos2], b: &[Pos2]) -> usize {
+    a.iter().filter(|p| b.iter().any(|q| p.distance(*q) < 1e-3)).count()
+}
+
+#[test]
+fn hexagons_share_edges_with_their_neighbors() {
+    let origin = pos2(0.0, 0.0);
+    let hex = |x, y| cell_polygon(Lattice::Hexagonal, x, y, 5, 10.0, origin);
+    assert_eq!(hex(2, 2).len(), 6);
+    for (x, y) in [(1, 1), (2, 1), (1, 2), (3, 2), (2, 3), (3, 3)] {
+        assert_eq!(shared_corners(&hex(2, 2), &hex(x, y)), 2, "({}, {})", x, y);
+    }
+    assert_eq!(shared_corners(&hex(2, 2), &hex(3, 1)), 0);
+    assert_eq!(shared_corners(&hex(2, 2), &hex(1, 3)), 0);
+}
+
+#[test]
+fn triangles_alternate_and_share_edges() {
+    let origin = pos2(0.0, 0.0);
+    let tri = |x, y| cell_polygon(Lattice::Triangular, x, y, 4, 10.0, origin);
+    let up = tri(2, 2);
+    let down = tri(3, 2);
+    assert!(up[0].y < up[1].y, "up triangles have their tip first");
+    assert!(down[0].y < down[2].y, "down triangles have their tip last");
+    assert_eq!(shared_corners(&up, &down), 2);
+    assert_eq!(shared_corners(&up, &tri(2, 3)), 2);
+    assert_eq!(shared_corners(&up, &tri(2, 1)), 1);
+}
+
+#[test]
+fn pixel_to_cell_inverts_cell_polygon() {
+    let origin = pos2(4.0, 6.0);
+    let (width, height) = (7, 5);
+    for lattice in [Lattice::Square, Lattice::Hexagonal, Lattice::Triangular] {
+        let extent = lattice_extent(lattice, width, height, 12.0);
+        for y in 0..height {
+            for x in 0..width {
+                let polygon = cell_polygon(lattice, x, y, height, 12.0, origin);
+                for p in &polygon {
+                    assert!(p.x <= origin.x + extent.x + 1e-3 && p.y <= origin.y + extent.y + 1e-3);
+                }
+                let hit = pixel_to_cell(lattice, centroid(&polygon), width, height, 12.0, origin);
+                assert_eq!(hit, Some((x, y)), "{:?}", lattice);
+            }
+        }
+        assert_eq!(pixel_to_cell(lattice, pos2(0.0, 0.0), width, height, 12.0, origin), None);
+    }
+}
+
+#[test]
+fn selection_rect_spans_both_corner_cells() {
+    let origin = pos2(10.0, 20.0);
+    let rect = selection_rect(Position::new(1, 2), Position::new(3, 2), 8.0, origin);
+    assert_eq!(rect.min, pos2(18.0, 36.0));
+    assert_eq!(rect.max, pos2(42.0, 44.0));
+}
@@ -0,0 +1,29 @@
+// Tests derived from src/ui/components/voxel_view.rs
+
+use cellularity::ui::components::voxel_view::{isometric_image, isometric_origin, isometric_size};
+use cellularity::{CellState, DenseGrid3D, Position3D};
+use eframe::egui::Color32;
+
+#[test]
+fn isometric_image_draws_shaded_cubes() {
+    assert_eq!(isometric_size(2, 3, 4, 2), [20, 26]);
+
+    let mut grid = DenseGrid3D::new(2, 2, 2).unwrap();
+    let empty = isometric_image(&grid, 2, |_| Color32::WHITE);
+    assert!(empty.pixels.iter().all(|&p| p == Color32::TRANSPARENT));
+
+    let pos = Position3D::new(1, 0, 0);
+    grid.set(pos, CellState::Alive).unwrap();
+    let image = isometric_image(&grid, 2, |_| Color32::from_rgb(200, 200, 200));
+    let (left, top) = isometric_origin(pos, 2, 2, 2);
+    let pixel = |u: usize, v: usize| image.pixels[(top + v) * image.size[0] + left + u];
+    // Top face in full colour, the right side darker than the left
+    assert_eq!(pixel(4, 2), Color32::from_rgb(200, 200, 200));
+    assert!(pixel(1, 5).r() < 200);
+    assert!(pixel(6, 5).r() < pixel(1, 5).r());
+    // Corners of the sprite stay transparent
+    assert_eq!(pixel(0, 0), Color32::TRANSPARENT);
+    assert_eq!(pixel(7, 7), Color32::TRANSPARENT);
+    let drawn = image.pixels.iter().filter(|&&p| p != Color32::TRANSPARENT).count();
+    assert_eq!(drawn, 3 * 16);
+}
@@ -10,5 +10,56 @@ mod boundary_tests;
 mod rule_tests;
 #[path = "unit/automata_tests.rs"]
 mod automata_tests;
-
-
+#[path = "unit/elementary_tests.rs"]
+mod elementary_tests;
+#[path = "unit/prefix_sum_tests.rs"]
+mod prefix_sum_tests;
+#[path = "unit/larger_than_life_tests.rs"]
+mod larger_than_life_tests;
+#[path = "unit/weighted_life_tests.rs"]
+mod weighted_life_tests;
+#[path = "unit/hensel_tests.rs"]
+mod hensel_tests;
+#[path = "unit/multi_state_tests.rs"]
+mod multi_state_tests;
+#[path = "unit/turmite_tests.rs"]
+mod turmite_tests;
+#[path = "unit/margolus_tests.rs"]
+mod margolus_tests;
+#[path = "unit/lattice_tests.rs"]
+mod lattice_tests;
+#[path = "unit/life_3d_tests.rs"]
+mod life_3d_tests;
+#[path = "unit/lenia_tests.rs"]
+mod lenia_tests;
+#[path = "unit/stochastic_tests.rs"]
+mod stochastic_tests;
+#[path = "unit/topology_tests.rs"]
+mod topology_tests;
+#[path = "unit/pattern_library_tests.rs"]
+mod pattern_library_tests;
+#[path = "unit/rle_tests.rs"]
+mod rle_tests;
+#[path = "unit/transform_tests.rs"]
+mod transform_tests;
+#[path = "unit/recorder_tests.rs"]
+mod recorder_tests;
+#[path = "unit/image_import_tests.rs"]
+mod image_import_tests;
+#[path = "unit/svg_export_tests.rs"]
+mod svg_export_tests;
+#[cfg(feature = "server")]
+#[path = "unit/server_tests.rs"]
+mod server_tests;
+#[cfg(feature = "server")]
+#[path = "unit/stream_tests.rs"]
+mod stream_tests;
+#[cfg(feature = "python")]
+#[path = "unit/python_tests.rs"]
+mod python_tests;
+#[cfg(feature = "ffi")]
+#[path = "unit/ffi_tests.rs"]
+mod ffi_tests;
+#[cfg(feature = "scripting")]
+#[path = "unit/scripting_tests.rs"]
+mod scripting_tests;
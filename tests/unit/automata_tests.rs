@@ -1,3 +1,4 @@
+use cellularity::core::rules::{Neighbors, Rule};
 use cellularity::{
     Automata, ConwayRule, ToroidalBoundary, MooreNeighborhood, CellState, Position, Grid,
 };
@@ -113,3 +114,59 @@ fn test_empty_grid_stays_empty() {
 }
 
 
+
+/// Makes a cell alive whenever it is told which of its neighbors are alive
+struct ConfigurationProbe {
+    configuration: bool,
+}
+
+impl Rule for ConfigurationProbe {
+    fn apply(&self, current_state: CellState, _alive_neighbors: usize) -> CellState {
+        current_state
+    }
+
+    fn apply_neighbors(&self, _current_state: CellState, neighbors: &Neighbors) -> CellState {
+        if neighbors.mask != 0 || !neighbors.states.is_empty() {
+            CellState::Alive
+        } else {
+            CellState::Dead
+        }
+    }
+
+    fn needs_configuration(&self) -> bool {
+        self.configuration
+    }
+
+    fn name(&self) -> &str {
+        "Configuration probe"
+    }
+}
+
+#[test]
+fn test_configuration_is_only_given_to_rules_that_need_it() {
+    for configuration in [false, true] {
+        let mut automata = create_test_automata(5, 5);
+        automata.set_rule(Box::new(ConfigurationProbe { configuration }));
+        automata.grid_mut().set(Position::new(2, 2), CellState::Alive).unwrap();
+        automata.step();
+        let expected = if configuration { 25 } else { 0 };
+        assert_eq!(automata.grid().count_alive(), expected, "{}", configuration);
+    }
+}
+
+#[test]
+fn test_grid_storage_stays_put_across_steps() {
+    let mut automata = create_test_automata(6, 6);
+    for x in 1..=3 {
+        automata.grid_mut().set(Position::new(x, 2), CellState::Alive).unwrap();
+    }
+    let storage = automata.grid().states().as_ptr();
+    automata.step_n(3);
+    assert_eq!(automata.grid().states().as_ptr(), storage);
+    // The blinker is vertical after an odd number of steps
+    let column: Vec<u8> = (1..=3).map(|y| automata.grid().states()[y * 6 + 2]).collect();
+    assert_eq!(column, [1, 1, 1]);
+
+    automata.grid_mut().states_mut()[0] = 3;
+    assert_eq!(automata.grid().get(Position::new(0, 0)).unwrap(), CellState::State(3));
+}
@@ -1,4 +1,7 @@
-use cellularity::{Boundary, ToroidalBoundary, WalledBoundary, Position};
+use cellularity::{
+    Automata, Boundary, CellState, ConstantBoundary, ConwayRule, DeadBoundary, EdgeBehavior,
+    Grid, MixedBoundary, MooreNeighborhood, Position, ToroidalBoundary, WalledBoundary,
+};
 
 // Tests derived from src/core/boundary.rs using a local mock
 struct MockBoundary;
@@ -166,4 +169,80 @@ fn test_walled_boundary_single_cell() {
     assert_eq!(boundary.wrap(5, 5, 1, 1).unwrap(), Position::new(0, 0));
 }
 
+// Dead, constant and mixed boundary tests
+fn conway_automata(width: usize, height: usize, boundary: Box<dyn Boundary>) -> Automata {
+    Automata::new(
+        width,
+        height,
+        Box::new(ConwayRule::new()),
+        boundary,
+        Box::new(MooreNeighborhood::new()),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_dead_boundary_treats_all_edges_alike() {
+    let boundary = DeadBoundary::new();
+    assert_eq!(boundary.wrap(9, 9, 10, 10), Some(Position::new(9, 9)));
+    assert_eq!(boundary.wrap(-1, 5, 10, 10), None);
+    assert_eq!(boundary.wrap(10, 5, 10, 10), None);
+    assert_eq!(boundary.wrap(5, -1, 10, 10), None);
+    assert_eq!(boundary.wrap(5, 10, 10, 10), None);
+    assert_eq!(boundary.outside_state(), CellState::Dead);
+}
+
+#[test]
+fn test_dead_boundary_keeps_corner_blocks_still() {
+    // Clamping would count the corner cells as their own neighbors
+    for (x, y) in [(0, 0), (4, 0), (0, 4), (4, 4)] {
+        let mut automata = conway_automata(6, 6, Box::new(DeadBoundary::new()));
+        let block: Vec<Position> = [(0, 0), (1, 0), (0, 1), (1, 1)]
+            .iter()
+            .map(|&(dx, dy)| Position::new(x + dx, y + dy))
+            .collect();
+        for &pos in &block {
+            automata.grid_mut().set(pos, CellState::Alive).unwrap();
+        }
+        automata.step();
+        assert_eq!(automata.grid().count_alive(), 4);
+        assert!(block.iter().all(|&pos| automata.grid().get(pos).unwrap().is_alive()));
+    }
+}
+
+#[test]
+fn test_constant_boundary_reads_configured_state() {
+    let boundary = ConstantBoundary::new(CellState::Alive);
+    assert_eq!(boundary.state(), CellState::Alive);
+    assert_eq!(boundary.wrap(-1, 0, 3, 3), None);
+    assert_eq!(boundary.outside_state(), CellState::Alive);
 
+    // Edge cells of an empty grid see three live border cells and are born,
+    // corners see five and the centre none
+    let mut automata = conway_automata(3, 3, Box::new(boundary));
+    automata.step();
+    let alive: Vec<Position> = automata
+        .grid()
+        .iter()
+        .filter(|(_, state)| state.is_alive())
+        .map(|(pos, _)| pos)
+        .collect();
+    assert_eq!(
+        alive,
+        vec![Position::new(1, 0), Position::new(0, 1), Position::new(2, 1), Position::new(1, 2)]
+    );
+}
+
+#[test]
+fn test_mixed_boundary_resolves_each_pair_of_edges() {
+    let cylinder = MixedBoundary::new(EdgeBehavior::Wrap, EdgeBehavior::Dead);
+    assert_eq!(cylinder.wrap(-1, 3, 10, 8), Some(Position::new(9, 3)));
+    assert_eq!(cylinder.wrap(10, 3, 10, 8), Some(Position::new(0, 3)));
+    assert_eq!(cylinder.wrap(3, -1, 10, 8), None);
+    assert_eq!(cylinder.wrap(3, 8, 10, 8), None);
+
+    let mirrored = MixedBoundary::new(EdgeBehavior::Dead, EdgeBehavior::Mirror);
+    assert_eq!(mirrored.wrap(3, -1, 10, 8), Some(Position::new(3, 0)));
+    assert_eq!(mirrored.wrap(3, 9, 10, 8), Some(Position::new(3, 6)));
+    assert_eq!(mirrored.wrap(-1, 3, 10, 8), None);
+}
@@ -0,0 +1,174 @@
+use cellularity::{
+    CellState, EdgeCondition, ElementaryAutomata, Grid, Position, Rule1D, SpacetimeDiagram,
+    TotalisticCodeRule, WolframRule,
+};
+use cellularity::core::rules::wolfram::MAX_TOTALISTIC_RADIUS;
+
+fn seeded(width: usize, rule: Box<dyn Rule1D>, edge: EdgeCondition) -> ElementaryAutomata {
+    let mut automata = ElementaryAutomata::new(width, rule, edge).unwrap();
+    automata.seed_center();
+    automata
+}
+
+// Tests derived from src/core/rules/wolfram.rs
+#[test]
+fn test_wolfram_rule_lookup() {
+    let rule = WolframRule::new(30);
+    // 30 = 0b00011110: 100, 011, 010, 001 -> 1
+    assert_eq!(rule.apply(&[1, 1, 1]), 0);
+    assert_eq!(rule.apply(&[1, 1, 0]), 0);
+    assert_eq!(rule.apply(&[1, 0, 1]), 0);
+    assert_eq!(rule.apply(&[1, 0, 0]), 1);
+    assert_eq!(rule.apply(&[0, 1, 1]), 1);
+    assert_eq!(rule.apply(&[0, 1, 0]), 1);
+    assert_eq!(rule.apply(&[0, 0, 1]), 1);
+    assert_eq!(rule.apply(&[0, 0, 0]), 0);
+}
+
+#[test]
+fn test_wolfram_rule_name() {
+    assert_eq!(WolframRule::new(110).name(), "Rule 110");
+    assert_eq!(WolframRule::new(110).radius(), 1);
+    assert_eq!(WolframRule::new(110).states(), 2);
+}
+
+#[test]
+fn test_wolfram_rule_with_radius_validation() {
+    assert!(WolframRule::with_radius(u32::MAX as u128, 2).is_ok());
+    assert!(WolframRule::with_radius(1 << 32, 2).is_err());
+    assert!(WolframRule::with_radius(u128::MAX, 3).is_ok());
+    assert!(WolframRule::with_radius(1, 4).is_err());
+}
+
+#[test]
+fn test_totalistic_code_validation() {
+    // k=3, r=1 has 7 possible sums, so codes must be below 3^7 = 2187
+    assert!(TotalisticCodeRule::new(1635, 3, 1).is_ok());
+    assert!(TotalisticCodeRule::new(2187, 3, 1).is_err());
+    assert!(TotalisticCodeRule::new(1, 1, 1).is_err());
+    assert!(TotalisticCodeRule::new(10, 255, MAX_TOTALISTIC_RADIUS).is_ok());
+    assert!(TotalisticCodeRule::new(10, 2, MAX_TOTALISTIC_RADIUS + 1).is_err());
+    assert!(TotalisticCodeRule::new(10, 2, usize::MAX / 2).is_err());
+}
+
+#[test]
+fn test_totalistic_code_digits() {
+    // 1635 in base 3 is 2020120 (most significant first)
+    let rule = TotalisticCodeRule::new(1635, 3, 1).unwrap();
+    assert_eq!(rule.apply(&[0, 0, 0]), 0);
+    assert_eq!(rule.apply(&[0, 1, 0]), 2);
+    assert_eq!(rule.apply(&[1, 1, 0]), 1);
+    assert_eq!(rule.apply(&[1, 1, 1]), 0);
+    assert_eq!(rule.apply(&[2, 2, 2]), 2);
+    assert_eq!(rule.states(), 3);
+}
+
+// Tests derived from src/core/elementary_automata.rs
+#[test]
+fn test_elementary_invalid_width() {
+    assert!(ElementaryAutomata::new(0, Box::new(WolframRule::new(30)), EdgeCondition::Periodic).is_err());
+}
+
+#[test]
+fn test_elementary_get_set() {
+    let mut automata =
+        ElementaryAutomata::new(5, Box::new(WolframRule::new(30)), EdgeCondition::Periodic).unwrap();
+    automata.set(3, 1).unwrap();
+    assert_eq!(automata.get(3).unwrap(), 1);
+    assert!(automata.get(5).is_err());
+    assert!(automata.set(5, 1).is_err());
+}
+
+#[test]
+fn test_rule_30_from_single_cell() {
+    let mut automata = seeded(11, Box::new(WolframRule::new(30)), EdgeCondition::Periodic);
+    automata.step();
+    assert_eq!(automata.cells(), &[0, 0, 0, 0, 1, 1, 1, 0, 0, 0, 0]);
+    automata.step();
+    assert_eq!(automata.cells(), &[0, 0, 0, 1, 1, 0, 0, 1, 0, 0, 0]);
+    automata.step();
+    assert_eq!(automata.cells(), &[0, 0, 1, 1, 0, 1, 1, 1, 1, 0, 0]);
+    assert_eq!(automata.generation(), 3);
+}
+
+#[test]
+fn test_rule_90_sierpinski_population() {
+    // Rule 90 from a single cell has 2^popcount(t) live cells at generation t
+    let mut automata = seeded(129, Box::new(WolframRule::new(90)), EdgeCondition::Periodic);
+    for t in 1..32u32 {
+        automata.step();
+        let alive = automata.cells().iter().filter(|&&s| s == 1).count();
+        assert_eq!(alive, 1 << t.count_ones());
+    }
+}
+
+#[test]
+fn test_rule_150_matches_totalistic_code_10() {
+    // Rule 150 is the XOR of three cells, i.e. totalistic code 10 (digits 0101)
+    let mut wolfram = seeded(31, Box::new(WolframRule::new(150)), EdgeCondition::Periodic);
+    let mut totalistic =
+        seeded(31, Box::new(TotalisticCodeRule::new(10, 2, 1).unwrap()), EdgeCondition::Periodic);
+    for _ in 0..20 {
+        wolfram.step();
+        totalistic.step();
+        assert_eq!(wolfram.cells(), totalistic.cells());
+    }
+}
+
+#[test]
+fn test_periodic_edge_wraps() {
+    let mut automata =
+        ElementaryAutomata::new(5, Box::new(WolframRule::new(90)), EdgeCondition::Periodic).unwrap();
+    automata.set(0, 1).unwrap();
+    automata.step();
+    assert_eq!(automata.cells(), &[0, 1, 0, 0, 1]);
+}
+
+#[test]
+fn test_fixed_edge() {
+    let mut dead =
+        ElementaryAutomata::new(5, Box::new(WolframRule::new(90)), EdgeCondition::Fixed(0)).unwrap();
+    dead.set(0, 1).unwrap();
+    dead.step();
+    assert_eq!(dead.cells(), &[0, 1, 0, 0, 0]);
+
+    let mut alive =
+        ElementaryAutomata::new(5, Box::new(WolframRule::new(90)), EdgeCondition::Fixed(1)).unwrap();
+    alive.step();
+    assert_eq!(alive.cells(), &[1, 0, 0, 0, 1]);
+}
+
+#[test]
+fn test_elementary_reset() {
+    let mut automata = seeded(9, Box::new(WolframRule::new(30)), EdgeCondition::Periodic);
+    automata.step_n(4);
+    assert_eq!(automata.generation(), 4);
+    automata.reset();
+    assert_eq!(automata.generation(), 0);
+    assert!(automata.cells().iter().all(|&s| s == 0));
+}
+
+#[test]
+fn test_spacetime_diagram_rows() {
+    let mut diagram = SpacetimeDiagram::new(3, 2).unwrap();
+    diagram.push(&[1, 0, 0]);
+    assert_eq!(diagram.rows(), 1);
+    diagram.push(&[0, 1, 0]);
+    assert_eq!(diagram.grid().get(Position::new(0, 0)).unwrap(), CellState::Alive);
+    assert_eq!(diagram.grid().get(Position::new(1, 1)).unwrap(), CellState::Alive);
+}
+
+#[test]
+fn test_spacetime_diagram_scrolls() {
+    let mut diagram = SpacetimeDiagram::new(3, 2).unwrap();
+    diagram.push(&[1, 0, 0]);
+    diagram.push(&[0, 1, 0]);
+    diagram.push(&[0, 0, 2]);
+    assert_eq!(diagram.rows(), 2);
+    assert_eq!(diagram.grid().get(Position::new(1, 0)).unwrap(), CellState::Alive);
+    assert_eq!(diagram.grid().get(Position::new(0, 0)).unwrap(), CellState::Dead);
+    assert_eq!(diagram.grid().get(Position::new(2, 1)).unwrap(), CellState::from_index(2));
+    diagram.clear();
+    assert_eq!(diagram.rows(), 0);
+    assert_eq!(diagram.grid().count_alive(), 0);
+}
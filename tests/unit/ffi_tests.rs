@@ -0,0 +1,138 @@
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+use cellularity::ffi::automaton::*;
+use cellularity::ffi::cellularity_last_error;
+use cellularity::ffi::status::{cellularity_status_name, CellularityStatus};
+
+// Tests derived from src/ffi/mod.rs, src/ffi/automaton.rs and
+// src/ffi/status.rs
+
+/// Creates an automaton, failing the test if the API refuses
+fn new_automaton(rule: &str, width: usize, height: usize) -> *mut CellularityAutomaton {
+    let rule = CString::new(rule).unwrap();
+    let mut handle = ptr::null_mut();
+    let status = unsafe {
+        cellularity_automaton_new(rule.as_ptr(), width, height, ptr::null(), &mut handle)
+    };
+    assert_eq!(status, CellularityStatus::Ok);
+    handle
+}
+
+fn last_error() -> String {
+    unsafe { CStr::from_ptr(cellularity_last_error()) }.to_string_lossy().into_owned()
+}
+
+#[test]
+fn test_ffi_sets_steps_and_reads_cells() {
+    let handle = new_automaton("B3/S23", 5, 5);
+    unsafe {
+        for x in 1..=3 {
+            assert_eq!(cellularity_automaton_set_cell(handle, x, 2, 1), CellularityStatus::Ok);
+        }
+        assert_eq!(cellularity_automaton_population(handle), 3);
+        assert_eq!(cellularity_automaton_step(handle, 1), CellularityStatus::Ok);
+        assert_eq!(cellularity_automaton_generation(handle), 1);
+
+        let mut state = 9;
+        assert_eq!(cellularity_automaton_get_cell(handle, 2, 1, &mut state), CellularityStatus::Ok);
+        assert_eq!(state, 1);
+
+        let mut cells = [0u8; 25];
+        let status = cellularity_automaton_read_grid(handle, cells.as_mut_ptr(), cells.len());
+        assert_eq!(status, CellularityStatus::Ok);
+        let alive: Vec<usize> = (0..25).filter(|&i| cells[i] == 1).collect();
+        assert_eq!(alive, vec![7, 12, 17]);
+        cellularity_automaton_free(handle);
+    }
+}
+
+#[test]
+fn test_ffi_buffers_must_match_the_grid() {
+    let handle = new_automaton("Wireworld", 3, 2);
+    unsafe {
+        let written = [3u8, 1, 2, 0, 3, 3];
+        let status = cellularity_automaton_write_grid(handle, written.as_ptr(), written.len());
+        assert_eq!(status, CellularityStatus::Ok);
+        let mut read = [0u8; 6];
+        cellularity_automaton_read_grid(handle, read.as_mut_ptr(), read.len());
+        assert_eq!(read, written);
+
+        let status = cellularity_automaton_read_grid(handle, read.as_mut_ptr(), 5);
+        assert_eq!(status, CellularityStatus::BufferSize);
+        assert!(last_error().contains("6 cells"));
+        let status = cellularity_automaton_write_grid(handle, ptr::null(), 6);
+        assert_eq!(status, CellularityStatus::NullPointer);
+        cellularity_automaton_free(handle);
+    }
+}
+
+#[test]
+fn test_ffi_loads_rle_centred_or_at_a_corner() {
+    let handle = new_automaton("B3/S23", 11, 11);
+    let glider = CString::new("x = 3, y = 3\nbo$2bo$3o!").unwrap();
+    unsafe {
+        let status = cellularity_automaton_load_rle(
+            handle,
+            glider.as_ptr(),
+            CELLULARITY_CENTER,
+            CELLULARITY_CENTER,
+            true,
+        );
+        assert_eq!(status, CellularityStatus::Ok);
+        let mut state = 0;
+        cellularity_automaton_get_cell(handle, 5, 4, &mut state);
+        assert_eq!(state, 1);
+
+        let status = cellularity_automaton_load_rle(handle, glider.as_ptr(), 0, 0, false);
+        assert_eq!(status, CellularityStatus::Ok);
+        assert_eq!(cellularity_automaton_population(handle), 10);
+
+        let status = cellularity_automaton_load_rle(handle, glider.as_ptr(), 11, 0, true);
+        assert_eq!(status, CellularityStatus::OutOfBounds);
+        assert_eq!(cellularity_automaton_population(handle), 10);
+        cellularity_automaton_free(handle);
+    }
+}
+
+#[test]
+fn test_ffi_maps_errors_to_status_codes() {
+    let mut handle = ptr::null_mut();
+    let bad_rule = CString::new("B9/S").unwrap();
+    let status = unsafe {
+        cellularity_automaton_new(bad_rule.as_ptr(), 4, 4, ptr::null(), &mut handle)
+    };
+    assert_eq!(status, CellularityStatus::InvalidRule);
+    assert!(handle.is_null());
+    assert!(!last_error().is_empty());
+
+    let rule = CString::new("B3/S23").unwrap();
+    let status = unsafe {
+        cellularity_automaton_new(rule.as_ptr(), 0, 4, ptr::null(), &mut handle)
+    };
+    assert_eq!(status, CellularityStatus::InvalidDimensions);
+
+    let handle = new_automaton("B3/S23", 4, 4);
+    assert!(last_error().is_empty());
+    unsafe {
+        assert_eq!(cellularity_automaton_set_cell(handle, 4, 0, 1), CellularityStatus::OutOfBounds);
+        assert_eq!(cellularity_automaton_step(ptr::null_mut(), 1), CellularityStatus::NullPointer);
+        assert_eq!(cellularity_automaton_width(ptr::null()), 0);
+        cellularity_automaton_free(handle);
+        cellularity_automaton_free(ptr::null_mut());
+    }
+
+    let name = unsafe { CStr::from_ptr(cellularity_status_name(CellularityStatus::Io)) };
+    assert_eq!(name.to_str().unwrap(), "I/O error");
+}
+
+#[test]
+fn test_committed_header_matches_the_generated_one() {
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/cellularity.h"));
+    let committed = include_str!("../../include/cellularity.h");
+    assert!(
+        committed == generated,
+        "include/cellularity.h is out of date; run CELLULARITY_WRITE_HEADER=1 cargo build \
+         --features ffi"
+    );
+}
@@ -170,6 +170,7 @@ fn test_resize_invalid_dimensions() {
 }
 
 #[test]
+#[allow(clippy::manual_range_contains)]
 fn test_new_random() {
     let grid = DenseGrid::new_random(10, 10, 0.5).unwrap();
     let alive_count = grid.count_alive();
@@ -217,3 +218,63 @@ fn test_count_alive() {
 }
 
 
+
+// Region and clipboard operations on DenseGrid
+fn grid_from_rows(rows: &[&str]) -> DenseGrid {
+    let mut grid = DenseGrid::new(rows[0].len(), rows.len()).unwrap();
+    for (y, row) in rows.iter().enumerate() {
+        for (x, c) in row.chars().enumerate() {
+            if c == 'o' {
+                grid.set(Position::new(x, y), CellState::Alive).unwrap();
+            }
+        }
+    }
+    grid
+}
+
+#[test]
+fn test_dense_grid_extract_region() {
+    let grid = grid_from_rows(&["o...", ".oo.", "...o"]);
+    let region = grid.extract(Position::new(1, 1), 3, 2).unwrap();
+    assert_eq!(region, grid_from_rows(&["oo.", "..o"]));
+    assert!(grid.extract(Position::new(2, 1), 3, 2).is_err());
+    assert!(grid.extract(Position::new(0, 0), 0, 2).is_err());
+}
+
+#[test]
+fn test_dense_grid_stamp_keeps_cells_under_dead_pattern_cells_and_clips() {
+    let mut grid = grid_from_rows(&["....", "o...", "...."]);
+    let pattern = grid_from_rows(&[".o", "o.", "oo"]);
+    grid.stamp(&pattern, Position::new(0, 0));
+    assert_eq!(grid, grid_from_rows(&[".o..", "o...", "oo.."]));
+
+    grid.stamp(&pattern, Position::new(3, 1));
+    assert_eq!(grid, grid_from_rows(&[".o..", "o...", "oo.o"]));
+}
+
+#[test]
+fn test_dense_grid_fill_and_randomize_region() {
+    let mut grid = DenseGrid::new(4, 4).unwrap();
+    grid.fill_region(Position::new(2, 2), 5, 5, CellState::Alive);
+    assert_eq!(grid, grid_from_rows(&["....", "....", "..oo", "..oo"]));
+
+    grid.randomize_region(Position::new(0, 0), 2, 2, 1.0);
+    assert_eq!(grid.count_alive(), 8);
+    grid.randomize_region(Position::new(0, 0), 4, 1, 0.0);
+    assert_eq!(grid, grid_from_rows(&["....", "oo..", "..oo", "..oo"]));
+}
+
+#[test]
+fn test_dense_grid_rotate_and_flip() {
+    let glider = grid_from_rows(&[".o.", "..o", "ooo"]);
+    let l_shape = grid_from_rows(&["o..", "ooo"]);
+    assert_eq!(l_shape.rotate_clockwise(), grid_from_rows(&["oo", "o.", "o."]));
+    assert_eq!(l_shape.flip_horizontal(), grid_from_rows(&["..o", "ooo"]));
+
+    let mut turned = glider.clone();
+    for _ in 0..4 {
+        turned = turned.rotate_clockwise();
+    }
+    assert_eq!(turned, glider);
+    assert_eq!(glider.flip_horizontal().flip_horizontal(), glider);
+}
@@ -0,0 +1,150 @@
+use cellularity::{
+    parse_rule, Automata, CellState, ConwayRule, DenseGrid, Grid, HenselRule, MooreNeighborhood,
+    Neighbors, Position, Rule, ToroidalBoundary,
+};
+
+fn automata_with(rule: Box<dyn Rule>, size: usize) -> Automata {
+    Automata::new(
+        size,
+        size,
+        rule,
+        Box::new(ToroidalBoundary::new()),
+        Box::new(MooreNeighborhood::new()),
+    )
+    .unwrap()
+}
+
+fn set_cells(automata: &mut Automata, cells: &[(usize, usize)]) {
+    for &(x, y) in cells {
+        automata.grid_mut().set(Position::new(x, y), CellState::Alive).unwrap();
+    }
+}
+
+fn alive_cells(automata: &Automata) -> Vec<(usize, usize)> {
+    automata
+        .grid()
+        .iter()
+        .filter(|(_, state)| state.is_alive())
+        .map(|(pos, _)| (pos.x, pos.y))
+        .collect()
+}
+
+// Tests derived from src/core/rules/hensel.rs
+#[test]
+fn test_hensel_has_51_transitions() {
+    let transitions = HenselRule::transitions();
+    assert_eq!(transitions.len(), 51);
+    assert_eq!(transitions.first().unwrap(), "0");
+    assert_eq!(transitions.last().unwrap(), "8");
+    assert!(transitions.contains(&"4z".to_string()));
+    assert!(transitions.contains(&"5y".to_string()));
+    assert!(!transitions.contains(&"1a".to_string()));
+}
+
+#[test]
+fn test_hensel_parse_normalizes() {
+    assert_eq!(HenselRule::parse("B3/S23").unwrap().description(), "B3/S23");
+    assert_eq!(HenselRule::parse("B3/S2-i34q").unwrap().description(), "B3/S2-i34q");
+    assert_eq!(HenselRule::parse("b2cekain/s").unwrap().description(), "B2/S");
+    assert_eq!(HenselRule::parse("S12/B2-a").unwrap().description(), "B2-a/S12");
+    assert_eq!(HenselRule::parse("B2ce/S").unwrap().description(), "B2ce/S");
+    // MCell survival/birth order
+    assert_eq!(HenselRule::parse("23/3").unwrap().description(), "B3/S23");
+}
+
+#[test]
+fn test_hensel_parse_errors() {
+    assert!(HenselRule::parse("B3S23").is_err());
+    assert!(HenselRule::parse("B1a/S").is_err());
+    assert!(HenselRule::parse("B9/S").is_err());
+    assert!(HenselRule::parse("B3-/S23").is_err());
+    assert!(HenselRule::parse("B3/X23").is_err());
+}
+
+#[test]
+fn test_hensel_totalistic_detection() {
+    assert!(HenselRule::parse("B36/S23").unwrap().is_totalistic());
+    assert!(!HenselRule::parse("B2-a/S12").unwrap().is_totalistic());
+}
+
+#[test]
+fn test_hensel_count_only_apply() {
+    let rule = HenselRule::parse("B2-a/S12").unwrap();
+    assert_eq!(rule.apply(CellState::Alive, 1), CellState::Alive);
+    assert_eq!(rule.apply(CellState::Alive, 3), CellState::Dead);
+    // Not every 2-neighbor configuration gives birth, so a bare count does not
+    assert_eq!(rule.apply(CellState::Dead, 2), CellState::Dead);
+    assert_eq!(rule.apply(CellState::Dead, 9), CellState::Dead);
+    assert!(rule.needs_configuration());
+}
+
+#[test]
+fn test_hensel_mask_distinguishes_configurations() {
+    let rule = HenselRule::parse("B2-a/S").unwrap();
+    // Moore offset order: NW N NE W E SW S SE
+    let adjacent = Neighbors { mask: 0b0000_0011, ..Neighbors::new(2) }; // NW, N: 2a
+    let opposite = Neighbors { mask: 0b0100_0010, ..Neighbors::new(2) }; // N, S: 2i
+    assert_eq!(rule.apply_neighbors(CellState::Dead, &adjacent), CellState::Dead);
+    assert_eq!(rule.apply_neighbors(CellState::Dead, &opposite), CellState::Alive);
+}
+
+#[test]
+fn test_hensel_life_matches_conway() {
+    let mut conway = automata_with(Box::new(ConwayRule::new()), 20);
+    let mut hensel = automata_with(Box::new(HenselRule::parse("B3/S23").unwrap()), 20);
+    let seed = DenseGrid::new_random(20, 20, 0.35).unwrap();
+    *conway.grid_mut() = seed.clone();
+    *hensel.grid_mut() = seed;
+    for _ in 0..12 {
+        conway.step();
+        hensel.step();
+        assert_eq!(alive_cells(&conway), alive_cells(&hensel));
+    }
+}
+
+#[test]
+fn test_block_dies_without_3a_survival() {
+    // Each cell of a block sees its three neighbors as a 3a corner
+    let block = [(4, 4), (5, 4), (4, 5), (5, 5)];
+    let mut life = automata_with(Box::new(HenselRule::parse("B3/S23").unwrap()), 10);
+    set_cells(&mut life, &block);
+    life.step();
+    assert_eq!(alive_cells(&life).len(), 4);
+
+    let mut no_3a = automata_with(Box::new(HenselRule::parse("B3/S23-a").unwrap()), 10);
+    set_cells(&mut no_3a, &block);
+    no_3a.step();
+    assert!(alive_cells(&no_3a).is_empty());
+}
+
+#[test]
+fn test_tlife_blinker_loses_center() {
+    // In tlife (B3/S2-i34q) the blinker's centre sees 2i and dies,
+    // while the cells above and below it are born from 3i
+    let mut automata = automata_with(Box::new(HenselRule::parse("B3/S2-i34q").unwrap()), 10);
+    set_cells(&mut automata, &[(4, 5), (5, 5), (6, 5)]);
+    automata.step();
+    assert_eq!(alive_cells(&automata), vec![(5, 4), (5, 6)]);
+}
+
+#[test]
+fn test_domino_is_stable_in_b2_minus_a() {
+    // Every dead cell touching a domino with two neighbors sees them as 2a
+    let domino = [(4, 4), (5, 4)];
+    let mut automata = automata_with(Box::new(HenselRule::parse("B2-a/S12").unwrap()), 10);
+    set_cells(&mut automata, &domino);
+    automata.step_n(5);
+    assert_eq!(alive_cells(&automata), vec![(4, 4), (5, 4)]);
+
+    let mut totalistic = automata_with(Box::new(HenselRule::parse("B2/S12").unwrap()), 10);
+    set_cells(&mut totalistic, &domino);
+    totalistic.step();
+    assert!(alive_cells(&totalistic).len() > 2);
+}
+
+#[test]
+fn test_parse_rule_hensel() {
+    let parsed = parse_rule("B3/S2-i34q").unwrap();
+    assert_eq!(parsed.rule.name(), "Isotropic non-totalistic");
+    assert_eq!(parsed.neighborhood.offsets().len(), 8);
+}
@@ -0,0 +1,122 @@
+use cellularity::{CellState, DenseGrid, Error, FrameStyle, Grid, ImageImport, Position, Raster};
+
+// Tests derived from src/io/raster.rs and src/io/image_import.rs
+
+fn glider() -> DenseGrid {
+    let mut grid = DenseGrid::new(3, 3).unwrap();
+    for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+        grid.set(Position::new(x, y), CellState::Alive).unwrap();
+    }
+    grid
+}
+
+fn encode_png(style: &FrameStyle, grid: &DenseGrid) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    style.write_png(grid, &mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn test_raster_decodes_plain_and_raw_netpbm() {
+    let plain = Raster::decode(b"P1\n# a comment\n3 2\n0 1 0\n110").unwrap();
+    assert_eq!((plain.width, plain.height), (3, 2));
+    assert_eq!(plain.pixel(1, 0), [0; 3]);
+    assert_eq!(plain.pixel(2, 1), [255; 3]);
+
+    let raw = Raster::decode(b"P4\n10 1\n\x80\x40").unwrap();
+    assert_eq!(raw.pixel(0, 0), [0; 3]);
+    assert_eq!(raw.pixel(9, 0), [0; 3]);
+    assert_eq!(raw.pixel(8, 0), [255; 3]);
+
+    let gray = Raster::decode(b"P2 2 1 15 0 15").unwrap();
+    assert_eq!(gray.pixels, vec![[0; 3], [255; 3]]);
+    let color = Raster::decode(b"P6 1 1 255\n\x10\x20\x30").unwrap();
+    assert_eq!(color.pixels, vec![[0x10, 0x20, 0x30]]);
+}
+
+#[test]
+fn test_raster_rejects_malformed_images() {
+    assert!(Raster::decode(b"GIF89a").is_err());
+    assert!(Raster::decode(b"P7 1 1").is_err());
+    assert!(Raster::decode(b"P1 3 3 0 1").is_err());
+    assert!(Raster::decode(b"P5 2 2 65535 ").is_err());
+    assert!(Raster::decode(b"P4 8 2 \xff").is_err());
+}
+
+#[test]
+fn test_raster_rejects_netpbm_sizes_that_overflow() {
+    let max = usize::MAX;
+    for header in [
+        format!("P6 {} 2 255\n", max / 2),
+        format!("P5 {} 1 255\n", max),
+        format!("P4 {} {}\n", max, max / 4),
+        format!("P2 {} {} 255 0", max, max),
+    ] {
+        let result = Raster::decode(header.as_bytes());
+        assert!(
+            matches!(&result, Err(Error::PatternParseError(message))
+                if message.contains("too large")),
+            "{}",
+            header
+        );
+    }
+}
+
+#[test]
+fn test_import_thresholds_and_inverts() {
+    let image = Raster::decode(b"P2 3 1 255 0 100 200").unwrap();
+    let mut settings = ImageImport::new();
+    let states = |grid: DenseGrid| grid.iter().map(|(_, state)| state.index()).collect::<Vec<_>>();
+    assert_eq!(states(settings.to_grid(&image).unwrap()), [1, 1, 0]);
+    settings.threshold = 50;
+    assert_eq!(states(settings.to_grid(&image).unwrap()), [1, 0, 0]);
+    settings.invert = true;
+    assert_eq!(states(settings.to_grid(&image).unwrap()), [0, 1, 1]);
+}
+
+#[test]
+fn test_import_scales_blocks_by_average_colour() {
+    // A 5x4 image: the left 2x2 block is black, the top-right block half black
+    let image = Raster::decode(b"P1 5 4 11010 11010 00000 00001").unwrap();
+    let settings = ImageImport { scale: 2, threshold: 160, ..ImageImport::new() };
+    let grid = settings.to_grid(&image).unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    let alive: Vec<Position> =
+        grid.iter().filter(|(_, state)| state.is_alive()).map(|(pos, _)| pos).collect();
+    assert_eq!(alive, [Position::new(0, 0), Position::new(1, 0), Position::new(2, 1)]);
+
+    let zero = ImageImport { scale: 0, ..ImageImport::new() };
+    assert!(zero.to_grid(&image).is_err());
+}
+
+#[test]
+fn test_import_maps_colours_to_nearest_palette_state() {
+    let palette = vec![[0, 0, 0], [0, 0, 255], [255, 0, 0], [255, 255, 0]];
+    let settings = ImageImport { palette: Some(palette), ..ImageImport::new() };
+    assert_eq!(settings.state([250, 10, 5]), CellState::State(2));
+    assert_eq!(settings.state([200, 220, 40]), CellState::State(3));
+    assert_eq!(settings.state([10, 10, 10]), CellState::Dead);
+    assert_eq!(settings.state([0, 0, 200]), CellState::Alive);
+}
+
+#[test]
+fn test_export_round_trips_through_png_and_pbm() {
+    let settings = ImageImport::new();
+    let style = settings.export_style();
+    assert_eq!(style.cell_size, 1);
+    let png = Raster::decode(&encode_png(&style, &glider())).unwrap();
+    assert_eq!(settings.to_grid(&png).unwrap(), glider());
+
+    let mut pbm = Vec::new();
+    style.write_pbm(&glider(), &mut pbm).unwrap();
+    assert_eq!(String::from_utf8(pbm.clone()).unwrap(), "P1\n3 3\n0 1 0\n0 0 1\n1 1 1\n");
+    assert_eq!(settings.to_grid(&Raster::decode(&pbm).unwrap()).unwrap(), glider());
+
+    let mut multi = glider();
+    multi.set(Position::new(0, 0), CellState::State(3)).unwrap();
+    let palette = vec![[0, 0, 0], [0, 0, 255], [255, 0, 0], [255, 255, 0]];
+    let colors = ImageImport { palette: Some(palette), scale: 2, ..ImageImport::new() };
+    let png = Raster::decode(&encode_png(&colors.export_style(), &multi)).unwrap();
+    assert_eq!((png.width, png.height), (6, 6));
+    assert_eq!(colors.to_grid(&png).unwrap(), multi);
+}
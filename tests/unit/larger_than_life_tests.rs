@@ -0,0 +1,125 @@
+use cellularity::{
+    Automata, CellState, ConwayRule, DenseGrid, Grid, LtLNeighborhood, LtLRule, MooreNeighborhood,
+    Position, Rule, ToroidalBoundary,
+};
+
+// Tests derived from src/core/rules/larger_than_life.rs
+#[test]
+fn test_ltl_parse_bosco() {
+    let rule = LtLRule::parse("R5,C0,M1,S34..58,B34..45,NM").unwrap();
+    assert_eq!(rule.range(), 5);
+    assert!(rule.include_middle());
+    assert_eq!(rule.survival(), &(34..=58));
+    assert_eq!(rule.birth(), &(34..=45));
+    assert_eq!(rule.neighborhood_kind(), LtLNeighborhood::Moore);
+    assert_eq!(rule.neighborhood().offsets().len(), 120);
+    assert_eq!(rule.description(), "R5,C0,M1,S34..58,B34..45,NM");
+    assert_eq!(rule.name(), "Larger than Life");
+}
+
+#[test]
+fn test_ltl_parse_neighborhood_kinds() {
+    let von_neumann = LtLRule::parse("R2,C0,M0,S1..2,B2..3,NN").unwrap();
+    assert_eq!(von_neumann.neighborhood_kind(), LtLNeighborhood::VonNeumann);
+    assert_eq!(von_neumann.neighborhood().offsets().len(), 12);
+    let circular = LtLRule::parse("r3,c0,m0,s5..9,b4..6,nc").unwrap();
+    assert_eq!(circular.neighborhood_kind(), LtLNeighborhood::Circular);
+    let hexagonal = LtLRule::parse("R2,C0,M0,S4..7,B5..6,NH").unwrap();
+    assert_eq!(hexagonal.neighborhood().offsets().len(), 18);
+}
+
+#[test]
+fn test_ltl_parse_defaults_and_old_notation() {
+    let rule = LtLRule::parse("R2,S3..5,B4").unwrap();
+    assert!(!rule.include_middle());
+    assert_eq!(rule.birth(), &(4..=4));
+    assert_eq!(rule.neighborhood_kind(), LtLNeighborhood::Moore);
+
+    // r,bmin,bmax,smin,smax
+    let old = LtLRule::parse("5,34,45,34,58").unwrap();
+    assert_eq!(old.range(), 5);
+    assert!(old.include_middle());
+    assert_eq!(old.birth(), &(34..=45));
+    assert_eq!(old.survival(), &(34..=58));
+    let new = LtLRule::parse("R5,C0,M1,S34..58,B34..45,NM").unwrap();
+    assert_eq!(old.description(), new.description());
+}
+
+#[test]
+fn test_ltl_parse_errors() {
+    assert!(LtLRule::parse("").is_err());
+    assert!(LtLRule::parse("R0,C0,M0,S1..2,B1..2,NM").is_err());
+    assert!(LtLRule::parse("R501,C0,M0,S1..2,B1..2,NM").is_err());
+    assert!(LtLRule::parse("R2,C0,M2,S1..2,B1..2,NM").is_err());
+    assert!(LtLRule::parse("R2,C3,M0,S1..2,B1..2,NM").is_err());
+    assert!(LtLRule::parse("R2,C0,M0,S1..2,B1..2,NQ").is_err());
+    assert!(LtLRule::parse("R2,C0,M0,B1..2,NM").is_err());
+    assert!(LtLRule::parse("R2,C0,M0,Sx..2,B1..2,NM").is_err());
+}
+
+#[test]
+fn test_ltl_apply_counts_middle() {
+    let with_middle = LtLRule::parse("R1,C0,M1,S3..4,B3..3,NM").unwrap();
+    // A live cell with 2 neighbors has a total of 3 when the middle counts
+    assert_eq!(with_middle.apply(CellState::Alive, 2), CellState::Alive);
+    assert_eq!(with_middle.apply(CellState::Alive, 4), CellState::Dead);
+    assert_eq!(with_middle.apply(CellState::Dead, 3), CellState::Alive);
+    assert_eq!(with_middle.apply(CellState::Dead, 2), CellState::Dead);
+}
+
+#[test]
+fn test_ltl_range_one_matches_conway() {
+    let ltl = LtLRule::parse("R1,C0,M0,S2..3,B3..3,NM").unwrap();
+    let mut a = Automata::new(
+        20,
+        20,
+        Box::new(ConwayRule::new()),
+        Box::new(ToroidalBoundary::new()),
+        Box::new(MooreNeighborhood::new()),
+    )
+    .unwrap();
+    let mut b = Automata::new(
+        20,
+        20,
+        Box::new(ltl.clone()),
+        Box::new(ToroidalBoundary::new()),
+        ltl.neighborhood(),
+    )
+    .unwrap();
+    let seed = DenseGrid::new_random(20, 20, 0.35).unwrap();
+    *a.grid_mut() = seed.clone();
+    *b.grid_mut() = seed;
+    for _ in 0..10 {
+        a.step();
+        b.step();
+        for (pos, state) in a.grid().iter() {
+            assert_eq!(b.grid().get(pos).unwrap(), state);
+        }
+    }
+}
+
+#[test]
+fn test_ltl_bosco_large_neighborhood_step() {
+    // A filled 11x11 square: every interior cell sees the whole square (121 with middle)
+    let rule = LtLRule::parse("R5,C0,M1,S34..58,B34..45,NM").unwrap();
+    let mut automata = Automata::new(
+        40,
+        40,
+        Box::new(rule.clone()),
+        Box::new(ToroidalBoundary::new()),
+        rule.neighborhood(),
+    )
+    .unwrap();
+    for y in 15..26 {
+        for x in 15..26 {
+            automata.grid_mut().set(Position::new(x, y), CellState::Alive).unwrap();
+        }
+    }
+    automata.step();
+    // Centre of the square has 121 live cells in range: overcrowded, dies
+    assert_eq!(automata.grid().get(Position::new(20, 20)).unwrap(), CellState::Dead);
+    // Corner cell sees a 6x6 block = 36 live cells including itself: survives
+    assert_eq!(automata.grid().get(Position::new(15, 15)).unwrap(), CellState::Alive);
+    // A dead cell diagonally outside the corner sees a 5x5 block = 25: stays dead
+    assert_eq!(automata.grid().get(Position::new(14, 14)).unwrap(), CellState::Dead);
+}
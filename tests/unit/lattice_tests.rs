@@ -0,0 +1,136 @@
+// Tests derived from src/core/lattice.rs and src/core/triangular_neighborhood.rs
+
+use cellularity::{
+    parse_rule, Automata, CellState, ConwayRule, DenseGrid, Grid, Lattice, MooreNeighborhood,
+    Neighborhood, Position, ToroidalBoundary, TriangularNeighborhood,
+};
+
+fn lattice_automata(width: usize, height: usize, lattice: Lattice, rule: &str) -> Automata {
+    let mut automata = Automata::new(
+        width,
+        height,
+        parse_rule(rule).unwrap().rule,
+        Box::new(ToroidalBoundary::new()),
+        Box::new(MooreNeighborhood::new()),
+    )
+    .unwrap();
+    automata.set_lattice(lattice).unwrap();
+    automata
+}
+
+fn alive_cells(grid: &DenseGrid) -> Vec<(usize, usize)> {
+    grid.iter().filter(|(_, s)| s.is_alive()).map(|(p, _)| (p.x, p.y)).collect()
+}
+
+/// Checks that adjacency is symmetric on a torus: every neighbor of a cell
+/// has the cell among its own neighbors
+fn assert_symmetric(neighborhood: &dyn Neighborhood, width: isize, height: isize) {
+    let wrap = |x: isize, y: isize| (x.rem_euclid(width), y.rem_euclid(height));
+    for y in 0..height {
+        for x in 0..width {
+            let pos = Position::new(x as usize, y as usize);
+            for &(dx, dy) in neighborhood.offsets_at(pos) {
+                let (nx, ny) = wrap(x + dx, y + dy);
+                let back = neighborhood.offsets_at(Position::new(nx as usize, ny as usize));
+                assert!(
+                    back.iter().any(|&(bx, by)| wrap(nx + bx, ny + by) == (x, y)),
+                    "({}, {}) -> ({}, {}) is one-way",
+                    x,
+                    y,
+                    nx,
+                    ny
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_triangle_orientation_and_offsets() {
+    assert!(TriangularNeighborhood::points_up(Position::new(0, 0)));
+    assert!(!TriangularNeighborhood::points_up(Position::new(1, 0)));
+    assert!(!TriangularNeighborhood::points_up(Position::new(0, 1)));
+
+    let edge = TriangularNeighborhood::new();
+    assert!(!edge.is_uniform());
+    assert_eq!(edge.offsets_at(Position::new(0, 0)), &[(-1, 0), (1, 0), (0, 1)]);
+    assert_eq!(edge.offsets_at(Position::new(1, 0)), &[(-1, 0), (1, 0), (0, -1)]);
+
+    let vertex = TriangularNeighborhood::with_vertices();
+    assert_eq!(vertex.offsets_at(Position::new(0, 0)).len(), 12);
+    assert_eq!(vertex.offsets_at(Position::new(1, 0)).len(), 12);
+    assert!(vertex.offsets_at(Position::new(1, 0)).contains(&(-2, -1)));
+}
+
+#[test]
+fn test_adjacency_is_symmetric() {
+    assert_symmetric(&TriangularNeighborhood::new(), 6, 4);
+    assert_symmetric(&TriangularNeighborhood::with_vertices(), 6, 4);
+    assert_symmetric(Lattice::Hexagonal.neighborhood().as_ref(), 5, 3);
+}
+
+#[test]
+fn test_set_lattice_checks_dimensions() {
+    let mut automata = lattice_automata(6, 5, Lattice::Hexagonal, "B3/S23");
+    assert_eq!(automata.lattice(), Lattice::Hexagonal);
+    assert_eq!(automata.neighborhood().offsets().len(), 6);
+    assert!(automata.set_lattice(Lattice::Triangular).is_err());
+    assert_eq!(automata.lattice(), Lattice::Hexagonal);
+
+    automata.set_lattice(Lattice::Square).unwrap();
+    assert_eq!(automata.neighborhood().offsets().len(), 8);
+    assert!(Lattice::Triangular.check_dimensions(6, 4).is_ok());
+}
+
+#[test]
+fn test_hexagonal_growth_wraps() {
+    let mut automata = lattice_automata(5, 5, Lattice::Hexagonal, "B1/S");
+    automata.grid_mut().set(Position::new(0, 0), CellState::Alive).unwrap();
+    automata.step();
+    let mut alive = alive_cells(automata.grid());
+    alive.sort();
+    assert_eq!(alive, vec![(0, 1), (0, 4), (1, 0), (1, 1), (4, 0), (4, 4)]);
+}
+
+#[test]
+fn test_triangular_growth_follows_orientation() {
+    let mut automata = lattice_automata(8, 6, Lattice::Triangular, "B1/S");
+    automata.grid_mut().set(Position::new(2, 2), CellState::Alive).unwrap();
+    automata.step();
+    let mut alive = alive_cells(automata.grid());
+    alive.sort();
+    assert_eq!(alive, vec![(1, 2), (2, 3), (3, 2)]);
+
+    // The down triangle to the right grows upwards
+    automata.grid_mut().clear();
+    automata.grid_mut().set(Position::new(3, 2), CellState::Alive).unwrap();
+    automata.step();
+    let mut alive = alive_cells(automata.grid());
+    alive.sort();
+    assert_eq!(alive, vec![(2, 2), (3, 1), (4, 2)]);
+}
+
+#[test]
+fn test_triangular_wraps_on_torus() {
+    let mut automata = lattice_automata(4, 4, Lattice::Triangular, "B1/S");
+    automata.grid_mut().set(Position::new(0, 3), CellState::Alive).unwrap();
+    automata.step();
+    let mut alive = alive_cells(automata.grid());
+    alive.sort();
+    // (0, 3) points down, so its third neighbor is the row above
+    assert_eq!(alive, vec![(0, 2), (1, 3), (3, 3)]);
+}
+
+#[test]
+fn test_rules_run_on_vertex_neighborhood() {
+    let mut automata = lattice_automata(8, 8, Lattice::Triangular, "B3/S23");
+    automata.set_rule(Box::new(ConwayRule::new()));
+    automata.set_neighborhood(Box::new(TriangularNeighborhood::with_vertices()));
+    // Three triangles around an up triangle's top vertex bring it to life
+    for x in [1, 2, 3] {
+        automata.grid_mut().set(Position::new(x, 1), CellState::Alive).unwrap();
+    }
+    automata.step();
+    assert_eq!(automata.grid().get(Position::new(2, 2)).unwrap(), CellState::Alive);
+    assert_eq!(automata.lattice(), Lattice::Triangular);
+}
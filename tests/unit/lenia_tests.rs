@@ -0,0 +1,127 @@
+// Tests derived from src/core/continuous_automata.rs, src/core/convolution.rs,
+// src/core/kernel_neighborhood.rs and src/core/rules/lenia.rs
+
+use cellularity::core::convolution::FFT_MIN_NEIGHBORS;
+use cellularity::{
+    ContinuousAutomata, ContinuousRule, Convolution, FloatGrid, KernelNeighborhood, LeniaRule,
+    Position,
+};
+
+fn assert_close(a: &[f64], b: &[f64]) {
+    assert_eq!(a.len(), b.len());
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+        assert!((x - y).abs() < 1e-9, "cell {}: {} != {}", i, x, y);
+    }
+}
+
+#[test]
+fn test_growth_function() {
+    let rule = LeniaRule::orbium();
+    assert!((rule.growth(0.15) - 1.0).abs() < 1e-12);
+    assert!((rule.growth(0.0) + 1.0).abs() < 1e-6);
+    assert!(rule.growth(0.16) > 0.0 && rule.growth(0.2) < 0.0);
+
+    assert_eq!(rule.apply(0.0, 0.0), 0.0);
+    assert_eq!(rule.apply(0.95, 0.15), 1.0);
+    assert!((rule.apply(0.5, 0.15) - 0.6).abs() < 1e-12);
+    assert_eq!(rule.name(), "Lenia");
+}
+
+#[test]
+fn test_ring_kernel_is_normalised() {
+    let kernel = KernelNeighborhood::ring(13);
+    assert_eq!(kernel.radius(), 13);
+    assert!((kernel.weights().iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    assert!(!kernel.offsets().contains(&(0, 0)));
+    assert!(kernel.offsets().iter().all(|&(dx, dy)| dx * dx + dy * dy < 13 * 13));
+
+    // The single ring peaks halfway out
+    let weight = |offset| {
+        let i = kernel.offsets().iter().position(|&o| o == offset).unwrap();
+        kernel.weights()[i]
+    };
+    assert!(weight((6, 0)) > weight((2, 0)));
+    assert!(weight((6, 0)) > weight((11, 0)));
+
+    let two = KernelNeighborhood::rings(10, &[0.5, 1.0]);
+    assert!((two.weights().iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    assert!(KernelNeighborhood::disk(3).offsets().contains(&(0, 0)));
+}
+
+#[test]
+fn test_convolution_chooses_method_by_size() {
+    assert!(KernelNeighborhood::ring(2).offsets().len() < FFT_MIN_NEIGHBORS);
+    assert!(!Convolution::new(&KernelNeighborhood::ring(2), 16, 16).is_fft());
+    assert!(Convolution::new(&KernelNeighborhood::ring(13), 64, 64).is_fft());
+}
+
+#[test]
+fn test_fft_matches_direct_convolution() {
+    // Sizes that are not powers of two, and a kernel wider than the grid
+    for (width, height, radius) in [(32, 32, 5), (45, 27, 9), (12, 10, 8)] {
+        let grid = FloatGrid::new_random(width, height).unwrap();
+        let kernel = KernelNeighborhood::rings(radius, &[1.0, 0.4]);
+        let (mut direct, mut fft) = (Vec::new(), Vec::new());
+        Convolution::direct(&kernel, width, height).apply(&grid, &mut direct);
+        Convolution::fft(&kernel, width, height).apply(&grid, &mut fft);
+        assert_close(&direct, &fft);
+    }
+}
+
+#[test]
+fn test_convolution_wraps_and_gathers() {
+    // A single full cell spreads its value to the cells that see it
+    let mut grid = FloatGrid::new(8, 8).unwrap();
+    grid.set(Position::new(0, 0), 1.0).unwrap();
+    let kernel = KernelNeighborhood::ring(2);
+    for mut convolution in [Convolution::direct(&kernel, 8, 8), Convolution::fft(&kernel, 8, 8)] {
+        let mut potential = Vec::new();
+        convolution.apply(&grid, &mut potential);
+        assert!((potential.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(potential[7] > 0.0, "wraps to the right edge");
+        assert!(potential[0].abs() < 1e-9, "ring kernels skip the centre");
+    }
+
+    // A uniform grid has the same potential everywhere
+    let mut uniform = FloatGrid::new(20, 20).unwrap();
+    uniform.values_mut().fill(0.3);
+    let mut potential = Vec::new();
+    Convolution::new(&KernelNeighborhood::ring(6), 20, 20).apply(&uniform, &mut potential);
+    assert_close(&potential, &[0.3; 400]);
+}
+
+#[test]
+fn test_float_grid_bounds_and_total() {
+    assert!(FloatGrid::new(0, 3).is_err());
+    let mut grid = FloatGrid::new(4, 3).unwrap();
+    grid.set(Position::new(3, 2), 0.25).unwrap();
+    grid.set(Position::new(1, 0), 0.5).unwrap();
+    assert!(grid.set(Position::new(4, 0), 1.0).is_err());
+    assert_eq!(grid.get(Position::new(3, 2)).unwrap(), 0.25);
+    assert_eq!(grid.total(), 0.75);
+    assert_eq!(grid.iter().filter(|&(_, v)| v > 0.0).count(), 2);
+}
+
+#[test]
+fn test_lenia_evolution_stays_in_range() {
+    let mut lenia = ContinuousAutomata::new(
+        64,
+        64,
+        Box::new(LeniaRule::orbium()),
+        KernelNeighborhood::ring(13),
+    )
+    .unwrap();
+    lenia.step();
+    assert_eq!(lenia.grid().total(), 0.0, "empty space stays empty");
+
+    lenia.seed_random_patch(Position::new(32, 32), 30);
+    lenia.step_n(20);
+    assert_eq!(lenia.generation(), 21);
+    assert_eq!(lenia.potential().len(), 64 * 64);
+    assert!(lenia.grid().values().iter().all(|v| (0.0..=1.0).contains(v)));
+
+    lenia.set_kernel(KernelNeighborhood::ring(4));
+    lenia.step();
+    lenia.reset();
+    assert_eq!((lenia.generation(), lenia.grid().total()), (0, 0.0));
+}
@@ -0,0 +1,140 @@
+// Tests derived from src/core/automata_3d.rs, src/core/dense_grid_3d.rs and
+// src/core/rules/life_3d.rs
+
+use cellularity::{
+    Automata3D, Axis, Boundary3D, CellState, DenseGrid3D, Grid, Life3DNeighborhood, Life3DRule,
+    Position, Position3D, Rule, ToroidalBoundary, WalledBoundary,
+};
+
+fn torus(size: usize, rule: &str) -> Automata3D {
+    let rule = Life3DRule::parse(rule).unwrap();
+    Automata3D::new(
+        size,
+        size,
+        size,
+        Box::new(rule.clone()),
+        Box::new(ToroidalBoundary::new()),
+        rule.neighborhood(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_parse_3d_rules() {
+    let life = Life3DRule::parse("4555").unwrap();
+    assert_eq!(life.spec(), "4-5/5/2/M");
+    assert!(life.survives(4) && life.survives(5) && !life.survives(6));
+    assert!(life.is_born(5) && !life.is_born(4));
+    assert_eq!(life.spec(), Life3DRule::life_4555().spec());
+
+    let clouds = Life3DRule::parse("clouds 1").unwrap();
+    assert_eq!(clouds.spec(), "13-26/13-14,17-19/2/M");
+    assert_eq!(Life3DRule::parse(clouds.spec()).unwrap().spec(), clouds.spec());
+
+    let decay = Life3DRule::parse("4/4/5/M").unwrap();
+    assert_eq!(decay.states(), 5);
+    let faces = Life3DRule::parse("/1/2/N").unwrap();
+    assert_eq!(faces.neighborhood_kind(), Life3DNeighborhood::VonNeumann);
+    assert_eq!(faces.neighborhood().offsets().len(), 6);
+
+    assert!(Life3DRule::parse("27/3/2/M").is_err());
+    assert!(Life3DRule::parse("7/3/2/N").is_err());
+    assert!(Life3DRule::parse("0-4000000000/3").is_err());
+    assert!(Life3DRule::parse("0-7/1/2/N").is_err());
+    assert!(Life3DRule::parse("4/4/1/M").is_err());
+    assert!(Life3DRule::parse("4/4/2/Q").is_err());
+    assert!(Life3DRule::parse("x/4").is_err());
+    assert!(Life3DRule::parse("4").is_err());
+}
+
+#[test]
+fn test_grid_3d_indexing_and_slices() {
+    assert!(DenseGrid3D::new(4, 4, 0).is_err());
+    assert!(DenseGrid3D::new(0, 4, 4).is_err());
+
+    let mut grid = DenseGrid3D::new(4, 3, 2).unwrap();
+    assert_eq!(grid.size(), 24);
+    let pos = Position3D::new(3, 1, 1);
+    grid.set(pos, CellState::Alive).unwrap();
+    assert_eq!(grid.get(pos).unwrap(), CellState::Alive);
+    assert!(grid.get(Position3D::new(0, 0, 2)).is_err());
+    assert_eq!(grid.iter().find(|(_, s)| s.is_alive()).unwrap().0, pos);
+
+    for axis in [Axis::X, Axis::Y, Axis::Z] {
+        let index = match axis {
+            Axis::X => 3,
+            Axis::Y => 1,
+            Axis::Z => 1,
+        };
+        let slice = grid.slice(axis, index).unwrap();
+        assert_eq!((slice.width(), slice.height()), grid.slice_size(axis));
+        assert_eq!(slice.count_alive(), 1);
+        let (cell, _) = slice.iter().find(|(_, s)| s.is_alive()).unwrap();
+        assert_eq!(DenseGrid3D::slice_position(axis, index, cell.x, cell.y), pos);
+    }
+    assert_eq!(grid.slice(Axis::Z, 0).unwrap().count_alive(), 0);
+    assert_eq!(grid.slice(Axis::Z, 1).unwrap().get(Position::new(3, 1)).unwrap(), CellState::Alive);
+}
+
+#[test]
+fn test_boundaries_in_three_dimensions() {
+    let torus = ToroidalBoundary::new();
+    assert_eq!(Boundary3D::wrap(&torus, -1, 4, -5, 4, 4, 4), Some(Position3D::new(3, 0, 3)));
+    let walled = WalledBoundary::new();
+    assert_eq!(Boundary3D::wrap(&walled, 1, 2, 9, 4, 4, 4), Some(Position3D::new(1, 2, 3)));
+    assert_eq!(Boundary3D::wrap(&walled, 1, 2, -1, 4, 4, 4), None);
+}
+
+#[test]
+fn test_moore_neighborhood_wraps_on_torus() {
+    // On a 3x3x3 torus every other cell is a distinct Moore neighbor
+    let mut automata = torus(3, "/1/2/M");
+    automata.grid_mut().set(Position3D::new(0, 0, 0), CellState::Alive).unwrap();
+    automata.step();
+    assert_eq!(automata.grid().count_alive(), 26);
+    assert_eq!(automata.grid().get(Position3D::new(0, 0, 0)).unwrap(), CellState::Dead);
+    assert_eq!(automata.generation(), 1);
+}
+
+#[test]
+fn test_von_neumann_growth_forms_octahedron() {
+    let mut automata = torus(9, "0-6/1-6/2/N");
+    automata.grid_mut().set(Position3D::new(4, 4, 4), CellState::Alive).unwrap();
+    automata.step_n(2);
+    // Every cell within Manhattan distance 2
+    assert_eq!(automata.grid().count_alive(), 25);
+    assert!(automata.grid().get(Position3D::new(4, 6, 4)).unwrap().is_alive());
+    assert!(automata.grid().get(Position3D::new(5, 5, 4)).unwrap().is_alive());
+    assert!(!automata.grid().get(Position3D::new(5, 5, 5)).unwrap().is_alive());
+}
+
+#[test]
+fn test_life_4555_cube_dies() {
+    // Each cell of a 2x2x2 cube has 7 neighbors, too many to survive, and no
+    // outside cell touches more than 4 of them
+    let mut automata = torus(8, "4555");
+    for z in 3..5 {
+        for y in 3..5 {
+            for x in 3..5 {
+                automata.grid_mut().set(Position3D::new(x, y, z), CellState::Alive).unwrap();
+            }
+        }
+    }
+    automata.step();
+    assert_eq!(automata.grid().count_alive(), 0);
+}
+
+#[test]
+fn test_extra_states_decay() {
+    let mut automata = torus(5, "4/4/3/M");
+    let pos = Position3D::new(2, 2, 2);
+    automata.grid_mut().set(pos, CellState::Alive).unwrap();
+    automata.step();
+    assert_eq!(automata.grid().get(pos).unwrap(), CellState::State(2));
+    automata.step();
+    assert_eq!(automata.grid().get(pos).unwrap(), CellState::Dead);
+
+    automata.reset();
+    assert_eq!(automata.generation(), 0);
+    assert_eq!(automata.grid().count_alive(), 0);
+}
@@ -0,0 +1,131 @@
+// Tests derived from src/core/margolus.rs
+
+use cellularity::{
+    BlockRule, CellState, DenseGrid, Grid, MargolusAutomata, Position, ToroidalBoundary,
+    WalledBoundary,
+};
+
+fn torus(width: usize, height: usize, rule: BlockRule) -> MargolusAutomata {
+    MargolusAutomata::new(width, height, rule, Box::new(ToroidalBoundary::new())).unwrap()
+}
+
+fn alive_cells(grid: &DenseGrid) -> Vec<(usize, usize)> {
+    grid.iter().filter(|(_, s)| s.is_alive()).map(|(p, _)| (p.x, p.y)).collect()
+}
+
+#[test]
+fn test_builtin_rules_are_reversible() {
+    assert!(BlockRule::critters().is_reversible());
+    assert!(BlockRule::tron().is_reversible());
+    assert!(BlockRule::billiard_balls().is_reversible());
+
+    let collapse = BlockRule::new([0; 16]).unwrap();
+    assert!(!collapse.is_reversible());
+    assert!(collapse.inverse().is_none());
+}
+
+#[test]
+fn test_inverse_undoes_table() {
+    let critters = BlockRule::critters();
+    let inverse = critters.inverse().unwrap();
+    for block in 0..16u8 {
+        let next = critters.table(false)[block as usize];
+        assert_eq!(inverse.table(false)[next as usize], block);
+    }
+}
+
+#[test]
+fn test_parse_block_rules() {
+    let critters = BlockRule::parse("MS,D15;14;13;3;11;5;6;1;7;9;10;2;12;4;8;0").unwrap();
+    assert_eq!(critters, BlockRule::critters());
+    assert_eq!(BlockRule::parse(&critters.spec()).unwrap(), critters);
+    assert_eq!(BlockRule::parse("tron").unwrap(), BlockRule::tron());
+    assert_eq!(BlockRule::parse("BBM").unwrap(), BlockRule::billiard_balls());
+
+    let identity: Vec<String> = (0..16).map(|i| i.to_string()).collect();
+    let alternating =
+        BlockRule::parse(&format!("{}|{}", identity.join(","), BlockRule::tron().spec())).unwrap();
+    assert_eq!(alternating.table(false)[0], 0);
+    assert_eq!(alternating.table(true)[0], 15);
+
+    assert!(BlockRule::parse("MS,D1;2;3").is_err());
+    assert!(BlockRule::parse("MS,D16;1;2;3;4;5;6;7;8;9;10;11;12;13;14;15").is_err());
+    assert!(BlockRule::parse("MS,Dx;1;2;3;4;5;6;7;8;9;10;11;12;13;14;15").is_err());
+}
+
+#[test]
+fn test_requires_even_dimensions() {
+    let boundary = || Box::new(ToroidalBoundary::new());
+    assert!(MargolusAutomata::new(5, 4, BlockRule::tron(), boundary()).is_err());
+    assert!(MargolusAutomata::new(4, 3, BlockRule::tron(), boundary()).is_err());
+    assert!(MargolusAutomata::new(4, 4, BlockRule::tron(), boundary()).is_ok());
+}
+
+#[test]
+fn test_billiard_ball_moves_diagonally() {
+    let mut automata = torus(8, 8, BlockRule::billiard_balls());
+    automata.grid_mut().set(Position::new(2, 2), CellState::Alive).unwrap();
+    automata.step();
+    assert_eq!(alive_cells(automata.grid()), vec![(3, 3)]);
+    automata.step();
+    assert_eq!(alive_cells(automata.grid()), vec![(4, 4)]);
+}
+
+#[test]
+fn test_odd_partition_wraps_on_torus() {
+    let mut automata = torus(6, 4, BlockRule::billiard_balls());
+    automata.grid_mut().set(Position::new(4, 2), CellState::Alive).unwrap();
+    automata.step();
+    assert_eq!(alive_cells(automata.grid()), vec![(5, 3)]);
+    // The odd block at (5, 3) spans the right and bottom edges
+    automata.step();
+    assert_eq!(alive_cells(automata.grid()), vec![(0, 0)]);
+}
+
+#[test]
+fn test_tron_complements_uniform_blocks() {
+    let mut automata = torus(4, 4, BlockRule::tron());
+    automata.step();
+    assert_eq!(alive_cells(automata.grid()).len(), 16);
+    automata.grid_mut().set(Position::new(0, 0), CellState::Dead).unwrap();
+    automata.step();
+    // Odd blocks containing (0, 0) keep their contents, the others empty
+    assert_eq!(alive_cells(automata.grid()).len(), 3);
+}
+
+#[test]
+fn test_running_backwards_restores_initial_grid() {
+    let mut automata = torus(32, 24, BlockRule::critters());
+    *automata.grid_mut() = DenseGrid::new_random(32, 24, 0.3).unwrap();
+    let initial = automata.grid().clone();
+
+    automata.step_n(101);
+    assert_ne!(automata.grid(), &initial);
+    for _ in 0..101 {
+        automata.step_back().unwrap();
+    }
+    assert_eq!(automata.generation(), 0);
+    assert_eq!(automata.grid(), &initial);
+    assert!(automata.step_back().is_err());
+
+    assert!(automata.check_reversibility(50));
+    assert_eq!(automata.grid(), &initial);
+}
+
+#[test]
+fn test_irreversible_rule_cannot_step_back() {
+    let mut automata = torus(4, 4, BlockRule::new([0; 16]).unwrap());
+    automata.step();
+    assert!(automata.step_back().is_err());
+    assert!(!automata.check_reversibility(4));
+}
+
+#[test]
+fn test_walled_boundary_skips_edge_blocks() {
+    let mut automata =
+        MargolusAutomata::new(4, 4, BlockRule::tron(), Box::new(WalledBoundary::new())).unwrap();
+    automata.step();
+    automata.step();
+    // Only the one odd block fully inside the grid is complemented back
+    assert_eq!(alive_cells(automata.grid()).len(), 12);
+}
@@ -0,0 +1,222 @@
+// Tests derived from src/core/rules/{wireworld,brians_brain,rule_table}.rs
+
+use cellularity::{
+    parse_rule, Automata, BriansBrainRule, CellState, DenseGrid, Grid, MooreNeighborhood,
+    Position, Rule, RuleTable, TableNeighborhood, TableSymmetry, ToroidalBoundary,
+    WalledBoundary, WireworldRule,
+};
+
+fn automata_from(grid: &DenseGrid, rule: Box<dyn Rule>) -> Automata {
+    let mut automata = Automata::new(
+        grid.width(),
+        grid.height(),
+        rule,
+        Box::new(WalledBoundary::new()),
+        Box::new(MooreNeighborhood::new()),
+    )
+    .unwrap();
+    for (pos, state) in grid.iter() {
+        automata.grid_mut().set(pos, state).unwrap();
+    }
+    automata
+}
+
+/// Generations within `steps` at which the cell holds an electron head
+fn heads_at(circuit: &str, pos: Position, steps: u64) -> Vec<u64> {
+    let grid = WireworldRule::circuit(circuit).unwrap();
+    let mut automata = automata_from(&grid, Box::new(WireworldRule::new()));
+    let mut heads = Vec::new();
+    for _ in 0..steps {
+        automata.step();
+        if automata.grid().get(pos).unwrap() == WireworldRule::HEAD {
+            heads.push(automata.generation());
+        }
+    }
+    heads
+}
+
+#[test]
+fn test_cell_state_index_round_trip() {
+    for index in 0..=255u8 {
+        assert_eq!(CellState::from_index(index).index(), index);
+    }
+    assert_eq!(CellState::from_index(0), CellState::Dead);
+    assert_eq!(CellState::from_index(1), CellState::Alive);
+    assert_eq!(CellState::from_index(3), CellState::State(3));
+    assert!(!CellState::State(2).is_alive());
+    assert_eq!(CellState::State(2).toggle(), CellState::Dead);
+}
+
+#[test]
+fn test_wireworld_transitions() {
+    let rule = WireworldRule::new();
+    assert_eq!(rule.states(), 4);
+    assert_eq!(rule.apply(WireworldRule::HEAD, 3), WireworldRule::TAIL);
+    assert_eq!(rule.apply(WireworldRule::TAIL, 1), WireworldRule::CONDUCTOR);
+    assert_eq!(rule.apply(WireworldRule::CONDUCTOR, 0), WireworldRule::CONDUCTOR);
+    assert_eq!(rule.apply(WireworldRule::CONDUCTOR, 1), WireworldRule::HEAD);
+    assert_eq!(rule.apply(WireworldRule::CONDUCTOR, 2), WireworldRule::HEAD);
+    assert_eq!(rule.apply(WireworldRule::CONDUCTOR, 3), WireworldRule::CONDUCTOR);
+    assert_eq!(rule.apply(WireworldRule::EMPTY, 2), WireworldRule::EMPTY);
+    assert_eq!(rule.palette().unwrap().len(), 4);
+}
+
+#[test]
+fn test_wireworld_circuit_parsing() {
+    let grid = WireworldRule::circuit(".tH#\n#").unwrap();
+    assert_eq!((grid.width(), grid.height()), (4, 2));
+    assert_eq!(grid.get(Position::new(1, 0)).unwrap(), WireworldRule::TAIL);
+    assert_eq!(grid.get(Position::new(2, 0)).unwrap(), WireworldRule::HEAD);
+    assert_eq!(grid.get(Position::new(3, 0)).unwrap(), WireworldRule::CONDUCTOR);
+    assert_eq!(grid.get(Position::new(3, 1)).unwrap(), WireworldRule::EMPTY);
+    assert!(WireworldRule::circuit("#?#").is_err());
+}
+
+#[test]
+fn test_wireworld_diode_passes_forward() {
+    // One electron arrives at the far end, and nothing travels back
+    assert_eq!(heads_at(WireworldRule::DIODE, Position::new(15, 2), 40), vec![13]);
+    assert!(heads_at(WireworldRule::DIODE, Position::new(1, 2), 40).is_empty());
+}
+
+#[test]
+fn test_wireworld_diode_blocks_reverse() {
+    assert!(heads_at(WireworldRule::DIODE_REVERSED, Position::new(1, 2), 40).is_empty());
+}
+
+#[test]
+fn test_wireworld_clock_period() {
+    let heads = heads_at(WireworldRule::CLOCK, Position::new(10, 2), 40);
+    assert_eq!(heads, vec![7, 13, 19, 25, 31, 37]);
+}
+
+#[test]
+fn test_brians_brain_transitions() {
+    let rule = BriansBrainRule::new();
+    assert_eq!(rule.apply(BriansBrainRule::OFF, 2), BriansBrainRule::FIRING);
+    assert_eq!(rule.apply(BriansBrainRule::OFF, 3), BriansBrainRule::OFF);
+    assert_eq!(rule.apply(BriansBrainRule::FIRING, 2), BriansBrainRule::REFRACTORY);
+    assert_eq!(rule.apply(BriansBrainRule::REFRACTORY, 2), BriansBrainRule::OFF);
+}
+
+#[test]
+fn test_brians_brain_pair_fires_neighbors() {
+    let mut grid = DenseGrid::new(8, 8).unwrap();
+    grid.set(Position::new(3, 3), BriansBrainRule::FIRING).unwrap();
+    grid.set(Position::new(4, 3), BriansBrainRule::FIRING).unwrap();
+    let mut automata = automata_from(&grid, Box::new(BriansBrainRule::new()));
+    automata.step();
+
+    let grid = automata.grid();
+    assert_eq!(grid.get(Position::new(3, 3)).unwrap(), BriansBrainRule::REFRACTORY);
+    assert_eq!(grid.get(Position::new(4, 3)).unwrap(), BriansBrainRule::REFRACTORY);
+    for x in 3..=4 {
+        assert_eq!(grid.get(Position::new(x, 2)).unwrap(), BriansBrainRule::FIRING);
+        assert_eq!(grid.get(Position::new(x, 4)).unwrap(), BriansBrainRule::FIRING);
+    }
+    assert_eq!(grid.get(Position::new(2, 2)).unwrap(), BriansBrainRule::OFF);
+}
+
+#[test]
+fn test_rule_table_symmetry_and_variables() {
+    let table = RuleTable::parse(
+        "@RULE Test\n@TABLE\nn_states:3\nneighborhood:vonNeumann\nsymmetries:rotate4\n\
+         var a={0,1,2}\n0,1,a,a,0,2\n# comment\n1,0,0,0,0,0\n",
+    )
+    .unwrap();
+    assert_eq!(table.name(), "Test");
+    assert_eq!(table.states(), 3);
+    assert_eq!(table.neighborhood_kind(), TableNeighborhood::VonNeumann);
+    assert_eq!(table.symmetry(), TableSymmetry::Rotate4);
+    assert_eq!(table.len(), 2);
+
+    assert_eq!(table.transition(0, &[1, 2, 2, 0]), Some(2));
+    // Rotated by 90 degrees
+    assert_eq!(table.transition(0, &[0, 1, 2, 2]), Some(2));
+    // Variable bound to different values
+    assert_eq!(table.transition(0, &[1, 1, 2, 0]), None);
+    // Mirror images are not included by rotate4
+    assert_eq!(table.transition(0, &[1, 0, 2, 2]), None);
+    assert_eq!(table.transition(1, &[0, 0, 0, 0]), Some(0));
+}
+
+#[test]
+fn test_rule_table_first_transition_wins() {
+    let table =
+        RuleTable::parse("n_states:2\nneighborhood:Moore\nsymmetries:permute\n0,1,1,0,0,0,0,0,0,1\n\
+                          0,0,0,0,0,0,0,1,1,0\n")
+            .unwrap();
+    assert_eq!(table.transition(0, &[0, 0, 0, 1, 0, 1, 0, 0]), Some(1));
+}
+
+#[test]
+fn test_rule_table_rejects_invalid_tables() {
+    assert!(RuleTable::parse("neighborhood:Moore\n0,0,0,0,0,0,0,0,0,0").is_err());
+    assert!(RuleTable::parse("n_states:2\nneighborhood:hex\n").is_err());
+    assert!(RuleTable::parse("n_states:2\nneighborhood:vonNeumann\nsymmetries:rotate8\n").is_err());
+    assert!(RuleTable::parse("n_states:2\nneighborhood:vonNeumann\n0,0,0,0,0,2\n").is_err());
+    assert!(RuleTable::parse("n_states:2\nneighborhood:vonNeumann\n0,0,0,0\n").is_err());
+}
+
+#[test]
+fn test_langtons_loops_has_transition_for_every_cell() {
+    let table = RuleTable::langtons_loops();
+    assert_eq!(table.states(), 8);
+    assert_eq!(table.len(), 219);
+    assert_eq!(table.palette().map(<[_]>::len), Some(8));
+
+    let size = 50;
+    let mut automata = Automata::new(
+        size,
+        size,
+        Box::new(table.clone()),
+        Box::new(ToroidalBoundary::new()),
+        table.neighborhood(),
+    )
+    .unwrap();
+    let seed = RuleTable::pattern(RuleTable::LANGTONS_LOOP).unwrap();
+    for (pos, state) in seed.iter() {
+        automata.grid_mut().set(Position::new(pos.x + 15, pos.y + 15), state).unwrap();
+    }
+    let population = |automata: &Automata| {
+        automata.grid().iter().filter(|(_, state)| *state != CellState::Dead).count()
+    };
+    let initial = population(&automata);
+
+    // Langton's table is complete for every neighborhood the loop produces
+    let state = |automata: &Automata, x: isize, y: isize| {
+        let wrap = |v: isize| v.rem_euclid(size as isize) as usize;
+        automata.grid().get(Position::new(wrap(x), wrap(y))).unwrap().index()
+    };
+    for _ in 0..151 {
+        for (pos, cell) in automata.grid().iter() {
+            let (x, y) = (pos.x as isize, pos.y as isize);
+            let neighbors = [
+                state(&automata, x, y - 1),
+                state(&automata, x + 1, y),
+                state(&automata, x, y + 1),
+                state(&automata, x - 1, y),
+            ];
+            assert!(
+                table.transition(cell.index(), &neighbors).is_some(),
+                "no transition for {} {:?} at generation {}",
+                cell.index(),
+                neighbors,
+                automata.generation()
+            );
+        }
+        automata.step();
+    }
+    assert!(population(&automata) > initial);
+}
+
+#[test]
+fn test_parse_rule_named_multi_state_rules() {
+    assert_eq!(parse_rule("Wireworld").unwrap().rule.name(), "Wireworld");
+    assert_eq!(parse_rule("Brian's Brain").unwrap().rule.states(), 3);
+    let loops = parse_rule("Langton's Loops").unwrap();
+    assert_eq!(loops.rule.name(), "LangtonsLoops");
+    assert_eq!(loops.neighborhood.offsets().len(), 4);
+    let table = parse_rule("@RULE Tiny\n@TABLE\nn_states:2\nneighborhood:Moore\n").unwrap();
+    assert_eq!(table.rule.name(), "Tiny");
+}
@@ -1,4 +1,8 @@
-use cellularity::{Neighborhood, MooreNeighborhood, VonNeumannNeighborhood};
+use cellularity::{
+    CircularNeighborhood, CustomNeighborhood, HexagonalNeighborhood, MooreNeighborhood, Neighborhood,
+    VonNeumannNeighborhood,
+};
+use cellularity::core::hexagonal_neighborhood::hex_distance;
 
 // Tests derived from src/core/neighborhood.rs using a local mock
 struct MockNeighborhood { offsets: Vec<(isize, isize)> }
@@ -83,3 +87,149 @@ fn test_von_neumann_neighborhood_default() {
 }
 
 
+
+#[test]
+fn test_neighborhood_default_radius() {
+    let neighborhood = MockNeighborhood { offsets: vec![(-2, 0), (1, 1)] };
+    assert_eq!(neighborhood.radius(), 2);
+    let empty = MockNeighborhood { offsets: vec![] };
+    assert_eq!(empty.radius(), 0);
+}
+
+#[test]
+fn test_moore_neighborhood_with_radius() {
+    let neighborhood = MooreNeighborhood::with_radius(2);
+    assert_eq!(neighborhood.offsets().len(), 24);
+    assert_eq!(neighborhood.radius(), 2);
+    assert!(neighborhood.offsets().contains(&(-2, -2)));
+    assert!(!neighborhood.offsets().contains(&(0, 0)));
+    assert_eq!(neighborhood.name(), "Moore (r=2, 24 neighbors)");
+    assert_eq!(MooreNeighborhood::with_radius(5).offsets().len(), 120);
+}
+
+#[test]
+fn test_von_neumann_neighborhood_with_radius() {
+    let neighborhood = VonNeumannNeighborhood::with_radius(2);
+    assert_eq!(neighborhood.offsets().len(), 12);
+    assert!(neighborhood.offsets().contains(&(0, -2)));
+    assert!(neighborhood.offsets().contains(&(1, 1)));
+    assert!(!neighborhood.offsets().contains(&(2, 1)));
+    assert_eq!(neighborhood.radius(), 2);
+}
+
+#[test]
+fn test_radius_one_matches_fixed_offsets() {
+    assert_eq!(
+        MooreNeighborhood::with_radius(1).offsets(),
+        &[(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)]
+    );
+    assert_eq!(
+        VonNeumannNeighborhood::with_radius(1).offsets(),
+        &[(0, -1), (-1, 0), (1, 0), (0, 1)]
+    );
+}
+
+// Tests derived from src/core/circular_neighborhood.rs
+#[test]
+fn test_circular_neighborhood() {
+    assert_eq!(CircularNeighborhood::new(1).offsets().len(), 8);
+    let neighborhood = CircularNeighborhood::new(2);
+    // 5x5 square without its four corners
+    assert_eq!(neighborhood.offsets().len(), 20);
+    assert!(!neighborhood.offsets().contains(&(2, 2)));
+    assert!(neighborhood.offsets().contains(&(2, 1)));
+    assert_eq!(neighborhood.radius(), 2);
+}
+
+// Tests derived from src/core/hexagonal_neighborhood.rs
+#[test]
+fn test_hexagonal_neighborhood() {
+    let neighborhood = HexagonalNeighborhood::new();
+    let offsets = neighborhood.offsets();
+    assert_eq!(offsets.len(), 6);
+    assert!(!offsets.contains(&(1, -1)));
+    assert!(!offsets.contains(&(-1, 1)));
+    assert!(offsets.contains(&(-1, -1)));
+    assert!(offsets.contains(&(1, 1)));
+    assert_eq!(neighborhood.name(), "Hexagonal (6 neighbors)");
+}
+
+#[test]
+fn test_hexagonal_neighborhood_with_radius() {
+    // A hexagon of radius r holds 3r(r+1) cells around the centre
+    for r in 1..5 {
+        assert_eq!(HexagonalNeighborhood::with_radius(r).offsets().len(), 3 * r * (r + 1));
+    }
+    assert_eq!(hex_distance(2, -1), 3);
+    assert_eq!(hex_distance(2, 1), 2);
+}
+
+// Tests derived from src/core/custom_neighborhood.rs
+#[test]
+fn test_custom_neighborhood_from_offsets() {
+    let neighborhood = CustomNeighborhood::from_offsets(vec![(-1, 0), (1, 0), (0, 0)]);
+    assert_eq!(neighborhood.offsets().len(), 3);
+    assert!(neighborhood.includes_center());
+    assert!(neighborhood.weights().is_none());
+    assert_eq!(neighborhood.name(), "Custom (3 neighbors)");
+}
+
+#[test]
+fn test_custom_neighborhood_weighted_offsets() {
+    let neighborhood = CustomNeighborhood::from_weighted_offsets(vec![((0, -1), 2), ((0, 1), -1)]);
+    assert_eq!(neighborhood.weights(), Some(&[2, -1][..]));
+    assert!(!neighborhood.includes_center());
+
+    // All-ones weights are the same as an unweighted neighborhood
+    let ones = CustomNeighborhood::from_weighted_offsets(vec![((0, -1), 1), ((0, 1), 1)]);
+    assert!(ones.weights().is_none());
+}
+
+#[test]
+fn test_custom_neighborhood_char_mask() {
+    let neighborhood = CustomNeighborhood::from_mask(
+        "# von Neumann
+         .X.
+         XOX
+         .X.",
+    )
+    .unwrap();
+    let offsets = neighborhood.offsets();
+    assert_eq!(offsets.len(), 4);
+    assert!(offsets.contains(&(0, -1)));
+    assert!(offsets.contains(&(-1, 0)));
+    assert!(!neighborhood.includes_center());
+}
+
+#[test]
+fn test_custom_neighborhood_mask_with_center_and_digits() {
+    let neighborhood = CustomNeighborhood::from_mask("...\n.1.\n.2.").unwrap();
+    assert_eq!(neighborhood.offsets(), &[(0, 0), (0, 1)]);
+    assert!(neighborhood.includes_center());
+    assert_eq!(neighborhood.weights(), Some(&[1, 2][..]));
+}
+
+#[test]
+fn test_custom_neighborhood_token_mask() {
+    let neighborhood = CustomNeighborhood::from_mask(" 2 -1  2\n-1  0 -1\n 2 -1  2").unwrap();
+    assert_eq!(neighborhood.offsets().len(), 8);
+    assert_eq!(neighborhood.weights().unwrap().iter().sum::<i32>(), 4);
+}
+
+#[test]
+fn test_custom_neighborhood_mask_errors() {
+    assert!(CustomNeighborhood::from_mask("XX\nXX").is_err());
+    assert!(CustomNeighborhood::from_mask("XXX\nX\nXXX").is_err());
+    assert!(CustomNeighborhood::from_mask("X?X\nXOX\nXXX").is_err());
+    assert!(CustomNeighborhood::from_mask("").is_err());
+}
+
+#[test]
+fn test_custom_neighborhood_load_file() {
+    let path = std::env::temp_dir().join("cellularity_mask_test.txt");
+    std::fs::write(&path, "XXX\nXOX\nXXX\n").unwrap();
+    let neighborhood = CustomNeighborhood::load(&path).unwrap();
+    assert_eq!(neighborhood.offsets().len(), 8);
+    std::fs::remove_file(&path).unwrap();
+    assert!(CustomNeighborhood::load(&path).is_err());
+}
@@ -0,0 +1,128 @@
+use std::collections::{BTreeSet, HashSet};
+
+use cellularity::{
+    Automata, ConwayRule, DeadBoundary, DenseGrid, Grid, LibraryPattern, MooreNeighborhood,
+    PatternCategory, PatternLibrary, Position,
+};
+
+// Tests derived from src/core/pattern_library.rs
+
+/// Places a pattern in the middle of an empty dead-edged plane
+fn automata_with(pattern: &DenseGrid, pad: usize) -> Automata {
+    let mut automata = Automata::new(
+        pattern.width() + 2 * pad,
+        pattern.height() + 2 * pad,
+        Box::new(ConwayRule::new()),
+        Box::new(DeadBoundary::new()),
+        Box::new(MooreNeighborhood::new()),
+    )
+    .unwrap();
+    for (pos, state) in pattern.iter() {
+        automata.grid_mut().set(Position::new(pos.x + pad, pos.y + pad), state).unwrap();
+    }
+    automata
+}
+
+/// Returns the live cells relative to the top-left live cell
+fn shape(grid: &DenseGrid) -> BTreeSet<(usize, usize)> {
+    let alive: Vec<Position> =
+        grid.iter().filter(|(_, state)| state.is_alive()).map(|(pos, _)| pos).collect();
+    let min_x = alive.iter().map(|pos| pos.x).min().unwrap_or(0);
+    let min_y = alive.iter().map(|pos| pos.y).min().unwrap_or(0);
+    alive.iter().map(|pos| (pos.x - min_x, pos.y - min_y)).collect()
+}
+
+fn patterns_in(category: PatternCategory) -> Vec<&'static LibraryPattern> {
+    PatternLibrary::by_category(category).collect()
+}
+
+#[test]
+fn test_library_has_every_category_and_unique_names() {
+    assert!(PatternLibrary::all().len() >= 24);
+    for category in PatternCategory::ALL {
+        assert!(!patterns_in(category).is_empty(), "no {}", category.name());
+    }
+    let names: HashSet<String> =
+        PatternLibrary::all().iter().map(|pattern| pattern.name.to_lowercase()).collect();
+    assert_eq!(names.len(), PatternLibrary::all().len());
+    for pattern in PatternLibrary::all() {
+        let grid = pattern.grid().unwrap();
+        assert!(grid.count_alive() > 0, "{} is empty", pattern.name);
+        assert!(!pattern.author.is_empty());
+        assert_eq!(pattern.period.is_none(), pattern.category == PatternCategory::Methuselah);
+    }
+}
+
+#[test]
+fn test_library_lookup_by_name() {
+    let glider = PatternLibrary::get("glider").unwrap();
+    assert_eq!(glider.name, "Glider");
+    assert_eq!(glider.rule, "B3/S23");
+    assert_eq!(glider.period, Some(4));
+    assert_eq!(glider.category, PatternCategory::Spaceship);
+    assert_eq!(glider.grid().unwrap().count_alive(), 5);
+    assert!(PatternLibrary::get(" Gosper Glider Gun ").is_some());
+    assert!(PatternLibrary::get("Unicorn").is_none());
+
+    let rle = glider.to_rle_pattern().unwrap();
+    assert_eq!(rle.name.as_deref(), Some("Glider"));
+    assert_eq!(rle.author.as_deref(), Some("Richard Guy"));
+    assert_eq!(rle.rule.as_deref(), Some("B3/S23"));
+}
+
+#[test]
+fn test_still_lifes_oscillators_and_spaceships_have_their_period() {
+    let periodic = [
+        PatternCategory::StillLife,
+        PatternCategory::Oscillator,
+        PatternCategory::Spaceship,
+    ];
+    for pattern in periodic.iter().flat_map(|&category| patterns_in(category)) {
+        let period = pattern.period.unwrap() as usize;
+        let grid = pattern.grid().unwrap();
+        let start = shape(&grid);
+        let mut automata = automata_with(&grid, 4 + period);
+        for generation in 1..=period {
+            automata.step();
+            let same = shape(automata.grid()) == start;
+            assert_eq!(same, generation == period, "{} at {}", pattern.name, generation);
+        }
+        if pattern.category != PatternCategory::Spaceship {
+            // Still lifes and oscillators come back in the same place
+            let placed = automata_with(&grid, 4 + period);
+            assert_eq!(automata.grid(), placed.grid(), "{} moved", pattern.name);
+        }
+    }
+}
+
+#[test]
+fn test_guns_emit_a_glider_every_period() {
+    for pattern in patterns_in(PatternCategory::Gun) {
+        let grid = pattern.grid().unwrap();
+        let population = grid.count_alive();
+        let mut automata = automata_with(&grid, 20);
+        automata.step_n(pattern.period.unwrap() as usize);
+        assert_eq!(automata.grid().count_alive(), population + 5, "{}", pattern.name);
+    }
+}
+
+#[test]
+fn test_puffers_move_and_leave_debris() {
+    for pattern in patterns_in(PatternCategory::Puffer) {
+        let grid = pattern.grid().unwrap();
+        let mut automata = automata_with(&grid, 90);
+        automata.step_n(pattern.period.unwrap() as usize);
+        assert!(automata.grid().count_alive() > 2 * grid.count_alive(), "{}", pattern.name);
+        assert_ne!(shape(automata.grid()), shape(&grid));
+    }
+}
+
+#[test]
+fn test_diehard_dies_after_130_generations() {
+    let diehard = PatternLibrary::get("Diehard").unwrap().grid().unwrap();
+    let mut automata = automata_with(&diehard, 20);
+    automata.step_n(129);
+    assert!(automata.grid().count_alive() > 0);
+    automata.step();
+    assert_eq!(automata.grid().count_alive(), 0);
+}
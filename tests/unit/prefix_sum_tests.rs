@@ -0,0 +1,90 @@
+use cellularity::{
+    Boundary, CellState, CircularNeighborhood, ConstantBoundary, DenseGrid, EdgeBehavior, Grid,
+    HexagonalNeighborhood, MixedBoundary, MooreNeighborhood, Neighborhood, Position, RowSpans,
+    SummedAreaTable, ToroidalBoundary, VonNeumannNeighborhood, WalledBoundary,
+};
+
+fn naive_count(
+    grid: &DenseGrid,
+    boundary: &dyn Boundary,
+    offsets: &[(isize, isize)],
+    pos: Position,
+) -> usize {
+    offsets
+        .iter()
+        .map(|&(dx, dy)| {
+            boundary
+                .wrap(pos.x as isize + dx, pos.y as isize + dy, grid.width(), grid.height())
+                .map_or(boundary.outside_state(), |p| grid.get(p).unwrap())
+        })
+        .filter(|state| state.is_alive())
+        .count()
+}
+
+// Tests derived from src/core/prefix_sum.rs
+#[test]
+fn test_summed_area_table_rect_sum() {
+    let mut grid = DenseGrid::new(4, 3).unwrap();
+    grid.set(Position::new(0, 0), CellState::Alive).unwrap();
+    grid.set(Position::new(3, 2), CellState::Alive).unwrap();
+    grid.set(Position::new(1, 1), CellState::Alive).unwrap();
+    let table = SummedAreaTable::build(&grid, &ToroidalBoundary::new(), 1);
+    assert_eq!(table.rect_sum(0, 0, 3, 2), 3);
+    assert_eq!(table.rect_sum(0, 0, 1, 1), 2);
+    assert_eq!(table.rect_sum(2, 0, 3, 1), 0);
+    // Padding wraps around: (-1, -1) is the cell at (3, 2)
+    assert_eq!(table.rect_sum(-1, -1, -1, -1), 1);
+}
+
+#[test]
+fn test_row_spans_moore_is_single_rect() {
+    let spans = RowSpans::from_offsets(MooreNeighborhood::with_radius(3).offsets()).unwrap();
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans.radius(), 3);
+}
+
+#[test]
+fn test_row_spans_diamond_is_one_rect_per_row() {
+    let spans = RowSpans::from_offsets(VonNeumannNeighborhood::with_radius(3).offsets()).unwrap();
+    assert_eq!(spans.len(), 7);
+}
+
+#[test]
+fn test_row_spans_rejects_non_convex_rows() {
+    assert!(RowSpans::from_offsets(&[(-2, 0), (2, 0)]).is_none());
+    assert!(RowSpans::from_offsets(&[(0, -1), (0, -1)]).is_none());
+    assert!(RowSpans::from_offsets(&[(-1, 1), (1, 1)]).is_none());
+}
+
+#[test]
+fn test_row_spans_match_naive_count() {
+    let grid = DenseGrid::new_random(23, 17, 0.4).unwrap();
+    let neighborhoods: Vec<Box<dyn Neighborhood>> = vec![
+        Box::new(MooreNeighborhood::with_radius(4)),
+        Box::new(VonNeumannNeighborhood::with_radius(3)),
+        Box::new(CircularNeighborhood::new(5)),
+        Box::new(HexagonalNeighborhood::with_radius(3)),
+    ];
+    let boundaries: Vec<Box<dyn Boundary>> = vec![
+        Box::new(ToroidalBoundary::new()),
+        Box::new(WalledBoundary::new()),
+        Box::new(ConstantBoundary::new(CellState::Alive)),
+        Box::new(MixedBoundary::new(EdgeBehavior::Mirror, EdgeBehavior::Dead)),
+    ];
+
+    for neighborhood in &neighborhoods {
+        let spans = RowSpans::from_offsets(neighborhood.offsets()).unwrap();
+        for boundary in &boundaries {
+            let table = SummedAreaTable::build(&grid, boundary.as_ref(), spans.radius());
+            for (pos, _) in grid.iter() {
+                assert_eq!(
+                    spans.count(&table, &grid, pos),
+                    naive_count(&grid, boundary.as_ref(), neighborhood.offsets(), pos),
+                    "{} at {:?}",
+                    neighborhood.name(),
+                    pos
+                );
+            }
+        }
+    }
+}
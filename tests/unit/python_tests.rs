@@ -0,0 +1,123 @@
+use std::ffi::CString;
+
+use cellularity::core::grid::Grid;
+use cellularity::python::automata::PyAutomata;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+// Tests derived from src/python/mod.rs, src/python/automata.rs,
+// src/python/rule.rs, src/python/boundary.rs and src/python/neighborhood.rs
+
+/// Runs Python code with the module imported as `cy`, failing the test on
+/// any exception
+fn run_python(code: &str) {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = pyo3::wrap_pymodule!(cellularity::python::cellularity)(py);
+        let globals = PyDict::new(py);
+        globals.set_item("cy", module).unwrap();
+        let code = CString::new(code).unwrap();
+        if let Err(err) = py.run(&code, Some(&globals), None) {
+            panic!("{}", err.value(py));
+        }
+    });
+}
+
+#[test]
+fn test_python_builds_and_steps_automata() {
+    run_python(
+        r#"
+a = cy.Automata(12, 10, rule=cy.Rule("B3/S23"), boundary="plane")
+assert (a.width, a.height, a.generation, a.population) == (12, 10, 0, 0)
+a.place("3o!", x=2, y=3)
+assert a.population == 3
+a.step()
+a.step_n(2)
+assert (a.generation, a.population) == (3, 3)
+assert "3bo$3bo$3bo!" in a.to_rle(), a.to_rle()
+assert repr(a) == "Automata(12x10, rule='B3/S23', generation=3)", repr(a)
+
+a.place("o!", x=0, y=0, clear=False)
+assert a.population == 4
+a.reset()
+assert (a.generation, a.population) == (0, 0)
+"#,
+    );
+}
+
+#[test]
+fn test_python_rule_boundary_and_neighborhood_constructors() {
+    run_python(
+        r#"
+rule = cy.Rule("B3/S23:T30,20")
+assert (rule.spec, rule.states, str(rule)) == ("B3/S23:T30,20", 2, "B3/S23:T30,20")
+assert cy.Rule("Wireworld").states == 4
+assert cy.Boundary("Klein").name == "klein"
+assert cy.Neighborhood("von_neumann").size == 4
+assert cy.Neighborhood("hexagonal").size == 6
+
+a = cy.Automata(rule=rule)
+assert (a.width, a.height, a.boundary) == (30, 20, "T30,20")
+a = cy.Automata(8, 8, neighborhood=cy.Neighborhood("circular:2"))
+assert "Circular" in a.neighborhood or "circular" in a.neighborhood, a.neighborhood
+a.set_rule("B36/S23")
+assert a.rule.spec == "B36/S23"
+a.set_boundary(cy.Boundary("mirror"))
+assert a.boundary == "mirror"
+
+for bad in [lambda: cy.Rule("nonsense"), lambda: cy.Boundary("donut"),
+            lambda: cy.Neighborhood("circular:x"), lambda: a.set_rule("B3/S23:T10,10")]:
+    try:
+        bad()
+    except ValueError:
+        pass
+    else:
+        raise AssertionError("expected a ValueError")
+try:
+    cy.Automata(rule=3)
+except TypeError:
+    pass
+else:
+    raise AssertionError("expected a TypeError")
+"#,
+    );
+}
+
+#[test]
+fn test_python_loads_and_saves_patterns() {
+    let dir = std::env::temp_dir().join(format!("cellularity_python_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let glider = dir.join("glider.rle");
+    std::fs::write(&glider, "x = 3, y = 3, rule = B36/S23\nbo$2bo$3o!\n").unwrap();
+    let saved = dir.join("saved.rle");
+
+    run_python(&format!(
+        r#"
+a = cy.Automata.from_file({glider:?}, margin=4)
+assert (a.width, a.height, a.population, a.rule.spec) == (11, 11, 5, "B36/S23")
+a.step_n(4)
+a.save({saved:?})
+
+b = cy.Automata(11, 11, rule="B36/S23", boundary="plane")
+b.load({saved:?})
+assert b.to_rle() == a.to_rle()
+try:
+    b.load({missing:?})
+except OSError:
+    pass
+else:
+    raise AssertionError("expected an OSError")
+"#,
+        glider = glider.display().to_string(),
+        saved = saved.display().to_string(),
+        missing = dir.join("missing.rle").display().to_string(),
+    ));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_python_automata_wraps_rust_automata() {
+    let automata = cellularity::Automata::from_spec("B3/S23", 6, 6, "torus").unwrap();
+    let wrapped = PyAutomata::wrap(automata, "B3/S23".to_string(), "torus".to_string());
+    assert_eq!(wrapped.automata().grid().width(), 6);
+}
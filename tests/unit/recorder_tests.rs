@@ -0,0 +1,120 @@
+use cellularity::cli::{automata_for, parse_color};
+use cellularity::{CellState, DenseGrid, FrameStyle, Grid, Position, Recorder};
+
+// Tests derived from src/io/frame_style.rs, src/io/recorder.rs and src/cli.rs
+
+const ALIVE: [u8; 3] = [255, 255, 255];
+const DEAD: [u8; 3] = [0, 0, 0];
+
+fn style(cell_size: usize) -> FrameStyle {
+    FrameStyle {
+        cell_size,
+        alive_color: ALIVE,
+        dead_color: DEAD,
+        ..FrameStyle::new()
+    }
+}
+
+fn glider() -> DenseGrid {
+    let mut grid = DenseGrid::new(3, 3).unwrap();
+    for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+        grid.set(Position::new(x, y), CellState::Alive).unwrap();
+    }
+    grid
+}
+
+#[test]
+fn test_frame_style_colors_and_palette() {
+    let mut style = style(1);
+    assert_eq!(style.color(CellState::Alive), ALIVE);
+    assert_eq!(style.color(CellState::State(5)), ALIVE);
+    assert_eq!(style.color(CellState::Dead), DEAD);
+
+    style.palette = Some(vec![[1, 1, 1], [2, 2, 2], [3, 3, 3]]);
+    assert_eq!(style.color(CellState::State(2)), [3, 3, 3]);
+    assert_eq!(style.color(CellState::State(3)), [255, 0, 255]);
+    assert_eq!(style.color_table().len(), 256);
+}
+
+#[test]
+fn test_frame_style_scales_and_crops() {
+    let mut grid = DenseGrid::new(3, 2).unwrap();
+    grid.set(Position::new(1, 0), CellState::Alive).unwrap();
+    let mut style = style(2);
+    assert_eq!(style.image_size(&grid).unwrap(), (6, 4));
+    let top_row = [0, 0, 1, 1, 0, 0];
+    let expected: Vec<u8> = [top_row, top_row, [0; 6], [0; 6]].concat();
+    assert_eq!(style.render_indexed(&grid).unwrap(), expected);
+
+    style.crop = Some((Position::new(1, 0), 1, 1));
+    assert_eq!(style.render_rgb(&grid).unwrap(), ALIVE.repeat(4));
+    style.crop = Some((Position::new(2, 1), 2, 1));
+    assert!(style.render_indexed(&grid).is_err());
+}
+
+#[test]
+fn test_recorder_captures_generations_and_writes_gif() {
+    let mut automata = automata_for("B3/S23", &glider(), 4).unwrap();
+    let recorder = Recorder {
+        style: style(3),
+        frames: 5,
+        frame_delay_ms: 50,
+    };
+    let frames = recorder.capture(&mut automata);
+    assert_eq!(frames.len(), 5);
+    assert_eq!(frames[0].count_alive(), 5);
+    assert_eq!(frames[4].canonical(), frames[0].canonical());
+    assert_ne!(frames[4], frames[0]);
+
+    let mut bytes = Vec::new();
+    recorder.write_gif(&frames, &mut bytes).unwrap();
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::Indexed);
+    let mut decoder = options.read_info(bytes.as_slice()).unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (33, 33));
+    let mut decoded = 0;
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        assert_eq!(frame.delay, 5);
+        assert_eq!(frame.buffer.iter().filter(|&&index| index == 1).count(), 5 * 9);
+        decoded += 1;
+    }
+    assert_eq!(decoded, 5);
+}
+
+#[test]
+fn test_recorder_rejects_empty_and_mismatched_frames() {
+    let recorder = Recorder::new();
+    assert!(recorder.write_gif(&[], Vec::new()).is_err());
+    let frames = [DenseGrid::new(3, 3).unwrap(), DenseGrid::new(4, 3).unwrap()];
+    assert!(recorder.write_gif(&frames, Vec::new()).is_err());
+}
+
+#[test]
+fn test_recorder_writes_png_sequence() {
+    let directory = std::env::temp_dir().join("cellularity_png_sequence_test");
+    let _ = std::fs::remove_dir_all(&directory);
+    let recorder = Recorder { style: style(2), ..Recorder::new() };
+    let frames = vec![glider(); 3];
+    let paths = recorder.save(&frames, &directory).unwrap();
+    assert_eq!(paths.len(), 3);
+    assert!(paths[2].ends_with("frame_0002.png"));
+
+    let file = std::fs::File::open(&paths[0]).unwrap();
+    let decoder = png::Decoder::new(std::io::BufReader::new(file));
+    let reader = decoder.read_info().unwrap();
+    assert_eq!((reader.info().width, reader.info().height), (6, 6));
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_cli_helpers() {
+    assert_eq!(parse_color("#3cdc78").unwrap(), [0x3c, 0xdc, 0x78]);
+    assert_eq!(parse_color("FF0000").unwrap(), [255, 0, 0]);
+    assert!(parse_color("12345").is_err());
+    assert!(parse_color("zz0000").is_err());
+
+    let automata = automata_for("B3/S23:T20,10", &glider(), 4).unwrap();
+    assert_eq!((automata.grid().width(), automata.grid().height()), (20, 10));
+    assert_eq!(automata.grid().bounding_box(), Some((Position::new(8, 3), 3, 3)));
+    assert!(automata_for("nonsense", &glider(), 4).is_err());
+}
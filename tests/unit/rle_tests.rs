@@ -0,0 +1,97 @@
+use cellularity::{CellState, DenseGrid, Grid, PatternLibrary, Position, RlePattern, Topology};
+
+// Tests derived from src/core/rle.rs
+
+#[test]
+fn test_rle_parse_header_and_metadata() {
+    let text = "#N Glider\n#O Richard K. Guy\n#C The smallest spaceship.\n\
+                x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n";
+    let pattern = RlePattern::parse(text).unwrap();
+    assert_eq!(pattern.name.as_deref(), Some("Glider"));
+    assert_eq!(pattern.author.as_deref(), Some("Richard K. Guy"));
+    assert_eq!(pattern.comments, vec!["The smallest spaceship.".to_string()]);
+    assert_eq!(pattern.rule.as_deref(), Some("B3/S23"));
+    assert_eq!((pattern.grid.width(), pattern.grid.height()), (3, 3));
+    let alive: Vec<Position> =
+        pattern.grid.iter().filter(|(_, s)| s.is_alive()).map(|(pos, _)| pos).collect();
+    assert_eq!(
+        alive,
+        vec![
+            Position::new(1, 0),
+            Position::new(2, 1),
+            Position::new(0, 2),
+            Position::new(1, 2),
+            Position::new(2, 2)
+        ]
+    );
+}
+
+#[test]
+fn test_rle_rule_with_commas_and_topology() {
+    let pattern = RlePattern::parse("x = 4, y = 2, rule = B3/S23:T30,20\n2o$2o!").unwrap();
+    assert_eq!(pattern.rule.as_deref(), Some("B3/S23:T30,20"));
+    assert_eq!((pattern.grid.width(), pattern.grid.height()), (4, 2));
+    let (_, topology) = Topology::split_rule(pattern.rule.as_deref().unwrap()).unwrap();
+    assert_eq!(topology.unwrap().width(), 30);
+
+    let ltl = RlePattern::parse("x = 1, y = 1, rule = R5,C0,M1,S34..58,B34..45,NM\no!").unwrap();
+    assert_eq!(ltl.rule.as_deref(), Some("R5,C0,M1,S34..58,B34..45,NM"));
+}
+
+#[test]
+fn test_rle_blank_rows_and_missing_header() {
+    let pattern = RlePattern::parse("o2$\n3bo!").unwrap();
+    assert_eq!((pattern.grid.width(), pattern.grid.height()), (4, 3));
+    assert!(pattern.grid.get(Position::new(0, 0)).unwrap().is_alive());
+    assert!(pattern.grid.get(Position::new(3, 2)).unwrap().is_alive());
+    assert_eq!(pattern.grid.count_alive(), 2);
+}
+
+#[test]
+fn test_rle_multi_state_round_trip() {
+    let mut grid = DenseGrid::new(5, 2).unwrap();
+    grid.set(Position::new(0, 0), CellState::Alive).unwrap();
+    grid.set(Position::new(1, 0), CellState::from_index(2)).unwrap();
+    grid.set(Position::new(2, 0), CellState::from_index(2)).unwrap();
+    grid.set(Position::new(4, 1), CellState::from_index(30)).unwrap();
+    let text = RlePattern::new(grid.clone()).to_rle();
+    assert!(text.contains("A2B$4.pF!"), "{}", text);
+    assert_eq!(RlePattern::parse(&text).unwrap().grid, grid);
+}
+
+#[test]
+fn test_rle_write_round_trip_with_short_lines() {
+    let gun = PatternLibrary::get("Gosper glider gun").unwrap();
+    let mut pattern = gun.to_rle_pattern().unwrap();
+    pattern.comments.push("Emits a glider every 30 generations".to_string());
+    let text = pattern.to_rle();
+    assert!(text.lines().all(|line| line.len() <= 70), "{}", text);
+    assert!(text.contains("x = 36, y = 9, rule = B3/S23"));
+    assert_eq!(RlePattern::parse(&text).unwrap(), pattern);
+}
+
+#[test]
+fn test_rle_rejects_invalid_input() {
+    assert!(RlePattern::parse("x = 3, y = 3\nbo$2bz!").is_err());
+    assert!(RlePattern::parse("x = three, y = 3\nbo!").is_err());
+    assert!(RlePattern::parse("x = 1, y = 1\nyX!").is_err());
+}
+
+#[test]
+fn test_rle_rejects_oversized_patterns() {
+    use cellularity::core::rle::MAX_PATTERN_CELLS;
+    use cellularity::Error;
+
+    let oversized =
+        |text: &str| matches!(RlePattern::parse(text), Err(Error::PatternParseError(_)));
+    assert!(oversized("x = 1000000, y = 1000000\no!"));
+    assert!(oversized("x = 18446744073709551615, y = 2\no!"));
+    assert!(oversized("99999999999999999999999o!"));
+    assert!(oversized(&format!("{}o!", MAX_PATTERN_CELLS + 1)));
+    assert!(oversized(&format!("{}bo!", MAX_PATTERN_CELLS)));
+    assert!(oversized(&format!("{}$o!", MAX_PATTERN_CELLS)));
+    assert!(oversized(&format!("{}o$o!", MAX_PATTERN_CELLS)));
+
+    let wide = RlePattern::parse("x = 4096, y = 2\n3o!").unwrap();
+    assert_eq!((wide.grid.width(), wide.grid.height()), (4096, 2));
+}
@@ -0,0 +1,170 @@
+use cellularity::core::automata::Automata;
+use cellularity::core::cell::{CellState, Position};
+use cellularity::core::dense_grid::DenseGrid;
+use cellularity::core::grid::Grid;
+use cellularity::core::lattice::Lattice;
+use cellularity::core::rules::{ConwayRule, Rule};
+use cellularity::error::Error;
+use cellularity::scripting::script::Script;
+
+// Tests derived from src/scripting/script.rs, src/scripting/script_rule.rs
+// and src/scripting/sim.rs
+
+const HIGHLIFE: &str = r#"
+fn name() { "HighLife" }
+fn apply(state, alive) {
+    if state == 0 { alive == 3 || alive == 6 } else { alive == 2 || alive == 3 }
+}
+"#;
+
+fn is_script_error<T>(result: cellularity::error::Result<T>, needle: &str) -> bool {
+    matches!(result, Err(Error::ScriptError(message)) if message.contains(needle))
+}
+
+#[test]
+fn test_script_rule_matches_the_builtin_rule() {
+    let seed = DenseGrid::new_random(24, 24, 0.4).unwrap();
+    let mut scripted = Automata::from_spec("B3/S23", 24, 24, "torus").unwrap();
+    let mut builtin = Automata::from_spec("B36/S23", 24, 24, "torus").unwrap();
+    Script::parse(HIGHLIFE).unwrap().install(&mut scripted).unwrap();
+    assert_eq!(scripted.rule().name(), "HighLife");
+    *scripted.grid_mut() = seed.clone();
+    *builtin.grid_mut() = seed;
+    scripted.step_n(20);
+    builtin.step_n(20);
+    assert_eq!(scripted.grid(), builtin.grid());
+}
+
+#[test]
+fn test_script_rules_take_states_and_check_their_output() {
+    let decay = Script::parse(
+        r#"
+fn states() { 3 }
+fn apply(state, alive) {
+    if state == 0 { if alive == 2 { 1 } else { 0 } } else { (state + 1) % 3 }
+}
+"#,
+    )
+    .unwrap();
+    let rule = decay.rule(8).unwrap();
+    assert_eq!(rule.states(), 3);
+    assert_eq!(rule.apply(CellState::Dead, 2), CellState::Alive);
+    assert_eq!(rule.apply(CellState::Alive, 0), CellState::from_index(2));
+    assert_eq!(rule.apply(CellState::from_index(2), 5), CellState::Dead);
+    assert_eq!(rule.apply(CellState::Dead, 40), CellState::Dead);
+
+    let overflowing = Script::parse("fn apply(state, alive) { alive }").unwrap();
+    assert!(is_script_error(overflowing.rule(8), "apply(0, 2) returned 2"));
+    let texty = Script::parse(r#"fn apply(state, alive) { "alive" }"#).unwrap();
+    assert!(is_script_error(texty.rule(8), "returned alive"));
+}
+
+#[test]
+fn test_script_neighborhoods_by_offsets_and_by_name() {
+    let cross = Script::parse(&format!(
+        "fn neighborhood() {{ [[0, -1], [-1, 0], [1, 0], [0, 1, 2]] }}\n{}",
+        "fn apply(state, alive) { alive >= 2 }"
+    ))
+    .unwrap();
+    let mut automata = Automata::from_spec("B3/S23", 8, 8, "plane").unwrap();
+    cross.install(&mut automata).unwrap();
+    assert_eq!(automata.neighborhood().offsets().len(), 4);
+    assert_eq!(automata.neighborhood().weights(), Some(&[1, 1, 1, 2][..]));
+    automata.grid_mut().set(Position::new(3, 4), CellState::Alive).unwrap();
+    automata.grid_mut().set(Position::new(5, 4), CellState::Alive).unwrap();
+    automata.step();
+    // (4, 4) has two neighbors of weight 1; (3, 3) and (5, 3) each have
+    // one below them, which weighs 2
+    for (x, y) in [(4, 4), (3, 3), (5, 3)] {
+        assert_eq!(automata.grid().get(Position::new(x, y)).unwrap(), CellState::Alive);
+    }
+    assert_eq!(automata.grid().count_alive(), 3);
+
+    // The cross rule only knows sums up to 5, and six neighbors reach 6
+    let hexagonal = Script::parse(r#"fn neighborhood() { "hexagonal" }"#).unwrap();
+    assert!(is_script_error(hexagonal.install(&mut automata), "install the script again"));
+    assert_eq!(automata.lattice(), Lattice::Square);
+    automata.set_rule(Box::new(ConwayRule::new()));
+    hexagonal.install(&mut automata).unwrap();
+    assert_eq!(automata.lattice(), Lattice::Hexagonal);
+
+    let broken = Script::parse("fn neighborhood() { [[1, 2, 3, 4]] }").unwrap();
+    assert!(is_script_error(broken.install(&mut automata), "[dx, dy] or"));
+    let heavy = Script::parse(
+        "fn neighborhood() { [[0, 1, 100000]] }\nfn apply(state, alive) { alive > 0 }",
+    )
+    .unwrap();
+    assert!(is_script_error(heavy.install(&mut automata), "more than 4096"));
+    assert_eq!(automata.lattice(), Lattice::Hexagonal);
+}
+
+#[test]
+fn test_script_rules_refuse_neighborhoods_with_larger_sums() {
+    let mut automata = Automata::from_spec("B3/S23", 8, 8, "torus").unwrap();
+    automata.set_neighborhood_by_name("von_neumann").unwrap();
+    Script::parse(HIGHLIFE).unwrap().install(&mut automata).unwrap();
+    assert!(is_script_error(automata.set_lattice(Lattice::Hexagonal), "not 0 to 6"));
+    assert!(is_script_error(automata.set_neighborhood_by_name("moore"), "HighLife"));
+    assert_eq!(automata.lattice(), Lattice::Square);
+    assert_eq!(automata.neighborhood().offsets().len(), 4);
+
+    // Fewer neighbors stay within the table, and installing the script again
+    // tabulates it for the new neighborhood
+    automata.set_lattice(Lattice::Triangular).unwrap();
+    let source = format!("{}\nfn neighborhood() {{ \"hexagonal\" }}", HIGHLIFE);
+    Script::parse(&source).unwrap().install(&mut automata).unwrap();
+    assert_eq!(automata.lattice(), Lattice::Hexagonal);
+    assert!(automata.set_neighborhood_by_name("circular:2").is_err());
+    assert!(automata.set_lattice(Lattice::Square).is_err());
+}
+
+#[test]
+fn test_script_automation_drives_the_automaton() {
+    let script = Script::parse(
+        r#"
+fn run(sim) {
+    sim.randomize(0.0);
+    for y in 0..10 { for x in 0..10 { sim.set(x + 10, y + 10, 1); } }
+    while sim.population >= 100 { sim.step(); }
+    print(`settled at ${sim.generation}`);
+    sim.clear();
+    sim.stamp("bo$2bo$3o!");
+    sim.step(4);
+    sim.set(0, 0, 1);
+}
+"#,
+    )
+    .unwrap();
+    let mut automata = Automata::from_spec("B3/S23", 30, 30, "plane").unwrap();
+    let printed = script.run(&mut automata).unwrap();
+    assert_eq!(printed, vec!["settled at 1".to_string()]);
+    assert_eq!(automata.grid().count_alive(), 6);
+    assert_eq!(automata.grid().get(Position::new(0, 0)).unwrap(), CellState::Alive);
+
+    let failing = Script::parse("fn run(sim) { sim.step(2); sim.get(30, 0) }").unwrap();
+    assert!(is_script_error(failing.run(&mut automata), "out of bounds"));
+    assert_eq!(automata.grid().width(), 30);
+    assert_eq!(automata.generation(), 7);
+}
+
+#[test]
+fn test_script_errors_are_reported() {
+    assert!(is_script_error(Script::parse("fn apply(state, alive) { state + }"), "line 1"));
+    assert!(is_script_error(Script::parse(r#"throw "refused";"#), "refused"));
+    assert!(matches!(Script::load("/no/such/script.rhai"), Err(Error::IoError(_))));
+
+    let mut endless = Script::parse("fn run(sim) { loop { sim.step(); } }").unwrap();
+    endless.set_max_operations(1_000);
+    let mut automata = Automata::from_spec("B3/S23", 4, 4, "plane").unwrap();
+    assert!(is_script_error(endless.run(&mut automata), "Too many operations"));
+
+    let long = Script::parse("fn run(sim) { sim.step(2); sim.step(1_000_000_000); }").unwrap();
+    let mut stepped = Automata::from_spec("B3/S23", 4, 4, "plane").unwrap();
+    assert!(is_script_error(long.run(&mut stepped), "too many steps"));
+    assert_eq!(stepped.generation(), 2);
+
+    let empty = Script::parse("let x = 1;").unwrap();
+    assert!(!empty.defines_rule() && !empty.defines_neighborhood());
+    assert!(is_script_error(empty.install(&mut automata), "no apply"));
+    assert!(is_script_error(empty.run(&mut automata), "no run(sim)"));
+}
@@ -0,0 +1,187 @@
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+
+use cellularity::server::api::{Api, MAX_CELL_UPDATES, MAX_SESSIONS};
+use cellularity::server::http_server::Server;
+use cellularity::server::session::MAX_CELLS;
+use serde_json::Value;
+
+// Tests derived from src/server/api.rs, src/server/session.rs and
+// src/server/http_server.rs
+
+fn json(api: &mut Api, method: &str, url: &str, body: &str) -> (u16, Value) {
+    let response = api.handle(method, url, body);
+    assert_eq!(response.content_type, "application/json", "{}", response.body);
+    (response.status, serde_json::from_str(&response.body).unwrap())
+}
+
+#[test]
+fn test_api_creates_steps_and_downloads_sessions() {
+    let mut api = Api::new();
+    let config = r#"{"rule": "B3/S23", "width": 12, "height": 10, "boundary": "plane"}"#;
+    let (status, info) = json(&mut api, "POST", "/sessions", config);
+    assert_eq!(status, 201);
+    assert_eq!((info["id"].as_u64(), info["width"].as_u64()), (Some(1), Some(12)));
+
+    let (status, info) = json(&mut api, "PUT", "/sessions/1/pattern?x=2&y=3", "3o!");
+    assert_eq!((status, info["population"].as_u64()), (200, Some(3)));
+    let (_, population) = json(&mut api, "POST", "/sessions/1/step?steps=3", "");
+    assert_eq!(population, serde_json::json!({"generation": 3, "population": 3}));
+
+    let (_, grid) = json(&mut api, "GET", "/sessions/1/grid", "");
+    assert_eq!((grid["width"].as_u64(), grid["height"].as_u64()), (Some(12), Some(10)));
+    let column: Vec<u64> = (2..5).map(|y| grid["cells"][y][3].as_u64().unwrap()).collect();
+    assert_eq!(column, [1, 1, 1]);
+
+    let rle = api.handle("GET", "/sessions/1/grid?format=rle", "");
+    assert!(rle.body.starts_with("x = 12, y = 10, rule = B3/S23\n"), "{}", rle.body);
+    assert!(rle.body.contains("3bo$3bo$3bo!"), "{}", rle.body);
+
+    let (_, population) = json(&mut api, "POST", "/sessions/1/reset", "");
+    assert_eq!(population["population"].as_u64(), Some(0));
+}
+
+#[test]
+fn test_api_sessions_are_independent() {
+    let mut api = Api::new();
+    json(&mut api, "POST", "/sessions", "");
+    json(&mut api, "POST", "/sessions", r#"{"rule": "B3/S23:T20,16"}"#);
+    json(&mut api, "PUT", "/sessions/1/pattern", "bo$2bo$3o!");
+    json(&mut api, "POST", "/sessions/1/step?steps=4", "");
+
+    let (_, second) = json(&mut api, "GET", "/sessions/2", "");
+    assert_eq!((second["width"].as_u64(), second["generation"].as_u64()), (Some(20), Some(0)));
+    let (_, list) = json(&mut api, "GET", "/sessions", "");
+    assert_eq!(list.as_array().map(Vec::len), Some(2));
+
+    let (status, _) = json(&mut api, "DELETE", "/sessions/1", "");
+    assert_eq!(status, 200);
+    assert_eq!(api.session_count(), 1);
+    let (status, _) = json(&mut api, "GET", "/sessions/1/population", "");
+    assert_eq!(status, 404);
+    let (_, third) = json(&mut api, "POST", "/sessions", "");
+    assert_eq!(third["id"].as_u64(), Some(3));
+}
+
+#[test]
+fn test_api_uploads_json_grids_and_stamps_without_clearing() {
+    let mut api = Api::new();
+    let config = r#"{"width": 6, "height": 4, "boundary": "mirror",
+                     "neighborhood": "von_neumann"}"#;
+    let (_, info) = json(&mut api, "POST", "/sessions", config);
+    assert!(info["neighborhood"].as_str().unwrap().contains("Von Neumann"), "{}", info);
+
+    let grid = r#"{"width": 2, "height": 1, "cells": [[1, 2]]}"#;
+    json(&mut api, "PUT", "/sessions/1/pattern?x=0&y=0", grid);
+    json(&mut api, "PUT", "/sessions/1/pattern?x=4&y=3&clear=false", "o!");
+    let (_, grid) = json(&mut api, "GET", "/sessions/1/grid?format=json", "");
+    assert_eq!(grid["cells"][0], serde_json::json!([1, 2, 0, 0, 0, 0]));
+    assert_eq!(grid["cells"][3][4].as_u64(), Some(1));
+}
+
+#[test]
+fn test_api_reports_errors() {
+    let mut api = Api::new();
+    let cases = [
+        ("POST", "/sessions", r#"{"rule": "not a rule"}"#, 400),
+        ("POST", "/sessions", r#"{"boundary": "moebius"}"#, 400),
+        ("POST", "/sessions", r#"{"colour": "red"}"#, 400),
+        ("POST", "/sessions", "{", 400),
+        ("GET", "/sessions/7", "", 404),
+        ("GET", "/nowhere", "", 404),
+    ];
+    for (method, url, body, status) in cases {
+        assert_eq!(json(&mut api, method, url, body).0, status, "{} {} {}", method, url, body);
+    }
+
+    json(&mut api, "POST", "/sessions", "");
+    for (method, url, body) in [
+        ("POST", "/sessions/1/step?steps=many", ""),
+        ("POST", "/sessions/1/step?steps=99999999", ""),
+        ("PUT", "/sessions/1/pattern", "3z!"),
+        ("PUT", "/sessions/1/pattern", r#"{"width": 2, "height": 1, "cells": [[1]]}"#),
+        ("PUT", "/sessions/1/pattern?x=left", "o!"),
+        ("GET", "/sessions/1/grid?format=xml", ""),
+    ] {
+        assert_eq!(json(&mut api, method, url, body).0, 400, "{} {}", method, url);
+    }
+    assert_eq!(json(&mut api, "PATCH", "/sessions/1", "").0, 404);
+}
+
+#[test]
+fn test_api_rejects_oversized_grids() {
+    let mut api = Api::new();
+    for body in [
+        r#"{"width": 1000000, "height": 1000000}"#,
+        r#"{"width": 18446744073709551615, "height": 2}"#,
+        r#"{"rule": "B3/S23:T1000000,1000000"}"#,
+    ] {
+        assert_eq!(json(&mut api, "POST", "/sessions", body).0, 400, "{}", body);
+    }
+    assert_eq!(api.session_count(), 0);
+
+    let side = (MAX_CELLS as f64).sqrt() as usize;
+    let body = format!(r#"{{"width": {}, "height": {}}}"#, side, side);
+    assert_eq!(json(&mut api, "POST", "/sessions", &body).0, 201);
+    assert_eq!(json(&mut api, "POST", "/sessions", "").0, 201);
+    // Steps are limited by the work they make on the session's grid
+    let steps = format!("/sessions/1/step?steps={}", MAX_CELL_UPDATES / MAX_CELLS as u64 + 1);
+    assert_eq!(json(&mut api, "POST", &steps, "").0, 400);
+    assert_eq!(json(&mut api, "POST", "/sessions/2/step?steps=1000", "").0, 200);
+    for body in [
+        "x = 1000000, y = 1000000\no!",
+        r#"{"width": 1000000, "height": 1000000, "cells": []}"#,
+    ] {
+        assert_eq!(json(&mut api, "PUT", "/sessions/2/pattern", body).0, 400, "{}", body);
+    }
+}
+
+/// Sends one HTTP/1.0 request and returns the status code and body
+fn request(addr: std::net::SocketAddr, method: &str, path: &str, body: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.0\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+        method,
+        path,
+        body.len(),
+        body
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let status = response[9..12].parse().unwrap();
+    let body = response.split_once("\r\n\r\n").map_or("", |(_, body)| body).to_string();
+    (status, body)
+}
+
+#[test]
+fn test_server_answers_over_localhost() {
+    let server = Arc::new(Server::bind("127.0.0.1:0").unwrap());
+    let addr = server.local_addr().unwrap();
+    let running = Arc::clone(&server);
+    let thread = std::thread::spawn(move || running.run());
+
+    let (status, body) = request(addr, "POST", "/sessions", r#"{"width": 8, "height": 8}"#);
+    assert_eq!(status, 201, "{}", body);
+    assert_eq!(request(addr, "PUT", "/sessions/1/pattern", "2o$2o!").0, 200);
+    let (_, body) = request(addr, "POST", "/sessions/1/step?steps=5", "");
+    assert_eq!(body, r#"{"generation":5,"population":4}"#);
+    assert_eq!(request(addr, "GET", "/sessions/2", "").0, 404);
+
+    server.stop();
+    thread.join().unwrap();
+}
+
+#[test]
+fn test_api_limits_the_number_of_sessions() {
+    let mut api = Api::new();
+    for _ in 0..MAX_SESSIONS {
+        assert_eq!(json(&mut api, "POST", "/sessions", "").0, 201);
+    }
+    assert_eq!(json(&mut api, "POST", "/sessions", "").0, 429);
+    assert_eq!(json(&mut api, "DELETE", "/sessions/1", "").0, 200);
+    assert_eq!(json(&mut api, "POST", "/sessions", "").0, 201);
+    assert_eq!(api.session_count(), MAX_SESSIONS);
+}
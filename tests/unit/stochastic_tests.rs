@@ -0,0 +1,140 @@
+// Tests derived from src/core/rules/stochastic.rs and src/core/update_scheme.rs
+
+use cellularity::{
+    parse_rule, Automata, CellState, ConwayRule, DenseGrid, Grid, MooreNeighborhood, Position, Rule,
+    StochasticRule, ToroidalBoundary, UpdateScheme,
+};
+
+fn automata(rule: Box<dyn Rule>, grid: &DenseGrid) -> Automata {
+    let mut automata = Automata::new(
+        grid.width(),
+        grid.height(),
+        rule,
+        Box::new(ToroidalBoundary::new()),
+        Box::new(MooreNeighborhood::new()),
+    )
+    .unwrap();
+    *automata.grid_mut() = grid.clone();
+    automata
+}
+
+fn stochastic_life(birth: f64, survival: f64) -> Box<dyn Rule> {
+    Box::new(StochasticRule::new(Box::new(ConwayRule::new()), birth, survival).unwrap())
+}
+
+fn soup() -> DenseGrid {
+    DenseGrid::new_random(24, 24, 0.35).unwrap()
+}
+
+#[test]
+fn test_probabilities_are_validated() {
+    assert!(StochasticRule::new(Box::new(ConwayRule::new()), 1.5, 1.0).is_err());
+    assert!(StochasticRule::new(Box::new(ConwayRule::new()), 0.5, -0.1).is_err());
+    let rule = StochasticRule::new(Box::new(ConwayRule::new()), 0.25, 0.75).unwrap();
+    assert_eq!((rule.birth_probability(), rule.survival_probability()), (0.25, 0.75));
+    assert_eq!(rule.name(), ConwayRule::new().name());
+}
+
+#[test]
+fn test_certain_probabilities_match_wrapped_rule() {
+    let grid = soup();
+    let mut plain = automata(Box::new(ConwayRule::new()), &grid);
+    let mut certain = automata(stochastic_life(1.0, 1.0), &grid);
+    plain.step_n(10);
+    certain.step_n(10);
+    assert_eq!(plain.grid(), certain.grid());
+}
+
+#[test]
+fn test_no_births_without_birth_probability() {
+    let mut automata = automata(stochastic_life(0.0, 1.0), &soup());
+    let mut alive = automata.grid().count_alive();
+    for _ in 0..10 {
+        automata.step();
+        let now = automata.grid().count_alive();
+        assert!(now <= alive);
+        alive = now;
+    }
+}
+
+#[test]
+fn test_same_seed_replays_run() {
+    let grid = soup();
+    let run = |seed: u64, scheme: UpdateScheme| {
+        let mut automata = automata(stochastic_life(0.6, 0.8), &grid);
+        automata.set_update_scheme(scheme);
+        automata.set_seed(seed);
+        automata.step_n(8);
+        automata.grid().clone()
+    };
+    for scheme in [
+        UpdateScheme::Synchronous,
+        UpdateScheme::Asynchronous { alpha: 0.5 },
+        UpdateScheme::RandomSequential,
+    ] {
+        assert_eq!(run(7, scheme), run(7, scheme), "{:?}", scheme);
+        assert_ne!(run(7, scheme), run(8, scheme), "{:?}", scheme);
+    }
+
+    // Reset restarts the generator from the seed
+    let mut automata = automata(stochastic_life(0.6, 0.8), &grid);
+    automata.set_seed(42);
+    automata.step_n(5);
+    let first = automata.grid().clone();
+    automata.reset();
+    *automata.grid_mut() = grid.clone();
+    automata.step_n(5);
+    assert_eq!(automata.grid(), &first);
+    assert_eq!(automata.seed(), 42);
+}
+
+#[test]
+fn test_alpha_asynchrony_extremes() {
+    let grid = soup();
+    let mut frozen = automata(Box::new(ConwayRule::new()), &grid);
+    frozen.set_update_scheme(UpdateScheme::Asynchronous { alpha: 0.0 });
+    frozen.step_n(3);
+    assert_eq!(frozen.grid(), &grid);
+    assert_eq!(frozen.generation(), 3);
+
+    let mut full = automata(Box::new(ConwayRule::new()), &grid);
+    full.set_update_scheme(UpdateScheme::Asynchronous { alpha: 1.0 });
+    let mut synchronous = automata(Box::new(ConwayRule::new()), &grid);
+    full.step_n(3);
+    synchronous.step_n(3);
+    assert_eq!(full.grid(), synchronous.grid());
+}
+
+#[test]
+fn test_random_sequential_updates_each_cell_once() {
+    // Every update flips the cell, so each cell must be visited exactly once
+    let flip = parse_rule("B012345678/S").unwrap().rule;
+    let mut automata = automata(flip, &DenseGrid::new(9, 7).unwrap());
+    automata.set_update_scheme(UpdateScheme::RandomSequential);
+    automata.step();
+    assert_eq!(automata.grid().count_alive(), 63);
+    automata.step();
+    assert_eq!(automata.grid().count_alive(), 0);
+}
+
+#[test]
+fn test_line_by_line_sees_earlier_updates() {
+    let mut grid = DenseGrid::new(7, 7).unwrap();
+    grid.set(Position::new(3, 3), CellState::Alive).unwrap();
+    let spread = || parse_rule("B12345678/S012345678").unwrap().rule;
+
+    let mut synchronous = automata(spread(), &grid);
+    synchronous.step();
+    assert_eq!(synchronous.grid().count_alive(), 9);
+
+    // Births earlier in the sweep trigger births later in the same generation
+    let mut sweep = automata(spread(), &grid);
+    sweep.set_update_scheme(UpdateScheme::LineByLine);
+    sweep.step();
+    assert!(sweep.grid().count_alive() > 9);
+    let mut again = automata(spread(), &grid);
+    again.set_update_scheme(UpdateScheme::LineByLine);
+    again.set_seed(99);
+    again.step();
+    assert_eq!(sweep.grid(), again.grid());
+}
@@ -0,0 +1,219 @@
+use std::sync::{Arc, Mutex};
+
+use cellularity::core::automata::Automata;
+use cellularity::core::cell::{CellState, Position};
+use cellularity::core::dense_grid::DenseGrid;
+use cellularity::core::grid::Grid;
+use cellularity::server::api::Api;
+use cellularity::server::delta_encoder::DeltaEncoder;
+use cellularity::server::grid_mirror::GridMirror;
+use cellularity::server::stream_client::StreamClient;
+use cellularity::server::stream_frame::StreamFrame;
+use cellularity::server::stream_server::StreamServer;
+
+// Tests derived from src/server/stream_frame.rs, src/server/delta_encoder.rs,
+// src/server/grid_mirror.rs, src/server/stream_server.rs and
+// src/server/stream_client.rs
+
+/// Returns an API holding session 1: a glider on a 20 by 20 torus
+fn glider_api() -> Api {
+    let mut api = Api::new();
+    let config = r#"{"width": 20, "height": 20, "boundary": "torus"}"#;
+    assert_eq!(api.handle("POST", "/sessions", config).status, 201);
+    assert_eq!(api.handle("PUT", "/sessions/1/pattern", "bo$2bo$3o!").status, 200);
+    api
+}
+
+fn glider_automata(api: &mut Api) -> &mut Automata {
+    &mut api.session_mut(1).unwrap().automata
+}
+
+#[test]
+fn test_frames_round_trip_through_json_and_binary() {
+    let mut api = glider_api();
+    let automata = glider_automata(&mut api);
+    automata.grid_mut().set(Position::new(15, 16), CellState::State(3)).unwrap();
+    let before = automata.grid().clone();
+    automata.step();
+    let delta = StreamFrame::delta(&before, automata.grid(), 1).unwrap();
+    let keyframe = StreamFrame::Keyframe { generation: 1, grid: automata.grid().clone() };
+
+    for frame in [&keyframe, &delta] {
+        assert_eq!(&StreamFrame::from_bytes(&frame.to_bytes().unwrap()).unwrap(), frame);
+    }
+    assert_eq!(StreamFrame::from_json(&keyframe.to_json()).unwrap(), keyframe);
+    let StreamFrame::Delta { changes, .. } = &delta else { panic!("expected a delta") };
+    let StreamFrame::Delta { changes: decoded, generation, .. } =
+        StreamFrame::from_json(&delta.to_json()).unwrap()
+    else {
+        panic!("expected a delta")
+    };
+    assert_eq!((&decoded, generation), (changes, 1));
+
+    // JSON deltas carry the grid size, so they convert to binary too
+    let from_json = StreamFrame::from_json(&delta.to_json()).unwrap();
+    assert_eq!(from_json, delta);
+    assert_eq!(from_json.to_bytes().unwrap(), delta.to_bytes().unwrap());
+    let sizeless = r#"{"type": "delta", "generation": 1, "born": [[0, 0]]}"#;
+    assert!(StreamFrame::from_json(sizeless).is_err());
+    let outside = StreamFrame::Delta {
+        generation: 1,
+        width: 0,
+        height: 0,
+        changes: vec![(Position::new(1, 1), CellState::Alive)],
+    };
+    assert!(outside.to_bytes().is_err());
+    let unordered = StreamFrame::Delta {
+        generation: 1,
+        width: 4,
+        height: 4,
+        changes: vec![
+            (Position::new(1, 1), CellState::Alive),
+            (Position::new(0, 1), CellState::Alive),
+        ],
+    };
+    assert!(unordered.to_bytes().is_err());
+
+    // A glider step changes a handful of cells, far less than the grid
+    let bytes = delta.to_bytes().unwrap();
+    assert!(bytes.len() < 20, "{:?}", bytes);
+    assert!(StreamFrame::from_bytes(&[1, 0, 2, 2, 1, 9, 1]).is_err());
+    // Sizes and distances from the wire are checked before anything is
+    // allocated or added up
+    let huge = [0xff, 0xff, 0xff, 0xff, 0x0f];
+    let keyframe = [&[0, 0][..], &huge, &huge].concat();
+    assert!(StreamFrame::from_bytes(&keyframe).is_err());
+    let large = [0x80, 0x80, 0x80, 0x08];
+    assert!(StreamFrame::from_bytes(&[&[0, 0][..], &large, &large].concat()).is_err());
+    let far = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+    let delta = [&[1, 0, 4, 4, 2, 1, 1][..], &far, &[1]].concat();
+    assert!(StreamFrame::from_bytes(&delta).is_err());
+    assert!(StreamFrame::from_json(r#"{"type": "nothing", "generation": 0}"#).is_err());
+}
+
+#[test]
+fn test_encoder_sends_keyframes_and_coalesces_skipped_generations() {
+    let mut api = glider_api();
+    let automata = glider_automata(&mut api);
+    let mut encoder = DeltaEncoder::new(3);
+    let mut mirror = GridMirror::new();
+
+    let first = encoder.encode(automata.grid(), 0).unwrap();
+    assert!(matches!(first, StreamFrame::Keyframe { .. }));
+    mirror.apply(&first).unwrap();
+    assert_eq!(encoder.encode(automata.grid(), 0), None);
+
+    let mut kinds = Vec::new();
+    for _ in 0..5 {
+        // Skipped generations fold into the next delta
+        automata.step();
+        automata.step();
+        let frame = encoder.encode(automata.grid(), automata.generation()).unwrap();
+        kinds.push(matches!(frame, StreamFrame::Keyframe { .. }));
+        mirror.apply(&frame).unwrap();
+        assert_eq!(mirror.grid(), Some(automata.grid()));
+        assert_eq!(mirror.generation(), automata.generation());
+    }
+    assert_eq!(kinds, [false, false, false, true, false]);
+
+    encoder.request_keyframe();
+    let frame = encoder.encode(automata.grid(), automata.generation()).unwrap();
+    assert!(matches!(frame, StreamFrame::Keyframe { .. }));
+}
+
+#[test]
+fn test_mirror_rejects_deltas_it_cannot_apply() {
+    let grid = DenseGrid::new(4, 4).unwrap();
+    let delta = StreamFrame::Delta {
+        generation: 2,
+        width: 4,
+        height: 4,
+        changes: vec![(Position::new(1, 1), CellState::Alive)],
+    };
+    let mut mirror = GridMirror::new();
+    assert!(mirror.apply(&delta).is_err());
+
+    mirror.apply(&StreamFrame::Keyframe { generation: 5, grid }).unwrap();
+    assert!(mirror.apply(&delta).is_err());
+    let outside = StreamFrame::Delta {
+        generation: 6,
+        width: 4,
+        height: 4,
+        changes: vec![
+            (Position::new(0, 0), CellState::Alive),
+            (Position::new(9, 0), CellState::Alive),
+        ],
+    };
+    assert!(mirror.apply(&outside).is_err());
+    assert_eq!(mirror.grid().unwrap().count_alive(), 0);
+}
+
+#[test]
+fn test_stream_mirrors_a_running_session_over_localhost() {
+    let api = Arc::new(Mutex::new(glider_api()));
+    let server = StreamServer::bind("127.0.0.1:0", Arc::clone(&api)).unwrap();
+    let addr = server.local_addr().unwrap();
+    std::thread::spawn(move || server.run());
+
+    assert!(StreamClient::connect(&format!("ws://{}/sessions/9", addr)).is_err());
+    for format in ["json", "binary"] {
+        let url = format!("ws://{}/sessions/1?format={}&run=true&interval=5", addr, format);
+        let mut client = StreamClient::connect(&url).unwrap();
+        while client.mirror().generation() < 8 {
+            assert!(client.update().unwrap());
+        }
+        let generation = client.mirror().generation();
+        let mirrored = client.mirror().grid().unwrap().clone();
+        client.close().unwrap();
+
+        // Replay the session to the generation the mirror reached
+        let mut replay = glider_api();
+        replay.handle("POST", &format!("/sessions/1/step?steps={}", generation), "");
+        assert_eq!(&mirrored, glider_automata(&mut replay).grid(), "{} stream", format);
+    }
+
+    // Without run the stream follows steps made through the API, and ends
+    // when the session is deleted
+    let mut client = StreamClient::connect(&format!("ws://{}/sessions/1", addr)).unwrap();
+    assert!(client.update().unwrap());
+    let generation = client.mirror().generation();
+    api.lock().unwrap().handle("POST", "/sessions/1/step?steps=2", "");
+    assert!(client.update().unwrap());
+    assert_eq!(client.mirror().generation(), generation + 2);
+    api.lock().unwrap().handle("DELETE", "/sessions/1", "");
+    assert!(!client.update().unwrap());
+}
+
+#[test]
+fn test_stream_sends_edits_that_keep_the_generation() {
+    let mut api = Api::new();
+    let config = r#"{"width": 20, "height": 20, "boundary": "torus"}"#;
+    assert_eq!(api.handle("POST", "/sessions", config).status, 201);
+    let api = Arc::new(Mutex::new(api));
+    let server = StreamServer::bind("127.0.0.1:0", Arc::clone(&api)).unwrap();
+    let addr = server.local_addr().unwrap();
+    std::thread::spawn(move || server.run());
+
+    let mut client = StreamClient::connect(&format!("ws://{}/sessions/1", addr)).unwrap();
+    assert!(client.update().unwrap());
+    assert_eq!(client.mirror().grid().unwrap().count_alive(), 0);
+
+    // An upload after connecting reaches the viewer without any step
+    api.lock().unwrap().handle("PUT", "/sessions/1/pattern", "bo$2bo$3o!");
+    assert!(client.update().unwrap());
+    assert_eq!(client.mirror().generation(), 0);
+    assert_eq!(client.mirror().grid(), Some(glider_automata(&mut api.lock().unwrap()).grid()));
+
+    // A reset goes back in time, so the mirror is brought back by a
+    // keyframe rather than a delta it would refuse
+    api.lock().unwrap().handle("POST", "/sessions/1/step?steps=3", "");
+    assert!(client.update().unwrap());
+    assert_eq!(client.mirror().generation(), 3);
+    api.lock().unwrap().handle("POST", "/sessions/1/reset", "");
+    api.lock().unwrap().handle("PUT", "/sessions/1/pattern", "3o!");
+    while client.mirror().grid().unwrap().count_alive() != 3 {
+        assert!(client.update().unwrap());
+    }
+    assert_eq!(client.mirror().generation(), 0);
+    assert_eq!(client.mirror().grid(), Some(glider_automata(&mut api.lock().unwrap()).grid()));
+}
@@ -0,0 +1,94 @@
+use cellularity::{CellState, DenseGrid, Grid, Position, SvgExport};
+
+// Tests derived from src/io/svg_export.rs
+
+fn grid_with(cells: &[(usize, usize, CellState)]) -> DenseGrid {
+    let mut grid = DenseGrid::new(4, 3).unwrap();
+    for &(x, y, state) in cells {
+        grid.set(Position::new(x, y), state).unwrap();
+    }
+    grid
+}
+
+fn plain() -> SvgExport {
+    SvgExport {
+        grid_lines: false,
+        ..SvgExport::new()
+    }
+}
+
+#[test]
+fn test_svg_draws_live_cells_over_dead_background() {
+    let grid = grid_with(&[(1, 0, CellState::Alive), (3, 2, CellState::State(4))]);
+    let svg = plain().to_svg(&grid).unwrap();
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.contains("width=\"41\" height=\"31\""));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"40\" height=\"30\" fill=\"#1e1e23\"/>"));
+    assert!(svg.contains("<rect x=\"10\" y=\"0\" width=\"10\" height=\"10\" fill=\"#3cdc78\"/>"));
+    assert!(svg.contains("<rect x=\"30\" y=\"20\" width=\"10\" height=\"10\" fill=\"#3cdc78\"/>"));
+    assert_eq!(svg.matches("<rect").count(), 3);
+    assert!(!svg.contains("<path"));
+}
+
+#[test]
+fn test_svg_crops_and_uses_palette() {
+    let grid = grid_with(&[(1, 1, CellState::Alive), (2, 1, CellState::State(2))]);
+    let mut export = plain();
+    export.style.crop = Some((Position::new(1, 1), 2, 1));
+    export.style.palette = Some(vec![[0, 0, 0], [0, 0, 255], [255, 0, 0]]);
+    let svg = export.to_svg(&grid).unwrap();
+    assert!(svg.contains("width=\"21\" height=\"11\""));
+    assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"10\" height=\"10\" fill=\"#0000ff\"/>"));
+    assert!(svg.contains("<rect x=\"10\" y=\"0\" width=\"10\" height=\"10\" fill=\"#ff0000\"/>"));
+
+    export.style.crop = Some((Position::new(3, 1), 2, 1));
+    assert!(export.to_svg(&grid).is_err());
+}
+
+#[test]
+fn test_svg_grid_lines_borders_highlights_and_labels() {
+    let grid = grid_with(&[(0, 0, CellState::Alive)]);
+    let mut export = SvgExport::new();
+    export.cell_borders = true;
+    export.labels = true;
+    export.label_step = 2;
+    export.highlights = vec![Position::new(2, 1), Position::new(9, 9)];
+    let svg = export.to_svg(&grid).unwrap();
+
+    assert!(svg.contains("<path d=\"M24 24v30M34 24v30"));
+    assert!(svg.contains("stroke=\"#808080\" stroke-width=\"1\"/>"));
+    assert_eq!(svg.matches("stroke=\"#fac83c\"").count(), 1);
+    assert!(svg.contains("<rect x=\"45\" y=\"35\" width=\"8\" height=\"8\" fill=\"none\""));
+    let labels: Vec<&str> = svg
+        .lines()
+        .filter(|line| line.contains("<text"))
+        .map(|line| &line[line.find('>').unwrap() + 1..line.find("</text>").unwrap()])
+        .collect();
+    assert_eq!(labels, ["0", "2", "0", "2"]);
+}
+
+#[test]
+fn test_svg_stack_places_generations_side_by_side() {
+    let frames = vec![
+        grid_with(&[(0, 0, CellState::Alive)]),
+        grid_with(&[(1, 0, CellState::Alive)]),
+        grid_with(&[(2, 0, CellState::Alive)]),
+    ];
+    let mut export = plain();
+    export.gap = 1;
+    let svg = export.stack(&frames).unwrap();
+    // Panels are 40 pixels wide with 10 pixels between them
+    assert!(svg.contains("width=\"141\" height=\"31\""));
+    for x in [0, 60, 120] {
+        assert!(svg.contains(&format!("<rect x=\"{}\" y=\"0\" width=\"10\"", x)), "{}", x);
+    }
+
+    export.labels = true;
+    let svg = export.stack(&frames).unwrap();
+    assert!(svg.contains(">t = 2</text>"));
+
+    assert!(export.stack::<DenseGrid>(&[]).is_err());
+    let mismatched = vec![DenseGrid::new(4, 3).unwrap(), DenseGrid::new(2, 2).unwrap()];
+    assert!(export.stack(&mismatched).is_err());
+}
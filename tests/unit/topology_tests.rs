@@ -0,0 +1,142 @@
+use cellularity::{
+    Automata, Boundary, ConwayRule, CrossSurfaceBoundary, EdgePair, Grid, KleinBottleBoundary,
+    MirrorBoundary, MooreNeighborhood, Position, ShiftedTorusBoundary, SphereBoundary,
+    Topology, CellState,
+};
+
+// Tests derived from src/core/klein_bottle_boundary.rs, cross_surface_boundary.rs,
+// sphere_boundary.rs, mirror_boundary.rs, shifted_torus_boundary.rs and topology.rs
+
+#[test]
+fn test_klein_bottle_mirrors_across_twisted_edges() {
+    let boundary = KleinBottleBoundary::new(EdgePair::TopBottom);
+    // Leaving the bottom near the left re-enters the top near the right
+    assert_eq!(boundary.wrap(1, 10, 10, 10), Some(Position::new(8, 0)));
+    assert_eq!(boundary.wrap(1, -1, 10, 10), Some(Position::new(8, 9)));
+    // The other edges wrap as on a torus
+    assert_eq!(boundary.wrap(-1, 3, 10, 10), Some(Position::new(9, 3)));
+    // Crossing twice undoes the twist
+    assert_eq!(boundary.wrap(1, 23, 10, 10), Some(Position::new(1, 3)));
+
+    let boundary = KleinBottleBoundary::new(EdgePair::LeftRight);
+    assert_eq!(boundary.wrap(10, 2, 10, 10), Some(Position::new(0, 7)));
+    assert_eq!(boundary.wrap(2, 10, 10, 10), Some(Position::new(2, 0)));
+}
+
+#[test]
+fn test_cross_surface_twists_both_pairs() {
+    let boundary = CrossSurfaceBoundary::new();
+    assert_eq!(boundary.wrap(2, -1, 10, 8), Some(Position::new(7, 7)));
+    assert_eq!(boundary.wrap(-1, 2, 10, 8), Some(Position::new(9, 5)));
+    assert_eq!(boundary.wrap(4, 4, 10, 8), Some(Position::new(4, 4)));
+}
+
+#[test]
+fn test_sphere_joins_adjacent_edges() {
+    let boundary = SphereBoundary::new();
+    // Up from column x enters row x from the left, and back again
+    assert_eq!(boundary.wrap(3, -1, 10, 10), Some(Position::new(0, 3)));
+    assert_eq!(boundary.wrap(-1, 3, 10, 10), Some(Position::new(3, 0)));
+    // Down from column x enters row x from the right, and back again
+    assert_eq!(boundary.wrap(3, 10, 10, 10), Some(Position::new(9, 3)));
+    assert_eq!(boundary.wrap(10, 3, 10, 10), Some(Position::new(3, 9)));
+    assert_eq!(boundary.wrap(3, 11, 10, 10), Some(Position::new(8, 3)));
+    // Corners and non-square grids have no cells beyond the edge
+    assert_eq!(boundary.wrap(-1, -1, 10, 10), None);
+    assert_eq!(boundary.wrap(3, -1, 10, 8), None);
+}
+
+#[test]
+fn test_mirror_reflects_about_edges() {
+    let boundary = MirrorBoundary::new();
+    assert_eq!(boundary.wrap(-1, 0, 10, 10), Some(Position::new(0, 0)));
+    assert_eq!(boundary.wrap(-2, 0, 10, 10), Some(Position::new(1, 0)));
+    assert_eq!(boundary.wrap(10, 11, 10, 10), Some(Position::new(9, 8)));
+    assert_eq!(boundary.wrap(25, -12, 10, 10), Some(Position::new(5, 8)));
+}
+
+#[test]
+fn test_shifted_torus_offsets_the_wrap() {
+    let boundary = ShiftedTorusBoundary::new(EdgePair::TopBottom, 3);
+    assert_eq!(boundary.wrap(2, 10, 10, 10), Some(Position::new(5, 0)));
+    assert_eq!(boundary.wrap(5, -1, 10, 10), Some(Position::new(2, 9)));
+    assert_eq!(boundary.wrap(-1, 4, 10, 10), Some(Position::new(9, 4)));
+
+    let boundary = ShiftedTorusBoundary::new(EdgePair::LeftRight, -2);
+    assert_eq!(boundary.wrap(10, 1, 10, 10), Some(Position::new(0, 9)));
+}
+
+#[test]
+fn test_topology_parse_and_display() {
+    let torus = |shifted, shift| Topology::Torus { width: 30, height: 20, shifted, shift };
+    let cases = [
+        ("T30,20", torus(EdgePair::TopBottom, 0)),
+        ("T30+5,20", torus(EdgePair::TopBottom, 5)),
+        ("T30,20-2", torus(EdgePair::LeftRight, -2)),
+        ("K30*,20", Topology::KleinBottle { width: 30, height: 20, twisted: EdgePair::TopBottom }),
+        ("K30,20*", Topology::KleinBottle { width: 30, height: 20, twisted: EdgePair::LeftRight }),
+        ("C30,20", Topology::CrossSurface { width: 30, height: 20 }),
+        ("P30,20", Topology::Plane { width: 30, height: 20 }),
+        ("S30", Topology::Sphere { size: 30 }),
+    ];
+    for (spec, expected) in cases {
+        let topology = Topology::parse(spec).unwrap();
+        assert_eq!(topology, expected);
+        assert_eq!(topology.to_string(), spec);
+    }
+    assert_eq!(Topology::parse("s30").unwrap().width(), 30);
+
+    for invalid in ["", "X30,20", "T0,20", "T30", "K30,20", "K30*,20*", "T30*,20", "T3+1,2-1"] {
+        assert!(Topology::parse(invalid).is_err(), "{} should not parse", invalid);
+    }
+}
+
+#[test]
+fn test_topology_split_rule() {
+    let (rule, topology) = Topology::split_rule("B3/S23:K30*,20").unwrap();
+    assert_eq!(rule, "B3/S23");
+    assert_eq!(
+        topology,
+        Some(Topology::KleinBottle { width: 30, height: 20, twisted: EdgePair::TopBottom })
+    );
+
+    let (rule, topology) = Topology::split_rule("B3/S23").unwrap();
+    assert_eq!(rule, "B3/S23");
+    assert_eq!(topology, None);
+    assert!(Topology::split_rule("B3/S23:Q1").is_err());
+}
+
+#[test]
+fn test_glider_on_klein_bottle_returns_mirrored() {
+    let topology = Topology::parse("K8*,8").unwrap();
+    let mut automata = Automata::new(
+        topology.width(),
+        topology.height(),
+        Box::new(ConwayRule::new()),
+        topology.boundary(),
+        Box::new(MooreNeighborhood::new()),
+    )
+    .unwrap();
+    // South-east glider
+    for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+        automata.grid_mut().set(Position::new(x, y), CellState::Alive).unwrap();
+    }
+    // A glider moves one cell diagonally every four generations, so after
+    // 32 it has crossed the twisted bottom edge once and comes back mirrored
+    automata.step_n(32);
+    let normalize = |cells: Vec<(usize, usize)>| {
+        let min_x = cells.iter().map(|c| c.0).min().unwrap();
+        let min_y = cells.iter().map(|c| c.1).min().unwrap();
+        let mut cells: Vec<_> = cells.iter().map(|&(x, y)| (x - min_x, y - min_y)).collect();
+        cells.sort();
+        cells
+    };
+    let alive = automata
+        .grid()
+        .iter()
+        .filter(|(_, state)| state.is_alive())
+        .map(|(pos, _)| (pos.x, pos.y))
+        .collect();
+    let mirrored_start = vec![(1, 0), (0, 1), (2, 2), (1, 2), (0, 2)];
+    assert_eq!(normalize(alive), normalize(mirrored_start));
+}
@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+
+use cellularity::{CellState, DenseGrid, Grid, PatternLibrary, Position, Transform};
+
+// Tests derived from src/core/transform.rs and the DenseGrid transforms
+
+fn grid_from_rows(rows: &[&str]) -> DenseGrid {
+    let mut grid = DenseGrid::new(rows[0].len(), rows.len()).unwrap();
+    for (y, row) in rows.iter().enumerate() {
+        for (x, c) in row.chars().enumerate() {
+            if c == 'o' {
+                grid.set(Position::new(x, y), CellState::Alive).unwrap();
+            }
+        }
+    }
+    grid
+}
+
+#[test]
+fn test_transforms_of_an_asymmetric_shape() {
+    let shape = grid_from_rows(&["oo.", "o..", "..."]);
+    let cases = [
+        (Transform::Identity, ["oo.", "o..", "..."]),
+        (Transform::Rotate90, [".oo", "..o", "..."]),
+        (Transform::Rotate180, ["...", "..o", ".oo"]),
+        (Transform::Rotate270, ["...", "o..", "oo."]),
+        (Transform::FlipHorizontal, [".oo", "..o", "..."]),
+        (Transform::FlipVertical, ["...", "o..", "oo."]),
+        (Transform::FlipDiagonal, ["oo.", "o..", "..."]),
+        (Transform::FlipAntiDiagonal, ["...", "..o", ".oo"]),
+    ];
+    for (transform, rows) in cases {
+        assert_eq!(shape.transform(transform), grid_from_rows(&rows), "{}", transform.name());
+    }
+
+    let tall = grid_from_rows(&["o..", "ooo"]);
+    assert_eq!(tall.flip_diagonal(), grid_from_rows(&["oo", ".o", ".o"]));
+    assert_eq!(tall.rotate_counterclockwise(), grid_from_rows(&[".o", ".o", "oo"]));
+    assert_eq!(tall.rotate_180(), grid_from_rows(&["ooo", "..o"]));
+    assert_eq!(tall.flip_vertical(), grid_from_rows(&["ooo", "o.."]));
+}
+
+#[test]
+fn test_transform_inverse_undoes_it_on_non_square_grids() {
+    let grid = grid_from_rows(&["oo..", "o.o.", "...o"]);
+    for transform in Transform::ALL {
+        let turned = grid.transform(transform);
+        assert_eq!(turned.width() == grid.height(), transform.swaps_axes());
+        assert_eq!(turned.transform(transform.inverse()), grid, "{}", transform.name());
+    }
+}
+
+#[test]
+fn test_translate_clips_cells_moved_off_the_grid() {
+    let grid = grid_from_rows(&["o..", ".o.", "..o"]);
+    assert_eq!(grid.translate(1, 0), grid_from_rows(&[".o.", "..o", "..."]));
+    assert_eq!(grid.translate(-1, -1), grid_from_rows(&["o..", ".o.", "..."]));
+    assert_eq!(grid.translate(3, 0).count_alive(), 0);
+    assert_eq!(grid.translate(0, 0), grid);
+}
+
+#[test]
+fn test_bounding_box_and_crop() {
+    let grid = grid_from_rows(&[".....", "..o..", "....o", "....."]);
+    assert_eq!(grid.bounding_box(), Some((Position::new(2, 1), 3, 2)));
+    assert_eq!(grid.crop(), Some(grid_from_rows(&["o..", "..o"])));
+
+    let empty = DenseGrid::new(4, 4).unwrap();
+    assert_eq!(empty.bounding_box(), None);
+    assert_eq!(empty.crop(), None);
+}
+
+#[test]
+fn test_canonical_form_ignores_position_rotation_and_reflection() {
+    let glider = grid_from_rows(&[".o.", "..o", "ooo"]);
+    let canonical = glider.canonical();
+    for transform in Transform::ALL {
+        let mut placed = DenseGrid::new(10, 8).unwrap();
+        placed.stamp(&glider.transform(transform), Position::new(4, 3));
+        assert_eq!(placed.canonical(), canonical, "{}", transform.name());
+    }
+    assert_ne!(grid_from_rows(&["oo.", ".oo"]).canonical(), canonical);
+    assert_eq!(DenseGrid::new(3, 3).unwrap().canonical(), DenseGrid::new(1, 1).unwrap());
+}
+
+#[test]
+fn test_canonical_form_keeps_states_apart() {
+    let mut alive = DenseGrid::new(2, 1).unwrap();
+    alive.set(Position::new(0, 0), CellState::Alive).unwrap();
+    let mut dying = alive.clone();
+    dying.set(Position::new(0, 0), CellState::State(2)).unwrap();
+    assert_ne!(alive.canonical(), dying.canonical());
+}
+
+#[test]
+fn test_library_patterns_are_distinct_up_to_symmetry() {
+    let mut seen = HashSet::new();
+    for pattern in PatternLibrary::all() {
+        let canonical = pattern.grid().unwrap().canonical();
+        assert!(seen.insert(canonical), "{} is a duplicate", pattern.name);
+    }
+}
+
+#[test]
+fn test_identify_finds_library_patterns_in_any_orientation() {
+    let mut grid = DenseGrid::new(20, 20).unwrap();
+    let pattern = PatternLibrary::get("Lightweight spaceship").unwrap();
+    grid.stamp(&pattern.grid().unwrap().rotate_clockwise().flip_vertical(), Position::new(7, 9));
+    assert_eq!(PatternLibrary::identify(&grid).map(|p| p.name), Some("Lightweight spaceship"));
+
+    grid.set(Position::new(0, 0), CellState::Alive).unwrap();
+    assert_eq!(PatternLibrary::identify(&grid), None);
+}
@@ -0,0 +1,147 @@
+// Tests derived from src/core/turmite.rs
+
+use cellularity::{
+    Ant, Automata, CellState, DenseGrid, Grid, Heading, IdentityRule, MooreNeighborhood, Position,
+    ToroidalBoundary, Turn, TurmiteRule,
+};
+
+fn ant_automata(width: usize, height: usize) -> Automata {
+    Automata::new(
+        width,
+        height,
+        Box::new(IdentityRule::new()),
+        Box::new(ToroidalBoundary::new()),
+        Box::new(MooreNeighborhood::new()),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_heading_turns() {
+    assert_eq!(Heading::North.turn(Turn::Right), Heading::East);
+    assert_eq!(Heading::North.turn(Turn::Left), Heading::West);
+    assert_eq!(Heading::East.turn(Turn::UTurn), Heading::West);
+    assert_eq!(Heading::South.turn(Turn::None), Heading::South);
+    assert_eq!(Heading::West.turn(Turn::Right), Heading::North);
+    assert_eq!(Heading::North.offset(), (0, -1));
+    assert_eq!(Heading::East.offset(), (1, 0));
+}
+
+#[test]
+fn test_parse_langtons_ant() {
+    let ant = TurmiteRule::parse("{{{1, 2, 0}, {0, 8, 0}}}").unwrap();
+    assert_eq!(ant, TurmiteRule::langtons_ant());
+    assert_eq!(ant.states(), 1);
+    assert_eq!(ant.colors(), 2);
+    assert_eq!(ant.spec(), "{{{1, 2, 0}, {0, 8, 0}}}");
+
+    let json = TurmiteRule::parse("[[[1,2,0],[0,8,0]]]").unwrap();
+    assert_eq!(json, ant);
+}
+
+#[test]
+fn test_parse_multi_state_turmite() {
+    // Fibonacci spiral
+    let turmite = TurmiteRule::parse("{{{1,8,1},{1,8,1}},{{1,2,1},{0,1,0}}}").unwrap();
+    assert_eq!(turmite.states(), 2);
+    assert_eq!(turmite.colors(), 2);
+    let transition = turmite.transition(1, 1);
+    assert_eq!((transition.write, transition.turn, transition.next_state), (0, Turn::None, 0));
+}
+
+#[test]
+fn test_parse_rejects_invalid_turmites() {
+    assert!(TurmiteRule::parse("{{{1, 3, 0}, {0, 8, 0}}}").is_err());
+    assert!(TurmiteRule::parse("{{{2, 2, 0}, {0, 8, 0}}}").is_err());
+    assert!(TurmiteRule::parse("{{{1, 2, 1}, {0, 8, 0}}}").is_err());
+    assert!(TurmiteRule::parse("{{{1, 2, 0}, {0, 8, 0}}").is_err());
+    assert!(TurmiteRule::parse("{{{1, 2, 0}, {0, 8}}}").is_err());
+    assert!(TurmiteRule::parse("{{{1, 2, 0}, {0, 8, 0}}, {{1, 2, 0}}}").is_err());
+    assert!(TurmiteRule::parse("{{{1, 2, 0}}}").is_err());
+    assert!(TurmiteRule::parse("ant").is_err());
+}
+
+#[test]
+fn test_from_turns() {
+    let ant = TurmiteRule::from_turns("LLRR").unwrap();
+    assert_eq!(ant.colors(), 4);
+    assert_eq!(ant.transition(0, 3).write, 0);
+    assert_eq!(ant.transition(0, 1).write, 2);
+    assert_eq!(ant.transition(0, 2).turn, Turn::Right);
+    assert!(TurmiteRule::from_turns("RX").is_err());
+    assert!(TurmiteRule::from_turns("R").is_err());
+}
+
+#[test]
+fn test_langtons_ant_first_steps() {
+    let mut automata = ant_automata(11, 11);
+    let ant = Ant::new(Position::new(5, 5), Heading::North, TurmiteRule::langtons_ant());
+    automata.add_ant(ant).unwrap();
+
+    automata.step_n(4);
+    // Four right turns round a square of black cells, back at the start
+    assert_eq!(automata.ants()[0].position, Position::new(5, 5));
+    assert_eq!(automata.ants()[0].heading, Heading::North);
+    for (x, y) in [(5, 5), (6, 5), (6, 6), (5, 6)] {
+        assert_eq!(automata.grid().get(Position::new(x, y)).unwrap(), CellState::Alive);
+    }
+
+    // On a black cell the ant turns left and flips it back to white
+    automata.step();
+    assert_eq!(automata.ants()[0].position, Position::new(4, 5));
+    assert_eq!(automata.ants()[0].heading, Heading::West);
+    assert_eq!(automata.grid().get(Position::new(5, 5)).unwrap(), CellState::Dead);
+}
+
+#[test]
+fn test_langtons_ant_builds_highway() {
+    let mut grid = DenseGrid::new(200, 200).unwrap();
+    let boundary = ToroidalBoundary::new();
+    let mut ant = Ant::new(Position::new(100, 100), Heading::North, TurmiteRule::langtons_ant());
+    for _ in 0..11_000 {
+        ant.step(&mut grid, &boundary);
+    }
+
+    // The highway repeats every 104 steps, moving two cells diagonally
+    let start = ant.position;
+    for _ in 0..104 {
+        ant.step(&mut grid, &boundary);
+    }
+    let dx = ant.position.x as isize - start.x as isize;
+    let dy = ant.position.y as isize - start.y as isize;
+    assert_eq!((dx.abs(), dy.abs()), (2, 2));
+}
+
+#[test]
+fn test_ant_wraps_with_boundary() {
+    let mut automata = ant_automata(5, 5);
+    let ant = Ant::new(Position::new(4, 2), Heading::North, TurmiteRule::langtons_ant());
+    automata.add_ant(ant).unwrap();
+    automata.step();
+    assert_eq!(automata.ants()[0].position, Position::new(0, 2));
+    assert_eq!(automata.ants()[0].heading, Heading::East);
+}
+
+#[test]
+fn test_multi_colour_ant_writes_extra_states() {
+    let mut automata = ant_automata(9, 9);
+    let ant = Ant::new(Position::new(4, 4), Heading::North, TurmiteRule::from_turns("RLR").unwrap());
+    automata.add_ant(ant).unwrap();
+    automata.grid_mut().set(Position::new(4, 4), CellState::Alive).unwrap();
+    automata.step();
+    assert_eq!(automata.grid().get(Position::new(4, 4)).unwrap(), CellState::State(2));
+}
+
+#[test]
+fn test_add_ant_out_of_bounds_and_reset() {
+    let mut automata = ant_automata(5, 5);
+    let outside = Ant::new(Position::new(5, 0), Heading::North, TurmiteRule::langtons_ant());
+    assert!(automata.add_ant(outside).is_err());
+
+    let ant = Ant::new(Position::new(2, 2), Heading::North, TurmiteRule::langtons_ant());
+    automata.add_ant(ant.clone()).unwrap();
+    automata.add_ant(ant).unwrap();
+    assert_eq!(automata.ants().len(), 2);
+    automata.reset();
+    assert!(automata.ants().is_empty());
+}
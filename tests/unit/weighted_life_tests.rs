@@ -0,0 +1,118 @@
+use cellularity::{
+    parse_rule, Automata, CellState, ConwayRule, CustomNeighborhood, DenseGrid, Grid,
+    MooreNeighborhood, Neighborhood, Neighbors, Position, Rule, ToroidalBoundary, WeightedLifeRule,
+};
+
+const LIFE: &str = "NW1,NN1,NE1,WW1,ME0,EE1,SW1,SS1,SE1,HI0,RS2,RS3,RB3";
+
+// Tests derived from src/core/rules/weighted_life.rs
+#[test]
+fn test_weighted_life_parse() {
+    let rule =
+        WeightedLifeRule::parse("NW5,NN1,NE5,WW1,ME0,EE1,SW5,SS1,SE5,HI0,RS2,RS6,RB3,RB5").unwrap();
+    assert_eq!(rule.weights(), [5, 1, 5, 1, 0, 1, 5, 1, 5]);
+    assert_eq!(rule.survival(), &[2, 6]);
+    assert_eq!(rule.birth(), &[3, 5]);
+    assert_eq!(rule.name(), "Weighted Life");
+    assert_eq!(rule.description(), "NW5,NN1,NE5,WW1,ME0,EE1,SW5,SS1,SE5,HI0,RS2,RS6,RB3,RB5");
+}
+
+#[test]
+fn test_weighted_life_parse_errors() {
+    assert!(WeightedLifeRule::parse("NW1,HI2,RB3").is_err());
+    assert!(WeightedLifeRule::parse("XX1,RB3").is_err());
+    assert!(WeightedLifeRule::parse("NWx,RB3").is_err());
+    assert!(WeightedLifeRule::parse("RS2,RB3").is_err());
+    assert!(WeightedLifeRule::parse("N").is_err());
+}
+
+#[test]
+fn test_weighted_life_import_file() {
+    let file = "#MCell 4.20\n#GAME Weighted Life\n#RULE NW1,NN1,NE1,WW1,ME0,\n#RULE EE1,SW1,SS1,SE1,HI0,RS2,RS3,RB3\n#BOARD 100x100\n";
+    let rule = WeightedLifeRule::import(file).unwrap();
+    assert_eq!(rule.weights(), [1, 1, 1, 1, 0, 1, 1, 1, 1]);
+
+    assert!(WeightedLifeRule::import("#GAME Generations\n#RULE 23/3/2").is_err());
+    assert!(WeightedLifeRule::import("#MCell 4.20\n").is_err());
+}
+
+#[test]
+fn test_weighted_life_neighborhood() {
+    let rule = WeightedLifeRule::parse("NN2,ME1,SS-1,RB2").unwrap();
+    let neighborhood: CustomNeighborhood = rule.neighborhood();
+    assert_eq!(neighborhood.offsets(), &[(0, -1), (0, 0), (0, 1)]);
+    assert_eq!(neighborhood.weights(), Some(&[2, 1, -1][..]));
+    assert_eq!(neighborhood.name(), "Weighted Life");
+}
+
+#[test]
+fn test_weighted_life_uses_weighted_sum() {
+    let rule = WeightedLifeRule::parse("NN2,SS-1,RS1,RB2").unwrap();
+    let two_alive = Neighbors { weighted_sum: 1, ..Neighbors::new(2) };
+    assert_eq!(rule.apply_neighbors(CellState::Alive, &two_alive), CellState::Alive);
+    assert_eq!(rule.apply_neighbors(CellState::Dead, &two_alive), CellState::Dead);
+    let north_only = Neighbors { weighted_sum: 2, ..Neighbors::new(1) };
+    assert_eq!(rule.apply_neighbors(CellState::Dead, &north_only), CellState::Alive);
+}
+
+#[test]
+fn test_weighted_life_conway_weights_match_conway() {
+    let weighted = WeightedLifeRule::parse(LIFE).unwrap();
+    let mut a = Automata::new(
+        16,
+        16,
+        Box::new(ConwayRule::new()),
+        Box::new(ToroidalBoundary::new()),
+        Box::new(MooreNeighborhood::new()),
+    )
+    .unwrap();
+    let mut b = Automata::new(
+        16,
+        16,
+        Box::new(weighted.clone()),
+        Box::new(ToroidalBoundary::new()),
+        Box::new(weighted.neighborhood()),
+    )
+    .unwrap();
+    let seed = DenseGrid::new_random(16, 16, 0.4).unwrap();
+    *a.grid_mut() = seed.clone();
+    *b.grid_mut() = seed;
+    for _ in 0..8 {
+        a.step();
+        b.step();
+        for (pos, state) in a.grid().iter() {
+            assert_eq!(b.grid().get(pos).unwrap(), state);
+        }
+    }
+}
+
+#[test]
+fn test_automata_passes_weighted_sum() {
+    // North neighbor weighs 3, south weighs -1; birth only on a weighted sum of 2
+    let rule = WeightedLifeRule::parse("NN3,SS-1,RB2").unwrap();
+    let mut automata = Automata::new(
+        5,
+        5,
+        Box::new(rule.clone()),
+        Box::new(ToroidalBoundary::new()),
+        Box::new(rule.neighborhood()),
+    )
+    .unwrap();
+    automata.grid_mut().set(Position::new(2, 1), CellState::Alive).unwrap();
+    automata.grid_mut().set(Position::new(2, 3), CellState::Alive).unwrap();
+    automata.step();
+    assert_eq!(automata.grid().get(Position::new(2, 2)).unwrap(), CellState::Alive);
+    assert_eq!(automata.grid().count_alive(), 1);
+}
+
+// Tests derived from src/core/rules/parse.rs
+#[test]
+fn test_parse_rule_dispatch() {
+    assert_eq!(parse_rule("Life").unwrap().rule.name(), "Conway's Game of Life");
+    let ltl = parse_rule("R2,C0,M0,S3..5,B4..4,NM").unwrap();
+    assert_eq!(ltl.rule.name(), "Larger than Life");
+    assert_eq!(ltl.neighborhood.offsets().len(), 24);
+    let weighted = parse_rule(LIFE).unwrap();
+    assert_eq!(weighted.rule.name(), "Weighted Life");
+    assert!(parse_rule("nonsense").is_err());
+}